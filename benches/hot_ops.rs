@@ -0,0 +1,190 @@
+//! Benchmarks of the hot operations across buffer sizes.
+//!
+//! Every operation is a [`Case`] listing its implementations, the crate's own first and a plain
+//! scalar reference after it. An optimized path added later registers one more entry in the
+//! matching case and is measured against the same inputs.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+
+use rebite::{
+    bytes::ByteString,
+    checksum::Crc32
+};
+
+const SIZES: [usize; 3] = [64, 4 << 10, 1 << 20];
+
+/// Inputs shared by every implementation of a case
+#[derive(Clone)]
+struct Fixture {
+    a: Vec<u8>,
+    b: Vec<u8>,
+    hex: String,
+    out: Vec<u8>,
+}
+
+impl Fixture {
+
+    fn new(len: usize) -> Self {
+        let mut state = 0x250u64;
+        let a: Vec<u8> = (0..len).map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        }).collect();
+
+        let hex = a.iter().map(|e| format!("{:02x}", e)).collect();
+        Self { b: a.clone(), a, hex, out: vec![0; 2 * len] }
+    }
+}
+
+type Impl = fn(&mut Fixture);
+
+struct Case {
+    name: &'static str,
+    impls: &'static [(&'static str, Impl)],
+}
+
+fn views<'x>(a: &'x mut [u8], b: &'x mut [u8], b_reversed: bool) -> (ByteString<'x>, ByteString<'x>) {
+    let mut bytes_b = ByteString::new(b);
+    if b_reversed { bytes_b.interpret_reverse_endian(); }
+    (ByteString::new(a), bytes_b)
+}
+
+// Scalar references, deliberately simple byte at a time loops
+mod reference {
+
+    pub fn xor(a: &mut [u8], b: &[u8]) { a.iter_mut().zip(b).for_each(|(x, y)| *x ^= y); }
+    pub fn and(a: &mut [u8], b: &[u8]) { a.iter_mut().zip(b).for_each(|(x, y)| *x &= y); }
+    pub fn or(a: &mut [u8], b: &[u8]) { a.iter_mut().zip(b).for_each(|(x, y)| *x |= y); }
+
+    // Big-endian left shift of the whole slice
+    pub fn shl(a: &mut [u8], n: usize) {
+        let (bytes, bits) = (n / 8, n % 8);
+        for ix in 0..a.len() {
+            let high = a.get(ix + bytes).copied().unwrap_or(0);
+            let low = a.get(ix + bytes + 1).copied().unwrap_or(0);
+            a[ix] = if bits == 0 { high } else { high << bits | low >> (8 - bits) };
+        }
+    }
+
+    pub fn shr(a: &mut [u8], n: usize) {
+        let (bytes, bits) = (n / 8, n % 8);
+        for ix in (0..a.len()).rev() {
+            let high = if ix >= bytes { a[ix - bytes] } else { 0 };
+            let low = if ix > bytes { a[ix - bytes - 1] } else { 0 };
+            a[ix] = if bits == 0 { high } else { high >> bits | low << (8 - bits) };
+        }
+    }
+
+    pub fn crc32(data: &[u8]) -> u32 {
+        let mut crc = 0xFFFF_FFFFu32;
+        for byte in data {
+            crc ^= *byte as u32;
+            for _ in 0..8 {
+                crc = if crc & 1 == 1 { crc >> 1 ^ 0xEDB8_8320 } else { crc >> 1 };
+            }
+        }
+        !crc
+    }
+
+    pub fn hex_decode(hex: &str, out: &mut [u8]) {
+        for (dst, pair) in out.iter_mut().zip(hex.as_bytes().chunks(2)) {
+            let digit = |c: u8| (c as char).to_digit(16).unwrap() as u8;
+            *dst = digit(pair[0]) << 4 | digit(pair[1]);
+        }
+    }
+}
+
+const CASES: &[Case] = &[
+    Case { name: "xor_assign", impls: &[
+        ("crate", |f| { let (mut a, b) = views(&mut f.a, &mut f.b, false); a ^= &b; }),
+        ("crate_mixed_flags", |f| { let (mut a, b) = views(&mut f.a, &mut f.b, true); a ^= &b; }),
+        ("reference", |f| reference::xor(&mut f.a, &f.b)),
+    ]},
+    Case { name: "and_assign", impls: &[
+        ("crate", |f| { let (mut a, b) = views(&mut f.a, &mut f.b, false); a &= &b; }),
+        ("reference", |f| reference::and(&mut f.a, &f.b)),
+    ]},
+    Case { name: "or_assign", impls: &[
+        ("crate", |f| { let (mut a, b) = views(&mut f.a, &mut f.b, false); a |= &b; }),
+        ("reference", |f| reference::or(&mut f.a, &f.b)),
+    ]},
+    Case { name: "shl_3_bits", impls: &[
+        ("crate", |f| { let mut a = ByteString::new(&mut f.a); a <<= 3; }),
+        ("reference", |f| reference::shl(&mut f.a, 3)),
+    ]},
+    Case { name: "shl_16_bytes", impls: &[
+        ("crate", |f| { let mut a = ByteString::new(&mut f.a); a <<= 128; }),
+        ("reference", |f| reference::shl(&mut f.a, 128)),
+    ]},
+    Case { name: "shr_3_bits", impls: &[
+        ("crate", |f| { ByteString::new(&mut f.a).ops().shr_bits(3); }),
+        ("reference", |f| reference::shr(&mut f.a, 3)),
+    ]},
+    Case { name: "shr_16_bytes", impls: &[
+        ("crate", |f| { ByteString::new(&mut f.a).ops().shr_bits(128); }),
+        ("reference", |f| reference::shr(&mut f.a, 128)),
+    ]},
+    Case { name: "is_zero", impls: &[
+        ("crate", |f| { black_box(ByteString::new(&mut f.out).is_zero()); }),
+        ("reference", |f| { black_box(f.out.iter().all(|e| *e == 0)); }),
+    ]},
+    Case { name: "count_ones", impls: &[
+        ("crate", |f| { black_box(ByteString::new(&mut f.a).count_ones()); }),
+        ("reference", |f| { black_box(f.a.iter().map(|e| e.count_ones() as usize).sum::<usize>()); }),
+    ]},
+    Case { name: "eq_matching_flags", impls: &[
+        ("crate", |f| { let (a, b) = views(&mut f.a, &mut f.b, false); black_box(a == b); }),
+        ("reference", |f| { black_box(f.a == f.b); }),
+    ]},
+    Case { name: "eq_mismatched_flags", impls: &[
+        ("crate", |f| { let (a, b) = views(&mut f.a, &mut f.b, true); black_box(a == b); }),
+        ("reference", |f| { black_box(f.a.iter().eq(f.b.iter().rev())); }),
+    ]},
+    Case { name: "hex_encode", impls: &[
+        ("crate_display", |f| { black_box(ByteString::new(&mut f.a).to_string()); }),
+        ("crate_write_radix", |f| { let n = f.a.len(); let mut out = vec![0u8; 2 * n]; black_box(ByteString::new(&mut f.a).write_radix(&mut out, 16, false).unwrap()); }),
+        ("reference", |f| { black_box(f.a.iter().map(|e| format!("{:02x}", e)).collect::<String>()); }),
+    ]},
+    Case { name: "hex_decode", impls: &[
+        ("crate", |f| { let n = f.a.len(); ByteString::new(&mut f.out[..n]).assign_from_radix_str(&f.hex, 16).unwrap(); }),
+        ("reference", |f| { let n = f.a.len(); reference::hex_decode(&f.hex, &mut f.out[..n]); }),
+    ]},
+    Case { name: "crc32", impls: &[
+        ("crate", |f| { black_box(ByteString::new(&mut f.a).checksum(Crc32::default())); }),
+        ("reference", |f| { black_box(reference::crc32(&f.a)); }),
+    ]},
+];
+
+// Radix conversion is quadratic, its hex cases stop at the middle size
+fn skip(case: &Case, implementation: &str, size: usize) -> bool {
+    size > SIZES[1] && (case.name == "hex_decode" || implementation == "crate_write_radix")
+}
+
+fn bench_cases(c: &mut Criterion) {
+    for case in CASES {
+        let mut group = c.benchmark_group(case.name);
+
+        for size in SIZES {
+            group.throughput(Throughput::Bytes(size as u64));
+            let template = Fixture::new(size);
+
+            for (implementation, run) in case.impls {
+                if skip(case, implementation, size) { continue; }
+
+                // Every iteration starts from the same inputs, mutating cases would otherwise drift to zero
+                group.bench_with_input(BenchmarkId::new(*implementation, size), &size, |bencher, _| {
+                    bencher.iter_batched_ref(|| template.clone(), |fixture| run(black_box(fixture)), BatchSize::LargeInput);
+                });
+            }
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_cases);
+criterion_main!(benches);
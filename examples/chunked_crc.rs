@@ -0,0 +1,54 @@
+//! Compute a CRC-32 over input that arrives in chunks and verify it against the one-shot result.
+
+use rebite::{
+    bytes::ByteString,
+    checksum::{Checksum, Crc32}
+};
+
+// Feed `data` in chunks of varying size, some of them stored back to front
+fn crc_chunked(data: &[u8], chunk_sizes: &[usize]) -> u32 {
+    let mut crc = Crc32::default();
+    let mut rest = data;
+
+    for (ix, size) in chunk_sizes.iter().cycle().enumerate() {
+        if rest.is_empty() { break; }
+
+        let (chunk, tail) = rest.split_at((*size).min(rest.len()));
+        rest = tail;
+
+        if ix % 2 == 0 {
+            crc.update_slice(chunk);
+            continue;
+        }
+
+        let mut stored: Vec<u8> = chunk.iter().rev().copied().collect();
+        let mut view = ByteString::new(&mut stored);
+        view.interpret_reverse_endian();
+        crc.update(&view);
+    }
+
+    crc.finalize()
+}
+
+fn main() {
+    let mut check = *b"123456789";
+    assert_eq!(ByteString::new(&mut check).checksum(Crc32::default()), 0xCBF4_3926);
+
+    let mut data: Vec<u8> = (0..10_000u32).map(|e| (e * 31 % 251) as u8).collect();
+    let expected = ByteString::new(&mut data).checksum(Crc32::default());
+
+    for sizes in [&[1][..], &[7, 64, 3], &[4096], &[1000, 1, 1]] {
+        let crc = crc_chunked(&data, sizes);
+        println!("chunks {:?}: {:#010x}", sizes, crc);
+        assert_eq!(crc, expected);
+    }
+
+    // Any flipped bit changes the checksum
+    data[5000] ^= 0x10;
+    assert_ne!(crc_chunked(&data, &[333]), expected);
+}
+
+#[test]
+fn run() {
+    main();
+}
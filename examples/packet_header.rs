@@ -0,0 +1,110 @@
+//! Parse a binary packet header with mixed-endian fields, patch it and serialize it again.
+//!
+//! Layout, in logical byte order:
+//!
+//! | offset | size | field        | byte order |
+//! |--------|------|--------------|------------|
+//! | 0      | 2    | magic        | big        |
+//! | 2      | 1    | version      |            |
+//! | 3      | 1    | flags        |            |
+//! | 4      | 4    | sequence     | little     |
+//! | 8      | 2    | payload len  | big        |
+//! | 10     | 2    | checksum     | little     |
+
+use rebite::{
+    bytes::ByteString,
+    error::Error,
+    typed::Endianness,
+    writer::ByteStringWriter
+};
+
+const HEADER_LEN: usize = 12;
+const MAGIC: u16 = 0xCAFE;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Header {
+    version: u8,
+    flags: u8,
+    sequence: u32,
+    payload_len: u16,
+}
+
+// Sum of the first ten header bytes, stored in the trailing checksum field
+fn header_sum(bytes: &ByteString) -> u16 {
+    bytes.iter_range(0..10).fold(0u16, |acc, e| acc.wrapping_add(*e as u16))
+}
+
+fn parse(bytes: &ByteString) -> Result<Header, Error> {
+    if bytes.read_u16_at(0, Endianness::Big)? != MAGIC {
+        return Err(Error::MalformedInput);
+    }
+
+    if bytes.read_u16_at(10, Endianness::Little)? != header_sum(bytes) {
+        return Err(Error::MalformedInput);
+    }
+
+    let mut small = [0u8; 2];
+    small.iter_mut().zip(bytes.try_iter_range(2..4)?).for_each(|(dst, src)| *dst = *src);
+
+    Ok(Header {
+        version: small[0],
+        flags: small[1],
+        sequence: bytes.read_u32_at(4, Endianness::Little)?,
+        payload_len: bytes.read_u16_at(8, Endianness::Big)?,
+    })
+}
+
+fn serialize(header: &Header, bytes: &mut ByteString) -> Result<(), Error> {
+    let mut w = ByteStringWriter::new(bytes);
+
+    w.write_all_or_nothing(|w| {
+        w.write_slice(&MAGIC.to_be_bytes())?;
+        w.write_u8(header.version)?;
+        w.write_u8(header.flags)?;
+        w.write_slice(&header.sequence.to_le_bytes())?;
+        w.write_slice(&header.payload_len.to_be_bytes())?;
+        w.ensure(2)?;
+        Ok(())
+    })?;
+
+    let sum = header_sum(bytes);
+    bytes.write_u16_at(10, sum, Endianness::Little)
+}
+
+fn main() {
+    let mut wire = [0xCA, 0xFE, 0x02, 0x81, 0x78, 0x56, 0x34, 0x12, 0x01, 0x00, 0x00, 0x00];
+    let sum = wire[..10].iter().fold(0u16, |acc, e| acc.wrapping_add(*e as u16));
+    wire[10..].copy_from_slice(&sum.to_le_bytes());
+
+    let mut bytes = ByteString::new(&mut wire);
+    let mut header = parse(&bytes).unwrap();
+    assert_eq!(header, Header { version: 2, flags: 0x81, sequence: 0x1234_5678, payload_len: 0x0100 });
+    println!("parsed {:?} from {}", header, bytes);
+
+    // Bump the sequence number and write the header back in place
+    header.sequence += 1;
+    serialize(&header, &mut bytes).unwrap();
+    assert_eq!(parse(&bytes).unwrap(), header);
+    assert_eq!(bytes.read_u32_at(4, Endianness::Little), Ok(0x1234_5679));
+    println!("re-serialized {}", bytes);
+
+    // A receiver that stores frames back to front sees the same fields through the flag
+    let mut stored: Vec<u8> = bytes.iter().rev().copied().collect();
+    let mut reversed = ByteString::new(&mut stored);
+    reversed.interpret_reverse_endian();
+    assert_eq!(parse(&reversed).unwrap(), header);
+
+    // Corruption is caught by the checksum
+    bytes.write_bit_range(8 * (HEADER_LEN - 3)..8 * (HEADER_LEN - 3) + 1, 1).unwrap();
+    assert_eq!(parse(&bytes), Err(Error::MalformedInput));
+
+    // Serializing into a short buffer leaves it untouched
+    let mut short = [0xEEu8; 9];
+    assert!(serialize(&header, &mut ByteString::new(&mut short)).is_err());
+    assert_eq!(short, [0xEE; 9]);
+}
+
+#[test]
+fn run() {
+    main();
+}
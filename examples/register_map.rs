@@ -0,0 +1,53 @@
+//! Read/modify/write flow over a memory mapped peripheral register block using bit ranges.
+//!
+//! The block is two 32-bit registers stored little-endian, as most MCUs do. Interpreting the
+//! block in reverse makes bit offsets match the datasheet numbering of the combined value.
+
+use std::ops::Range;
+
+use rebite::bytes::ByteString;
+
+// CTRL register, offsets within the combined 64-bit value
+const ENABLE: Range<usize> = 0..1;
+const MODE: Range<usize> = 1..4;
+const PRESCALER: Range<usize> = 8..20;
+// STATUS register occupies the upper word
+const FIFO_LEVEL: Range<usize> = 32..38;
+const ERROR_FLAGS: Range<usize> = 56..64;
+
+fn main() {
+    // Reset values as raw memory: CTRL = 0x0000_1F00, STATUS = 0xA500_0011
+    let mut memory = [0x00, 0x1F, 0x00, 0x00, 0x11, 0x00, 0x00, 0xA5];
+    let mut regs = ByteString::new(&mut memory);
+    regs.interpret_reverse_endian();
+
+    assert_eq!(regs.read_bit_range(PRESCALER), Ok(0x1F));
+    assert_eq!(regs.read_bit_range(FIFO_LEVEL), Ok(0x11));
+    assert_eq!(regs.read_bit_range(ERROR_FLAGS), Ok(0xA5));
+
+    // Configure: mode 5, prescaler 0x123, then enable
+    regs.write_bit_range(MODE, 5).unwrap();
+    regs.write_bit_range(PRESCALER, 0x123).unwrap();
+    regs.set_bit(ENABLE.start, true);
+
+    println!("registers {}", regs);
+    assert_eq!(regs.read_bit_range(0..32), Ok(0x0001_230B));
+
+    // Acknowledge the low error flags by clearing them
+    let flags = regs.read_bit_range(ERROR_FLAGS).unwrap();
+    regs.write_bit_range(ERROR_FLAGS, flags & !0x0F).unwrap();
+
+    // Neighbouring fields are untouched by the field writes
+    assert_eq!(regs.read_bit_range(FIFO_LEVEL), Ok(0x11));
+    assert_eq!(regs.read_bit_range(ERROR_FLAGS), Ok(0xA0));
+
+    // Out of range fields are reported instead of corrupting memory
+    assert!(regs.write_bit_range(60..70, 0).is_err());
+
+    assert_eq!(memory, [0x0B, 0x23, 0x01, 0x00, 0x11, 0x00, 0x00, 0xA0]);
+}
+
+#[test]
+fn run() {
+    main();
+}
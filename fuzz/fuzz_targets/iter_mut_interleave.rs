@@ -0,0 +1,36 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rebite::bytes::ByteString;
+
+// Input: length byte, flag byte, then one byte per step choosing next or next_back
+fuzz_target!(|data: &[u8]| {
+    let [len, flags, steps @ ..] = data else { return };
+
+    let len = *len as usize % 64;
+    let reversed = flags & 1 == 1;
+
+    let mut raw: Vec<u8> = (0..len as u8).collect();
+    let mut bytes = ByteString::new(&mut raw);
+    if reversed { bytes.interpret_reverse_endian(); }
+
+    // Logical order of physical indices, consumed from both ends by the oracle
+    let mut oracle: std::collections::VecDeque<u8> = if reversed { (0..len as u8).rev().collect() } else { (0..len as u8).collect() };
+
+    let mut iter = bytes.iter_mut();
+    for step in steps {
+        let (got, expected) = if step & 1 == 0 { (iter.next(), oracle.pop_front()) } else { (iter.next_back(), oracle.pop_back()) };
+
+        assert_eq!(got.as_deref().copied(), expected);
+        assert_eq!(iter.len(), oracle.len());
+
+        // Every handed out reference is unique, so writing through it must not disturb the others
+        if let Some(byte) = got {
+            *byte |= 0x80;
+        }
+    }
+    drop(iter);
+
+    let touched = raw.iter().filter(|e| **e & 0x80 != 0).count();
+    assert_eq!(touched, len - oracle.len());
+});
@@ -0,0 +1,41 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rebite::bytes::ByteString;
+
+// Input: radix byte, width byte, then the text to parse
+fuzz_target!(|data: &[u8]| {
+    let [radix, width, text @ ..] = data else { return };
+    let Ok(text) = std::str::from_utf8(text) else { return };
+
+    let radix = 2 + *radix as u32 % 35;
+    let width = 1 + *width as usize % 16;
+
+    let mut raw = [0u8; 16];
+    let mut bytes = ByteString::new(&mut raw[16 - width..]);
+
+    let parsed = bytes.assign_from_radix_str(text, radix);
+
+    // Oracle: u128 parsing of the same digits, bounded to the buffer width
+    let expected = u128::from_str_radix(text, radix).ok()
+        .filter(|_| !text.starts_with('+'))
+        .filter(|value| width == 16 || *value >> (width * 8) == 0);
+
+    match (parsed, expected) {
+        (Ok(()), Some(value)) => assert_eq!(u128::from_be_bytes(raw), value),
+        (Err(_), None) => assert!(raw.iter().all(|e| *e == 0)),
+        (Ok(()), None) => panic!("accepted {:?} in radix {}", text, radix),
+        (Err(err), Some(_)) => panic!("rejected {:?} in radix {}: {}", text, radix, err)
+    }
+
+    if expected.is_some() {
+        let bytes = ByteString::new(&mut raw[16 - width..]);
+        let mut out = [0u8; 130];
+        let n = bytes.write_radix(&mut out, radix, false).unwrap();
+
+        let mut copy = [0u8; 16];
+        let mut reparsed = ByteString::new(&mut copy[16 - width..]);
+        reparsed.assign_from_radix_str(std::str::from_utf8(&out[..n]).unwrap(), radix).unwrap();
+        assert_eq!(copy, raw);
+    }
+});
@@ -0,0 +1,39 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rebite::bytes::ByteString;
+
+// Input: start, end, count, flag byte, then up to 16 value bytes in logical order
+fuzz_target!(|data: &[u8]| {
+    let [start, end, count, flags, value @ ..] = data else { return };
+    if value.len() > 16 { return; }
+
+    let width = value.len() * 8;
+    let (start, end) = (*start as usize, *end as usize);
+    let reversed = flags & 1 == 1;
+
+    let mut raw = value.to_vec();
+    if reversed { raw.reverse(); }
+    let mut bytes = ByteString::new(&mut raw);
+    if reversed { bytes.interpret_reverse_endian(); }
+
+    let result = bytes.rotl_bit_range(start..end, *count as usize);
+    if start > end || end > width {
+        assert!(result.is_err());
+        return;
+    }
+    result.unwrap();
+
+    if reversed { raw.reverse(); }
+
+    // Oracle: rotate the field bit by bit over the value bytes
+    let get = |bytes: &[u8], offset: usize| bytes[bytes.len() - 1 - offset / 8] >> (offset % 8) & 1;
+    let len = end - start;
+
+    for offset in 0..width {
+        let expected = if offset < start || offset >= end { get(value, offset) }
+        else { get(value, start + (offset - start + len - *count as usize % len) % len) };
+
+        assert_eq!(get(&raw, offset), expected, "bit {}", offset);
+    }
+});
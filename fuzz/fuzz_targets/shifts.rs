@@ -0,0 +1,34 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rebite::{bytes::ByteString, fixed::Rounding};
+
+// Input: two shift count bytes, flag byte, then the value bytes in logical order
+fuzz_target!(|data: &[u8]| {
+    let [count_lo, count_hi, flags, value @ ..] = data else { return };
+
+    let width = value.len() * 8;
+    let count = u16::from_le_bytes([*count_lo, *count_hi]) as usize % (width + 64);
+    let reversed = flags & 1 == 1;
+
+    // Oracle: the value as a bit vector of any length, most significant first
+    let bits: Vec<bool> = value.iter().flat_map(|byte| (0..8).rev().map(move |ix| byte >> ix & 1 == 1)).collect();
+    let kept = width.saturating_sub(count);
+    let shl: Vec<bool> = bits[width - kept..].iter().copied().chain(std::iter::repeat_n(false, width - kept)).collect();
+    let shr: Vec<bool> = std::iter::repeat_n(false, width - kept).chain(bits[..kept].iter().copied()).collect();
+
+    let run = |op: &dyn Fn(&mut ByteString)| -> Vec<bool> {
+        let mut raw = value.to_vec();
+        if reversed { raw.reverse(); }
+
+        let mut bytes = ByteString::new(&mut raw);
+        if reversed { bytes.interpret_reverse_endian(); }
+        op(&mut bytes);
+
+        bytes.iter().flat_map(|byte| (0..8).rev().map(move |ix| byte >> ix & 1 == 1)).collect()
+    };
+
+    assert_eq!(run(&|bytes| *bytes <<= count), shl, "<<= {}", count);
+    assert_eq!(run(&|bytes| *bytes >>= count), shr, ">>= {}", count);
+    assert_eq!(run(&|bytes| bytes.scale_shr_round(count, Rounding::Floor)), shr, "scale_shr_round {}", count);
+});
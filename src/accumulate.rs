@@ -0,0 +1,184 @@
+//! Running XOR and sum accumulators over a stream of equally long chunks
+
+use crate::bytes::ByteString;
+
+/// Folds a stream of chunks into a fixed length state, cycling over the state.
+///
+/// Stream position `i` lands on logical state byte `i % state_len`, regardless of how the stream is split into chunks.
+///
+/// ```
+/// use rebite::{accumulate::Accumulator, bytes::ByteString};
+///
+/// let mut raw = [0u8; 2];
+/// let mut acc = Accumulator::new(ByteString::new(&mut raw));
+///
+/// // However the stream is split, position i lands on state byte i % 2
+/// acc.xor_chunk(&[0x01]);
+/// acc.xor_chunk(&[0x02, 0x04]);
+/// assert_eq!(acc.finish().bytes(), [0x05, 0x02]);
+/// ```
+pub struct Accumulator<'a> {
+    state: ByteString<'a>,
+    offset: usize
+}
+
+impl<'a> Accumulator<'a> {
+
+    /// Accumulate into `state`, starting from its current bytes.
+    ///
+    /// ```
+    /// use rebite::{accumulate::Accumulator, bytes::ByteString};
+    ///
+    /// let mut raw = [0xF0u8, 0x0F];
+    /// let mut acc = Accumulator::new(ByteString::new(&mut raw));
+    ///
+    /// // The existing bytes are the starting state, not zeros
+    /// acc.xor_chunk(&[0xFF, 0xFF]);
+    /// assert_eq!(acc.finish().bytes(), [0x0F, 0xF0]);
+    /// ```
+    pub fn new(state: ByteString<'a>) -> Self {
+        Self { state, offset: 0 }
+    }
+
+    /// XOR the chunk into the state.
+    ///
+    /// ```
+    /// use rebite::{accumulate::Accumulator, bytes::ByteString};
+    ///
+    /// let mut raw = [0u8; 2];
+    /// let mut acc = Accumulator::new(ByteString::new(&mut raw));
+    ///
+    /// // A chunk longer than the state wraps around, and the next chunk continues where it stopped
+    /// acc.xor_chunk(&[0x01, 0x02, 0x04]);
+    /// acc.xor_chunk(&[0x08]);
+    /// assert_eq!(acc.finish().bytes(), [0x05, 0x0A]);
+    /// ```
+    pub fn xor_chunk(&mut self, chunk: &[u8]) {
+        self.fold(chunk, |state, byte| state ^ byte);
+    }
+
+    /// Add each chunk byte to its state byte, wrapping modulo 256.
+    ///
+    /// ```
+    /// use rebite::{accumulate::Accumulator, bytes::ByteString};
+    ///
+    /// let mut raw = [0xFFu8, 0x00];
+    /// let mut acc = Accumulator::new(ByteString::new(&mut raw));
+    ///
+    /// // No carry moves into the neighbouring byte
+    /// acc.add_chunk(&[0x02, 0x01]);
+    /// assert_eq!(acc.finish().bytes(), [0x01, 0x01]);
+    /// ```
+    pub fn add_chunk(&mut self, chunk: &[u8]) {
+        self.fold(chunk, |state, byte| state.wrapping_add(byte));
+    }
+
+    /// The folded state, the next chunk starts again at state byte 0.
+    ///
+    /// ```
+    /// use rebite::{accumulate::Accumulator, bytes::ByteString};
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let mut acc = Accumulator::new(ByteString::new(&mut raw));
+    ///
+    /// acc.xor_chunk(&[0x11]);
+    /// assert_eq!(acc.finish().bytes(), [0x11, 0x00, 0x00, 0x00]);
+    ///
+    /// // Restarted at byte 0 rather than continuing at byte 1
+    /// acc.xor_chunk(&[0x22]);
+    /// assert_eq!(acc.finish().bytes(), [0x33, 0x00, 0x00, 0x00]);
+    /// ```
+    pub fn finish(&mut self) -> &ByteString<'a> {
+        self.offset = 0;
+        &self.state
+    }
+
+    /// Give back the state.
+    ///
+    /// ```
+    /// use rebite::{accumulate::Accumulator, bytes::ByteString};
+    ///
+    /// let mut raw = [0u8; 2];
+    /// let mut acc = Accumulator::new(ByteString::new(&mut raw));
+    /// acc.add_chunk(&[1, 2, 3]);
+    ///
+    /// let state = acc.into_inner();
+    /// assert_eq!(state.bytes(), [4, 2]);
+    /// ```
+    pub fn into_inner(self) -> ByteString<'a> {
+        self.state
+    }
+
+    fn fold<F: Fn(u8, u8) -> u8>(&mut self, chunk: &[u8], f: F) {
+
+        let len = self.state.byte_len();
+        if len == 0 {
+            return;
+        }
+
+        for byte in chunk {
+            let ix = self.state.physical_index(self.offset);
+            self.state.bytes[ix] = f(self.state.bytes[ix], *byte);
+            self.offset = (self.offset + 1) % len;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::{view, XorShift};
+
+    fn fold_split(data: &[u8], state_len: usize, splits: &[usize], reversed: bool, add: bool) -> Vec<u8> {
+        let mut raw = vec![0x11u8; state_len];
+        let state = view(&mut raw, reversed);
+
+        let mut acc = Accumulator::new(state);
+        let mut rest = data;
+        for split in splits {
+            let (chunk, tail) = rest.split_at((*split).min(rest.len()));
+            if add { acc.add_chunk(chunk); } else { acc.xor_chunk(chunk); }
+            rest = tail;
+        }
+        if add { acc.add_chunk(rest); } else { acc.xor_chunk(rest); }
+
+        acc.finish().iter().copied().collect()
+    }
+
+    #[test]
+    fn test_splits_match_single_call() {
+        let mut rng = XorShift::new(0x231);
+
+        for _ in 0..300 {
+            let mut data = vec![0u8; rng.below(100)];
+            rng.fill(&mut data);
+            let state_len = rng.below(9);
+            let splits: Vec<usize> = (0..rng.below(6)).map(|_| rng.below(20)).collect();
+            let reversed = rng.next_bool();
+
+            for add in [false, true] {
+                assert_eq!(fold_split(&data, state_len, &splits, reversed, add), fold_split(&data, state_len, &[], reversed, add));
+            }
+        }
+    }
+
+    #[test]
+    fn test_cycling_positions() {
+        assert_eq!(fold_split(&[1, 2, 3, 4, 5], 2, &[1, 3], false, false), [0x11 ^ 1 ^ 3 ^ 5, 0x11 ^ 2 ^ 4]);
+        assert_eq!(fold_split(&[0xF0, 0x20, 0x01], 2, &[2], true, true), [0x11u8.wrapping_add(0xF0).wrapping_add(0x01), 0x31]);
+    }
+
+    #[test]
+    fn test_finish_restarts_alignment() {
+        let mut raw = [0u8; 3];
+        let mut acc = Accumulator::new(ByteString::new(&mut raw));
+
+        acc.xor_chunk(&[1, 2]);
+        acc.finish();
+        acc.xor_chunk(&[4]);
+
+        acc.into_inner();
+        assert_eq!(raw, [5, 2, 0]);
+    }
+}
@@ -0,0 +1,1099 @@
+use std::{
+    cmp::Ordering,
+    iter
+};
+
+use crate::{
+    bytes::ByteString,
+    error::Error
+};
+
+impl<'a> ByteString<'a> {
+
+    /// out = a + b, returns the carry out of the most significant byte of `out`.
+    ///
+    /// The inputs are zero-extended to the length of `out` and may not be longer.
+    /// `out` cannot alias an input, taking it as a separate exclusive borrow enforces that.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let (mut a, mut b, mut out) = ([0xFFu8], [0x01u8, 0x00], [0u8; 2]);
+    /// let (a, b) = (ByteString::new(&mut a), ByteString::new(&mut b));
+    /// let mut out = ByteString::new(&mut out);
+    ///
+    /// // 0xFF + 0x100 fits two bytes, the carry only reports overflow of `out`
+    /// assert_eq!(ByteString::add_into(&a, &b, &mut out), Ok(false));
+    /// assert_eq!(out.to_u64(), Ok(0x01FF));
+    ///
+    /// // An input longer than `out` is rejected
+    /// let mut short = [0u8; 1];
+    /// assert_eq!(ByteString::add_into(&a, &b, &mut ByteString::new(&mut short)), Err(Error::OutputTooSmall));
+    /// ```
+    pub fn add_into(a: &ByteString, b: &ByteString, out: &mut ByteString) -> Result<bool, Error> {
+        combine_into(a, b, out, |x, y, carry| {
+            let v = x as u16 + y as u16 + carry as u16;
+            (v as u8, v > 0xFF)
+        })
+    }
+
+    /// out = a - b wrapping at the width of `out`, returns whether a borrow occurred (a < b).
+    ///
+    /// Same length rules as [`ByteString::add_into`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let (mut a, mut b, mut out) = ([0x01u8], [0x02u8], [0u8; 2]);
+    /// let (a, b) = (ByteString::new(&mut a), ByteString::new(&mut b));
+    /// let mut out = ByteString::new(&mut out);
+    ///
+    /// // 1 - 2 wraps at the two bytes of `out`
+    /// assert_eq!(ByteString::sub_into(&a, &b, &mut out), Ok(true));
+    /// assert_eq!(out.to_u64(), Ok(0xFFFF));
+    /// ```
+    pub fn sub_into(a: &ByteString, b: &ByteString, out: &mut ByteString) -> Result<bool, Error> {
+        combine_into(a, b, out, |x, y, borrow| {
+            let (v, under_a) = x.overflowing_sub(y);
+            let (v, under_b) = v.overflowing_sub(borrow as u8);
+            (v, under_a || under_b)
+        })
+    }
+
+    /// self = self + rhs wrapping like `+=`, returns whether the exact sum did not fit.
+    ///
+    /// That is the carry out of the most significant byte of self, a longer rhs also overflows if
+    /// it has nonzero bytes past the width of self.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let (mut a, mut b) = ([0x00u8, 0x01], [0x01u8, 0x00, 0x00]);
+    /// let mut bytes = ByteString::new(&mut a);
+    ///
+    /// // The low bytes of rhs add without a carry, its third byte is lost
+    /// assert!(bytes.overflowing_add_assign(&ByteString::new(&mut b)));
+    /// assert_eq!(bytes.to_u64(), Ok(0x0001));
+    /// ```
+    pub fn overflowing_add_assign(&mut self, rhs: &ByteString) -> bool {
+        let width = self.byte_len();
+        let rhs_low = rhs.iter().rev().chain(iter::repeat(&0));
+
+        let mut carry = 0u16;
+        for (a, b) in self.iter_mut().rev().zip(rhs_low) {
+            let sum = *a as u16 + *b as u16 + carry;
+            *a = sum as u8;
+            carry = sum >> 8;
+        }
+
+        carry != 0 || rhs.iter().rev().skip(width).any(|b| *b != 0)
+    }
+
+    /// self = self + value wrapping like `+= value`, returns whether the exact sum did not fit.
+    ///
+    /// As with [`ByteString::overflowing_add_assign`], bytes of `value` past the width of self count as overflow.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x00u8, 0xFF];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// assert!(!bytes.add_assign_scalar(1));
+    /// assert_eq!(bytes.to_u64(), Ok(0x0100));
+    ///
+    /// // 0x1_0000 has no bits inside the two bytes, it only overflows
+    /// assert!(bytes.add_assign_scalar(0x1_0000));
+    /// assert_eq!(bytes.to_u64(), Ok(0x0100));
+    /// ```
+    pub fn add_assign_scalar(&mut self, value: u64) -> bool {
+        let mut rest = value;
+        let mut carry = 0u16;
+
+        for byte in self.iter_mut().rev() {
+            if rest == 0 && carry == 0 {
+                return false;
+            }
+
+            let sum = *byte as u16 + (rest & 0xFF) as u16 + carry;
+            *byte = sum as u8;
+            carry = sum >> 8;
+            rest >>= 8;
+        }
+
+        rest != 0 || carry != 0
+    }
+
+    /// self = self - rhs if rhs is not numerically larger, returns whether the subtraction happened.
+    ///
+    /// Unlike `-=` this never wraps, an underflow leaves self untouched and returns false.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let (mut a, mut two, mut five) = ([0x03u8], [0x02u8], [0x05u8]);
+    /// let mut bytes = ByteString::new(&mut a);
+    ///
+    /// assert!(!bytes.checked_sub_assign(&ByteString::new(&mut five)));
+    /// assert_eq!(bytes.to_u64(), Ok(3));
+    /// assert!(bytes.checked_sub_assign(&ByteString::new(&mut two)));
+    /// assert_eq!(bytes.to_u64(), Ok(1));
+    /// ```
+    pub fn checked_sub_assign(&mut self, rhs: &ByteString) -> bool {
+        if self.cmp_numeric(rhs) == Ordering::Less {
+            return false;
+        }
+
+        self.wrapping_sub_assign(rhs);
+        true
+    }
+
+    /// self = self + rhs clamped to all 0xFF bytes, returns whether the sum was clamped.
+    ///
+    /// Same alignment and overflow rule as [`ByteString::overflowing_add_assign`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let (mut a, mut b) = ([0xFFu8, 0x00], [0x01u8, 0x00]);
+    /// let mut bytes = ByteString::new(&mut a);
+    ///
+    /// assert!(bytes.saturating_add_assign(&ByteString::new(&mut b)));
+    /// assert_eq!(bytes.bytes(), [0xFF, 0xFF]);
+    /// ```
+    pub fn saturating_add_assign(&mut self, rhs: &ByteString) -> bool {
+        let overflow = self.overflowing_add_assign(rhs);
+        if overflow {
+            self.set_bytes_with_value(0xFF);
+        }
+
+        overflow
+    }
+
+    /// self = self - rhs clamped to zero, returns whether the difference was clamped.
+    ///
+    /// A shorter rhs is zero-extended, a longer one underflows if it is numerically larger.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let (mut a, mut b) = ([0x01u8, 0x00], [0x00u8, 0x01, 0x01]);
+    /// let mut bytes = ByteString::new(&mut a);
+    ///
+    /// // 0x0100 - 0x000101 clamps to zero
+    /// assert!(bytes.saturating_sub_assign(&ByteString::new(&mut b)));
+    /// assert!(bytes.is_zero());
+    /// ```
+    pub fn saturating_sub_assign(&mut self, rhs: &ByteString) -> bool {
+        let underflow = self.wrapping_sub_assign(rhs);
+        if underflow {
+            self.set_zero();
+        }
+
+        underflow
+    }
+
+    /// self = |self - rhs| as unsigned integers, returns whether the difference did not fit.
+    ///
+    /// A shorter rhs is zero-extended. Only a longer rhs can exceed the width of self, the low
+    /// bytes of the difference are kept then. Works in place without scratch memory.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let (mut a, mut b) = ([0x01u8, 0x00], [0x01u8, 0x02]);
+    /// let mut bytes = ByteString::new(&mut a);
+    ///
+    /// assert!(!bytes.abs_diff_assign(&ByteString::new(&mut b)));
+    /// assert_eq!(bytes.to_u64(), Ok(0x02));
+    /// ```
+    pub fn abs_diff_assign(&mut self, rhs: &ByteString) -> bool {
+        if self.cmp_numeric(rhs) != Ordering::Less {
+            self.wrapping_sub_assign(rhs);
+            return false;
+        }
+
+        // rhs - self = -(self - rhs), exact modulo the width of self
+        self.wrapping_sub_assign(rhs);
+        self.negate();
+
+        // The exact difference is self + (high - borrow) * 2^width, with `high` the bytes of rhs
+        // past the width and a borrow exactly when the low bytes of rhs are below the result
+        let extra = rhs.byte_len().saturating_sub(self.byte_len());
+        let high = match extra {
+            0 => 0,
+            _ if rhs.iter().take(extra - 1).any(|e| *e != 0) => 2,
+            _ => rhs.iter().nth(extra - 1).copied().unwrap().min(2),
+        };
+
+        match high {
+            0 => false,
+            1 => self.iter().cmp(rhs.iter().skip(extra)) != Ordering::Greater,
+            _ => true,
+        }
+    }
+
+    /// Two's complement negation in place, returns the carry out of the most significant byte.
+    ///
+    /// The carry is set only when negating zero. The most negative value, `0x80 00 .. 00`, negates
+    /// to itself without a carry.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x00u8, 0x01];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// assert!(!bytes.negate());
+    /// assert_eq!(bytes.bytes(), [0xFF, 0xFF]);
+    ///
+    /// let mut min = [0x80u8, 0x00];
+    /// let mut bytes = ByteString::new(&mut min);
+    /// assert!(!bytes.negate());
+    /// assert_eq!(bytes.bytes(), [0x80, 0x00]);
+    ///
+    /// let mut zero = [0u8; 2];
+    /// assert!(ByteString::new(&mut zero).negate());
+    /// ```
+    pub fn negate(&mut self) -> bool {
+        // Not and add one in a single pass from the least significant byte
+        let mut carry = true;
+        for byte in self.iter_mut().rev() {
+            let (v, c) = (!*byte).overflowing_add(carry as u8);
+            *byte = v;
+            carry = c;
+        }
+
+        carry
+    }
+
+    /// self = self + 1, returns whether the value wrapped to zero.
+    ///
+    /// Stops at the first byte that does not carry, so most calls touch a single byte.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x00u8, 0xFF];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// // Reversed, the physical first byte is the least significant one
+    /// bytes.interpret_reverse_endian();
+    /// assert!(!bytes.increment());
+    /// assert_eq!(raw, [0x01, 0xFF]);
+    ///
+    /// let mut max = [0xFFu8; 2];
+    /// assert!(ByteString::new(&mut max).increment());
+    /// assert_eq!(max, [0x00, 0x00]);
+    /// ```
+    pub fn increment(&mut self) -> bool {
+        for byte in self.iter_mut().rev() {
+            let (v, carry) = byte.overflowing_add(1);
+            *byte = v;
+            if !carry {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// self = self - 1, returns whether the value wrapped to all ones.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x01u8, 0x00];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// assert!(!bytes.decrement());
+    /// assert_eq!(bytes.bytes(), [0x00, 0xFF]);
+    ///
+    /// let mut zero = [0u8; 2];
+    /// assert!(ByteString::new(&mut zero).decrement());
+    /// assert_eq!(zero, [0xFF, 0xFF]);
+    /// ```
+    pub fn decrement(&mut self) -> bool {
+        for byte in self.iter_mut().rev() {
+            let (v, borrow) = byte.overflowing_sub(1);
+            *byte = v;
+            if !borrow {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// self = self / divisor rounded down, the non-panicking form of `/=`.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let (mut a, mut seven, mut zero) = ([0x00u8, 0x64], [0x07u8], [0x00u8, 0x00]);
+    /// let mut bytes = ByteString::new(&mut a);
+    ///
+    /// assert_eq!(bytes.try_div_assign(&ByteString::new(&mut zero)), Err(Error::DivisionByZero));
+    /// assert_eq!(bytes.try_div_assign(&ByteString::new(&mut seven)), Ok(()));
+    /// assert_eq!(bytes.to_u64(), Ok(14));
+    /// ```
+    pub fn try_div_assign(&mut self, divisor: &ByteString) -> Result<(), Error> {
+        self.shift_subtract(divisor).map(|_| ())
+    }
+
+    /// self = self mod divisor, the non-panicking form of `%=`.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let (mut a, mut wide) = ([0x64u8], [0x00u8, 0x00, 0x07]);
+    /// let mut bytes = ByteString::new(&mut a);
+    ///
+    /// // A divisor wider than self is fine, the remainder always fits
+    /// assert_eq!(bytes.try_rem_assign(&ByteString::new(&mut wide)), Ok(()));
+    /// assert_eq!(bytes.to_u64(), Ok(2));
+    /// ```
+    pub fn try_rem_assign(&mut self, divisor: &ByteString) -> Result<(), Error> {
+        let remainder = self.shift_subtract(divisor)?;
+
+        // The remainder is below the dividend, so it fits whatever the divisor width
+        let mut low = remainder.iter().rev().chain(iter::repeat(&0));
+        for byte in self.iter_mut().rev() {
+            *byte = *low.next().unwrap();
+        }
+
+        Ok(())
+    }
+
+    /// Quotient into self and remainder into `remainder`, from a single long division.
+    ///
+    /// `remainder` needs room for the significant bytes of `divisor`, or of self if that is shorter,
+    /// and is zero-extended. Fails with [`Error::DivisionByZero`] or [`Error::OutputTooSmall`]
+    /// before anything is written.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let (mut a, mut d, mut r) = ([0x01u8, 0x00], [0x00u8, 0x00, 0x07], [0xAAu8; 2]);
+    /// let mut bytes = ByteString::new(&mut a);
+    /// let divisor = ByteString::new(&mut d);
+    ///
+    /// // One byte of remainder suffices for a divisor with one significant byte
+    /// assert_eq!(bytes.div_rem(&divisor, &mut ByteString::new(&mut r[..1])), Ok(()));
+    /// assert_eq!(bytes.to_u64(), Ok(36));
+    /// assert_eq!(r, [0x04, 0xAA]);
+    ///
+    /// let mut none = [0u8; 0];
+    /// assert_eq!(bytes.div_rem(&divisor, &mut ByteString::new(&mut none)), Err(Error::OutputTooSmall));
+    /// assert_eq!(bytes.to_u64(), Ok(36));
+    /// ```
+    pub fn div_rem(&mut self, divisor: &ByteString, remainder: &mut ByteString) -> Result<(), Error> {
+
+        let needed = (divisor.byte_len() - divisor.leading_zero_len()).min(self.byte_len());
+        if divisor.is_zero() {
+            return Err(Error::DivisionByZero);
+        }
+        if remainder.byte_len() < needed {
+            return Err(Error::OutputTooSmall);
+        }
+
+        let rest = self.shift_subtract(divisor)?;
+
+        let mut low = rest.iter().rev().chain(iter::repeat(&0));
+        for byte in remainder.iter_mut().rev() {
+            *byte = *low.next().unwrap();
+        }
+
+        Ok(())
+    }
+
+    // Binary long division: leaves the quotient in self and returns the remainder as logical bytes,
+    // one longer than the divisor so the doubled partial remainder cannot overflow
+    pub(crate) fn shift_subtract(&mut self, divisor: &ByteString) -> Result<Vec<u8>, Error> {
+
+        if divisor.is_zero() {
+            return Err(Error::DivisionByZero);
+        }
+
+        let mut remainder = vec![0u8; divisor.byte_len() + 1];
+        let mut partial = ByteString::new(&mut remainder);
+
+        // Every quotient bit is written only after the dividend bit at the same offset was consumed
+        for offset in (0..self.bit_len()).rev() {
+            partial <<= 1;
+            partial.set_bit(0, self.bit(offset));
+
+            let fits = partial.cmp_numeric(divisor) != Ordering::Less;
+            if fits {
+                partial -= divisor;
+            }
+            self.set_bit(offset, fits);
+        }
+
+        Ok(remainder)
+    }
+
+    /// self = self * mul + add, returns the part of the result that did not fit
+    pub(crate) fn mul_add_small(&mut self, mul: u32, add: u32) -> u32 {
+
+        let mut carry = add as u64;
+
+        // Least significant byte is the last logical byte
+        for byte in self.iter_mut().rev() {
+            let v = *byte as u64 * mul as u64 + carry;
+            *byte = v as u8;
+            carry = v >> 8;
+        }
+
+        carry as u32
+    }
+
+    /// self = self / div, returns the remainder, `div` must be nonzero
+    pub(crate) fn div_rem_small(&mut self, div: u32) -> u32 {
+
+        let mut rem = 0u64;
+
+        // Most significant byte is the first logical byte
+        for byte in self.iter_mut() {
+            let v = (rem << 8) | *byte as u64;
+            *byte = (v / div as u64) as u8;
+            rem = v % div as u64;
+        }
+
+        rem as u32
+    }
+
+    /// Bit at `offset` counted from the least significant bit of the value, panics if out of range.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x00u8, 0x04];
+    /// assert!(ByteString::new(&mut raw).bit(2));
+    /// ```
+    #[track_caller]
+    pub fn bit(&self, offset: usize) -> bool {
+        self.try_bit(offset).expect("bit offset out of range")
+    }
+
+    /// Non-panicking form of [`ByteString::bit`].
+    ///
+    /// Offsets count from the least significant bit of the value whatever the interpretation flag.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let mut raw = [0x80u8, 0x01];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// assert_eq!(bytes.try_bit(0), Ok(true));
+    /// assert_eq!(bytes.try_bit(15), Ok(true));
+    ///
+    /// bytes.interpret_reverse_endian();
+    /// assert_eq!(bytes.try_bit(0), Ok(false));
+    /// assert_eq!(bytes.try_bit(7), Ok(true));
+    /// assert_eq!(bytes.try_bit(16), Err(Error::OutOfBounds));
+    /// ```
+    pub fn try_bit(&self, offset: usize) -> Result<bool, Error> {
+        let ix = self.bit_byte_index(offset)?;
+        Ok(self.bytes[ix] >> (offset % 8) & 1 == 1)
+    }
+
+    /// Set the bit at `offset` counted from the least significant bit of the value, panics if out of range.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x00u8, 0x00];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    ///
+    /// // Offset 8 is in the more significant byte, physically the last one when reversed
+    /// bytes.set_bit(8, true);
+    /// assert_eq!(raw, [0x00, 0x01]);
+    /// ```
+    #[track_caller]
+    pub fn set_bit(&mut self, offset: usize, value: bool) {
+        self.try_set_bit(offset, value).expect("bit offset out of range")
+    }
+
+    /// Non-panicking form of [`ByteString::set_bit`].
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let mut raw = [0xFFu8];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.try_set_bit(7, false), Ok(()));
+    /// assert_eq!(bytes.try_set_bit(8, true), Err(Error::OutOfBounds));
+    /// assert_eq!(raw, [0x7F]);
+    /// ```
+    pub fn try_set_bit(&mut self, offset: usize, value: bool) -> Result<(), Error> {
+        let ix = self.bit_byte_index(offset)?;
+        let mask = 1 << (offset % 8);
+
+        if value { self.bytes[ix] |= mask; } else { self.bytes[ix] &= !mask; }
+        Ok(())
+    }
+
+    // Physical index of the byte holding the bit at `offset`
+    fn bit_byte_index(&self, offset: usize) -> Result<usize, Error> {
+        if offset >= self.bit_len() {
+            return Err(Error::OutOfBounds);
+        }
+
+        Ok(self.physical_index(self.byte_len() - 1 - offset / 8))
+    }
+
+    // Same alignment as `+=`, a borrow out of the most significant byte wraps. Returns whether the
+    // exact difference was negative, counting nonzero bytes of a longer rhs past the width of self
+    pub(crate) fn wrapping_sub_assign(&mut self, rhs: &ByteString) -> bool {
+        let width = self.byte_len();
+        let rhs_low = rhs.iter().rev().chain(iter::repeat(&0));
+
+        let mut borrow = false;
+        for (a, b) in self.iter_mut().rev().zip(rhs_low) {
+            let (v, under_a) = a.overflowing_sub(*b);
+            let (v, under_b) = v.overflowing_sub(borrow as u8);
+            *a = v;
+            borrow = under_a || under_b;
+        }
+
+        borrow || rhs.iter().rev().skip(width).any(|b| *b != 0)
+    }
+
+    /// Logical left shift by `n` bits below the bit length, one logical byte at a time
+    pub(crate) fn shl_bits(&mut self, n: usize) {
+
+        let shift_per_byte = n % 8;
+        let shifted_out_bytes = n / 8;
+
+        // Perform bitshift: ignore top bytes that would be shifted out and start with lowest byte
+        if shift_per_byte > 0 {
+            let mut carry_bits = 0;
+            for byte in self.iter_mut().skip(shifted_out_bytes).rev() {
+                let tmp_carry_bits = *byte >> (8 - shift_per_byte);
+                *byte <<= shift_per_byte;
+                *byte |= carry_bits;
+                carry_bits = tmp_carry_bits;
+            }
+        }
+
+        // Zeroize shifted out bytes
+        for byte in self.iter_mut().take(shifted_out_bytes) {
+            *byte = 0;
+        }
+
+        // Rotate leading zeros and rest of bytes in place
+        self.rotl_bytes(shifted_out_bytes);
+    }
+
+    /// Logical right shift by `n` bits, shifting in zeros
+    pub(crate) fn shr_bits(&mut self, n: usize) {
+
+        if n >= self.bit_len() {
+            self.set_zero();
+            return;
+        }
+
+        let byte_shift = n / 8;
+        let bit_shift = n % 8;
+
+        // Walk from the least significant byte so every source is read before it is overwritten
+        for ix in (0..self.byte_len()).rev() {
+
+            let high = if ix >= byte_shift { self.bytes[self.physical_index(ix - byte_shift)] } else { 0 };
+            let low = if ix > byte_shift { self.bytes[self.physical_index(ix - byte_shift - 1)] } else { 0 };
+
+            let value = if bit_shift == 0 { high } else { high >> bit_shift | low << (8 - bit_shift) };
+
+            let dst = self.physical_index(ix);
+            self.bytes[dst] = value;
+        }
+    }
+}
+
+// Ripple `op` from the least significant byte up, threading the carry flag
+fn combine_into<F>(a: &ByteString, b: &ByteString, out: &mut ByteString, op: F) -> Result<bool, Error>
+where F: Fn(u8, u8, bool) -> (u8, bool) {
+
+    if a.byte_len() > out.byte_len() || b.byte_len() > out.byte_len() {
+        return Err(Error::OutputTooSmall);
+    }
+
+    let a_bytes = a.iter().rev().copied().chain(iter::repeat(0));
+    let b_bytes = b.iter().rev().copied().chain(iter::repeat(0));
+
+    let mut carry = false;
+    for ((dst, x), y) in out.iter_mut().rev().zip(a_bytes).zip(b_bytes) {
+        let (v, c) = op(x, y, carry);
+        *dst = v;
+        carry = c;
+    }
+
+    Ok(carry)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::{view_logical, XorShift};
+
+    #[test]
+    fn test_add_sub_into_against_u128() {
+        let mut rng = XorShift::new(0x232);
+
+        for _ in 0..2000 {
+            let out_len = 1 + rng.below(15);
+            let a_len = rng.below(out_len + 1);
+            let b_len = rng.below(out_len + 1);
+            let value = |rng: &mut XorShift, len: usize| if len == 0 { 0 } else { ((rng.next_u64() as u128) << 64 | rng.next_u64() as u128) >> (128 - len * 8) };
+
+            let (x, y) = (value(&mut rng, a_len), value(&mut rng, b_len));
+            let modulus = 1u128 << (out_len * 8);
+
+            let mut a = x.to_be_bytes()[16 - a_len..].to_vec();
+            let mut b = y.to_be_bytes()[16 - b_len..].to_vec();
+            let bytes_a = view_logical(&mut a, rng.next_bool());
+            let bytes_b = view_logical(&mut b, rng.next_bool());
+
+            for subtract in [false, true] {
+                let out_reversed = rng.next_bool();
+                let mut out = vec![0xEEu8; out_len];
+                let mut bytes_out = view_logical(&mut out, out_reversed);
+
+                let flag = if subtract { ByteString::sub_into(&bytes_a, &bytes_b, &mut bytes_out) }
+                else { ByteString::add_into(&bytes_a, &bytes_b, &mut bytes_out) }.unwrap();
+
+                let mut logical: Vec<u8> = bytes_out.iter().copied().collect();
+                logical.splice(0..0, iter::repeat_n(0, 16 - out_len));
+                let result = u128::from_be_bytes(logical.try_into().unwrap());
+
+                let (expected, expected_flag) = if subtract { ((x + modulus - y) % modulus, x < y) }
+                else { ((x + y) % modulus, x + y >= modulus) };
+
+                assert_eq!((result, flag), (expected, expected_flag), "{:#x} {:#x} sub={}", x, y, subtract);
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_into_too_short() {
+        let mut a = [1u8, 2, 3];
+        let mut b = [1u8];
+        let mut out = [0u8; 2];
+
+        let result = ByteString::add_into(&ByteString::new(&mut a), &ByteString::new(&mut b), &mut ByteString::new(&mut out));
+        assert_eq!(result, Err(Error::OutputTooSmall));
+    }
+
+    #[test]
+    fn test_overflowing_add_assign() {
+        for reversed in [false, true] {
+            let mut a = [0xFFu8];
+            let mut b = [0x01u8];
+            let b = view_logical(&mut b, reversed);
+            let mut bytes = view_logical(&mut a, reversed);
+            assert!(bytes.overflowing_add_assign(&b));
+            assert!(bytes.is_zero());
+
+            let mut a = [0x12u8, 0xFE];
+            let mut bytes = view_logical(&mut a, reversed);
+            assert!(!bytes.overflowing_add_assign(&b));
+            assert_eq!(bytes.to_u64(), Ok(0x12FF));
+
+            // Ripple through every byte of a shorter rhs and out of the top
+            let mut a = [0xFFu8, 0xFF, 0xFF, 0xFF];
+            let mut bytes = view_logical(&mut a, reversed);
+            assert!(bytes.overflowing_add_assign(&b));
+            assert!(bytes.is_zero());
+
+            let mut a = [0x00u8, 0xFF, 0xFF, 0xFF];
+            let mut bytes = view_logical(&mut a, reversed);
+            assert!(!bytes.overflowing_add_assign(&b));
+            assert_eq!(bytes.to_u64(), Ok(0x0100_0000));
+        }
+    }
+
+    #[test]
+    fn test_overflowing_add_assign_chains_limbs() {
+        let mut rng = XorShift::new(0x258);
+
+        for _ in 0..500 {
+            let (x, y) = (rng.next_u64() as u128 | (rng.next_u64() as u128) << 64, rng.next_u64() as u128 | (rng.next_u64() as u128) << 64);
+
+            // Two 8 byte halves, the carry of the low half feeds the high one
+            let (mut x_high, mut x_low) = (((x >> 64) as u64).to_be_bytes(), (x as u64).to_be_bytes());
+            let (mut y_high, mut y_low) = (((y >> 64) as u64).to_be_bytes(), (y as u64).to_be_bytes());
+            let mut one = [1u8];
+
+            let mut low = view_logical(&mut x_low, rng.next_bool());
+            let carry = low.overflowing_add_assign(&view_logical(&mut y_low, rng.next_bool()));
+            let mut high = view_logical(&mut x_high, rng.next_bool());
+            let mut overflow = high.overflowing_add_assign(&view_logical(&mut y_high, rng.next_bool()));
+            if carry {
+                overflow |= high.overflowing_add_assign(&ByteString::new(&mut one));
+            }
+
+            let (sum, expected) = x.overflowing_add(y);
+            assert_eq!((high.to_u64(), overflow), (Ok((sum >> 64) as u64), expected));
+            assert_eq!(low.to_u64(), Ok(sum as u64));
+        }
+    }
+
+    #[test]
+    fn test_overflowing_add_assign_longer_rhs() {
+        let mut a = [0x01u8];
+        let mut b = [0x00u8, 0x02];
+        let mut c = [0x01u8, 0x00];
+
+        assert!(!ByteString::new(&mut a).overflowing_add_assign(&ByteString::new(&mut b)));
+        assert!(ByteString::new(&mut a).overflowing_add_assign(&ByteString::new(&mut c)));
+        assert_eq!(a, [0x03]);
+    }
+
+    #[test]
+    fn test_checked_sub_assign() {
+        for reversed in [false, true] {
+            // Equal values
+            let mut a = [0x12u8, 0x34];
+            let mut b = [0x12u8, 0x34];
+            let b = view_logical(&mut b, reversed);
+            let mut bytes = view_logical(&mut a, reversed);
+            assert!(bytes.checked_sub_assign(&b));
+            assert!(bytes.is_zero());
+
+            // Larger by one leaves self untouched
+            let mut a = [0x12u8, 0x33];
+            let mut bytes = view_logical(&mut a, reversed);
+            assert!(!bytes.checked_sub_assign(&b));
+            assert_eq!(bytes.to_u64(), Ok(0x1233));
+
+            let mut a = [0x01u8, 0x00, 0x00];
+            let mut bytes = view_logical(&mut a, reversed);
+            assert!(bytes.checked_sub_assign(&b));
+            assert_eq!(bytes.to_u64(), Ok(0xEDCC));
+        }
+    }
+
+    #[test]
+    fn test_checked_sub_assign_mismatched_lengths() {
+        // A longer rhs with leading zeros is fine, a longer significant one underflows
+        let mut a = [0x05u8];
+        let mut b = [0x00u8, 0x00, 0x03];
+        let mut c = [0x01u8, 0x00];
+
+        let mut bytes = ByteString::new(&mut a);
+        assert!(bytes.checked_sub_assign(&ByteString::new(&mut b)));
+        assert!(!bytes.checked_sub_assign(&ByteString::new(&mut c)));
+        assert_eq!(a, [0x02]);
+
+        let mut wide = [0x00u8, 0x01, 0x00, 0x00];
+        let mut one = [0x01u8];
+        let mut bytes = ByteString::new(&mut wide);
+        assert!(bytes.checked_sub_assign(&ByteString::new(&mut one)));
+        assert_eq!(wide, [0x00, 0x00, 0xFF, 0xFF]);
+    }
+
+    #[test]
+    fn test_saturating_clamps() {
+        for reversed in [false, true] {
+            let mut one = [0x01u8];
+            let one = view_logical(&mut one, reversed);
+
+            let mut a = [0xFFu8, 0xFF];
+            let mut bytes = view_logical(&mut a, reversed);
+            assert!(bytes.saturating_add_assign(&one));
+            assert_eq!(bytes.bytes(), [0xFF, 0xFF]);
+
+            let mut a = [0x80u8, 0x00];
+            let mut b = [0x80u8, 0x01];
+            let mut bytes = view_logical(&mut a, reversed);
+            assert!(bytes.saturating_add_assign(&view_logical(&mut b, !reversed)));
+            assert_eq!(bytes.bytes(), [0xFF, 0xFF]);
+
+            let mut a = [0x00u8, 0x00];
+            let mut bytes = view_logical(&mut a, reversed);
+            assert!(bytes.saturating_sub_assign(&one));
+            assert!(bytes.is_zero());
+
+            let mut a = [0x12u8, 0x34];
+            let mut b = [0x12u8, 0x35];
+            let mut bytes = view_logical(&mut a, reversed);
+            assert!(bytes.saturating_sub_assign(&view_logical(&mut b, !reversed)));
+            assert!(bytes.is_zero());
+        }
+    }
+
+    #[test]
+    fn test_saturating_mismatched_lengths() {
+        // Significant bytes of a longer rhs clamp, leading zeros do not
+        let mut a = [0x05u8];
+        let mut zeros = [0x00u8, 0x00, 0x03];
+        let mut wide = [0x01u8, 0x00];
+
+        let mut bytes = ByteString::new(&mut a);
+        assert!(!bytes.saturating_sub_assign(&ByteString::new(&mut zeros)));
+        assert!(!bytes.saturating_add_assign(&ByteString::new(&mut zeros)));
+        assert_eq!(bytes.to_u64(), Ok(0x05));
+        assert!(bytes.saturating_add_assign(&ByteString::new(&mut wide)));
+        assert_eq!(a, [0xFF]);
+
+        let mut bytes = ByteString::new(&mut a);
+        assert!(bytes.saturating_sub_assign(&ByteString::new(&mut wide)));
+        assert_eq!(a, [0x00]);
+    }
+
+    #[test]
+    fn test_saturating_matches_wrapping_without_overflow() {
+        let mut rng = XorShift::new(0x264);
+
+        for _ in 0..1000 {
+            let mut a = vec![0u8; 1 + rng.below(12)];
+            let mut b = vec![0u8; 1 + rng.below(12)];
+            rng.fill(&mut a);
+            rng.fill(&mut b);
+            let mut wrapping = a.clone();
+            let (subtract, reversed) = (rng.next_bool(), rng.next_bool());
+
+            let rhs = view_logical(&mut b, rng.next_bool());
+            let mut expected = view_logical(&mut wrapping, reversed);
+            let mut bytes = view_logical(&mut a, reversed);
+
+            let clamped = if subtract {
+                expected -= &rhs;
+                bytes.saturating_sub_assign(&rhs)
+            } else {
+                expected += &rhs;
+                bytes.saturating_add_assign(&rhs)
+            };
+
+            if clamped {
+                let fill = if subtract { 0x00 } else { 0xFF };
+                assert!(bytes.bytes().iter().all(|e| *e == fill));
+            } else {
+                assert_eq!(bytes.bytes(), expected.bytes());
+            }
+        }
+    }
+
+    #[test]
+    fn test_abs_diff_assign() {
+        for (lhs_reversed, rhs_reversed) in [(false, false), (false, true), (true, false), (true, true)] {
+            let cases: [(&[u8], &[u8], &[u8]); 4] = [
+                (&[0x12, 0x34], &[0x01, 0x35], &[0x10, 0xFF]),
+                (&[0x01, 0x35], &[0x12, 0x34], &[0x10, 0xFF]),
+                (&[0xAB, 0xCD], &[0xAB, 0xCD], &[0x00, 0x00]),
+                // Shorter rhs is zero-extended on both sides of the comparison
+                (&[0x00, 0x05], &[0x07], &[0x00, 0x02]),
+            ];
+
+            for (a, b, expected) in cases {
+                let (mut a, mut b, mut expected) = (a.to_vec(), b.to_vec(), expected.to_vec());
+                let rhs = view_logical(&mut b, rhs_reversed);
+                let mut bytes = view_logical(&mut a, lhs_reversed);
+
+                assert!(!bytes.abs_diff_assign(&rhs));
+                assert_eq!(bytes, view_logical(&mut expected, false));
+            }
+        }
+    }
+
+    #[test]
+    fn test_abs_diff_assign_against_u128() {
+        let mut rng = XorShift::new(0x270);
+
+        for _ in 0..2000 {
+            let (a_len, b_len) = (rng.below(8), rng.below(10));
+            let value = |rng: &mut XorShift, len: usize| {
+                let value = if len == 0 { 0 } else { rng.next_u64() as u128 >> (64 - len.min(8) * 8) };
+                // Small high parts reach the borderline overflow cases of a longer rhs
+                if len > 8 { value | (rng.below(3) as u128) << 64 } else { value }
+            };
+            let (x, y) = (value(&mut rng, a_len), value(&mut rng, b_len));
+
+            let mut a = x.to_be_bytes()[16 - a_len..].to_vec();
+            let mut b = y.to_be_bytes()[16 - b_len..].to_vec();
+            let rhs = view_logical(&mut b, rng.next_bool());
+            let mut bytes = view_logical(&mut a, rng.next_bool());
+
+            let overflow = bytes.abs_diff_assign(&rhs);
+            let diff = x.abs_diff(y);
+            let fits = diff >> (a_len * 8) == 0;
+
+            let mut logical: Vec<u8> = bytes.iter().copied().collect();
+            logical.splice(0..0, iter::repeat_n(0, 16 - a_len));
+            let result = u128::from_be_bytes(logical.try_into().unwrap());
+
+            let low = if a_len == 0 { 0 } else { diff & (u128::MAX >> (128 - a_len * 8)) };
+            assert_eq!((result, overflow), (low, !fits), "{:#x} {:#x} width={}", x, y, a_len);
+        }
+    }
+
+    #[test]
+    fn test_negate_edge_cases() {
+        for reversed in [false, true] {
+            let mut a = [0x00u8, 0x00, 0x00];
+            let mut bytes = view_logical(&mut a, reversed);
+            assert!(bytes.negate());
+            assert!(bytes.is_zero());
+
+            let mut a = [0x80u8, 0x00, 0x00];
+            let mut bytes = view_logical(&mut a, reversed);
+            assert!(!bytes.negate());
+            assert_eq!(bytes.to_u64(), Ok(0x80_0000));
+
+            let mut a = [0x00u8, 0x00, 0x01];
+            let mut bytes = view_logical(&mut a, reversed);
+            assert!(!bytes.negate());
+            assert_eq!(bytes.to_u64(), Ok(0xFF_FFFF));
+        }
+    }
+
+    #[test]
+    fn test_negate_against_i64() {
+        let mut rng = XorShift::new(0x260);
+
+        for _ in 0..500 {
+            let value = rng.next_u64() as i64;
+            let mut raw = value.to_be_bytes();
+            let mut bytes = view_logical(&mut raw, rng.next_bool());
+
+            assert_eq!(bytes.negate(), value == 0);
+            assert_eq!(bytes.to_u64(), Ok(value.wrapping_neg() as u64));
+        }
+    }
+
+    #[test]
+    fn test_increment_decrement_wrap() {
+        for reversed in [false, true] {
+            let mut a = [0xFFu8; 5];
+            let mut bytes = view_logical(&mut a, reversed);
+            assert!(bytes.increment());
+            assert!(bytes.is_zero());
+
+            assert!(bytes.decrement());
+            assert_eq!(bytes.count_ones(), 40);
+
+            let mut a = [0x12u8, 0x00, 0xFF];
+            let mut bytes = view_logical(&mut a, reversed);
+            assert!(!bytes.increment());
+            assert_eq!(bytes.to_u64(), Ok(0x12_0100));
+            assert!(!bytes.decrement());
+            assert!(!bytes.decrement());
+            assert_eq!(bytes.to_u64(), Ok(0x12_00FE));
+        }
+    }
+
+    #[test]
+    fn test_increment_stops_rippling() {
+        let mut a = [0xFFu8, 0xFE, 0xFF];
+        let mut bytes = ByteString::new(&mut a);
+        assert!(!bytes.increment());
+        assert_eq!(a, [0xFF, 0xFF, 0x00]);
+    }
+
+    #[test]
+    fn test_mul_add_small() {
+        let mut a = [0x00u8, 0x12, 0x34];
+        let mut bytes = ByteString::new(&mut a);
+
+        assert_eq!(bytes.mul_add_small(0x100, 0x56), 0);
+        assert!(bytes.iter().eq([0x12, 0x34, 0x56].iter()));
+
+        assert_eq!(bytes.mul_add_small(0x100, 0), 0x12);
+        assert!(bytes.iter().eq([0x34, 0x56, 0x00].iter()));
+    }
+
+    #[test]
+    fn test_div_rem_small() {
+        let mut a = [0x01u8, 0x00, 0x07];
+        let mut bytes = ByteString::new(&mut a);
+
+        assert_eq!(bytes.div_rem_small(10), (0x010007 % 10) as u32);
+        assert!(bytes.iter().eq(((0x010007u32 / 10).to_be_bytes()[1..]).iter()));
+    }
+
+    #[test]
+    fn test_shr_bits() {
+        let mut a = [0x12u8, 0x34, 0x56];
+        let mut bytes = ByteString::new(&mut a);
+
+        bytes.shr_bits(4);
+        assert_eq!(a, [0x01, 0x23, 0x45]);
+
+        let mut a = [0x56u8, 0x34, 0x12];
+        let mut bytes = ByteString::new(&mut a);
+        bytes.interpret_reverse_endian();
+
+        bytes.shr_bits(12);
+        assert!(bytes.bit(0) && !bytes.bit(2) && bytes.bit(8));
+        assert_eq!(a, [0x23, 0x01, 0x00]);
+    }
+
+    #[test]
+    fn test_mul_add_small_reversed() {
+        let mut a = [0x34u8, 0x12, 0x00];
+        let mut bytes = ByteString::new(&mut a);
+        bytes.interpret_reverse_endian();
+
+        assert_eq!(bytes.mul_add_small(10, 1), 0);
+        assert_eq!(a, [0x09, 0xB6, 0x00]);
+    }
+
+    #[test]
+    fn test_div_rem_reconstructs() {
+        let mut rng = XorShift::new(0x257);
+
+        for _ in 0..2000 {
+            let (a_len, b_len) = (1 + rng.below(16), 1 + rng.below(16));
+            let value = |rng: &mut XorShift, len: usize| ((rng.next_u64() as u128) << 64 | rng.next_u64() as u128) >> (128 - len * 8 + rng.below(len * 8));
+
+            let (x, y) = (value(&mut rng, a_len), value(&mut rng, b_len));
+            if y == 0 { continue; }
+
+            let mut a = x.to_be_bytes()[16 - a_len..].to_vec();
+            let mut b = y.to_be_bytes()[16 - b_len..].to_vec();
+            let mut quotient = view_logical(&mut a, rng.next_bool());
+            let divisor = view_logical(&mut b, rng.next_bool());
+
+            let r_len = b_len.min(a_len) + rng.below(3);
+            let mut r = vec![0xEEu8; r_len];
+            let mut remainder = view_logical(&mut r, rng.next_bool());
+
+            quotient.div_rem(&divisor, &mut remainder).unwrap();
+
+            // quotient * divisor + remainder, in a buffer wide enough for the dividend
+            let mut rebuilt = vec![0u8; a_len];
+            let mut product = ByteString::new(&mut rebuilt);
+            product.iter_mut().rev().zip(quotient.iter().rev()).for_each(|(dst, e)| *dst = *e);
+            product *= &divisor;
+            product += &remainder;
+
+            assert!(product.iter().eq(x.to_be_bytes()[16 - a_len..].iter()), "{:#x} / {:#x}", x, y);
+            assert_eq!(remainder.cmp_numeric(&divisor), std::cmp::Ordering::Less);
+        }
+    }
+
+    #[test]
+    fn test_div_rem_rejects_before_writing() {
+        let mut a = [0x12u8, 0x34, 0x56];
+        let mut b = [0x00u8, 0x01, 0x00];
+        let mut zero = [0x00u8];
+        let mut r = [0xEEu8];
+
+        let mut bytes = ByteString::new(&mut a);
+        let mut remainder = ByteString::new(&mut r);
+
+        assert_eq!(bytes.div_rem(&ByteString::new(&mut b), &mut remainder), Err(Error::OutputTooSmall));
+        assert_eq!(bytes.div_rem(&ByteString::new(&mut zero), &mut remainder), Err(Error::DivisionByZero));
+        assert_eq!((a, r), ([0x12, 0x34, 0x56], [0xEE]));
+
+        // A one byte dividend leaves a remainder below 0x100 whatever the divisor
+        let mut a = [0xFFu8];
+        let mut bytes = ByteString::new(&mut a);
+        let mut remainder = ByteString::new(&mut r);
+        bytes.div_rem(&ByteString::new(&mut b), &mut remainder).unwrap();
+        assert_eq!((a, r), ([0x00], [0xFF]));
+    }
+}
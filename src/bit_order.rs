@@ -0,0 +1,306 @@
+//! Stream bit addressing with a selectable bit order within each byte.
+//!
+//! The rest of the crate numbers bits by significance in the value, where the order of bits inside
+//! a byte has no meaning, so [`ByteString::bit`], the bit ranges and shifts are not affected by
+//! [`BitOrder`]. The methods here instead number bits as a stream: position `i` lies in logical
+//! byte `i / 8`, and the bit order picks which end of that byte it starts from. The byte order is
+//! still the interpretation flag, the two vary independently. The bit field cursors
+//! [`FieldReader`](crate::reader::FieldReader) and [`FieldWriter`](crate::writer::FieldWriter)
+//! take a bit order as well.
+
+use crate::{
+    bitfield::MAX_BIT_RANGE,
+    bytes::ByteString,
+    error::Error
+};
+
+/// Which bit of a byte comes first in a bit stream.
+///
+/// ```
+/// use rebite::{bit_order::BitOrder, bytes::ByteString};
+///
+/// let mut raw = [0b1000_0001u8, 0b0000_0010];
+/// let bytes = ByteString::new(&mut raw);
+///
+/// let msb: Vec<bool> = bytes.iter_bits(BitOrder::Msb0).take(2).collect();
+/// let lsb: Vec<bool> = bytes.iter_bits(BitOrder::Lsb0).take(2).collect();
+/// assert_eq!((msb, lsb), (vec![true, false], vec![true, false]));
+/// assert_eq!(BitOrder::default(), BitOrder::Msb0);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BitOrder {
+    /// The most significant bit is bit 0, as in network protocol diagrams
+    #[default]
+    Msb0,
+    /// The least significant bit is bit 0, as in most register and serial formats
+    Lsb0,
+}
+
+impl BitOrder {
+    // Shift that brings stream position `index` to the low bit of its byte
+    fn shift(self, index: usize) -> usize {
+        match self {
+            BitOrder::Msb0 => 7 - index % 8,
+            BitOrder::Lsb0 => index % 8,
+        }
+    }
+}
+
+impl<'a> ByteString<'a> {
+
+    /// Bits in stream order, logical byte by logical byte.
+    ///
+    /// ```
+    /// use rebite::{bit_order::BitOrder, bytes::ByteString};
+    ///
+    /// let mut raw = [0x01u8, 0x80];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// let msb: Vec<bool> = bytes.iter_bits(BitOrder::Msb0).collect();
+    /// assert_eq!(msb.iter().position(|e| *e), Some(7));
+    ///
+    /// // The bit order and the byte order are independent
+    /// bytes.interpret_reverse_endian();
+    /// let lsb: Vec<bool> = bytes.iter_bits(BitOrder::Lsb0).collect();
+    /// assert_eq!(lsb.iter().position(|e| *e), Some(7));
+    /// ```
+    pub fn iter_bits(&self, order: BitOrder) -> impl Iterator<Item = bool> + '_ {
+        self.iter().flat_map(move |byte| (0..8).map(move |ix| byte >> order.shift(ix) & 1 == 1))
+    }
+
+    /// Read `len` bits starting at stream position `start`, the first bit becomes the most significant.
+    ///
+    /// At most [`MAX_BIT_RANGE`] bits, fails with [`Error::OutOfBounds`] if the bits do not fit.
+    ///
+    /// ```
+    /// use rebite::{bit_order::BitOrder, bytes::ByteString, error::Error};
+    ///
+    /// let mut raw = [0b1100_0001u8, 0b0000_0011];
+    /// let bytes = ByteString::new(&mut raw);
+    ///
+    /// // Four bits across the byte boundary, read from either end of each byte
+    /// assert_eq!(bytes.extract_bits(6, 4, BitOrder::Msb0), Ok(0b0100));
+    /// assert_eq!(bytes.extract_bits(6, 4, BitOrder::Lsb0), Ok(0b1111));
+    /// assert_eq!(bytes.extract_bits(12, 5, BitOrder::Msb0), Err(Error::OutOfBounds));
+    /// ```
+    pub fn extract_bits(&self, start: usize, len: usize, order: BitOrder) -> Result<u64, Error> {
+        match start.checked_add(len) {
+            Some(end) if end <= self.bit_len() && len <= MAX_BIT_RANGE => (),
+            _ => return Err(Error::OutOfBounds),
+        }
+
+        Ok((start..start + len).fold(0, |acc, ix| acc << 1 | self.stream_bit(ix, order) as u64))
+    }
+
+    // Bit at stream position `position`, which must be below the bit length
+    pub(crate) fn stream_bit(&self, position: usize, order: BitOrder) -> bool {
+        self.bytes[self.physical_index(position / 8)] >> order.shift(position) & 1 == 1
+    }
+
+    pub(crate) fn set_stream_bit(&mut self, position: usize, order: BitOrder, value: bool) {
+        let byte = &mut self.bytes[self.physical_index(position / 8)];
+        let mask = 1 << order.shift(position);
+        *byte = if value { *byte | mask } else { *byte & !mask };
+    }
+
+    /// Pack `bits` most significant first in logical order, returning how many were written.
+    ///
+    /// Bit `i` of the sequence becomes stream position `i` of [`BitOrder::Msb0`], which is value bit
+    /// `bit_len() - 1 - i` of [`ByteString::bit`]. Bits past the sequence are cleared if `zero_rest`
+    /// is set and kept otherwise. Fails with [`Error::OutputTooSmall`] without writing if there are
+    /// more bits than the buffer holds.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let mut raw = [0xFFu8, 0xFF];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.assign_from_bits([false, true, false], false), Ok(3));
+    /// assert_eq!(raw, [0x5F, 0xFF]);
+    ///
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// assert_eq!(bytes.assign_from_bits([true; 17], true), Err(Error::OutputTooSmall));
+    /// assert_eq!(bytes.assign_from_bits([true], true), Ok(1));
+    /// assert_eq!(raw, [0x80, 0x00]);
+    /// ```
+    pub fn assign_from_bits(&mut self, bits: impl IntoIterator<Item = bool>, zero_rest: bool) -> Result<usize, Error> {
+        let bit_len = self.bit_len();
+
+        // Staged so an overlong sequence leaves the buffer untouched
+        let mut staged = vec![0u8; self.byte_len()];
+        let mut count = 0;
+        for bit in bits {
+            if count == bit_len {
+                return Err(Error::OutputTooSmall);
+            }
+
+            staged[count / 8] |= (bit as u8) << BitOrder::Msb0.shift(count);
+            count += 1;
+        }
+
+        for (ix, (dst, src)) in self.iter_mut().zip(staged).enumerate() {
+            // Number of this byte's bits covered by the sequence, from the top
+            let written = count.saturating_sub(ix * 8).min(8);
+            let keep = if zero_rest { 0 } else { 0xFFu8.checked_shr(written as u32).unwrap_or(0) };
+            *dst = *dst & keep | src;
+        }
+
+        Ok(count)
+    }
+
+    /// Append the bits to `out` in the order of [`ByteString::assign_from_bits`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0xA0u8];
+    /// let mut bits = vec![true];
+    ///
+    /// // Appended after what is already there
+    /// ByteString::new(&mut raw).collect_bits_into(&mut bits);
+    /// assert_eq!(bits, [true, true, false, true, false, false, false, false, false]);
+    /// ```
+    pub fn collect_bits_into(&self, out: &mut Vec<bool>) {
+        out.reserve(self.bit_len());
+        self.for_each_bit(|bit| out.push(bit));
+    }
+
+    /// Call `f` with every bit in the order of [`ByteString::assign_from_bits`], without allocating.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x00u8, 0x01];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    ///
+    /// // Reversed, the physical first byte comes last
+    /// let mut ones = Vec::new();
+    /// let mut ix = 0;
+    /// bytes.for_each_bit(|bit| { if bit { ones.push(ix); } ix += 1; });
+    /// assert_eq!(ones, [7]);
+    /// ```
+    pub fn for_each_bit(&self, mut f: impl FnMut(bool)) {
+        for byte in self.iter() {
+            for ix in 0..8 {
+                f(byte >> BitOrder::Msb0.shift(ix) & 1 == 1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::{view, XorShift};
+
+    #[test]
+    fn test_orders_reverse_each_byte() {
+        let mut raw = [0x80u8, 0x35, 0xC1];
+        let bytes = ByteString::new(&mut raw);
+
+        let msb0: Vec<bool> = bytes.iter_bits(BitOrder::Msb0).collect();
+        let lsb0: Vec<bool> = bytes.iter_bits(BitOrder::Lsb0).collect();
+
+        assert_eq!(msb0.len(), 24);
+        assert!(msb0[0] && !lsb0[0]);
+        for (m, l) in msb0.chunks(8).zip(lsb0.chunks(8)) {
+            assert!(m.iter().eq(l.iter().rev()));
+        }
+        assert_eq!(BitOrder::default(), BitOrder::Msb0);
+    }
+
+    #[test]
+    fn test_extract_bits_all_combinations() {
+        // Physical 0b1010_0001 0b0000_1111
+        let cases = [
+            (false, BitOrder::Msb0, 0b0001_0000),
+            (false, BitOrder::Lsb0, 0b0101_1111),
+            (true, BitOrder::Msb0, 0b1111_1010),
+            (true, BitOrder::Lsb0, 0b0000_1000),
+        ];
+
+        for (reversed, order, expected) in cases {
+            let mut raw = [0b1010_0001u8, 0b0000_1111];
+            let bytes = view(&mut raw, reversed);
+
+            // Eight bits straddling the logical byte boundary
+            assert_eq!(bytes.extract_bits(4, 8, order), Ok(expected), "reversed={} {:?}", reversed, order);
+
+            let whole = bytes.iter_bits(order).fold(0u64, |acc, bit| acc << 1 | bit as u64);
+            assert_eq!(bytes.extract_bits(0, 16, order), Ok(whole));
+        }
+    }
+
+    #[test]
+    fn test_extract_bits_bounds() {
+        let mut raw = [0xFFu8; 9];
+        let bytes = ByteString::new(&mut raw);
+
+        assert_eq!(bytes.extract_bits(8, 64, BitOrder::Lsb0), Ok(u64::MAX));
+        assert_eq!(bytes.extract_bits(0, 65, BitOrder::Msb0), Err(Error::OutOfBounds));
+        assert_eq!(bytes.extract_bits(70, 3, BitOrder::Msb0), Err(Error::OutOfBounds));
+        assert_eq!(bytes.extract_bits(72, 0, BitOrder::Msb0), Ok(0));
+        assert_eq!(bytes.extract_bits(usize::MAX, 2, BitOrder::Msb0), Err(Error::OutOfBounds));
+    }
+
+    #[test]
+    fn test_bits_round_trip_odd_lengths() {
+        let mut rng = XorShift::new(0x266);
+
+        for len in [0usize, 1, 3, 7, 9, 13, 17, 23] {
+            for reversed in [false, true] {
+                let bits: Vec<bool> = (0..len).map(|_| rng.next_bool()).collect();
+                let mut raw = vec![0xA5u8; 3];
+                let mut bytes = view(&mut raw, reversed);
+
+                assert_eq!(bytes.assign_from_bits(bits.iter().copied(), true), Ok(len));
+
+                // Sequence position i is value bit bit_len - 1 - i, the rest is cleared
+                for (ix, bit) in bits.iter().enumerate() {
+                    assert_eq!(bytes.bit(23 - ix), *bit);
+                }
+                assert_eq!(bytes.count_ones(), bits.iter().filter(|e| **e).count());
+
+                let mut out = Vec::new();
+                bytes.collect_bits_into(&mut out);
+                assert_eq!(out.len(), 24);
+                assert_eq!(out[..len], bits[..]);
+                assert!(bytes.iter_bits(BitOrder::Msb0).eq(out.iter().copied()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_assign_from_bits_keeps_the_rest() {
+        let mut raw = [0x00u8, 0xFF];
+        let mut bytes = ByteString::new(&mut raw);
+        bytes.interpret_reverse_endian();
+
+        // Logical bytes FF 00, the first five bits overwritten
+        assert_eq!(bytes.assign_from_bits([false, true, false, true, false], false), Ok(5));
+        assert_eq!(raw, [0x00, 0x57]);
+
+        let mut raw = [0xFFu8, 0xFF];
+        let mut bytes = ByteString::new(&mut raw);
+        assert_eq!(bytes.assign_from_bits([true; 8], false), Ok(8));
+        assert_eq!(bytes.assign_from_bits([], false), Ok(0));
+        assert_eq!(raw, [0xFF, 0xFF]);
+    }
+
+    #[test]
+    fn test_assign_from_bits_overflow() {
+        let mut raw = [0x12u8, 0x34];
+        let mut bytes = ByteString::new(&mut raw);
+
+        assert_eq!(bytes.assign_from_bits([true; 17], true), Err(Error::OutputTooSmall));
+        assert_eq!(bytes.assign_from_bits(std::iter::repeat(true), false), Err(Error::OutputTooSmall));
+        assert_eq!(raw, [0x12, 0x34]);
+
+        let mut empty: [u8; 0] = [];
+        assert_eq!(ByteString::new(&mut empty).assign_from_bits([], true), Ok(0));
+        assert_eq!(ByteString::new(&mut empty).assign_from_bits([false], true), Err(Error::OutputTooSmall));
+    }
+}
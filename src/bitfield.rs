@@ -0,0 +1,221 @@
+//! Reading, writing and moving bit ranges addressed by significance
+
+use std::ops::Range;
+
+use crate::{
+    bytes::ByteString,
+    error::Error
+};
+
+/// Widest bit range that [`ByteString::read_bit_range`] and [`ByteString::write_bit_range`] accept.
+///
+/// ```
+/// use rebite::{bitfield::MAX_BIT_RANGE, bytes::ByteString};
+///
+/// let mut raw = [0xFFu8; 9];
+/// let bytes = ByteString::new(&mut raw);
+///
+/// assert_eq!(bytes.read_bit_range(0..MAX_BIT_RANGE), Ok(u64::MAX));
+/// assert!(bytes.read_bit_range(0..MAX_BIT_RANGE + 1).is_err());
+/// ```
+pub const MAX_BIT_RANGE: usize = 64;
+
+impl<'a> ByteString<'a> {
+
+    /// Value of the bits in `range`, numbered from the least significant bit of the value as with shifts.
+    ///
+    /// The range may span bytes and be at most [`MAX_BIT_RANGE`] bits wide.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let mut raw = [0x0Au8, 0xBC];
+    /// let bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.read_bit_range(4..12), Ok(0xAB));
+    /// assert_eq!(bytes.read_bit_range(3..3), Ok(0));
+    /// assert_eq!(bytes.read_bit_range(8..17), Err(Error::OutOfBounds));
+    /// ```
+    pub fn read_bit_range(&self, range: Range<usize>) -> Result<u64, Error> {
+        self.check_bit_range(&range)?;
+
+        Ok(range.rev().fold(0, |acc, offset| acc << 1 | self.bit(offset) as u64))
+    }
+
+    /// Store the low bits of `value` into `range`, leaving all other bits untouched.
+    ///
+    /// Bits of `value` above the range width are ignored, as with a register field write.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0xFFu8, 0xFF];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// // Only the low four bits of 0x30 land in the field
+    /// bytes.write_bit_range(4..8, 0x30).unwrap();
+    /// assert_eq!(raw, [0xFF, 0x0F]);
+    /// ```
+    pub fn write_bit_range(&mut self, range: Range<usize>, value: u64) -> Result<(), Error> {
+        self.check_bit_range(&range)?;
+
+        for (ix, offset) in range.enumerate() {
+            self.set_bit(offset, value >> ix & 1 == 1);
+        }
+
+        Ok(())
+    }
+
+    /// Move `len_bits` bits starting at `src_bit` to start at `dst_bit`, like `memmove` at bit granularity.
+    ///
+    /// Offsets count from the least significant bit as with shifts. The ranges may overlap, bits
+    /// outside the destination keep their value. Fails with [`Error::OutOfBounds`] if either range
+    /// does not fit, leaving the buffer untouched.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let mut raw = [0x00u8, 0x3C];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// // Overlapping move up by two bits, the vacated low bits keep their old value
+    /// bytes.move_bits_within(2, 4, 4).unwrap();
+    /// assert_eq!(bytes.to_u64(), Ok(0xFC));
+    /// assert_eq!(bytes.move_bits_within(0, 9, 8), Err(Error::OutOfBounds));
+    /// ```
+    pub fn move_bits_within(&mut self, src_bit: usize, dst_bit: usize, len_bits: usize) -> Result<(), Error> {
+        let bit_len = self.bit_len();
+        let fits = |start: usize| start.checked_add(len_bits).is_some_and(|end| end <= bit_len);
+
+        if !fits(src_bit) || !fits(dst_bit) {
+            return Err(Error::OutOfBounds);
+        }
+
+        if src_bit == dst_bit || len_bits == 0 {
+            return Ok(());
+        }
+
+        // Whole bytes are one contiguous physical block in either interpretation, moving keeps their order
+        if (src_bit | dst_bit | len_bits).is_multiple_of(8) {
+            let len = self.byte_len();
+            let src = self.physical_range(len - (src_bit + len_bits) / 8..len - src_bit / 8)?;
+            let dst = self.physical_range(len - (dst_bit + len_bits) / 8..len - dst_bit / 8)?;
+
+            self.bytes.copy_within(src, dst.start);
+            return Ok(());
+        }
+
+        // Each chunk is read whole before it is written, starting at the end the move is heading to
+        // means every source chunk is read before a later write can reach it
+        let mut chunks = (0..len_bits).step_by(MAX_BIT_RANGE);
+        let step = |start: usize| {
+            let width = (len_bits - start).min(MAX_BIT_RANGE);
+            let value = self.read_bit_range(src_bit + start..src_bit + start + width)?;
+            self.write_bit_range(dst_bit + start..dst_bit + start + width, value)
+        };
+
+        if dst_bit > src_bit { chunks.rev().try_for_each(step) } else { chunks.try_for_each(step) }
+    }
+
+    fn check_bit_range(&self, range: &Range<usize>) -> Result<(), Error> {
+        if range.start > range.end || range.end > self.bit_len() || range.len() > MAX_BIT_RANGE {
+            return Err(Error::OutOfBounds);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::{view, view_logical, XorShift};
+
+    #[test]
+    fn test_bit_range_against_u128() {
+        let mut rng = XorShift::new(0x242);
+
+        for _ in 0..2000 {
+            let value = (rng.next_u64() as u128) << 64 | rng.next_u64() as u128;
+            let start = rng.below(128);
+            let end = start + rng.below((128 - start).min(MAX_BIT_RANGE) + 1);
+            let field = rng.next_u64();
+            let reversed = rng.next_bool();
+
+            let mask = if end - start == 128 { u128::MAX } else { ((1u128 << (end - start)) - 1) << start };
+
+            let mut raw = value.to_be_bytes();
+            let mut bytes = view_logical(&mut raw, reversed);
+
+            assert_eq!(bytes.read_bit_range(start..end), Ok(((value & mask) >> start) as u64));
+
+            bytes.write_bit_range(start..end, field).unwrap();
+            if reversed { raw.reverse(); }
+            assert_eq!(u128::from_be_bytes(raw), value & !mask | (field as u128) << start & mask);
+        }
+    }
+
+    #[test]
+    fn test_bit_range_out_of_bounds() {
+        let mut raw = [0u8; 16];
+        let mut bytes = ByteString::new(&mut raw);
+
+        assert_eq!(bytes.read_bit_range(0..65), Err(Error::OutOfBounds));
+        assert_eq!(bytes.read_bit_range(120..129), Err(Error::OutOfBounds));
+        assert_eq!(bytes.write_bit_range(0..129, 0), Err(Error::OutOfBounds));
+        assert_eq!(bytes.read_bit_range(7..7), Ok(0));
+    }
+
+    #[test]
+    fn test_move_bits_within_against_scratch() {
+        let mut rng = XorShift::new(0x255);
+
+        for _ in 0..3000 {
+            let len = 1 + rng.below(24);
+            let mut raw = vec![0u8; len];
+            rng.fill(&mut raw);
+            let mut bytes = view(&mut raw, rng.next_bool());
+
+            let bit_len = bytes.bit_len();
+            let mut len_bits = rng.below(bit_len + 1);
+            let mut src = rng.below(bit_len - len_bits + 1);
+            let mut dst = rng.below(bit_len - len_bits + 1);
+
+            // Exercise the whole byte path as well
+            if rng.below(3) == 0 {
+                (len_bits, src, dst) = (len_bits / 8 * 8, src / 8 * 8, dst / 8 * 8);
+            }
+
+            // Reference through a scratch copy of the source bits
+            let mut expected: Vec<bool> = (0..bit_len).map(|offset| bytes.bit(offset)).collect();
+            let moved: Vec<bool> = expected[src..src + len_bits].to_vec();
+            expected[dst..dst + len_bits].copy_from_slice(&moved);
+
+            bytes.move_bits_within(src, dst, len_bits).unwrap();
+            assert!((0..bit_len).map(|offset| bytes.bit(offset)).eq(expected));
+        }
+    }
+
+    #[test]
+    fn test_move_bits_within_record_shift() {
+        // A 12 bit record at offset 4 moved up by 3 bits within a 3 byte frame
+        let mut raw = [0xF0u8, 0xAB, 0xC5];
+        let mut bytes = ByteString::new(&mut raw);
+
+        bytes.move_bits_within(4, 7, 12).unwrap();
+        assert_eq!(raw, [0xF5, 0x5E, 0x45]);
+    }
+
+    #[test]
+    fn test_move_bits_within_out_of_bounds() {
+        let mut raw = [0x5Au8; 2];
+        let mut bytes = ByteString::new(&mut raw);
+
+        assert_eq!(bytes.move_bits_within(8, 0, 9), Err(Error::OutOfBounds));
+        assert_eq!(bytes.move_bits_within(0, 9, 8), Err(Error::OutOfBounds));
+        assert_eq!(bytes.move_bits_within(usize::MAX, 0, 2), Err(Error::OutOfBounds));
+        assert_eq!(bytes.move_bits_within(0, 16, 0), Ok(()));
+        assert_eq!(raw, [0x5A, 0x5A]);
+    }
+}
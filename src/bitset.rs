@@ -0,0 +1,183 @@
+//! Set relations between buffers read as bit sets
+
+use crate::{
+    bytes::ByteString,
+    error::LengthError
+};
+
+impl<'a> ByteString<'a> {
+
+    /// Number of set bits, the interpretation flag does not matter.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0xFFu8, 0x01, 0x00];
+    /// assert_eq!(ByteString::new(&mut raw).count_ones(), 9);
+    /// assert_eq!(ByteString::new(&mut []).count_ones(), 0);
+    /// ```
+    pub fn count_ones(&self) -> usize {
+        let split = self.words_and_tail();
+        let bytes = split.head.physical().iter().chain(split.tail.physical()).map(|e| e.count_ones() as usize).sum::<usize>();
+        bytes + split.words.map(|e| e.count_ones() as usize).sum::<usize>()
+    }
+
+    /// Every set bit of self is also set in `other`.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::LengthError};
+    ///
+    /// let (mut a, mut b, mut c) = ([0x01u8, 0x00], [0x01u8, 0x80], [0x01u8]);
+    /// let a = ByteString::new(&mut a);
+    ///
+    /// assert_eq!(a.is_subset_of(&ByteString::new(&mut b)), Ok(true));
+    /// // Sets of different width are not compared
+    /// assert_eq!(a.is_subset_of(&ByteString::new(&mut c)), Err(LengthError { index: 0, expected: 2, actual: 1 }));
+    /// ```
+    pub fn is_subset_of(&self, other: &ByteString) -> Result<bool, LengthError> {
+        self.all_pairs(other, |a, b| a & !b == 0)
+    }
+
+    /// Every set bit of `other` is also set in self.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let (mut a, mut empty) = ([0x81u8], [0x00u8]);
+    ///
+    /// // The empty set is a subset of everything
+    /// assert_eq!(ByteString::new(&mut a).is_superset_of(&ByteString::new(&mut empty)), Ok(true));
+    /// ```
+    pub fn is_superset_of(&self, other: &ByteString) -> Result<bool, LengthError> {
+        other.is_subset_of(self).map_err(|_| length_error(self, other))
+    }
+
+    /// No bit is set in both.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let (mut a, mut b) = ([0xF0u8, 0x00], [0x00u8, 0xF0]);
+    /// let mut b = ByteString::new(&mut b);
+    /// let a = ByteString::new(&mut a);
+    /// assert_eq!(a.is_disjoint_from(&b), Ok(true));
+    ///
+    /// // Bytes pair up in logical order, so the flag changes the answer
+    /// b.interpret_reverse_endian();
+    /// assert_eq!(a.is_disjoint_from(&b), Ok(false));
+    /// ```
+    pub fn is_disjoint_from(&self, other: &ByteString) -> Result<bool, LengthError> {
+        self.all_pairs(other, |a, b| a & b == 0)
+    }
+
+    /// At least one bit is set in both.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let (mut a, mut b) = ([0x01u8], [0x03u8]);
+    /// assert_eq!(ByteString::new(&mut a).intersects(&ByteString::new(&mut b)), Ok(true));
+    /// ```
+    pub fn intersects(&self, other: &ByteString) -> Result<bool, LengthError> {
+        self.is_disjoint_from(other).map(|disjoint| !disjoint)
+    }
+
+    // Check `f` on every pair of logical bytes, stopping at the first failure
+    fn all_pairs<F: Fn(u8, u8) -> bool>(&self, other: &ByteString, f: F) -> Result<bool, LengthError> {
+
+        if self.byte_len() != other.byte_len() {
+            return Err(length_error(self, other));
+        }
+
+        Ok(self.iter().zip(other.iter()).all(|(a, b)| f(*a, *b)))
+    }
+}
+
+fn length_error(expected: &ByteString, other: &ByteString) -> LengthError {
+    LengthError { index: 0, expected: expected.byte_len(), actual: other.byte_len() }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::test_util::{view, XorShift};
+
+    fn members(bytes: &ByteString) -> HashSet<usize> {
+        (0..bytes.bit_len()).filter(|offset| bytes.bit(*offset)).collect()
+    }
+
+    #[test]
+    fn test_against_set_model() {
+        let mut rng = XorShift::new(0x229);
+
+        for _ in 0..2000 {
+            let len = rng.below(6);
+            let mut a = vec![0u8; len];
+            let mut b = vec![0u8; len];
+
+            // Sparse random sets so that subsets and disjoint pairs actually occur
+            for byte in a.iter_mut().chain(b.iter_mut()) {
+                *byte = rng.next_u8() & rng.next_u8() & rng.next_u8();
+            }
+            if rng.below(4) == 0 { b.iter_mut().zip(&a).for_each(|(b, a)| *b |= a); }
+
+            let (a_reversed, b_reversed) = (rng.next_bool(), rng.next_bool());
+            let bytes_a = view(&mut a, a_reversed);
+            let bytes_b = view(&mut b, b_reversed);
+
+            let (set_a, set_b) = (members(&bytes_a), members(&bytes_b));
+
+            assert_eq!(bytes_a.is_subset_of(&bytes_b), Ok(set_a.is_subset(&set_b)));
+            assert_eq!(bytes_a.is_superset_of(&bytes_b), Ok(set_a.is_superset(&set_b)));
+            assert_eq!(bytes_a.is_disjoint_from(&bytes_b), Ok(set_a.is_disjoint(&set_b)));
+            assert_eq!(bytes_a.intersects(&bytes_b), Ok(!set_a.is_disjoint(&set_b)));
+            assert_eq!(bytes_a.count_ones(), set_a.len());
+
+            // Duality
+            assert_eq!(bytes_a.is_subset_of(&bytes_b), bytes_b.is_superset_of(&bytes_a));
+        }
+    }
+
+    #[test]
+    fn test_empty_buffers() {
+        let mut a = [];
+        let mut b = [];
+        let bytes_a = ByteString::new(&mut a);
+        let bytes_b = ByteString::new(&mut b);
+
+        assert_eq!(bytes_a.is_subset_of(&bytes_b), Ok(true));
+        assert_eq!(bytes_a.is_superset_of(&bytes_b), Ok(true));
+        assert_eq!(bytes_a.is_disjoint_from(&bytes_b), Ok(true));
+        assert_eq!(bytes_a.intersects(&bytes_b), Ok(false));
+    }
+
+    #[test]
+    fn test_reversed_pairing() {
+        let mut a = [0x01u8, 0x00];
+        let mut b = [0x00u8, 0x01];
+
+        let bytes_a = ByteString::new(&mut a);
+        let mut bytes_b = ByteString::new(&mut b);
+        bytes_b.interpret_reverse_endian();
+
+        assert_eq!(bytes_a.is_subset_of(&bytes_b), Ok(true));
+        assert_eq!(bytes_a.intersects(&bytes_b), Ok(true));
+    }
+
+    #[test]
+    fn test_length_mismatch() {
+        let mut a = [0u8; 2];
+        let mut b = [0u8; 3];
+        let bytes_a = ByteString::new(&mut a);
+        let bytes_b = ByteString::new(&mut b);
+
+        let expected = Err(LengthError { index: 0, expected: 2, actual: 3 });
+        assert_eq!(bytes_a.is_subset_of(&bytes_b), expected);
+        assert_eq!(bytes_a.is_superset_of(&bytes_b), expected);
+        assert_eq!(bytes_a.is_disjoint_from(&bytes_b), expected);
+        assert_eq!(bytes_a.intersects(&bytes_b), expected);
+    }
+}
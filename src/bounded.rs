@@ -0,0 +1,384 @@
+//! Buffers whose value is kept at or below an inclusive bound
+
+use std::{
+    cmp::Ordering,
+    ops::Deref
+};
+
+use crate::{
+    bytes::ByteString,
+    error::Error
+};
+
+/// What a mutation of a [`BoundedBytes`] does with a result above the bound.
+///
+/// ```
+/// use rebite::{bounded::{BoundedBytes, BoundMode}, bytes::ByteString};
+///
+/// let (mut a, mut b) = ([0x0Fu8], [0x0Fu8]);
+/// let mut checked = BoundedBytes::with_bit_width(ByteString::new(&mut a), 4, BoundMode::Checked).unwrap();
+/// let mut wrapping = BoundedBytes::with_bit_width(ByteString::new(&mut b), 4, BoundMode::Wrapping).unwrap();
+///
+/// // At the bound 15, a checked increment fails and a wrapping one starts over
+/// assert!(checked.increment().is_err());
+/// wrapping.increment().unwrap();
+/// assert_eq!((checked.to_u64(), wrapping.to_u64()), (Ok(15), Ok(0)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundMode {
+    /// Fail with [`Error::ExceedsBound`] and leave the value unchanged
+    Checked,
+    /// Reduce the result modulo bound + 1
+    Wrapping,
+}
+
+/// ByteString whose value never exceeds an inclusive upper bound.
+///
+/// Reads go through `Deref`, there is no mutable access to the inner ByteString so every write
+/// passes the bound check of [`BoundedBytes::add`], [`BoundedBytes::increment`] or [`BoundedBytes::assign_from_u64`].
+///
+/// ```
+/// use rebite::{bounded::{BoundedBytes, BoundMode}, bytes::ByteString};
+///
+/// let (mut raw, mut bound) = ([0u8; 2], [0x03u8, 0xE7]);
+/// let mut counter = BoundedBytes::new(ByteString::new(&mut raw), &ByteString::new(&mut bound), BoundMode::Checked).unwrap();
+///
+/// counter.assign_from_u64(999).unwrap();
+/// assert!(counter.assign_from_u64(1000).is_err());
+///
+/// // Reads go through Deref
+/// assert_eq!(counter.to_u64(), Ok(999));
+/// ```
+pub struct BoundedBytes<'a> {
+    bytes: ByteString<'a>,
+    // bound + 1 in logical order, one byte wider than the buffer so an all ones bound still fits
+    modulus: Vec<u8>,
+    mode: BoundMode,
+}
+
+impl<'a> BoundedBytes<'a> {
+
+    /// Bound the value of `bytes` by `bound`, inclusive.
+    ///
+    /// Fails with [`Error::OutOfBounds`] if the bound does not fit into the buffer and with
+    /// [`Error::ExceedsBound`] if the current value is already above it.
+    ///
+    /// ```
+    /// use rebite::{bounded::{BoundedBytes, BoundMode}, bytes::ByteString, error::Error};
+    ///
+    /// let (mut raw, mut bound, mut wide) = ([0x00u8, 0x64], [0x03u8, 0xE7], [0x01u8, 0x00, 0x00]);
+    ///
+    /// // A bound with leading zero bytes fits as long as its value does
+    /// assert!(matches!(BoundedBytes::new(ByteString::new(&mut raw), &ByteString::new(&mut wide), BoundMode::Checked), Err(Error::OutOfBounds)));
+    /// assert!(BoundedBytes::new(ByteString::new(&mut raw), &ByteString::new(&mut bound), BoundMode::Checked).is_ok());
+    ///
+    /// // 0x64 is above a bound of 0x63
+    /// let mut low = [0x63u8];
+    /// assert!(matches!(BoundedBytes::new(ByteString::new(&mut raw), &ByteString::new(&mut low), BoundMode::Checked), Err(Error::ExceedsBound)));
+    /// ```
+    pub fn new(bytes: ByteString<'a>, bound: &ByteString, mode: BoundMode) -> Result<Self, Error> {
+
+        let significant = bound.byte_len() - bound.leading_zero_len();
+        if significant > bytes.byte_len() {
+            return Err(Error::OutOfBounds);
+        }
+
+        let mut modulus = low_bytes(bound, bytes.byte_len() + 1);
+        let mut one = [1u8];
+        let one = ByteString::new(&mut one);
+        let mut view = ByteString::new(&mut modulus);
+        view += &one;
+
+        Self::from_modulus(bytes, modulus, mode)
+    }
+
+    /// Bound the value of `bytes` by the largest `bits` wide value, `2^bits - 1`.
+    ///
+    /// Fails like [`BoundedBytes::new`], `bits` may not exceed the bit length of the buffer.
+    ///
+    /// ```
+    /// use rebite::{bounded::{BoundedBytes, BoundMode}, bytes::ByteString};
+    ///
+    /// let mut raw = [0x00u8, 0x0F];
+    /// let mut nibble = BoundedBytes::with_bit_width(ByteString::new(&mut raw), 4, BoundMode::Wrapping).unwrap();
+    ///
+    /// nibble.increment().unwrap();
+    /// assert_eq!(nibble.to_u64(), Ok(0));
+    /// ```
+    pub fn with_bit_width(bytes: ByteString<'a>, bits: usize, mode: BoundMode) -> Result<Self, Error> {
+
+        if bits > bytes.bit_len() {
+            return Err(Error::OutOfBounds);
+        }
+
+        let mut modulus = vec![0u8; bytes.byte_len() + 1];
+        ByteString::new(&mut modulus).set_bit(bits, true);
+
+        Self::from_modulus(bytes, modulus, mode)
+    }
+
+    fn from_modulus(bytes: ByteString<'a>, mut modulus: Vec<u8>, mode: BoundMode) -> Result<Self, Error> {
+
+        if bytes.cmp_numeric(&ByteString::new(&mut modulus)) != Ordering::Less {
+            return Err(Error::ExceedsBound);
+        }
+
+        Ok(Self { bytes, modulus, mode })
+    }
+
+    /// What a mutation does with a result above the bound.
+    ///
+    /// ```
+    /// use rebite::{bounded::{BoundedBytes, BoundMode}, bytes::ByteString};
+    ///
+    /// let mut raw = [0u8];
+    /// let bounded = BoundedBytes::with_bit_width(ByteString::new(&mut raw), 8, BoundMode::Checked).unwrap();
+    /// assert_eq!(bounded.mode(), BoundMode::Checked);
+    /// ```
+    pub fn mode(&self) -> BoundMode {
+        self.mode
+    }
+
+    /// Give up the bound and return the ByteString.
+    ///
+    /// ```
+    /// use rebite::{bounded::{BoundedBytes, BoundMode}, bytes::ByteString};
+    ///
+    /// let mut raw = [0x07u8];
+    /// let bounded = BoundedBytes::with_bit_width(ByteString::new(&mut raw), 3, BoundMode::Checked).unwrap();
+    ///
+    /// // Writes are unchecked again
+    /// let mut bytes = bounded.into_inner();
+    /// assert!(!bytes.increment());
+    /// assert_eq!(raw, [0x08]);
+    /// ```
+    pub fn into_inner(self) -> ByteString<'a> {
+        self.bytes
+    }
+
+    /// self = self + rhs, subject to the bound mode.
+    ///
+    /// ```
+    /// use rebite::{bounded::{BoundedBytes, BoundMode}, bytes::ByteString, error::Error};
+    ///
+    /// let (mut raw, mut bound, mut five) = ([0x08u8], [0x09u8], [0x05u8]);
+    /// let five = ByteString::new(&mut five);
+    ///
+    /// let mut checked = BoundedBytes::new(ByteString::new(&mut raw), &ByteString::new(&mut bound), BoundMode::Checked).unwrap();
+    /// assert_eq!(checked.add(&five), Err(Error::ExceedsBound));
+    /// assert_eq!(checked.to_u64(), Ok(8));
+    ///
+    /// // Wrapping reduces modulo bound + 1, 8 + 5 = 13 = 3 mod 10
+    /// let mut wrapping = BoundedBytes::new(checked.into_inner(), &ByteString::new(&mut bound), BoundMode::Wrapping).unwrap();
+    /// wrapping.add(&five).unwrap();
+    /// assert_eq!(wrapping.to_u64(), Ok(3));
+    /// ```
+    pub fn add(&mut self, rhs: &ByteString) -> Result<(), Error> {
+        self.store_sum(true, rhs)
+    }
+
+    /// self = self + 1, subject to the bound mode.
+    ///
+    /// ```
+    /// use rebite::{bounded::{BoundedBytes, BoundMode}, bytes::ByteString, error::Error};
+    ///
+    /// let mut raw = [0x00u8, 0xFE];
+    /// let mut byte = BoundedBytes::with_bit_width(ByteString::new(&mut raw), 8, BoundMode::Checked).unwrap();
+    ///
+    /// assert_eq!(byte.increment(), Ok(()));
+    /// assert_eq!(byte.increment(), Err(Error::ExceedsBound));
+    /// assert_eq!(byte.to_u64(), Ok(0xFF));
+    /// ```
+    pub fn increment(&mut self) -> Result<(), Error> {
+        let mut one = [1u8];
+        self.store_sum(true, &ByteString::new(&mut one))
+    }
+
+    /// Replace the value with `value`, subject to the bound mode.
+    ///
+    /// ```
+    /// use rebite::{bounded::{BoundedBytes, BoundMode}, bytes::ByteString};
+    ///
+    /// let mut raw = [0u8; 2];
+    /// let mut degrees = BoundedBytes::with_bit_width(ByteString::new(&mut raw), 9, BoundMode::Wrapping).unwrap();
+    ///
+    /// // Wrapping applies to assignment too, 1000 mod 512
+    /// degrees.assign_from_u64(1000).unwrap();
+    /// assert_eq!(degrees.to_u64(), Ok(488));
+    /// ```
+    pub fn assign_from_u64(&mut self, value: u64) -> Result<(), Error> {
+        let mut raw = value.to_be_bytes();
+        self.store_sum(false, &ByteString::new(&mut raw))
+    }
+
+    // Write `rhs` plus the current value if `keep`, both terms are below the modulus so the sum
+    // fits the one byte wider scratch and needs at most one reduction. Nothing is written on error.
+    fn store_sum(&mut self, keep: bool, rhs: &ByteString) -> Result<(), Error> {
+
+        let width = self.modulus.len();
+        let modulus = ByteString::new(&mut self.modulus);
+
+        let mut term = match self.mode {
+            BoundMode::Checked if rhs.cmp_numeric(&modulus) != Ordering::Less => return Err(Error::ExceedsBound),
+            BoundMode::Checked => low_bytes(rhs, width),
+            BoundMode::Wrapping => {
+                // The remainder comes one byte wider than the modulus and below it
+                let mut dividend: Vec<u8> = rhs.iter().copied().collect();
+                ByteString::new(&mut dividend).shift_subtract(&modulus)?.split_off(1)
+            }
+        };
+
+        let mut current = if keep { low_bytes(&self.bytes, width) } else { vec![0u8; width] };
+        let term = ByteString::new(&mut term);
+        let mut sum = ByteString::new(&mut current);
+        sum += &term;
+
+        if sum.cmp_numeric(&modulus) != Ordering::Less {
+            if self.mode == BoundMode::Checked {
+                return Err(Error::ExceedsBound);
+            }
+            sum -= &modulus;
+        }
+
+        // The sum is below the modulus, its spare leading byte is zero
+        for (dst, value) in self.bytes.iter_mut().zip(sum.iter().skip(1)) {
+            *dst = *value;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> Deref for BoundedBytes<'a> {
+    type Target = ByteString<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.bytes
+    }
+}
+
+// The low `width` logical bytes of a value that fits them, zero-extended
+fn low_bytes(bytes: &ByteString, width: usize) -> Vec<u8> {
+    let mut out = vec![0u8; width];
+    for (dst, value) in out.iter_mut().rev().zip(bytes.iter().rev()) {
+        *dst = *value;
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::view;
+
+    #[test]
+    fn test_constructor_rejects() {
+        let mut raw = [0x01u8, 0x00];
+        let mut bound = [0x00u8, 0xFF];
+        let mut wide = [0x01u8, 0x00, 0x00];
+
+        assert!(matches!(BoundedBytes::new(ByteString::new(&mut raw), &ByteString::new(&mut bound), BoundMode::Checked), Err(Error::ExceedsBound)));
+        assert!(matches!(BoundedBytes::new(ByteString::new(&mut raw), &ByteString::new(&mut wide), BoundMode::Checked), Err(Error::OutOfBounds)));
+        assert!(matches!(BoundedBytes::with_bit_width(ByteString::new(&mut raw), 8, BoundMode::Wrapping), Err(Error::ExceedsBound)));
+        assert!(matches!(BoundedBytes::with_bit_width(ByteString::new(&mut raw), 17, BoundMode::Wrapping), Err(Error::OutOfBounds)));
+
+        // Equal to the bound is allowed, leading zeros of a wider bound do not count
+        let mut bound = [0x00u8, 0x01, 0x00];
+        assert!(BoundedBytes::new(ByteString::new(&mut raw), &ByteString::new(&mut bound), BoundMode::Checked).is_ok());
+        assert!(BoundedBytes::with_bit_width(ByteString::new(&mut raw), 9, BoundMode::Checked).is_ok());
+    }
+
+    #[test]
+    fn test_increment_hits_bound() {
+        for mode in [BoundMode::Checked, BoundMode::Wrapping] {
+            // Sequence number limited to 12 bits
+            let mut raw = [0x0Fu8, 0xFE];
+            let mut bounded = BoundedBytes::with_bit_width(ByteString::new(&mut raw), 12, mode).unwrap();
+
+            bounded.increment().unwrap();
+            assert_eq!(bounded.to_u64(), Ok(0xFFF));
+
+            match mode {
+                BoundMode::Checked => {
+                    assert_eq!(bounded.increment(), Err(Error::ExceedsBound));
+                    assert_eq!(bounded.to_u64(), Ok(0xFFF));
+                }
+                BoundMode::Wrapping => {
+                    bounded.increment().unwrap();
+                    assert!(bounded.is_zero());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_all_ones_bound_wraps_like_the_buffer() {
+        let mut raw = [0xFFu8, 0xFE];
+        let mut bound = [0xFFu8, 0xFF];
+        let mut bounded = BoundedBytes::new(ByteString::new(&mut raw), &ByteString::new(&mut bound), BoundMode::Wrapping).unwrap();
+
+        let mut rhs = [0x03u8];
+        bounded.add(&ByteString::new(&mut rhs)).unwrap();
+        assert_eq!(bounded.to_u64(), Ok(0x0001));
+    }
+
+    #[test]
+    fn test_modes_against_u64() {
+        let mut rng = crate::test_util::XorShift::new(0x256);
+
+        for _ in 0..1000 {
+            let bound = rng.next_u64() >> (16 + rng.below(40));
+            let start = rng.next_u64() % (bound + 1);
+            let rhs = rng.next_u64() >> rng.below(64);
+            let mode = if rng.next_bool() { BoundMode::Checked } else { BoundMode::Wrapping };
+            let reversed = rng.next_bool();
+
+            let mut raw = [0u8; 6];
+            let mut bytes = view(&mut raw, reversed);
+            bytes.assign_from_u64(start).unwrap();
+
+            let mut bound_raw = bound.to_be_bytes();
+            let mut bounded = BoundedBytes::new(bytes, &ByteString::new(&mut bound_raw), mode).unwrap();
+            let mut rhs_raw = rhs.to_be_bytes();
+            let rhs_bytes = ByteString::new(&mut rhs_raw);
+
+            let modulus = bound as u128 + 1;
+            let exact = start as u128 + rhs as u128;
+            let result = bounded.add(&rhs_bytes);
+
+            match mode {
+                BoundMode::Checked if exact > bound as u128 => {
+                    assert_eq!(result, Err(Error::ExceedsBound));
+                    assert_eq!(bounded.to_u64(), Ok(start));
+                }
+                BoundMode::Checked => assert_eq!(bounded.to_u64(), Ok(exact as u64)),
+                BoundMode::Wrapping => assert_eq!(bounded.to_u64(), Ok((exact % modulus) as u64)),
+            }
+
+            let result = bounded.assign_from_u64(rhs);
+            match mode {
+                BoundMode::Checked if rhs > bound => assert_eq!(result, Err(Error::ExceedsBound)),
+                BoundMode::Checked => assert_eq!(bounded.to_u64(), Ok(rhs)),
+                BoundMode::Wrapping => assert_eq!(bounded.to_u64(), Ok((rhs as u128 % modulus) as u64)),
+            }
+        }
+    }
+
+    #[test]
+    fn test_reads_delegate() {
+        let mut raw = [0x00u8, 0x2A];
+        let mut bytes = ByteString::new(&mut raw);
+        bytes.interpret_reverse_endian();
+
+        let bounded = BoundedBytes::with_bit_width(bytes, 16, BoundMode::Checked).unwrap();
+        assert_eq!(bounded.byte_len(), 2);
+        assert_eq!(bounded.to_string(), "2a00");
+        assert!(bounded.iter().eq([0x2A, 0x00].iter()));
+        assert_eq!(bounded.mode(), BoundMode::Checked);
+
+        let bytes = bounded.into_inner();
+        assert!(bytes.interpret_reverse_endian);
+    }
+}
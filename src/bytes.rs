@@ -1,3 +1,5 @@
+//! The [`ByteString`] view and its operator impls
+
 use std::ops::{
     AddAssign,
     SubAssign,
@@ -10,65 +12,371 @@ use std::ops::{
     RemAssign,
     ShlAssign,
     ShrAssign,
+    Range,
 };
 
 use crate::{
     util, 
-    bytes_iter::{BytesIter, BytesIterMut}
+    strict,
+    bytes_iter::{BytesIter, BytesIterMut},
+    error::{Error, LengthError}
 };
 
+/// Big-endian unsigned integer view over a mutable byte slice.
+///
+/// The logical byte order is the physical order of the slice, reversed while the interpretation
+/// flag is set. Values, offsets and ranges all refer to the logical order.
+///
+/// ```
+/// use rebite::bytes::ByteString;
+///
+/// let mut raw = [0x12u8, 0x34];
+/// let mut bytes = ByteString::new(&mut raw);
+/// assert_eq!(bytes.to_u64(), Ok(0x1234));
+///
+/// bytes.interpret_reverse_endian();
+/// assert_eq!(bytes.to_u64(), Ok(0x3412));
+/// ```
 pub struct ByteString<'a> {
-    bytes: &'a mut [u8],
-    interpret_reverse_endian: bool
+    pub(crate) bytes: &'a mut [u8],
+    pub(crate) interpret_reverse_endian: bool,
+    /// Formatting prints only the length, views derived from this one must copy the flag
+    pub(crate) sensitive: bool
 }
 
 impl<'a> ByteString<'a> {
 
+    /// View `bytes` in physical order, not sensitive.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x00u8, 0x2A];
+    /// let bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.to_u64(), Ok(42));
+    /// assert_eq!(bytes.to_string(), "002a");
+    /// ```
     pub fn new(bytes: &'a mut [u8]) -> ByteString<'a> {
-        Self { bytes, interpret_reverse_endian: false }
+        Self { bytes, interpret_reverse_endian: false, sensitive: false }
     }
 
+    /// Toggle the interpretation flag, reversing the logical byte order without moving any byte.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x01u8, 0x02, 0x03];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.interpret_reverse_endian();
+    /// assert!(bytes.iter().eq([0x03, 0x02, 0x01].iter()));
+    ///
+    /// // A second call toggles back
+    /// bytes.interpret_reverse_endian();
+    /// assert!(bytes.iter().eq([0x01, 0x02, 0x03].iter()));
+    /// assert_eq!(raw, [0x01, 0x02, 0x03]);
+    /// ```
     pub fn interpret_reverse_endian(&mut self) {
         self.interpret_reverse_endian = !self.interpret_reverse_endian;
     }
 
+    /// Length of the buffer in bytes.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0u8; 3];
+    /// assert_eq!(ByteString::new(&mut raw).byte_len(), 3);
+    /// assert_eq!(ByteString::new(&mut []).byte_len(), 0);
+    /// ```
     pub fn byte_len(&self) -> usize {
         self.bytes.len()
     }
 
+    /// Length of the buffer in bits.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// // Leading zeros count, this is the width and not the significant bits
+    /// let mut raw = [0x00u8, 0x01];
+    /// assert_eq!(ByteString::new(&mut raw).bit_len(), 16);
+    /// ```
     pub fn bit_len(&self) -> usize {
         self.byte_len() * 8
     }
 
+    /// The underlying slice in physical order, ignoring the interpretation flag.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x01u8, 0x02];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    ///
+    /// assert_eq!(bytes.bytes(), [0x01, 0x02]);
+    /// assert!(bytes.iter().eq([0x02, 0x01].iter()));
+    /// ```
     pub fn bytes(&self) -> &[u8] {
         self.bytes
     }
 
+    /// The underlying slice in physical order, ignoring the interpretation flag.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x00u8, 0x00];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    ///
+    /// // Physical index 0 is the least significant byte while reversed
+    /// bytes.bytes_mut()[0] = 0x01;
+    /// assert_eq!(bytes.to_u64(), Ok(0x0001));
+    /// ```
     pub fn bytes_mut(&mut self) -> &mut [u8] {
         self.bytes
     }
 
-    pub fn iter(&self) -> BytesIter {
+    /// Physical position of the byte at logical index `ix` under the current interpretation
+    pub(crate) fn physical_index(&self, ix: usize) -> usize {
+        if self.interpret_reverse_endian { self.byte_len() - 1 - ix } else { ix }
+    }
+
+    /// Assert the internal invariants, compiled to a no-op in release builds.
+    ///
+    /// The backing slice, the iterators and the bit length must agree on the length, and the
+    /// logical to physical mapping of every accessor must follow the interpretation flag. Mutating
+    /// operations call this on entry in debug builds.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x01u8, 0x02, 0x03];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    ///
+    /// bytes.debug_validate();
+    /// ```
+    #[inline]
+    pub fn debug_validate(&self) {
+        #[cfg(debug_assertions)]
+        {
+            let len = self.byte_len();
+
+            assert_eq!(self.bytes().len(), len, "backing slice length disagrees with byte length");
+            assert_eq!(self.iter().len(), len, "iterator length disagrees with byte length");
+            assert_eq!(self.iter().rev().len(), len, "reversed iterator length disagrees with byte length");
+            assert_eq!(self.bit_len(), len.checked_mul(8).expect("byte length overflows the bit length"));
+
+            if len > 0 {
+                let ends = (self.physical_index(0), self.physical_index(len - 1));
+                let expected = if self.interpret_reverse_endian { (len - 1, 0) } else { (0, len - 1) };
+                assert_eq!(ends, expected, "logical to physical mapping does not match the interpretation flag");
+
+                // The iterator resolves the flag on its own, it must land on the same physical bytes
+                let (first, last) = (self.iter().next(), self.iter().next_back());
+                assert_eq!(first, Some(&self.bytes[ends.0]), "iterator and physical_index disagree on the first logical byte");
+                assert_eq!(last, Some(&self.bytes[ends.1]), "iterator and physical_index disagree on the last logical byte");
+            }
+        }
+    }
+
+    /// Iterate over the logical bytes, most significant first.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x01u8, 0x02, 0x03];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    ///
+    /// assert!(bytes.iter().eq([0x03, 0x02, 0x01].iter()));
+    /// assert!(bytes.iter().rev().eq([0x01, 0x02, 0x03].iter()));
+    /// ```
+    pub fn iter(&self) -> BytesIter<'_> {
         BytesIter::new(self.bytes, self.interpret_reverse_endian)
     }
 
-    pub fn iter_mut(&mut self) -> BytesIterMut {
+    /// Mutably iterate over the logical bytes, most significant first.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x00u8, 0x00, 0x00];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    ///
+    /// for (ix, byte) in bytes.iter_mut().enumerate() {
+    ///     *byte = ix as u8;
+    /// }
+    /// assert_eq!(raw, [0x02, 0x01, 0x00]);
+    /// ```
+    pub fn iter_mut(&mut self) -> BytesIterMut<'_> {
         BytesIterMut::new(self.bytes, self.interpret_reverse_endian)
     }
 
+    /// Iterate over the logical bytes in `range`, panics if the range is out of bounds.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x01u8, 0x02, 0x03, 0x04];
+    /// let bytes = ByteString::new(&mut raw);
+    ///
+    /// assert!(bytes.iter_range(1..3).eq([0x02, 0x03].iter()));
+    /// assert_eq!(bytes.iter_range(4..4).count(), 0);
+    /// ```
+    #[track_caller]
+    pub fn iter_range(&self, range: Range<usize>) -> BytesIter<'_> {
+        self.try_iter_range(range).expect("logical range out of bounds")
+    }
+
+    /// Non-panicking form of [`ByteString::iter_range`].
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let mut raw = [0x01u8, 0x02, 0x03, 0x04];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    ///
+    /// // The range is logical, so it starts at the physical end
+    /// assert!(bytes.try_iter_range(0..2).unwrap().eq([0x04, 0x03].iter()));
+    /// assert!(matches!(bytes.try_iter_range(3..5), Err(Error::OutOfBounds)));
+    /// ```
+    pub fn try_iter_range(&self, range: Range<usize>) -> Result<BytesIter<'_>, Error> {
+        let physical = self.physical_range(range)?;
+        Ok(BytesIter::new(&self.bytes[physical], self.interpret_reverse_endian))
+    }
+
+    /// Mutably iterate over the logical bytes in `range`, panics if the range is out of bounds.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x00u8; 4];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    ///
+    /// // The two most significant logical bytes are the last two physical ones
+    /// bytes.iter_range_mut(0..2).for_each(|e| *e = 0xFF);
+    /// assert_eq!(raw, [0x00, 0x00, 0xFF, 0xFF]);
+    /// ```
+    #[track_caller]
+    pub fn iter_range_mut(&mut self, range: Range<usize>) -> BytesIterMut<'_> {
+        self.try_iter_range_mut(range).expect("logical range out of bounds")
+    }
+
+    /// Non-panicking form of [`ByteString::iter_range_mut`].
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let mut raw = [0x00u8; 2];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// assert!(matches!(bytes.try_iter_range_mut(2..1), Err(Error::OutOfBounds)));
+    /// bytes.try_iter_range_mut(1..2).unwrap().for_each(|e| *e = 0x7F);
+    /// assert_eq!(raw, [0x00, 0x7F]);
+    /// ```
+    pub fn try_iter_range_mut(&mut self, range: Range<usize>) -> Result<BytesIterMut<'_>, Error> {
+        let physical = self.physical_range(range)?;
+        Ok(BytesIterMut::new(&mut self.bytes[physical], self.interpret_reverse_endian))
+    }
+
+    // Physical slice range holding the logical bytes of `range`
+    pub(crate) fn physical_range(&self, range: Range<usize>) -> Result<Range<usize>, Error> {
+        if range.start > range.end || range.end > self.byte_len() {
+            return Err(Error::OutOfBounds);
+        }
+
+        if self.interpret_reverse_endian {
+            return Ok(self.byte_len() - range.end..self.byte_len() - range.start);
+        }
+
+        Ok(range)
+    }
+
+    // View of the logical bytes of `range` with the same flags, the range must be in bounds
+    pub(crate) fn sub_view(&mut self, range: Range<usize>) -> ByteString<'_> {
+        let physical = self.physical_range(range).expect("sub view out of bounds");
+        ByteString { bytes: &mut self.bytes[physical], interpret_reverse_endian: self.interpret_reverse_endian, sensitive: self.sensitive }
+    }
+
+    /// Set every byte to `value`.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x00u8; 3];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.set_bytes_with_value(0xFF);
+    /// assert_eq!(bytes.to_u64(), Ok(0xFF_FFFF));
+    /// ```
     pub fn set_bytes_with_value(&mut self, value: u8) {
+        #[cfg(debug_assertions)]
+        self.debug_validate();
+
         util::set_bytes(self.bytes, value);
     }
 
+    /// Set the value to zero.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x12u8, 0x34];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.set_zero();
+    /// assert!(bytes.is_zero());
+    /// ```
     pub fn set_zero(&mut self) {
         self.set_bytes_with_value(0);
     }
 
+    /// Whether every byte is zero.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x00u8; 20];
+    /// assert!(ByteString::new(&mut raw).is_zero());
+    ///
+    /// // An empty buffer is zero
+    /// assert!(ByteString::new(&mut []).is_zero());
+    ///
+    /// raw[19] = 0x01;
+    /// assert!(!ByteString::new(&mut raw).is_zero());
+    /// ```
     pub fn is_zero(&self) -> bool {
-        self.bytes().iter().all(|e| e == &0)
+        let mut split = self.words_and_tail();
+        split.head.physical().iter().all(|e| *e == 0)
+            && split.words.all(|e| e == 0)
+            && split.tail.physical().iter().all(|e| *e == 0)
     }
 
+    /// Reverse the physical byte order, the interpretation flag is left as it is.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x12u8, 0x34, 0x56];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    /// bytes.reverse_byte_endianness();
+    ///
+    /// // The flag still reverses, so the value reads as the original physical order
+    /// assert_eq!(bytes.to_u64(), Ok(0x123456));
+    /// assert_eq!(raw, [0x56, 0x34, 0x12]);
+    /// ```
     pub fn reverse_byte_endianness(&mut self) {
+        #[cfg(debug_assertions)]
+        self.debug_validate();
 
         let count = self.byte_len();
         let mid = ( count - ( count % 2 ) ) / 2;
@@ -82,45 +390,665 @@ impl<'a> ByteString<'a> {
         }
     }
 
+    /// Reverse the order of the bits within every byte, the byte order is unchanged.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x01u8, 0xC0];
+    /// ByteString::new(&mut raw).reverse_bit_endianness();
+    /// assert_eq!(raw, [0x80, 0x03]);
+    /// ```
     pub fn reverse_bit_endianness(&mut self) {
+        #[cfg(debug_assertions)]
+        self.debug_validate();
+
         for byte in self.bytes_mut() {
-            *byte = util::reverse_bit_endianness(*byte);
+            *byte = util::BIT_REVERSE[*byte as usize];
         }
     }
 
+    /// Rotate the logical bytes toward the front by `count`, reduced modulo the byte length
+    ///
+    /// # Examples
+    ///
+    /// The rotation is logical, with the flag set the physical bytes move the other way.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x01u8, 0x02, 0x03, 0x04];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.rotl_bytes(1);
+    /// assert_eq!(raw, [0x02, 0x03, 0x04, 0x01]);
+    ///
+    /// let mut raw = [0x01u8, 0x02, 0x03, 0x04];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    /// bytes.rotl_bytes(1);
+    /// assert_eq!(raw, [0x04, 0x01, 0x02, 0x03]);
+    /// ```
     pub fn rotl_bytes(&mut self, count: usize) {
+        #[cfg(debug_assertions)]
+        self.debug_validate();
+
+        if self.bytes.is_empty() {
+            return;
+        }
+
+        let count = count % self.bytes.len();
         if self.interpret_reverse_endian {
             self.bytes.rotate_right(count);
+        }
+        else {
+            self.bytes.rotate_left(count);
+        }
+    }
+
+    /// Checked form of [`ByteString::rotl_bytes`], fails without modification if `count` exceeds the byte length.
+    ///
+    /// A count equal to the byte length is a no-op. `rotl_bytes` itself accepts any count, so this is
+    /// not a `try_` twin of the panic policy but a check for callers that treat a count past the
+    /// length as a bug, as [`ByteString::try_shl`] is next to the saturating `<<=`.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let mut raw = [0x01u8, 0x02];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.try_rotl_bytes(2), Ok(()));
+    /// assert_eq!(bytes.try_rotl_bytes(3), Err(Error::OutOfBounds));
+    /// assert_eq!(raw, [0x01, 0x02]);
+    /// ```
+    pub fn try_rotl_bytes(&mut self, count: usize) -> Result<(), Error> {
+        if count > self.byte_len() {
+            return Err(Error::OutOfBounds);
+        }
+
+        self.rotl_bytes(count);
+        Ok(())
+    }
+
+    /// Rotate the logical bytes toward the back by `count`, reduced modulo the byte length
+    ///
+    /// # Examples
+    ///
+    /// The inverse of [`ByteString::rotl_bytes`], with the flag set the physical bytes move the other way.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x01u8, 0x02, 0x03, 0x04];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.rotr_bytes(1);
+    /// assert_eq!(raw, [0x04, 0x01, 0x02, 0x03]);
+    ///
+    /// let mut raw = [0x01u8, 0x02, 0x03, 0x04];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    /// bytes.rotr_bytes(5);
+    /// assert_eq!(raw, [0x02, 0x03, 0x04, 0x01]);
+    /// ```
+    pub fn rotr_bytes(&mut self, count: usize) {
+        let byte_len = self.byte_len();
+        if byte_len == 0 {
+            return;
+        }
+
+        self.rotl_bytes(byte_len - count % byte_len);
+    }
+
+    /// Checked left shift, fails without modification if `count` is not below the bit length.
+    ///
+    /// `<<=` instead saturates to zero for any larger count.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let mut raw = [0x00u8, 0x01];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.try_shl(16), Err(Error::OutOfBounds));
+    /// assert_eq!(bytes.to_u64(), Ok(0x0001));
+    ///
+    /// bytes <<= 16;
+    /// assert!(bytes.is_zero());
+    /// ```
+    pub fn try_shl(&mut self, count: usize) -> Result<(), Error> {
+        if count >= self.bit_len() {
+            return Err(Error::OutOfBounds);
+        }
+
+        *self <<= count;
+        Ok(())
+    }
+
+    /// Arithmetic right shift of the two's complement value, the vacated high bits copy the sign bit.
+    ///
+    /// The sign bit is the top bit of the first logical byte. A count of at least the bit length
+    /// leaves all 0x00 or all 0xFF bytes.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x80u8, 0x10];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.sar_assign(4);
+    /// assert_eq!(raw, [0xF8, 0x01]);
+    /// ```
+    pub fn sar_assign(&mut self, count: usize) {
+        let negative = self.iter().next().is_some_and(|e| e & 0x80 != 0);
+        *self >>= count;
+
+        if !negative || count == 0 {
             return;
         }
 
-        self.bytes.rotate_left(count);
+        // Ones into the top `count` bits, whole bytes first and then the rest of the next byte
+        let count = count.min(self.bit_len());
+        for (ix, byte) in self.iter_mut().take(count.div_ceil(8)).enumerate() {
+            *byte |= if ix < count / 8 { 0xFF } else { !(0xFF >> (count % 8)) };
+        }
+    }
+
+    /// self <<= count, returns the bits shifted out of the top in the low `count` bits of the result.
+    ///
+    /// Panics if `count` exceeds 64, see [`ByteString::try_shl_carry`]. A count past the bit length
+    /// ejects the whole value followed by zeros, as if the buffer were wider.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0xABu8, 0xCD];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.shl_carry(4), 0xA);
+    /// assert_eq!(raw, [0xBC, 0xD0]);
+    /// ```
+    #[track_caller]
+    pub fn shl_carry(&mut self, count: usize) -> u64 {
+        self.shl_assign_with_carry_in(count, 0)
+    }
+
+    /// Non-panicking form of [`ByteString::shl_carry`], fails with [`Error::OutOfBounds`] if `count` exceeds 64.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let mut raw = [0x81u8];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// // Past the bit length the value comes out followed by zeros
+    /// assert_eq!(bytes.try_shl_carry(12), Ok(0x810));
+    /// assert!(bytes.is_zero());
+    /// assert_eq!(bytes.try_shl_carry(65), Err(Error::OutOfBounds));
+    /// ```
+    pub fn try_shl_carry(&mut self, count: usize) -> Result<u64, Error> {
+        self.try_shl_assign_with_carry_in(count, 0)
+    }
+
+    /// self >>= count, returns the bits shifted out of the bottom in the low `count` bits of the result.
+    ///
+    /// Panics if `count` exceeds 64, see [`ByteString::try_shr_carry`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0xABu8, 0xCD];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.shr_carry(12), 0xBCD);
+    /// assert_eq!(raw, [0x00, 0x0A]);
+    /// ```
+    #[track_caller]
+    pub fn shr_carry(&mut self, count: usize) -> u64 {
+        self.shr_assign_with_carry_in(count, 0)
+    }
+
+    /// Non-panicking form of [`ByteString::shr_carry`], fails with [`Error::OutOfBounds`] if `count` exceeds 64.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let mut raw = [0x12u8, 0x34];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.try_shr_carry(0), Ok(0));
+    /// assert_eq!(bytes.try_shr_carry(65), Err(Error::OutOfBounds));
+    /// assert_eq!(raw, [0x12, 0x34]);
+    /// ```
+    pub fn try_shr_carry(&mut self, count: usize) -> Result<u64, Error> {
+        self.try_shr_assign_with_carry_in(count, 0)
+    }
+
+    /// self <<= count with the low `count` bits of `carry_in` filling the vacated bottom, returns the carry out.
+    ///
+    /// The buffer acts as the top limb of `(self, carry_in)` shifted left, so the carry of
+    /// [`ByteString::shl_carry`] on a less significant limb chains into the next one. Bits of
+    /// `carry_in` above `count` are ignored. Panics if `count` exceeds 64, see
+    /// [`ByteString::try_shl_assign_with_carry_in`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// // 0x12345678 as two 16 bit limbs, shifted left by 8 as a whole
+    /// let (mut high, mut low) = ([0x12u8, 0x34], [0x56u8, 0x78]);
+    /// let carry = ByteString::new(&mut low).shl_carry(8);
+    /// let out = ByteString::new(&mut high).shl_assign_with_carry_in(8, carry);
+    ///
+    /// assert_eq!((out, high, low), (0x12, [0x34, 0x56], [0x78, 0x00]));
+    /// ```
+    #[track_caller]
+    pub fn shl_assign_with_carry_in(&mut self, count: usize, carry_in: u64) -> u64 {
+        self.try_shl_assign_with_carry_in(count, carry_in).expect("carry shift count exceeds 64")
+    }
+
+    /// Non-panicking form of [`ByteString::shl_assign_with_carry_in`], fails with [`Error::OutOfBounds`] if `count` exceeds 64.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let mut raw = [0x00u8, 0x01];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// // Only the low 4 bits of the carry in are used
+    /// assert_eq!(bytes.try_shl_assign_with_carry_in(4, 0xFA), Ok(0));
+    /// assert_eq!(raw, [0x00, 0x1A]);
+    /// ```
+    pub fn try_shl_assign_with_carry_in(&mut self, count: usize, carry_in: u64) -> Result<u64, Error> {
+        if count > 64 {
+            return Err(Error::OutOfBounds);
+        }
+
+        // Bit `j` of the concatenation (self, carry_in) is self bit `j - count` above `count`
+        let bit_len = self.bit_len();
+        let combined = |bytes: &Self, j: usize| if j >= count { bytes.bit(j - count) } else { carry_in >> j & 1 == 1 };
+        let carry_out = (0..count).fold(0u64, |acc, i| acc | (combined(self, bit_len + i) as u64) << i);
+
+        *self <<= count;
+        for j in 0..count.min(bit_len) {
+            if carry_in >> j & 1 == 1 {
+                self.set_bit(j, true);
+            }
+        }
+
+        Ok(carry_out)
+    }
+
+    /// self >>= count with the low `count` bits of `carry_in` filling the vacated top, returns the carry out.
+    ///
+    /// The mirror of [`ByteString::shl_assign_with_carry_in`]: the buffer acts as the low limb of
+    /// `(carry_in, self)`, and the result holds the bits shifted out of the bottom. Panics if
+    /// `count` exceeds 64, see [`ByteString::try_shr_assign_with_carry_in`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// // 0x12345678 as two 16 bit limbs, shifted right by 8 as a whole
+    /// let (mut high, mut low) = ([0x12u8, 0x34], [0x56u8, 0x78]);
+    /// let carry = ByteString::new(&mut high).shr_carry(8);
+    /// let out = ByteString::new(&mut low).shr_assign_with_carry_in(8, carry);
+    ///
+    /// assert_eq!((out, high, low), (0x78, [0x00, 0x12], [0x34, 0x56]));
+    /// ```
+    #[track_caller]
+    pub fn shr_assign_with_carry_in(&mut self, count: usize, carry_in: u64) -> u64 {
+        self.try_shr_assign_with_carry_in(count, carry_in).expect("carry shift count exceeds 64")
+    }
+
+    /// Non-panicking form of [`ByteString::shr_assign_with_carry_in`], fails with [`Error::OutOfBounds`] if `count` exceeds 64.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let mut raw = [0x00u8; 16];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.try_shr_assign_with_carry_in(64, u64::MAX), Ok(0));
+    /// assert_eq!(bytes.try_shr_assign_with_carry_in(65, 0), Err(Error::OutOfBounds));
+    /// assert_eq!(raw[..8], [0xFF; 8]);
+    /// ```
+    pub fn try_shr_assign_with_carry_in(&mut self, count: usize, carry_in: u64) -> Result<u64, Error> {
+        if count > 64 {
+            return Err(Error::OutOfBounds);
+        }
+
+        // Bit `j` of the concatenation (carry_in, self) is carry_in bit `j - bit_len` above the buffer
+        let bit_len = self.bit_len();
+        let combined = |bytes: &Self, j: usize| if j < bit_len { bytes.bit(j) } else { carry_in >> (j - bit_len) & 1 == 1 };
+        let carry_out = (0..count).fold(0u64, |acc, i| acc | (combined(self, i) as u64) << i);
+
+        *self >>= count;
+        for j in bit_len.saturating_sub(count)..bit_len {
+            if carry_in >> (j + count - bit_len) & 1 == 1 {
+                self.set_bit(j, true);
+            }
+        }
+
+        Ok(carry_out)
+    }
+
+    /// self <<= count with the vacated low bits taken from the top `count` bits of `src`.
+    ///
+    /// The result is the window of `self` followed by `src` that starts `count` bits in, so
+    /// repeated calls slide over a message split across buffers. `src` may differ in length and
+    /// both flags apply. Fails with [`Error::OutOfBounds`] without modification if `count` exceeds
+    /// either bit length.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let (mut a, mut b) = ([0x12u8, 0x34], [0xAB]);
+    /// let mut bytes = ByteString::new(&mut a);
+    ///
+    /// bytes.funnel_shl(&ByteString::new(&mut b), 4).unwrap();
+    /// assert_eq!(a, [0x23, 0x4A]);
+    /// ```
+    pub fn funnel_shl(&mut self, src: &ByteString, count: usize) -> Result<(), Error> {
+        if count > self.bit_len() || count > src.bit_len() {
+            return Err(Error::OutOfBounds);
+        }
+
+        *self <<= count;
+        let src_low = src.bit_len() - count;
+        for j in 0..count {
+            if src.bit(src_low + j) {
+                self.set_bit(j, true);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// self >>= count with the vacated high bits taken from the low `count` bits of `src`.
+    ///
+    /// The mirror of [`ByteString::funnel_shl`]: the result is the window of `src` followed by
+    /// `self` that ends `count` bits before the end.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let (mut a, mut b) = ([0x12u8, 0x34], [0xAB]);
+    /// let mut bytes = ByteString::new(&mut a);
+    /// let src = ByteString::new(&mut b);
+    ///
+    /// bytes.funnel_shr(&src, 4).unwrap();
+    /// assert_eq!(bytes.bytes(), [0xB1, 0x23]);
+    ///
+    /// // The count may not exceed the shorter of the two
+    /// assert_eq!(bytes.funnel_shr(&src, 9), Err(Error::OutOfBounds));
+    /// ```
+    pub fn funnel_shr(&mut self, src: &ByteString, count: usize) -> Result<(), Error> {
+        if count > self.bit_len() || count > src.bit_len() {
+            return Err(Error::OutOfBounds);
+        }
+
+        *self >>= count;
+        let self_low = self.bit_len() - count;
+        for j in 0..count {
+            if src.bit(j) {
+                self.set_bit(self_low + j, true);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// self = !(self & rhs) pairwise over the logical bytes, with the length rules of [`BitAndAssign`].
+    ///
+    /// With the `debug-strict` feature a debug build panics if the lengths differ, unless a mismatch
+    /// handler is set, see [`ByteString::try_nand_assign`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let (mut a, mut b) = ([0b1100u8, 0xFF], [0xFFu8, 0b1010]);
+    /// let mut bytes = ByteString::new(&mut a);
+    /// let mut rhs = ByteString::new(&mut b);
+    /// rhs.interpret_reverse_endian();
+    ///
+    /// bytes.nand_assign(&rhs);
+    /// assert_eq!(a, [0b1111_0111, 0x00]);
+    /// ```
+    #[track_caller]
+    pub fn nand_assign(&mut self, rhs: &ByteString) {
+        self.zip_assign(rhs, |a, b| !(a & b));
+    }
+
+    /// Non-panicking form of [`ByteString::nand_assign`], fails without modification if the lengths differ.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::LengthError};
+    ///
+    /// let (mut a, mut b) = ([0xFFu8, 0xFF], [0x0Fu8]);
+    /// let mut bytes = ByteString::new(&mut a);
+    ///
+    /// let result = bytes.try_nand_assign(&ByteString::new(&mut b));
+    /// assert_eq!(result, Err(LengthError { index: 0, expected: 2, actual: 1 }));
+    /// assert_eq!(a, [0xFF, 0xFF]);
+    /// ```
+    pub fn try_nand_assign(&mut self, rhs: &ByteString) -> Result<(), LengthError> {
+        self.try_zip_assign(rhs, |a, b| !(a & b))
+    }
+
+    /// self = !(self | rhs) pairwise over the logical bytes, see [`ByteString::nand_assign`].
+    ///
+    /// With the `debug-strict` feature a debug build panics if the lengths differ, see
+    /// [`ByteString::try_nor_assign`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let (mut a, mut b) = ([0xF0u8, 0x00], [0x0Fu8, 0x01]);
+    /// let mut bytes = ByteString::new(&mut a);
+    ///
+    /// // Only bits clear on both sides become ones
+    /// bytes.nor_assign(&ByteString::new(&mut b));
+    /// assert_eq!(a, [0x00, 0xFE]);
+    /// ```
+    #[track_caller]
+    pub fn nor_assign(&mut self, rhs: &ByteString) {
+        self.zip_assign(rhs, |a, b| !(a | b));
+    }
+
+    /// Non-panicking form of [`ByteString::nor_assign`], fails without modification if the lengths differ.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let (mut a, mut b) = ([0xF0u8], [0x0Fu8, 0x00]);
+    /// let mut bytes = ByteString::new(&mut a);
+    ///
+    /// assert!(bytes.try_nor_assign(&ByteString::new(&mut b)).is_err());
+    /// assert_eq!(a, [0xF0]);
+    /// ```
+    pub fn try_nor_assign(&mut self, rhs: &ByteString) -> Result<(), LengthError> {
+        self.try_zip_assign(rhs, |a, b| !(a | b))
+    }
+
+    /// self = !(self ^ rhs) pairwise over the logical bytes, see [`ByteString::nand_assign`].
+    ///
+    /// With the `debug-strict` feature a debug build panics if the lengths differ, see
+    /// [`ByteString::try_xnor_assign`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let (mut a, mut b) = ([0x5Au8, 0x00], [0x5Au8, 0xFF]);
+    /// let mut bytes = ByteString::new(&mut a);
+    ///
+    /// // Equal bits become ones
+    /// bytes.xnor_assign(&ByteString::new(&mut b));
+    /// assert_eq!(a, [0xFF, 0x00]);
+    /// ```
+    #[track_caller]
+    pub fn xnor_assign(&mut self, rhs: &ByteString) {
+        self.zip_assign(rhs, |a, b| !(a ^ b));
+    }
+
+    /// Non-panicking form of [`ByteString::xnor_assign`], fails without modification if the lengths differ.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let (mut a, mut b) = ([0x5Au8], [0x5Au8]);
+    /// let mut bytes = ByteString::new(&mut a);
+    ///
+    /// assert_eq!(bytes.try_xnor_assign(&ByteString::new(&mut b)), Ok(()));
+    /// assert_eq!(a, [0xFF]);
+    /// ```
+    pub fn try_xnor_assign(&mut self, rhs: &ByteString) -> Result<(), LengthError> {
+        self.try_zip_assign(rhs, |a, b| !(a ^ b))
+    }
+
+    /// self = (self & !mask) | (other & mask), copying the bits selected by `mask` from `other`.
+    ///
+    /// Bytes pair up by logical index, each operand under its own interpretation flag.
+    /// Panics if `other` or `mask` differs in length from self, see [`ByteString::try_merge_bits`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let (mut a, mut b, mut m) = ([0xAAu8, 0xAA], [0x55u8, 0x55], [0x0Fu8, 0xFF]);
+    /// let mut bytes = ByteString::new(&mut a);
+    /// bytes.merge_bits(&ByteString::new(&mut b), &ByteString::new(&mut m));
+    /// assert_eq!(a, [0xA5, 0x55]);
+    /// ```
+    #[track_caller]
+    pub fn merge_bits(&mut self, other: &ByteString, mask: &ByteString) {
+        self.try_merge_bits(other, mask).expect("merge_bits operands differ in length");
+    }
+
+    /// Non-panicking form of [`ByteString::merge_bits`], error indices count `other` as 0 and `mask` as 1.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::LengthError};
+    ///
+    /// let (mut a, mut b, mut m) = ([0xAAu8, 0xAA], [0x55u8, 0x55], [0xFFu8]);
+    /// let mut bytes = ByteString::new(&mut a);
+    ///
+    /// let result = bytes.try_merge_bits(&ByteString::new(&mut b), &ByteString::new(&mut m));
+    /// assert_eq!(result, Err(LengthError { index: 1, expected: 2, actual: 1 }));
+    /// assert_eq!(a, [0xAA, 0xAA]);
+    /// ```
+    pub fn try_merge_bits(&mut self, other: &ByteString, mask: &ByteString) -> Result<(), LengthError> {
+        for (index, len) in [other.byte_len(), mask.byte_len()].into_iter().enumerate() {
+            if len != self.byte_len() {
+                return Err(LengthError { index, expected: self.byte_len(), actual: len });
+            }
+        }
+
+        // Masked merge from Bit Twiddling Hacks, one read and write of each byte
+        for ((a, b), m) in self.iter_mut().zip(other.iter()).zip(mask.iter()) {
+            *a ^= (*a ^ *b) & *m;
+        }
+
+        Ok(())
+    }
+
+    // Combine pairs of logical bytes from the most significant end like the bitwise operators
+    #[track_caller]
+    fn zip_assign<F: Fn(u8, u8) -> u8>(&mut self, rhs: &ByteString, f: F) {
+        #[cfg(debug_assertions)]
+        {
+            self.debug_validate();
+            rhs.debug_validate();
+        }
+        strict::check_zip_lengths(self.byte_len(), rhs.byte_len());
+
+        for (a, b) in self.iter_mut().zip(rhs.iter()) {
+            *a = f(*a, *b);
+        }
+    }
+
+    // Equal lengths never reach the debug-strict check in zip_assign
+    fn try_zip_assign<F: Fn(u8, u8) -> u8>(&mut self, rhs: &ByteString, f: F) -> Result<(), LengthError> {
+        if rhs.byte_len() != self.byte_len() {
+            return Err(LengthError { index: 0, expected: self.byte_len(), actual: rhs.byte_len() });
+        }
+
+        self.zip_assign(rhs, f);
+        Ok(())
     }
 
 }
 
+/// Equal when the logical bytes are equal, however they are laid out physically.
+///
+/// Leading zero bytes count, compare values with [`ByteString::cmp_numeric`] instead.
+///
+/// ```
+/// use rebite::bytes::ByteString;
+///
+/// let (mut a, mut b) = ([0x01u8, 0x02], [0x02u8, 0x01]);
+/// let a = ByteString::new(&mut a);
+/// let mut b = ByteString::new(&mut b);
+/// assert_ne!(a, b);
+///
+/// b.interpret_reverse_endian();
+/// assert_eq!(a, b);
+///
+/// let mut c = [0x00u8, 0x01, 0x02];
+/// assert_ne!(a, ByteString::new(&mut c));
+/// ```
 impl<'a> PartialEq for ByteString<'a> {
     fn eq(&self, other: &Self) -> bool {
-
-        let eq_len = self.byte_len() == other.byte_len();
-        let eq_mem = self.iter().eq(other.iter());
-
-        eq_len && eq_mem
+        self.first_mismatch_chunked(other).is_none()
     }
 }
 impl<'a> Eq for ByteString<'a> {}
 
+/// Bitwise complement of every byte.
+///
+/// ```
+/// use rebite::bytes::ByteString;
+///
+/// let mut raw = [0x0Fu8, 0x00];
+/// let bytes = !ByteString::new(&mut raw);
+/// assert_eq!(bytes.to_u64(), Ok(0xF0FF));
+/// ```
 impl<'a> Not for ByteString<'a> {
     type Output = Self;
 
     fn not(mut self) -> Self::Output {
+        #[cfg(debug_assertions)]
+        self.debug_validate();
+
         self.iter_mut().for_each(|e| *e = e.not() );
         self
     }
 }
 
+/// Bitwise and of the logical bytes pairwise from the most significant end.
+///
+/// Unlike the arithmetic operators the operands are not aligned by value, bytes past the shorter
+/// operand are left unchanged. With the `debug-strict` feature debug builds panic on operands of
+/// different lengths instead, or report them to the handler of `set_mismatch_handler`.
+///
+/// ```
+/// use rebite::bytes::ByteString;
+///
+/// # #[cfg(not(feature = "debug-strict"))] {
+/// let (mut a, mut b) = ([0xFFu8, 0xFF, 0xFF], [0x0Fu8]);
+/// let mut bytes = ByteString::new(&mut a);
+/// let rhs = ByteString::new(&mut b);
+///
+/// bytes &= &rhs;
+/// assert_eq!(a, [0x0F, 0xFF, 0xFF]);
+/// # }
+/// ```
 impl<'a, 'b: 'a> BitAndAssign<&'b ByteString<'b>> for ByteString<'a> {
+    #[track_caller]
     fn bitand_assign(&mut self, rhs: &'b ByteString<'b>) {
+        #[cfg(debug_assertions)]
+        {
+            self.debug_validate();
+            rhs.debug_validate();
+        }
+        strict::check_zip_lengths(self.byte_len(), rhs.byte_len());
+
         let iter = self.iter_mut().zip(rhs.iter());
         
         for (a,b) in iter {
@@ -129,8 +1057,17 @@ impl<'a, 'b: 'a> BitAndAssign<&'b ByteString<'b>> for ByteString<'a> {
     }
 }
 
+/// Bitwise xor of the logical bytes pairwise from the most significant end, see [`BitAndAssign`]
 impl<'a, 'b: 'a> BitXorAssign<&'b ByteString<'b>> for ByteString<'a> {
+    #[track_caller]
     fn bitxor_assign(&mut self, rhs: &'b ByteString<'b>) {
+        #[cfg(debug_assertions)]
+        {
+            self.debug_validate();
+            rhs.debug_validate();
+        }
+        strict::check_zip_lengths(self.byte_len(), rhs.byte_len());
+
         let iter = self.iter_mut().zip(rhs.iter());
         
         for (a,b) in iter {
@@ -139,8 +1076,17 @@ impl<'a, 'b: 'a> BitXorAssign<&'b ByteString<'b>> for ByteString<'a> {
     }
 }
 
+/// Bitwise or of the logical bytes pairwise from the most significant end, see [`BitAndAssign`]
 impl<'a, 'b: 'a> BitOrAssign<&'b ByteString<'b>> for ByteString<'a> {
+    #[track_caller]
     fn bitor_assign(&mut self, rhs: &'b ByteString<'b>) {
+        #[cfg(debug_assertions)]
+        {
+            self.debug_validate();
+            rhs.debug_validate();
+        }
+        strict::check_zip_lengths(self.byte_len(), rhs.byte_len());
+
         let iter = self.iter_mut().zip(rhs.iter());
         
         for (a,b) in iter {
@@ -149,8 +1095,215 @@ impl<'a, 'b: 'a> BitOrAssign<&'b ByteString<'b>> for ByteString<'a> {
     }
 }
 
+/// Wrapping addition, operands are aligned at the least significant byte.
+///
+/// ```
+/// use rebite::bytes::ByteString;
+///
+/// let (mut a, mut b) = ([0x00u8, 0xFF], [0x01u8]);
+/// let mut bytes = ByteString::new(&mut a);
+/// let rhs = ByteString::new(&mut b);
+///
+/// bytes += &rhs;
+/// assert_eq!(bytes.to_u64(), Ok(0x0100));
+///
+/// // The carry out of the most significant byte is dropped
+/// let mut all_ones = [0xFFu8, 0xFF];
+/// let mut bytes = ByteString::new(&mut all_ones);
+/// bytes += &rhs;
+/// assert!(bytes.is_zero());
+/// ```
+impl<'a, 'b: 'a> AddAssign<&'b ByteString<'b>> for ByteString<'a> {
+    fn add_assign(&mut self, rhs: &'b ByteString<'b>) {
+        #[cfg(debug_assertions)]
+        {
+            self.debug_validate();
+            rhs.debug_validate();
+        }
+
+        // Least significant bytes first, a shorter rhs is zero-extended and bytes of a longer one
+        // past the width of self only affect the wrapped part of the sum
+        self.overflowing_add_assign(rhs);
+    }
+}
+
+/// Wrapping addition of a scalar, its bytes past the width of self are dropped.
+///
+/// ```
+/// use rebite::bytes::ByteString;
+///
+/// let mut raw = [0xF0u8];
+/// let mut bytes = ByteString::new(&mut raw);
+/// bytes += 0x0123u64;
+/// assert_eq!(raw, [0x13]);
+/// ```
+impl<'a> AddAssign<u64> for ByteString<'a> {
+    fn add_assign(&mut self, rhs: u64) {
+        #[cfg(debug_assertions)]
+        self.debug_validate();
+
+        // High bytes of rhs past the width of self wrap away like the carry
+        self.add_assign_scalar(rhs);
+    }
+}
+
+/// Wrapping subtraction, operands are aligned at the least significant byte.
+///
+/// ```
+/// use rebite::bytes::ByteString;
+///
+/// let (mut a, mut b) = ([0x00u8, 0x01], [0x02u8]);
+/// let mut bytes = ByteString::new(&mut a);
+/// let rhs = ByteString::new(&mut b);
+///
+/// bytes -= &rhs;
+/// assert_eq!(bytes.to_u64(), Ok(0xFFFF));
+/// ```
+impl<'a, 'b: 'a> SubAssign<&'b ByteString<'b>> for ByteString<'a> {
+    fn sub_assign(&mut self, rhs: &'b ByteString<'b>) {
+        #[cfg(debug_assertions)]
+        {
+            self.debug_validate();
+            rhs.debug_validate();
+        }
+
+        self.wrapping_sub_assign(rhs);
+    }
+}
+
+/// Wrapping multiplication, the product is truncated to the width of self.
+///
+/// ```
+/// use rebite::bytes::ByteString;
+///
+/// let (mut a, mut b) = ([0x12u8, 0x34], [0x01u8, 0x00]);
+/// let mut bytes = ByteString::new(&mut a);
+/// let rhs = ByteString::new(&mut b);
+///
+/// bytes *= &rhs;
+/// assert_eq!(bytes.to_u64(), Ok(0x3400));
+/// ```
+impl<'a, 'b: 'a> MulAssign<&'b ByteString<'b>> for ByteString<'a> {
+    fn mul_assign(&mut self, rhs: &'b ByteString<'b>) {
+        #[cfg(debug_assertions)]
+        {
+            self.debug_validate();
+            rhs.debug_validate();
+        }
+
+        let len = self.byte_len();
+
+        // Schoolbook over least significant first limbs, partial products past len bytes are dropped
+        let lhs: Vec<u8> = self.iter().rev().copied().collect();
+        let mut acc = vec![0u8; len];
+
+        for (i, b) in rhs.iter().rev().take(len).enumerate() {
+            let mut carry = 0u16;
+            for (j, a) in lhs.iter().take(len - i).enumerate() {
+                // At most 0xFF + 0xFF * 0xFF + 0xFF, which still fits
+                let v = acc[i + j] as u16 + *a as u16 * *b as u16 + carry;
+                acc[i + j] = v as u8;
+                carry = v >> 8;
+            }
+        }
+
+        for (dst, value) in self.iter_mut().rev().zip(acc) {
+            *dst = value;
+        }
+    }
+}
+
+/// Wrapping multiplication by a byte, the product is truncated to the width of self
+impl<'a> MulAssign<u8> for ByteString<'a> {
+    fn mul_assign(&mut self, rhs: u8) {
+        #[cfg(debug_assertions)]
+        self.debug_validate();
+
+        // At most 0xFF * 0xFF + 0xFF, the carry out of the most significant byte is dropped
+        let mut carry = 0u16;
+        for byte in self.iter_mut().rev() {
+            let v = *byte as u16 * rhs as u16 + carry;
+            *byte = v as u8;
+            carry = v >> 8;
+        }
+    }
+}
+
+/// Wrapping multiplication by a scalar, the product is truncated to the width of self
+impl<'a> MulAssign<u64> for ByteString<'a> {
+    fn mul_assign(&mut self, rhs: u64) {
+        #[cfg(debug_assertions)]
+        self.debug_validate();
+
+        let mut carry = 0u128;
+        for byte in self.iter_mut().rev() {
+            let v = *byte as u128 * rhs as u128 + carry;
+            *byte = v as u8;
+            carry = v >> 8;
+        }
+    }
+}
+
+/// Division rounding down, panics if the divisor is zero. See [`ByteString::try_div_assign`].
+///
+/// ```
+/// use rebite::bytes::ByteString;
+///
+/// let (mut a, mut b) = ([0x01u8, 0x00], [0x00u8, 0x00, 0x03]);
+/// let mut bytes = ByteString::new(&mut a);
+/// let rhs = ByteString::new(&mut b);
+///
+/// bytes /= &rhs;
+/// assert_eq!(bytes.to_u64(), Ok(0x55));
+/// ```
+impl<'a, 'b: 'a> DivAssign<&'b ByteString<'b>> for ByteString<'a> {
+    #[track_caller]
+    fn div_assign(&mut self, rhs: &'b ByteString<'b>) {
+        #[cfg(debug_assertions)]
+        {
+            self.debug_validate();
+            rhs.debug_validate();
+        }
+
+        self.try_div_assign(rhs).expect("division by zero");
+    }
+}
+
+/// Remainder of the division, panics if the divisor is zero. See [`ByteString::try_rem_assign`].
+impl<'a, 'b: 'a> RemAssign<&'b ByteString<'b>> for ByteString<'a> {
+    #[track_caller]
+    fn rem_assign(&mut self, rhs: &'b ByteString<'b>) {
+        #[cfg(debug_assertions)]
+        {
+            self.debug_validate();
+            rhs.debug_validate();
+        }
+
+        self.try_rem_assign(rhs).expect("division by zero");
+    }
+}
+
+/// Left shift by bits toward the logical start, a count of at least the bit length gives zero.
+///
+/// ```
+/// use rebite::bytes::ByteString;
+///
+/// let mut raw = [0x01u8, 0x00];
+/// let mut bytes = ByteString::new(&mut raw);
+/// bytes.interpret_reverse_endian();
+///
+/// bytes <<= 12;
+/// assert_eq!(bytes.to_u64(), Ok(0x1000));
+/// assert_eq!(bytes.bytes(), [0x00, 0x10]);
+///
+/// bytes <<= 100;
+/// assert!(bytes.is_zero());
+/// ```
 impl<'a> ShlAssign<usize> for ByteString<'a> {
     fn shl_assign(&mut self, rhs: usize) {
+        #[cfg(debug_assertions)]
+        self.debug_validate();
+
         
         if self.is_zero() || rhs == 0 { 
             return; 
@@ -160,29 +1313,37 @@ impl<'a> ShlAssign<usize> for ByteString<'a> {
             return;
         }
 
-        let shift_count = rhs % self.bit_len();
-        let shift_per_byte = shift_count % 8;
-        let shifted_out_bytes = shift_count / 8;
+        // Without the reversed interpretation the logical bytes are the physical ones, eight at a time
+        if self.interpret_reverse_endian { self.shl_bits(rhs) } else { self.shl_words(rhs) }
+    }
+}
 
-        // Perform bitshift: ignore top bytes that would be shifted out and start with lowest byte
-        if shift_per_byte > 0 {
-            let mut carry_bits = 0;
-            for byte in self.iter_mut().skip(shifted_out_bytes).rev() {
-                
-                let tmp_carry_bits = *byte >> (8 - shift_per_byte);
-                *byte <<= shift_per_byte;
-                *byte |= carry_bits;
-                carry_bits = tmp_carry_bits;
-            }
+/// Logical right shift by bits, shifting in zeros, a count of at least the bit length gives zero.
+///
+/// ```
+/// use rebite::bytes::ByteString;
+///
+/// let mut raw = [0x80u8, 0x01];
+/// let mut bytes = ByteString::new(&mut raw);
+///
+/// bytes >>= 9;
+/// assert_eq!(raw, [0x00, 0x40]);
+/// ```
+impl<'a> ShrAssign<usize> for ByteString<'a> {
+    fn shr_assign(&mut self, rhs: usize) {
+        #[cfg(debug_assertions)]
+        self.debug_validate();
+
+        if self.is_zero() || rhs == 0 {
+            return;
         }
 
-        // Zeroize shifted out bytes
-        for byte in self.iter_mut().take(shifted_out_bytes) {
-            *byte = 0;
+        if rhs >= self.bit_len() {
+            self.set_zero();
+            return;
         }
 
-        // Rotate leading zeros and rest of bytes in place
-        self.rotl_bytes(shifted_out_bytes);
+        if self.interpret_reverse_endian { self.shr_bits(rhs) } else { self.shr_words(rhs) }
     }
 }
 
@@ -190,8 +1351,43 @@ impl<'a> ShlAssign<usize> for ByteString<'a> {
 mod tests {
 
     use super::*;
+    use crate::test_util::{view, view_logical};
+
+    // Shift with the flag set, returning the physical bytes
+    fn shl_reversed(physical: [u8; 4], count: usize) -> [u8; 4] {
+        let mut raw = physical;
+        let mut bytes = ByteString::new(&mut raw);
+        bytes.interpret_reverse_endian();
+        bytes <<= count;
+        raw
+    }
 
-    //TODO: Write tests for multi byte interpret_reverse_endian left-shift
+    #[test]
+    fn test_shl_multi_byte_reversed() {
+        // Physically little-endian 0x04030201
+        let physical = [1u8, 2, 3, 4];
+
+        assert_eq!(shl_reversed(physical, 7), [0x80, 0x00, 0x81, 0x01]);
+        assert_eq!(shl_reversed(physical, 8), [0x00, 0x01, 0x02, 0x03]);
+        assert_eq!(shl_reversed(physical, 9), [0x00, 0x02, 0x04, 0x06]);
+        assert_eq!(shl_reversed(physical, 31), [0x00, 0x00, 0x00, 0x80]);
+        assert_eq!(shl_reversed(physical, 32), [0x00; 4]);
+    }
+
+    #[test]
+    fn test_shl_reversed_matches_physical_reversal() {
+        for count in [0, 1, 7, 8, 9, 15, 16, 17, 31, 32] {
+            let physical = [0x81u8, 0x42, 0x24, 0x18];
+
+            // Reverse physically, shift without the flag, reverse back
+            let mut expected = physical;
+            expected.reverse();
+            ByteString::new(&mut expected).shl_assign(count);
+            expected.reverse();
+
+            assert_eq!(shl_reversed(physical, count), expected, "<< {}", count);
+        }
+    }
 
     #[test]
     fn test_shl_multi_byte_bigger_bitlen_shift() {
@@ -234,6 +1430,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_shl_multi_byte_0shift() {
         let mut a = [1u8,2,3,4];
         let expected = a.clone();
@@ -244,6 +1441,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_shl_single_byte_bigger_bitlen_shift() {
         let a = [1u8];
 
@@ -257,6 +1455,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_shl_single_byte_bitlen_shift() {
         let a = [1u8];
 
@@ -270,6 +1469,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_shl_single_byte_7shift() {
         let a = [1u8];
 
@@ -283,6 +1483,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_shl_single_byte_0shift() {
         let a = [1u8];
 
@@ -296,6 +1497,492 @@ mod tests {
     }
 
     #[test]
+    fn test_add_assign_carry_ripples() {
+        let mut a = [0x00u8, 0xFF, 0xFF];
+        let mut b = [0x01u8];
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes += &ByteString::new(&mut b);
+        assert_eq!(a, [0x01, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_add_assign_wraps() {
+        let mut a = [0xFFu8, 0xFF];
+        let mut b = [0x01u8];
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes += &ByteString::new(&mut b);
+        assert_eq!(a, [0x00, 0x00]);
+
+        // Bytes of rhs past the width of self are dropped along with the carry
+        let mut a = [0x80u8];
+        let mut b = [0x12u8, 0x80];
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes += &ByteString::new(&mut b);
+        assert_eq!(a, [0x00]);
+    }
+
+    #[test]
+    fn test_add_assign_reversed() {
+        let mut a = [0xFFu8, 0xFF, 0x00];
+        let mut b = [0x01u8, 0x00];
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes.interpret_reverse_endian();
+        let mut rhs = ByteString::new(&mut b);
+        rhs.interpret_reverse_endian();
+
+        bytes += &rhs;
+        assert_eq!(a, [0x00, 0x00, 0x01]);
+    }
+
+    #[test]
+    fn test_arithmetic_against_u32() {
+        type Op = for<'x> fn(&mut ByteString<'x>, &'x ByteString<'x>);
+        type Reference = fn(u32, u32) -> Option<u32>;
+
+        // Each operator next to its u32 reference, None where the reference rejects the operands
+        let ops: [(&str, Op, Reference); 4] = [
+            ("+=", |a, b| *a += b, |x, y| Some(x.wrapping_add(y))),
+            ("-=", |a, b| *a -= b, |x, y| Some(x.wrapping_sub(y))),
+            ("*=", |a, b| *a *= b, |x, y| Some(x.wrapping_mul(y))),
+            ("/=", |a, b| *a /= b, u32::checked_div),
+        ];
+        let mut rng = crate::test_util::XorShift::new(0x252);
+
+        for (name, op, reference) in ops {
+            for _ in 0..1000 {
+                // A random u32 and a rhs of 1 to 4 bytes, both shifted down at times for small values
+                let x = rng.next_u64() as u32 >> rng.below(32);
+                let rhs_len = 1 + rng.below(4);
+                let y = (rng.next_u64() as u32 & (u32::MAX >> (32 - 8 * rhs_len))) >> rng.below(8 * rhs_len);
+                let Some(expected) = reference(x, y) else { continue };
+
+                let mut a = x.to_be_bytes();
+                let mut b = (y as u64).to_be_bytes();
+                let b = &mut b[8 - rhs_len..];
+
+                let (a_reversed, b_reversed) = (rng.next_bool(), rng.next_bool());
+                if a_reversed { a.reverse(); }
+                if b_reversed { b.reverse(); }
+
+                let mut bytes = view(&mut a, a_reversed);
+                let rhs = view(b, b_reversed);
+
+                op(&mut bytes, &rhs);
+                assert!(bytes.iter().eq(expected.to_be_bytes().iter()), "{:#x} {} {:#x}", x, name, y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_assign_scalar_widths() {
+        let mut one = [0xF0u8];
+        let mut bytes = ByteString::new(&mut one);
+        bytes += 0x1234u64;
+        assert_eq!(one, [0x24]);
+        assert!(ByteString::new(&mut one).add_assign_scalar(0xFF));
+
+        let mut four = [0xFFu8, 0xFF, 0x00, 0x00];
+        let mut bytes = ByteString::new(&mut four);
+        bytes.interpret_reverse_endian();
+        bytes += 0x0102u64;
+        assert_eq!(bytes.bytes(), [0x01, 0x01, 0x01, 0x00]);
+        assert!(!bytes.add_assign_scalar(0xFFFE_FEFE));
+        assert_eq!(bytes.to_u64(), Ok(0xFFFF_FFFF));
+        assert!(bytes.add_assign_scalar(1));
+
+        let mut twelve = [0u8; 12];
+        let mut bytes = ByteString::new(&mut twelve);
+        bytes += u64::MAX;
+        assert_eq!(bytes.to_u64(), Ok(u64::MAX));
+    }
+
+    #[test]
+    fn test_add_assign_scalar_crosses_eight_bytes() {
+        for reversed in [false, true] {
+            let mut raw = [0x00u8, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+            let mut bytes = ByteString::new(&mut raw);
+            if reversed { bytes.reverse_byte_endianness(); bytes.interpret_reverse_endian(); }
+
+            assert!(!bytes.add_assign_scalar(2));
+            assert!(bytes.iter().eq([0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01].iter()));
+        }
+
+        let mut raw = [0xFFu8; 12];
+        assert!(ByteString::new(&mut raw).add_assign_scalar(1));
+        assert_eq!(raw, [0x00; 12]);
+    }
+
+    #[test]
+    fn test_sub_assign_borrow_ripples() {
+        let mut a = [0x00u8, 0x00];
+        let mut b = [0x00u8, 0x01];
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes -= &ByteString::new(&mut b);
+        assert_eq!(a, [0xFF, 0xFF]);
+
+        let mut a = [0x01u8, 0x00, 0x00];
+        let mut b = [0x01u8];
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes -= &ByteString::new(&mut b);
+        assert_eq!(a, [0x00, 0xFF, 0xFF]);
+    }
+
+    #[test]
+    fn test_sub_assign_equal_is_zero() {
+        let mut a = [0x12u8, 0x34, 0x56];
+        let mut b = [0x56u8, 0x34, 0x12];
+
+        let mut bytes = ByteString::new(&mut a);
+        let mut rhs = ByteString::new(&mut b);
+        rhs.interpret_reverse_endian();
+
+        bytes -= &rhs;
+        assert!(bytes.is_zero());
+    }
+
+    #[test]
+    fn test_mul_assign_known_products() {
+        let mut a = [0x12u8, 0x34];
+        let mut b = [0x00u8, 0x10];
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes *= &ByteString::new(&mut b);
+        assert_eq!(a, [0x23, 0x40]);
+
+        let mut a = [0x00u8, 0x00, 0x12, 0x34];
+        let mut b = [0x10u8];
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes *= &ByteString::new(&mut b);
+        assert_eq!(a, [0x00, 0x01, 0x23, 0x40]);
+    }
+
+    #[test]
+    fn test_mul_assign_truncates_high_bytes() {
+        // 0xFFFF * 0xFFFF = 0xFFFE0001, only the low two bytes remain
+        let mut a = [0xFFu8, 0xFF];
+        let mut b = [0xFFu8, 0xFF];
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes.interpret_reverse_endian();
+        bytes *= &ByteString::new(&mut b);
+        assert_eq!(a, [0x01, 0x00]);
+
+        // rhs wider than self only contributes its low bytes
+        let mut a = [0x03u8];
+        let mut b = [0x7Fu8, 0x00, 0x05];
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes *= &ByteString::new(&mut b);
+        assert_eq!(a, [0x0F]);
+    }
+
+    #[test]
+    fn test_mul_assign_scalar_known_products() {
+        for reversed in [false, true] {
+            let value = if reversed { 0x1234 } else { 0x3412 };
+
+            let mut raw = [0x34u8, 0x12];
+            let mut bytes = view(&mut raw, reversed);
+            bytes *= 0x10u8;
+            assert_eq!(bytes.to_u64(), Ok((value * 0x10) & 0xFFFF));
+
+            let mut raw = [0x34u8, 0x12];
+            let mut bytes = view(&mut raw, reversed);
+            bytes *= 0x10u64;
+            assert_eq!(bytes.to_u64(), Ok((value * 0x10) & 0xFFFF));
+
+            let mut raw = [0x34u8, 0x12];
+            let mut bytes = view(&mut raw, reversed);
+            bytes *= 1u8;
+            assert_eq!(bytes.to_u64(), Ok(value));
+            bytes *= 1u64;
+            assert_eq!(bytes.to_u64(), Ok(value));
+            bytes *= 0u8;
+            assert!(bytes.is_zero());
+
+            let mut raw = [0x34u8, 0x12];
+            let mut bytes = view(&mut raw, reversed);
+            bytes *= 0u64;
+            assert!(bytes.is_zero());
+        }
+    }
+
+    #[test]
+    fn test_mul_assign_scalar_overflow_truncates() {
+        let mut raw = [0x80u8, 0x01];
+        let mut bytes = ByteString::new(&mut raw);
+        bytes *= 0x02u8;
+        assert_eq!(raw, [0x00, 0x02]);
+
+        let mut raw = [0x00u8, 0x00, 0x01, 0x00];
+        let mut bytes = ByteString::new(&mut raw);
+        bytes *= u64::MAX;
+        assert_eq!(raw, [0xFF, 0xFF, 0xFF, 0x00]);
+    }
+
+    #[test]
+    fn test_mul_assign_scalar_against_u64() {
+        let mut rng = crate::test_util::XorShift::new(0x263);
+
+        for _ in 0..1000 {
+            let (x, y) = (rng.next_u64(), rng.next_u64() >> rng.below(64));
+            let mut raw = x.to_be_bytes();
+            let reversed = rng.next_bool();
+            let mut bytes = view_logical(&mut raw, reversed);
+
+            if y <= u8::MAX as u64 { bytes *= y as u8; } else { bytes *= y; }
+            assert_eq!(bytes.to_u64(), Ok(x.wrapping_mul(y)));
+        }
+    }
+
+    #[test]
+    fn test_div_assign_uneven_lengths() {
+        // 0x012345 / 0x0123 = 0x100, remainder 0x45
+        let mut a = [0x01u8, 0x23, 0x45];
+        let mut b = [0x01u8, 0x23];
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes /= &ByteString::new(&mut b);
+        assert_eq!(a, [0x00, 0x01, 0x00]);
+
+        let mut a = [0x0Au8];
+        let mut b = [0x00u8, 0x00, 0x03];
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes /= &ByteString::new(&mut b);
+        assert_eq!(a, [0x03]);
+    }
+
+    #[test]
+    fn test_div_assign_larger_divisor_and_exact() {
+        let mut a = [0x12u8, 0x34];
+        let mut b = [0x01u8, 0x00, 0x00];
+
+        let divisor = ByteString::new(&mut b);
+        let mut bytes = ByteString::new(&mut a);
+        bytes /= &divisor;
+        assert!(bytes.is_zero());
+
+        // 0xFFFE0001 = 0xFFFF * 0xFFFF
+        let mut a = [0x01u8, 0x00, 0xFE, 0xFF];
+        let mut b = [0xFFu8, 0xFF];
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes.interpret_reverse_endian();
+        bytes /= &ByteString::new(&mut b);
+        assert_eq!(a, [0xFF, 0xFF, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_try_div_assign_by_zero() {
+        let mut a = [0x12u8, 0x34];
+        let mut b = [0x00u8, 0x00];
+
+        let mut bytes = ByteString::new(&mut a);
+        assert_eq!(bytes.try_div_assign(&ByteString::new(&mut b)), Err(Error::DivisionByZero));
+        assert_eq!(bytes.try_div_assign(&ByteString::new(&mut [])), Err(Error::DivisionByZero));
+        assert_eq!(a, [0x12, 0x34]);
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn test_div_assign_by_zero_panics() {
+        let mut a = [0x12u8];
+        let mut b = [0x00u8];
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes /= &ByteString::new(&mut b);
+    }
+
+    #[test]
+    fn test_rem_assign_shorter_modulus() {
+        // 0x012345 % 0x0123 = 0x45
+        let mut a = [0x01u8, 0x23, 0x45];
+        let mut b = [0x23u8, 0x01];
+
+        let mut modulus = ByteString::new(&mut b);
+        modulus.interpret_reverse_endian();
+        let mut bytes = ByteString::new(&mut a);
+        bytes %= &modulus;
+        assert_eq!(a, [0x00, 0x00, 0x45]);
+
+        // A wider modulus above the value leaves it unchanged
+        let mut a = [0x12u8, 0x34];
+        let mut b = [0x00u8, 0x01, 0x00, 0x00];
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes.interpret_reverse_endian();
+        bytes %= &ByteString::new(&mut b);
+        assert_eq!(a, [0x12, 0x34]);
+    }
+
+    #[test]
+    fn test_try_rem_assign_by_zero() {
+        let mut a = [0x12u8, 0x34];
+        let mut b = [0x00u8];
+
+        let mut bytes = ByteString::new(&mut a);
+        assert_eq!(bytes.try_rem_assign(&ByteString::new(&mut b)), Err(Error::DivisionByZero));
+        assert_eq!(a, [0x12, 0x34]);
+    }
+
+    #[test]
+    fn test_rem_assign_against_u128() {
+        let mut rng = crate::test_util::XorShift::new(0x256);
+
+        for _ in 0..1000 {
+            let (a_len, b_len) = (8 + rng.below(9), 1 + rng.below(16));
+            let x = ((rng.next_u64() as u128) << 64 | rng.next_u64() as u128) >> (128 - 8 * a_len);
+            let y = ((rng.next_u64() as u128) << 64 | rng.next_u64() as u128) >> (128 - 8 * b_len + rng.below(8 * b_len));
+            if y == 0 { continue; }
+
+            let mut a = x.to_be_bytes()[16 - a_len..].to_vec();
+            let mut b = y.to_be_bytes()[16 - b_len..].to_vec();
+
+            let (a_reversed, b_reversed) = (rng.next_bool(), rng.next_bool());
+            let rhs = view_logical(&mut b, b_reversed);
+            let mut bytes = view_logical(&mut a, a_reversed);
+
+            bytes %= &rhs;
+            assert!(bytes.iter().eq((x % y).to_be_bytes()[16 - a_len..].iter()));
+        }
+    }
+
+    #[test]
+    fn test_shifts_move_counter_pattern() {
+        use crate::testing::{fill_counter, assert_counter_pattern};
+
+        for reversed in [false, true] {
+            let mut raw = [0u8; 8];
+            let mut bytes = view(&mut raw, reversed);
+
+            // Whole byte shifts move the pattern by one position per 8 bits
+            fill_counter(&mut bytes, 0x10);
+            bytes <<= 16;
+            assert!(bytes.iter().copied().eq((0x12..0x18).chain([0, 0])));
+
+            fill_counter(&mut bytes, 0x10);
+            bytes >>= 24;
+            assert!(bytes.iter().copied().eq([0, 0, 0].into_iter().chain(0x10..0x15)));
+
+            fill_counter(&mut bytes, 0x10);
+            bytes <<= 0;
+            bytes >>= 0;
+            assert_counter_pattern(&bytes, 0x10);
+        }
+    }
+
+    #[test]
+    fn test_shr_multi_byte_bigger_bitlen_shift() {
+        let mut a = [1u8,2,3,4];
+        let expected = [0;4];
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes >>= bytes.bit_len() + 1;
+        assert!(bytes.iter().eq(expected.iter()));
+    }
+
+    #[test]
+    fn test_shr_multi_byte_bitlen_shift() {
+        let mut a = [1u8,2,3,4];
+        let expected = [0;4];
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes >>= bytes.bit_len();
+        assert!(bytes.iter().eq(expected.iter()));
+    }
+
+    #[test]
+    fn test_shr_multi_byte_8shift() {
+        let mut a = [1u8,2,3,4];
+        let expected = [0x00, 0x01, 0x02, 0x03];
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes >>= 8;
+        assert!(bytes.iter().eq(expected.iter()));
+    }
+
+    #[test]
+    fn test_shr_multi_byte_7shift() {
+        let mut a = [1u8,2,3,4];
+        let expected = [0x00, 0x02, 0x04, 0x06];
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes >>= 7;
+        assert!(bytes.iter().eq(expected.iter()));
+    }
+
+    #[test]
+    fn test_shr_multi_byte_0shift() {
+        let mut a = [1u8,2,3,4];
+        let expected = a;
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes >>= 0;
+        assert!(bytes.iter().eq(expected.iter()));
+    }
+
+    #[test]
+    fn test_shr_multi_byte_reversed() {
+        let mut a = [4u8,3,2,1];
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes.interpret_reverse_endian();
+        bytes >>= 7;
+        assert!(bytes.iter().eq([0x00, 0x02, 0x04, 0x06].iter()));
+        assert_eq!(a, [0x06, 0x04, 0x02, 0x00]);
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes.interpret_reverse_endian();
+        bytes >>= 8;
+        assert_eq!(a, [0x04, 0x02, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_shr_single_byte_bigger_bitlen_shift() {
+        let mut a = [0x80u8];
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes >>= bytes.bit_len() + 1;
+        assert!(bytes.iter().eq([0u8].iter()));
+    }
+
+    #[test]
+    fn test_shr_single_byte_bitlen_shift() {
+        let mut a = [0x80u8];
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes >>= 8;
+        assert!(bytes.iter().eq([0u8].iter()));
+    }
+
+    #[test]
+    fn test_shr_single_byte_7shift() {
+        let mut a = [0x80u8];
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes >>= 7;
+        assert!(bytes.iter().eq([1u8].iter()));
+    }
+
+    #[test]
+    fn test_shr_single_byte_0shift() {
+        let mut a = [0x80u8];
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes >>= 0;
+        assert!(bytes.iter().eq([0x80u8].iter()));
+    }
+
+    #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_iter() {
 
         let mut a = [1u8,2,3,4];
@@ -305,6 +1992,68 @@ mod tests {
         assert!(expected.iter().eq(bytes.iter()));
     }
 
+    #[test]
+    fn test_iter_range() {
+        let a = [1u8, 2, 3, 4, 5, 6, 7];
+
+        for reversed in [false, true] {
+            let mut raw = a;
+            let bytes = view(&mut raw, reversed);
+
+            let logical: Vec<u8> = bytes.iter().copied().collect();
+
+            for start in 0..=a.len() {
+                for end in start..=a.len() {
+                    let expected = &logical[start..end];
+
+                    assert!(bytes.iter_range(start..end).eq(expected.iter()));
+                    assert!(bytes.iter_range(start..end).rev().eq(expected.iter().rev()));
+                    assert_eq!(bytes.iter_range(start..end).map(|e| *e as u32).sum::<u32>(), expected.iter().map(|e| *e as u32).sum());
+                    assert_eq!(bytes.iter_range(start..end).len(), end - start);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_iter_range_double_ended() {
+        let mut a = [1u8, 2, 3, 4, 5, 6];
+        let mut bytes = ByteString::new(&mut a);
+        bytes.interpret_reverse_endian();
+
+        let mut it = bytes.iter_range(1..5);
+        assert_eq!(it.next(), Some(&5));
+        assert_eq!(it.next_back(), Some(&2));
+        assert_eq!(it.next(), Some(&4));
+        assert_eq!(it.next_back(), Some(&3));
+        assert_eq!(it.next(), None);
+        assert_eq!(it.next_back(), None);
+    }
+
+    #[test]
+    fn test_iter_range_mut() {
+        let mut a = [1u8, 2, 3, 4, 5, 6];
+        let mut bytes = ByteString::new(&mut a);
+        bytes.interpret_reverse_endian();
+
+        for byte in bytes.iter_range_mut(0..2) {
+            *byte = 0;
+        }
+        assert_eq!(a, [1, 2, 3, 4, 0, 0]);
+    }
+
+    #[test]
+    fn test_try_iter_range_out_of_bounds() {
+        let mut a = [1u8, 2, 3, 4];
+        let mut bytes = ByteString::new(&mut a);
+
+        assert_eq!(bytes.try_iter_range(2..5).err(), Some(Error::OutOfBounds));
+        #[allow(clippy::reversed_empty_ranges)]
+        let backwards = 3..2;
+        assert_eq!(bytes.try_iter_range(backwards).err(), Some(Error::OutOfBounds));
+        assert!(bytes.try_iter_range_mut(4..4).is_ok());
+    }
+
     #[test]
     fn test_set_zero() {
 
@@ -326,6 +2075,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_eq() {
 
         let mut a = [1u8,2,3,4];
@@ -349,6 +2099,34 @@ mod tests {
     }
 
     #[test]
+    fn test_eq_through_chunked_compare() {
+        use crate::cmp::COMPARE_CHUNK_LEN;
+
+        // A prefix is not equal, whatever the flags
+        let mut a = [1u8, 2, 3, 4];
+        let mut prefix = [1u8, 2, 3];
+        assert!(ByteString::new(&mut a) != ByteString::new(&mut prefix));
+
+        // Both reversed compare the logical bytes
+        let mut b = [4u8, 3, 2, 1];
+        let mut c = [4u8, 3, 2, 1];
+        let mut bytes_b = ByteString::new(&mut b);
+        let mut bytes_c = ByteString::new(&mut c);
+        bytes_b.interpret_reverse_endian();
+        bytes_c.interpret_reverse_endian();
+        assert!(bytes_b == bytes_c);
+        assert!(bytes_b == ByteString::new(&mut a));
+
+        // A difference in the second chunk
+        let mut long_a = vec![0xA5u8; COMPARE_CHUNK_LEN + 5];
+        let mut long_b = long_a.clone();
+        assert!(ByteString::new(&mut long_a) == ByteString::new(&mut long_b));
+        long_b[COMPARE_CHUNK_LEN + 4] ^= 1;
+        assert!(ByteString::new(&mut long_a) != ByteString::new(&mut long_b));
+    }
+
+    #[test]
+    #[allow(clippy::needless_borrow)]
     fn test_reinterpret() {
 
         let mut arr = [0xFFu8; 8];
@@ -372,4 +2150,323 @@ mod tests {
         assert_eq!(arr, expected);
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn test_try_rotl_bytes_and_try_shl() {
+        let mut a = [0x01u8, 0x02, 0x03];
+        let mut bytes = ByteString::new(&mut a);
+        bytes.interpret_reverse_endian();
+
+        assert_eq!(bytes.try_rotl_bytes(4), Err(Error::OutOfBounds));
+        assert_eq!(bytes.try_shl(24), Err(Error::OutOfBounds));
+        assert!(bytes.iter().eq([0x03, 0x02, 0x01].iter()));
+
+        bytes.try_rotl_bytes(1).unwrap();
+        assert!(bytes.iter().eq([0x02, 0x01, 0x03].iter()));
+
+        bytes.try_shl(12).unwrap();
+        assert!(bytes.iter().eq([0x10, 0x30, 0x00].iter()));
+    }
+
+    #[test]
+    fn test_sar_assign() {
+        for reversed in [false, true] {
+            let sar = |value: u32, count: usize| {
+                let mut raw = if reversed { value.to_le_bytes() } else { value.to_be_bytes() };
+                let mut bytes = view(&mut raw, reversed);
+                bytes.sar_assign(count);
+                bytes.to_u64().unwrap() as u32
+            };
+
+            // Positive values shift like >>=
+            assert_eq!(sar(0x7654_3210, 4), 0x0765_4321);
+            assert_eq!(sar(0x7654_3210, 32), 0);
+
+            // Negative values, including counts straddling a byte boundary
+            assert_eq!(sar(0x8000_0000, 1), 0xC000_0000);
+            assert_eq!(sar(0xF654_3210, 12), 0xFFFF_6543);
+            assert_eq!(sar(0x8765_4321, 13), 0xFFFC_3B2A);
+            assert_eq!(sar(0x8000_0001, 31), 0xFFFF_FFFF);
+            assert_eq!(sar(0x8000_0001, 32), 0xFFFF_FFFF);
+            assert_eq!(sar(0x8000_0001, 100), 0xFFFF_FFFF);
+            assert_eq!(sar(0x8000_0001, 0), 0x8000_0001);
+
+            for (value, count) in [(0x9ABC_DEF0u32, 7), (0x1234_5678, 9), (0xFEDC_BA98, 17), (0x8000_0000, 24)] {
+                assert_eq!(sar(value, count), ((value as i32) >> count) as u32, "{:#x} >> {}", value, count);
+            }
+        }
+
+        let mut empty = [0u8; 0];
+        ByteString::new(&mut empty).sar_assign(3);
+    }
+
+    // A u64 limb stored under the given interpretation
+    fn limb(value: u64, reversed: bool) -> [u8; 8] {
+        if reversed { value.to_le_bytes() } else { value.to_be_bytes() }
+    }
+
+    fn limb_value(raw: &mut [u8; 8], reversed: bool) -> u64 {
+        let bytes = view(raw, reversed);
+        bytes.to_u64().unwrap()
+    }
+
+    #[test]
+    fn test_two_limb_shift_against_u128() {
+        let mut rng = crate::test_util::XorShift::new(0x276);
+
+        for _ in 0..500 {
+            let value = (rng.next_u64() as u128) << 64 | rng.next_u64() as u128;
+            let count = rng.below(65);
+            let (high_reversed, low_reversed) = (rng.next_bool(), rng.next_bool());
+
+            let (mut high, mut low) = (limb((value >> 64) as u64, high_reversed), limb(value as u64, low_reversed));
+            let mut high_bytes = view(&mut high, high_reversed);
+            let mut low_bytes = view(&mut low, low_reversed);
+
+            let carry = low_bytes.shl_carry(count);
+            let out = high_bytes.shl_assign_with_carry_in(count, carry);
+            let shifted = (limb_value(&mut high, high_reversed) as u128) << 64 | limb_value(&mut low, low_reversed) as u128;
+            let ejected = if count == 0 { 0 } else { (value >> (128 - count)) as u64 };
+            assert_eq!((shifted, out), (value << count, ejected), "{:#x} << {}", value, count);
+
+            let (mut high, mut low) = (limb((value >> 64) as u64, high_reversed), limb(value as u64, low_reversed));
+            let mut high_bytes = view(&mut high, high_reversed);
+            let mut low_bytes = view(&mut low, low_reversed);
+
+            let carry = high_bytes.shr_carry(count);
+            let out = low_bytes.shr_assign_with_carry_in(count, carry);
+            let shifted = (limb_value(&mut high, high_reversed) as u128) << 64 | limb_value(&mut low, low_reversed) as u128;
+            let ejected = value as u64 & u64::MAX.checked_shr(64 - count as u32).unwrap_or(0);
+            assert_eq!((shifted, out), (value >> count, ejected), "{:#x} >> {}", value, count);
+        }
+    }
+
+    #[test]
+    fn test_carry_shift_edges() {
+        // Counts past a short buffer eject the value followed by zeros, or the carry in passing through
+        let mut raw = [0xA5u8];
+        let mut bytes = ByteString::new(&mut raw);
+        assert_eq!(bytes.shl_assign_with_carry_in(12, 0xFFF), 0xA5F);
+        assert_eq!(raw, [0xFF]);
+
+        let mut raw = [0xA5u8];
+        let mut bytes = ByteString::new(&mut raw);
+        assert_eq!(bytes.shr_assign_with_carry_in(12, 0xABC), 0xCA5);
+        assert_eq!(raw, [0xAB]);
+
+        // Carry in bits above the count are ignored
+        let mut raw = [0x00u8, 0x00];
+        let mut bytes = ByteString::new(&mut raw);
+        assert_eq!(bytes.shl_assign_with_carry_in(4, u64::MAX), 0);
+        assert_eq!(bytes.shr_assign_with_carry_in(4, u64::MAX), 0xF);
+        assert_eq!(raw, [0xF0, 0x00]);
+
+        let mut raw = [0x12u8, 0x34];
+        let mut bytes = ByteString::new(&mut raw);
+        assert_eq!(bytes.shl_carry(0), 0);
+        assert_eq!(bytes.try_shl_carry(65), Err(Error::OutOfBounds));
+        assert_eq!(bytes.try_shr_assign_with_carry_in(65, 1), Err(Error::OutOfBounds));
+        assert_eq!(raw, [0x12, 0x34]);
+
+        let mut empty = [0u8; 0];
+        assert_eq!(ByteString::new(&mut empty).shl_assign_with_carry_in(3, 0b101), 0b101);
+    }
+
+    // Logical bytes of `raw` stored under the given interpretation, and its bits most significant first
+    fn stored(raw: &[u8], reversed: bool) -> (Vec<u8>, Vec<bool>) {
+        let bits = raw.iter().flat_map(|e| (0..8).rev().map(move |ix| e >> ix & 1 == 1)).collect();
+        let mut physical = raw.to_vec();
+        if reversed { physical.reverse(); }
+        (physical, bits)
+    }
+
+    #[test]
+    fn test_funnel_shifts_against_concatenation() {
+        let mut rng = crate::test_util::XorShift::new(0x2772);
+
+        for _ in 0..300 {
+            let (mut a, mut b) = (vec![0u8; 1 + rng.below(6)], vec![0u8; 1 + rng.below(6)]);
+            rng.fill(&mut a);
+            rng.fill(&mut b);
+            let (self_reversed, src_reversed) = (rng.next_bool(), rng.next_bool());
+            let count = rng.below(8 * a.len().min(b.len()) + 1);
+            let len = 8 * a.len();
+
+            let (mut a_raw, a_bits) = stored(&a, self_reversed);
+            let (mut b_raw, b_bits) = stored(&b, src_reversed);
+            let src = view(&mut b_raw, src_reversed);
+
+            let collect = |bytes: &ByteString| -> Vec<bool> { stored(&bytes.iter().copied().collect::<Vec<u8>>(), false).1 };
+
+            // self followed by src, the window starting `count` bits in
+            let concat: Vec<bool> = a_bits.iter().chain(&b_bits).copied().collect();
+            let mut bytes = view(&mut a_raw, self_reversed);
+            bytes.funnel_shl(&src, count).unwrap();
+            assert_eq!(collect(&bytes), concat[count..count + len], "{:02x?} {:02x?} << {}", a, b, count);
+
+            // src followed by self, the window ending `count` bits before the end
+            let concat: Vec<bool> = b_bits.iter().chain(&a_bits).copied().collect();
+            let (mut a_raw, _) = stored(&a, self_reversed);
+            let mut bytes = view(&mut a_raw, self_reversed);
+            bytes.funnel_shr(&src, count).unwrap();
+            let end = concat.len() - count;
+            assert_eq!(collect(&bytes), concat[end - len..end], "{:02x?} {:02x?} >> {}", a, b, count);
+        }
+    }
+
+    #[test]
+    fn test_funnel_shift_bounds() {
+        let (mut a, mut b) = ([0x12u8, 0x34], [0xABu8]);
+        let mut bytes = ByteString::new(&mut a);
+        let src = ByteString::new(&mut b);
+
+        assert_eq!(bytes.funnel_shl(&src, 9), Err(Error::OutOfBounds));
+        assert_eq!(bytes.funnel_shr(&src, 17), Err(Error::OutOfBounds));
+        assert_eq!(bytes.bytes(), [0x12, 0x34]);
+
+        // A full width shift replaces self with the whole of an equally long src
+        bytes.funnel_shr(&src, 8).unwrap();
+        assert_eq!(bytes.bytes(), [0xAB, 0x12]);
+        bytes.funnel_shl(&src, 0).unwrap();
+        assert_eq!(bytes.bytes(), [0xAB, 0x12]);
+    }
+
+    #[test]
+    fn test_rotl_bytes_reduces_count() {
+        for reversed in [false, true] {
+            let mut a = [0x01u8, 0x02, 0x03, 0x04];
+            let mut bytes = view_logical(&mut a, reversed);
+
+            bytes.rotl_bytes(4);
+            assert!(bytes.iter().eq([0x01, 0x02, 0x03, 0x04].iter()));
+            bytes.rotl_bytes(5);
+            assert!(bytes.iter().eq([0x02, 0x03, 0x04, 0x01].iter()));
+            bytes.rotl_bytes(usize::MAX);
+            assert!(bytes.iter().eq([0x01, 0x02, 0x03, 0x04].iter()));
+        }
+
+        let mut empty = [0u8; 0];
+        let mut bytes = ByteString::new(&mut empty);
+        bytes.rotl_bytes(0);
+        bytes.rotl_bytes(5);
+        assert_eq!(bytes.try_rotl_bytes(1), Err(Error::OutOfBounds));
+    }
+
+    #[test]
+    fn test_rotr_bytes() {
+        for reversed in [false, true] {
+            let mut a = [0x01u8, 0x02, 0x03];
+            let mut bytes = view_logical(&mut a, reversed);
+
+            bytes.rotr_bytes(0);
+            assert!(bytes.iter().eq([0x01, 0x02, 0x03].iter()));
+            bytes.rotr_bytes(1);
+            assert!(bytes.iter().eq([0x03, 0x01, 0x02].iter()));
+            bytes.rotr_bytes(3);
+            assert!(bytes.iter().eq([0x03, 0x01, 0x02].iter()));
+            bytes.rotr_bytes(8);
+            assert!(bytes.iter().eq([0x01, 0x02, 0x03].iter()));
+            bytes.rotr_bytes(usize::MAX - 1);
+            assert!(bytes.iter().eq([0x02, 0x03, 0x01].iter()));
+            bytes.rotr_bytes(1);
+
+            bytes.rotl_bytes(2);
+            bytes.rotr_bytes(2);
+            assert!(bytes.iter().eq([0x01, 0x02, 0x03].iter()));
+        }
+
+        let mut empty = [0u8; 0];
+        ByteString::new(&mut empty).rotr_bytes(1);
+    }
+
+    #[test]
+    fn test_negated_ops_truth_tables() {
+        // The low nibbles pair up 1/1, 1/0, 0/1 and 0/0, the high nibbles 0/0
+        for (op, expected) in [0b1111_0111u8, 0b1111_0001, 0b1111_1001].into_iter().enumerate() {
+            let mut a = [0b1100u8];
+            let mut b = [0b1010u8];
+            let mut bytes = ByteString::new(&mut a);
+            let rhs = ByteString::new(&mut b);
+
+            match op {
+                0 => bytes.nand_assign(&rhs),
+                1 => bytes.nor_assign(&rhs),
+                _ => bytes.xnor_assign(&rhs),
+            }
+            assert_eq!(a, [expected]);
+        }
+    }
+
+    #[test]
+    fn test_negated_ops_reversed() {
+        for (a_reversed, b_reversed) in [(false, true), (true, false), (true, true)] {
+            // Logical 12 34 56 against logical F0 0F FF
+            let mut a = [0x12u8, 0x34, 0x56];
+            let mut b = [0xF0u8, 0x0F, 0xFF];
+            if a_reversed { a.reverse(); }
+            if b_reversed { b.reverse(); }
+
+            let mut bytes = view(&mut a, a_reversed);
+            let rhs = view(&mut b, b_reversed);
+
+            bytes.nand_assign(&rhs);
+            assert!(bytes.iter().eq([0xEF, 0xFB, 0xA9].iter()));
+            bytes.nor_assign(&rhs);
+            assert!(bytes.iter().eq([0x00, 0x00, 0x00].iter()));
+            bytes.xnor_assign(&rhs);
+            assert!(bytes.iter().eq([0x0F, 0xF0, 0x00].iter()));
+        }
+    }
+
+    #[test]
+    fn test_merge_bits_low_nibbles() {
+        let mut a = [0x12u8, 0x34, 0x56];
+        let mut b = [0xABu8, 0xCD, 0xEF];
+        let mut m = [0x0Fu8; 3];
+
+        let mut bytes = ByteString::new(&mut a);
+        bytes.merge_bits(&ByteString::new(&mut b), &ByteString::new(&mut m));
+        assert_eq!(a, [0x1B, 0x3D, 0x5F]);
+    }
+
+    #[test]
+    fn test_merge_bits_reversed_mask() {
+        for (a_reversed, b_reversed) in [(false, false), (true, false), (false, true), (true, true)] {
+            // Logical 00 00 00, FF FF FF and mask F0 0F FF with the mask stored reversed
+            let mut a = [0x00u8; 3];
+            let mut b = [0xFFu8; 3];
+            let mut m = [0xFFu8, 0x0F, 0xF0];
+
+            let mut bytes = view(&mut a, a_reversed);
+            let other = view(&mut b, b_reversed);
+            let mut mask = ByteString::new(&mut m);
+            mask.interpret_reverse_endian();
+
+            bytes.merge_bits(&other, &mask);
+            assert!(bytes.iter().eq([0xF0, 0x0F, 0xFF].iter()));
+
+            // A zero mask keeps self whatever other holds
+            let mut zero = [0x00u8; 3];
+            bytes.merge_bits(&ByteString::new(&mut [0x00u8; 3]), &ByteString::new(&mut zero));
+            assert!(bytes.iter().eq([0xF0, 0x0F, 0xFF].iter()));
+        }
+    }
+
+    #[test]
+    fn test_merge_bits_length_mismatch() {
+        let mut a = [0x12u8, 0x34];
+        let mut bytes = ByteString::new(&mut a);
+
+        let err = bytes.try_merge_bits(&ByteString::new(&mut [0u8; 3]), &ByteString::new(&mut [0u8; 2]));
+        assert_eq!(err, Err(LengthError { index: 0, expected: 2, actual: 3 }));
+        let err = bytes.try_merge_bits(&ByteString::new(&mut [0u8; 2]), &ByteString::new(&mut [0xFFu8]));
+        assert_eq!(err, Err(LengthError { index: 1, expected: 2, actual: 1 }));
+        assert_eq!(bytes.to_u64(), Ok(0x1234));
+    }
+
+    #[test]
+    #[should_panic(expected = "merge_bits operands differ in length")]
+    fn test_merge_bits_mismatch_panics() {
+        let mut a = [0u8; 2];
+        ByteString::new(&mut a).merge_bits(&ByteString::new(&mut [0u8; 2]), &ByteString::new(&mut [0u8; 4]));
+    }
+}
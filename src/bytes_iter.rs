@@ -1,8 +1,13 @@
+//! Iterators over the logical bytes of a ByteString
+
 use std::{
     iter::Rev,
     ops::Range
 };
 
+#[cfg(feature = "forbid-unsafe")]
+use std::mem;
+
 enum Index {
     Iter(Range<usize>),
     RevIter(Rev<Range<usize>>)
@@ -47,12 +52,39 @@ impl Index {
 
 }
 
+/// Iterator over the logical bytes of a slice, see [`ByteString::iter`](crate::bytes::ByteString::iter).
+///
+/// ```
+/// use rebite::bytes::ByteString;
+///
+/// let mut raw = [1u8, 2, 3];
+/// let mut bytes = ByteString::new(&mut raw);
+/// bytes.interpret_reverse_endian();
+///
+/// let mut iter = bytes.iter();
+/// assert_eq!(iter.next(), Some(&3));
+/// assert_eq!(iter.next_back(), Some(&1));
+/// assert_eq!(iter.len(), 1);
+/// ```
 pub struct BytesIter<'a> {
     bytes: &'a [u8],
     index: Index
 }
 
 impl<'a> BytesIter<'a> {
+    /// Iterate over `bytes` front to back, or back to front if `should_iter_reverse`.
+    ///
+    /// ```
+    /// use rebite::bytes_iter::BytesIter;
+    ///
+    /// let raw = [0x01u8, 0x02, 0x03];
+    /// let mut iter = BytesIter::new(&raw, true);
+    ///
+    /// // Both ends stay available, next_back walks the physical order
+    /// assert_eq!(iter.next(), Some(&0x03));
+    /// assert_eq!(iter.next_back(), Some(&0x01));
+    /// assert_eq!(iter.len(), 1);
+    /// ```
     pub fn new(bytes: &'a [u8], should_iter_reverse: bool) -> Self {
 
         let range = 0..bytes.len();
@@ -94,12 +126,39 @@ impl<'a> DoubleEndedIterator for BytesIter<'a> {
 impl<'a> ExactSizeIterator for BytesIter<'a> {}
 
 
+/// Mutable iterator over the logical bytes of a slice, see [`ByteString::iter_mut`](crate::bytes::ByteString::iter_mut).
+///
+/// ```
+/// use rebite::bytes::ByteString;
+///
+/// let mut raw = [1u8, 2, 3];
+/// let mut bytes = ByteString::new(&mut raw);
+/// bytes.interpret_reverse_endian();
+///
+/// for (ix, byte) in bytes.iter_mut().enumerate() {
+///     *byte = ix as u8;
+/// }
+/// assert_eq!(raw, [2, 1, 0]);
+/// ```
+#[cfg(not(feature = "forbid-unsafe"))]
 pub struct BytesIterMut<'a> {
     bytes: &'a mut [u8],
     index: Index
 }
 
+#[cfg(not(feature = "forbid-unsafe"))]
 impl<'a> BytesIterMut<'a> {
+    /// Iterate over `bytes` front to back, or back to front if `should_iter_reverse`.
+    ///
+    /// ```
+    /// use rebite::bytes_iter::BytesIterMut;
+    ///
+    /// let mut raw = [0x00u8; 3];
+    /// for (ix, byte) in BytesIterMut::new(&mut raw, true).enumerate() {
+    ///     *byte = ix as u8;
+    /// }
+    /// assert_eq!(raw, [0x02, 0x01, 0x00]);
+    /// ```
     pub fn new(bytes: &'a mut [u8], should_iter_reverse: bool) -> Self {
 
         let range = 0..bytes.len();
@@ -111,6 +170,7 @@ impl<'a> BytesIterMut<'a> {
     }
 }
 
+#[cfg(not(feature = "forbid-unsafe"))]
 impl<'a> Iterator for BytesIterMut<'a> {
     type Item = &'a mut u8;
 
@@ -135,6 +195,7 @@ impl<'a> Iterator for BytesIterMut<'a> {
     }
 }
 
+#[cfg(not(feature = "forbid-unsafe"))]
 impl<'a> DoubleEndedIterator for BytesIterMut<'a> {
     fn next_back(&mut self) -> Option<Self::Item> {
         if let Some(ix) = self.index.next_back() {
@@ -152,6 +213,73 @@ impl<'a> DoubleEndedIterator for BytesIterMut<'a> {
     }
 }
 
+/// Safe variant, splits the next byte off the remaining slice instead of indexing through a pointer.
+///
+/// ```
+/// use rebite::bytes::ByteString;
+///
+/// let mut raw = [1u8, 2, 3];
+/// let mut bytes = ByteString::new(&mut raw);
+/// bytes.interpret_reverse_endian();
+///
+/// for (ix, byte) in bytes.iter_mut().enumerate() {
+///     *byte = ix as u8;
+/// }
+/// assert_eq!(raw, [2, 1, 0]);
+/// ```
+#[cfg(feature = "forbid-unsafe")]
+pub struct BytesIterMut<'a> {
+    bytes: &'a mut [u8],
+    reverse: bool
+}
+
+#[cfg(feature = "forbid-unsafe")]
+impl<'a> BytesIterMut<'a> {
+    /// Iterate over `bytes` front to back, or back to front if `should_iter_reverse`.
+    ///
+    /// ```
+    /// use rebite::bytes_iter::BytesIterMut;
+    ///
+    /// let mut raw = [0x00u8; 3];
+    /// for (ix, byte) in BytesIterMut::new(&mut raw, true).enumerate() {
+    ///     *byte = ix as u8;
+    /// }
+    /// assert_eq!(raw, [0x02, 0x01, 0x00]);
+    /// ```
+    pub fn new(bytes: &'a mut [u8], should_iter_reverse: bool) -> Self {
+        Self { bytes, reverse: should_iter_reverse }
+    }
+
+    // Split off the first physical byte when `front`, the last one otherwise
+    fn split(&mut self, front: bool) -> Option<&'a mut u8> {
+        let bytes = mem::take(&mut self.bytes);
+
+        let (byte, rest) = if front { bytes.split_first_mut()? } else { bytes.split_last_mut()? };
+        self.bytes = rest;
+        Some(byte)
+    }
+}
+
+#[cfg(feature = "forbid-unsafe")]
+impl<'a> Iterator for BytesIterMut<'a> {
+    type Item = &'a mut u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.split(!self.reverse)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.bytes.len(), Some(self.bytes.len()))
+    }
+}
+
+#[cfg(feature = "forbid-unsafe")]
+impl<'a> DoubleEndedIterator for BytesIterMut<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.split(self.reverse)
+    }
+}
+
 impl<'a> ExactSizeIterator for BytesIterMut<'a>{}
 
 #[cfg(test)]
@@ -193,6 +321,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_iter_rev() {
         let a = [1u8,2,3,4];
         let b = a.clone();
@@ -202,6 +331,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_iter_mut_rev() {
         let mut a = [1u8,2,3,4];
         let b = a.clone();
@@ -211,6 +341,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_iter_be_as_be() {
 
         let a = [1u8,2,3,4];
@@ -221,6 +352,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_iter_be_as_le() {
 
         let a = [1u8,2,3,4];
@@ -231,6 +363,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_iter_mut_be_as_be() {
 
         let mut a = [1u8,2,3,4];
@@ -241,6 +374,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_iter_mut_be_as_le() {
 
         let mut a = [1u8,2,3,4];
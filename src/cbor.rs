@@ -0,0 +1,210 @@
+//! CBOR byte string framing (RFC 8949 major type 2) without a serde dependency
+
+use crate::{
+    bytes::ByteString,
+    error::Error
+};
+
+/// Major type of a definite length byte string
+const MAJOR_BYTES: u8 = 2;
+
+/// Size of the CBOR header announcing a `len` byte payload.
+///
+/// ```
+/// use rebite::cbor::cbor_header_len;
+///
+/// // The argument moves out of the initial byte from 24 on
+/// assert_eq!(cbor_header_len(23), 1);
+/// assert_eq!(cbor_header_len(24), 2);
+/// assert_eq!(cbor_header_len(0x1_0000), 5);
+/// ```
+pub const fn cbor_header_len(len: usize) -> usize {
+    match len as u64 {
+        0..=23 => 1,
+        24..=0xFF => 2,
+        0x100..=0xFFFF => 3,
+        0x1_0000..=0xFFFF_FFFF => 5,
+        _ => 9,
+    }
+}
+
+// Initial byte and big-endian argument in the shortest tier holding `value`, returns the header length
+fn write_header(major: u8, value: u64, out: &mut [u8]) -> Result<usize, Error> {
+    let (info, width) = match value {
+        0..=23 => (value as u8, 0),
+        24..=0xFF => (24, 1),
+        0x100..=0xFFFF => (25, 2),
+        0x1_0000..=0xFFFF_FFFF => (26, 4),
+        _ => (27, 8),
+    };
+
+    let header = out.get_mut(..1 + width).ok_or(Error::OutputTooSmall)?;
+    header[0] = major << 5 | info;
+    header[1..].copy_from_slice(&value.to_be_bytes()[8 - width..]);
+
+    Ok(1 + width)
+}
+
+// Major type, argument and header length. Indefinite lengths and the reserved tiers 28..=30 are malformed.
+fn read_header(src: &[u8]) -> Result<(u8, u64, usize), Error> {
+    let initial = *src.first().ok_or(Error::MalformedInput)?;
+    let width = match initial & 0x1F {
+        info @ 0..=23 => return Ok((initial >> 5, info as u64, 1)),
+        24 => 1,
+        25 => 2,
+        26 => 4,
+        27 => 8,
+        _ => return Err(Error::MalformedInput),
+    };
+
+    let argument = src.get(1..1 + width).ok_or(Error::MalformedInput)?;
+    let value = argument.iter().fold(0u64, |acc, e| acc << 8 | *e as u64);
+
+    Ok((initial >> 5, value, 1 + width))
+}
+
+impl<'a> ByteString<'a> {
+
+    /// Encode the logical bytes as a CBOR byte string into `out`, returns the encoded length.
+    ///
+    /// The length uses the shortest header, `out` needs [`cbor_header_len`] plus `byte_len()` bytes.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x01u8, 0x02, 0x03, 0x04];
+    /// let mut out = [0u8; 5];
+    ///
+    /// assert_eq!(ByteString::new(&mut raw).to_cbor_bytes_into(&mut out), Ok(5));
+    /// assert_eq!(out, [0x44, 0x01, 0x02, 0x03, 0x04]);
+    /// ```
+    pub fn to_cbor_bytes_into(&self, out: &mut [u8]) -> Result<usize, Error> {
+        let total = cbor_header_len(self.byte_len()) + self.byte_len();
+        if out.len() < total {
+            return Err(Error::OutputTooSmall);
+        }
+
+        let header_len = write_header(MAJOR_BYTES, self.byte_len() as u64, out)?;
+        for (dst, value) in out[header_len..total].iter_mut().zip(self.iter()) {
+            *dst = *value;
+        }
+
+        Ok(total)
+    }
+
+    /// Parse a CBOR byte string at the start of `src`, returns it and the number of bytes consumed.
+    ///
+    /// The payload is copied into the front of `storage`, which backs the returned string. Fails
+    /// with [`Error::MalformedInput`] for another major type, an indefinite length or a truncated
+    /// item and with [`Error::OutputTooSmall`] if `storage` cannot hold the payload.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// // A two byte string followed by an unrelated item
+    /// let src = [0x42u8, 0xBE, 0xEF, 0xF6];
+    /// let mut storage = [0u8; 8];
+    ///
+    /// let (bytes, consumed) = ByteString::parse_cbor_bytes(&src, &mut storage).unwrap();
+    /// assert_eq!((bytes.bytes(), consumed), (&[0xBE, 0xEF][..], 3));
+    ///
+    /// // A text string (major type 3) is not a byte string
+    /// assert!(matches!(ByteString::parse_cbor_bytes(&[0x61, 0x41], &mut storage), Err(Error::MalformedInput)));
+    /// ```
+    pub fn parse_cbor_bytes(src: &[u8], storage: &'a mut [u8]) -> Result<(ByteString<'a>, usize), Error> {
+        let (major, len, header_len) = read_header(src)?;
+        if major != MAJOR_BYTES {
+            return Err(Error::MalformedInput);
+        }
+
+        let payload = usize::try_from(len).ok()
+            .and_then(|len| src[header_len..].get(..len))
+            .ok_or(Error::MalformedInput)?;
+
+        let storage = storage.get_mut(..payload.len()).ok_or(Error::OutputTooSmall)?;
+        storage.copy_from_slice(payload);
+
+        Ok((ByteString::new(storage), header_len + payload.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::view_logical;
+
+    fn encode(data: &[u8], reversed: bool) -> Vec<u8> {
+        let mut raw = data.to_vec();
+        let bytes = view_logical(&mut raw, reversed);
+
+        let mut out = vec![0u8; cbor_header_len(data.len()) + data.len()];
+        assert_eq!(bytes.to_cbor_bytes_into(&mut out), Ok(out.len()));
+        out
+    }
+
+    #[test]
+    fn test_rfc8949_examples() {
+        // Appendix A
+        assert_eq!(encode(&[], false), [0x40]);
+        assert_eq!(encode(&[0x01, 0x02, 0x03, 0x04], false), [0x44, 0x01, 0x02, 0x03, 0x04]);
+        assert_eq!(encode(&[0x01, 0x02, 0x03, 0x04], true), [0x44, 0x01, 0x02, 0x03, 0x04]);
+
+        let mut storage = [0u8; 8];
+        let (bytes, consumed) = ByteString::parse_cbor_bytes(&[0x44, 0x01, 0x02, 0x03, 0x04, 0xFF], &mut storage).unwrap();
+        assert_eq!((bytes.bytes(), consumed), (&[0x01u8, 0x02, 0x03, 0x04][..], 5));
+    }
+
+    #[test]
+    fn test_length_tiers_round_trip() {
+        let tiers: [(usize, &[u8]); 7] = [
+            (23, &[0x57]),
+            (24, &[0x58, 0x18]),
+            (255, &[0x58, 0xFF]),
+            (256, &[0x59, 0x01, 0x00]),
+            (65535, &[0x59, 0xFF, 0xFF]),
+            (65536, &[0x5A, 0x00, 0x01, 0x00, 0x00]),
+            (70000, &[0x5A, 0x00, 0x01, 0x11, 0x70]),
+        ];
+
+        for (len, header) in tiers {
+            let data: Vec<u8> = (0..len).map(|e| e as u8).collect();
+            let encoded = encode(&data, false);
+            assert_eq!(&encoded[..header.len()], header, "len={}", len);
+            assert_eq!(encoded.len(), header.len() + len);
+
+            let mut storage = vec![0u8; len];
+            let (bytes, consumed) = ByteString::parse_cbor_bytes(&encoded, &mut storage).unwrap();
+            assert_eq!((bytes.bytes(), consumed), (&data[..], encoded.len()));
+        }
+
+        // The u64 tier, too large to back with a real payload
+        let mut header = [0u8; 9];
+        assert_eq!(write_header(MAJOR_BYTES, 1 << 32, &mut header), Ok(9));
+        assert_eq!(header, [0x5B, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00]);
+        assert_eq!(read_header(&header), Ok((MAJOR_BYTES, 1 << 32, 9)));
+        assert_eq!(cbor_header_len(1 << 32), 9);
+    }
+
+    #[test]
+    fn test_parse_rejects() {
+        let mut storage = [0u8; 4];
+
+        // Truncated header and payload
+        for src in [&[][..], &[0x58], &[0x59, 0x00], &[0x5B, 0, 0, 0, 0, 0, 0, 0], &[0x43, 0x01, 0x02], &[0x58, 0x02, 0x01]] {
+            assert!(matches!(ByteString::parse_cbor_bytes(src, &mut storage), Err(Error::MalformedInput)), "{:02x?}", src);
+        }
+
+        // Text string, unsigned integer, reserved and indefinite length
+        for src in [&[0x61, 0x61][..], &[0x01], &[0x5C], &[0x5F, 0x41, 0x01, 0xFF]] {
+            assert!(matches!(ByteString::parse_cbor_bytes(src, &mut storage), Err(Error::MalformedInput)), "{:02x?}", src);
+        }
+
+        assert!(matches!(ByteString::parse_cbor_bytes(&[0x45, 1, 2, 3, 4, 5], &mut storage), Err(Error::OutputTooSmall)));
+        assert_eq!(storage, [0; 4]);
+
+        let mut raw = [0xAAu8; 24];
+        let mut out = [0u8; 25];
+        assert_eq!(ByteString::new(&mut raw).to_cbor_bytes_into(&mut out), Err(Error::OutputTooSmall));
+    }
+}
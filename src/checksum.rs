@@ -0,0 +1,654 @@
+//! Checksums, rolling hashes and stable short ids over the logical bytes
+
+use std::fmt;
+
+use crate::{
+    bytes::ByteString,
+    error::Error
+};
+
+/// Incremental integrity check over a stream of bytes.
+///
+/// ```
+/// use rebite::{bytes::ByteString, checksum::Checksum};
+///
+/// // A byte count, fed the way a stream arrives
+/// #[derive(Default)]
+/// struct Length(usize);
+///
+/// impl Checksum for Length {
+///     type Output = usize;
+///
+///     fn update_slice(&mut self, bytes: &[u8]) {
+///         self.0 += bytes.len();
+///     }
+///
+///     fn finalize(self) -> usize {
+///         self.0
+///     }
+/// }
+///
+/// let mut raw = [0u8; 100];
+/// let mut bytes = ByteString::new(&mut raw);
+/// bytes.interpret_reverse_endian();
+///
+/// // Reversed buffers arrive in several slices, the total is the same
+/// assert_eq!(bytes.checksum(Length::default()), 100);
+///
+/// let mut engine = Length::default();
+/// engine.update(&bytes);
+/// engine.update_slice(&[1, 2]);
+/// assert_eq!(engine.finalize(), 102);
+/// ```
+pub trait Checksum {
+    /// Result of [`Checksum::finalize`]
+    type Output;
+
+    /// Feed the logical bytes of `bytes`, in logical order
+    fn update(&mut self, bytes: &ByteString) {
+
+        if !bytes.interpret_reverse_endian {
+            self.update_slice(bytes.bytes());
+            return;
+        }
+
+        // Reversed buffers are staged through a small stack window
+        let mut window = [0u8; 64];
+        let mut iter = bytes.iter();
+        loop {
+            let n = window.iter_mut().zip(iter.by_ref()).map(|(dst, src)| *dst = *src).count();
+            if n == 0 { break; }
+
+            self.update_slice(&window[..n]);
+        }
+    }
+
+    /// Feed `bytes` in slice order
+    fn update_slice(&mut self, bytes: &[u8]);
+
+    /// Consume the engine and return the checksum of everything fed
+    fn finalize(self) -> Self::Output;
+}
+
+impl<'a> ByteString<'a> {
+
+    /// Run the checksum engine `c` over the logical bytes and return its result.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, checksum::{Adler32, Crc32}};
+    ///
+    /// let mut raw = *b"987654321";
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// // Logical order is what gets checksummed, reversed this is "123456789"
+    /// bytes.interpret_reverse_endian();
+    /// assert_eq!(bytes.checksum(Crc32::default()), 0xCBF4_3926);
+    /// assert_eq!(bytes.checksum(Adler32::default()), 0x091E_01DE);
+    /// ```
+    pub fn checksum<C: Checksum>(&self, mut c: C) -> C::Output {
+        c.update(self);
+        c.finalize()
+    }
+
+    /// Stable 64-bit fingerprint of the logical bytes for map keys and log tags, not for security.
+    ///
+    /// The id is the big-endian encoding of [`Fnv1a64`] over the logical bytes. This definition is
+    /// frozen, the same logical bytes give the same id in every version of the crate, whatever
+    /// their physical layout.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = *b"foobar";
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// assert_eq!(bytes.short_id(), [0x85, 0x94, 0x41, 0x71, 0xF7, 0x39, 0x67, 0xE8]);
+    /// assert_eq!(bytes.short_id_hex().to_string(), "85944171f73967e8");
+    ///
+    /// bytes.reverse_byte_endianness();
+    /// bytes.interpret_reverse_endian();
+    /// assert_eq!(bytes.short_id_hex().to_string(), "85944171f73967e8");
+    /// ```
+    pub fn short_id(&self) -> [u8; 8] {
+        self.checksum(Fnv1a64::default()).to_be_bytes()
+    }
+
+    /// [`ByteString::short_id`] formatted as 16 lowercase hex digits.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// // The empty input hashes to the FNV offset basis
+    /// assert_eq!(ByteString::new(&mut []).short_id_hex().to_string(), "cbf29ce484222325");
+    /// ```
+    pub fn short_id_hex(&self) -> ShortIdHex {
+        ShortIdHex(self.short_id())
+    }
+}
+
+/// Lowercase hex rendering of a short id, see [`ByteString::short_id_hex`].
+///
+/// ```
+/// use rebite::{bytes::ByteString, checksum::ShortIdHex};
+///
+/// let mut raw = *b"foobar";
+/// let id = ByteString::new(&mut raw).short_id_hex();
+///
+/// assert_eq!(id.to_string(), "85944171f73967e8");
+/// assert_eq!(id, ShortIdHex([0x85, 0x94, 0x41, 0x71, 0xF7, 0x39, 0x67, 0xE8]));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ShortIdHex(pub [u8; 8]);
+
+impl fmt::Display for ShortIdHex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|e| write!(f, "{:02x}", e))
+    }
+}
+
+impl fmt::Debug for ShortIdHex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// XOR of all bytes.
+///
+/// ```
+/// use rebite::{bytes::ByteString, checksum::XorFold};
+///
+/// let mut raw = *b"123456789";
+///
+/// assert_eq!(ByteString::new(&mut raw).checksum(XorFold::default()), 0x31);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct XorFold(u8);
+
+impl Checksum for XorFold {
+    type Output = u8;
+
+    fn update_slice(&mut self, bytes: &[u8]) {
+        self.0 = bytes.iter().fold(self.0, |acc, e| acc ^ e);
+    }
+
+    fn finalize(self) -> u8 {
+        self.0
+    }
+}
+
+/// Fletcher-16 over 8 bit words.
+///
+/// ```
+/// use rebite::{bytes::ByteString, checksum::Fletcher16};
+///
+/// let mut raw = *b"abcde";
+///
+/// assert_eq!(ByteString::new(&mut raw).checksum(Fletcher16::default()), 0xC8F0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Fletcher16 {
+    a: u16,
+    b: u16,
+}
+
+impl Checksum for Fletcher16 {
+    type Output = u16;
+
+    fn update_slice(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.a = (self.a + *byte as u16) % 255;
+            self.b = (self.b + self.a) % 255;
+        }
+    }
+
+    fn finalize(self) -> u16 {
+        self.b << 8 | self.a
+    }
+}
+
+/// Adler-32 as used by zlib.
+///
+/// ```
+/// use rebite::{bytes::ByteString, checksum::Adler32};
+///
+/// let mut raw = *b"123456789";
+///
+/// assert_eq!(ByteString::new(&mut raw).checksum(Adler32::default()), 0x091E_01DE);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+
+impl Default for Adler32 {
+    fn default() -> Self {
+        Self { a: 1, b: 0 }
+    }
+}
+
+impl Checksum for Adler32 {
+    type Output = u32;
+
+    fn update_slice(&mut self, bytes: &[u8]) {
+        const MOD: u32 = 65521;
+
+        for byte in bytes {
+            self.a = (self.a + *byte as u32) % MOD;
+            self.b = (self.b + self.a) % MOD;
+        }
+    }
+
+    fn finalize(self) -> u32 {
+        self.b << 16 | self.a
+    }
+}
+
+/// CRC-32 (IEEE 802.3, reflected), as used by zip and ethernet.
+///
+/// ```
+/// use rebite::{bytes::ByteString, checksum::{Checksum, Crc32}};
+///
+/// // The check value of the CRC catalogue, fed in two parts
+/// let (mut head, mut tail) = (*b"1234", *b"56789");
+/// let mut crc = Crc32::default();
+/// crc.update(&ByteString::new(&mut head));
+/// crc.update(&ByteString::new(&mut tail));
+///
+/// assert_eq!(crc.finalize(), 0xCBF4_3926);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Crc32(u32);
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self(0xFFFF_FFFF)
+    }
+}
+
+impl Checksum for Crc32 {
+    type Output = u32;
+
+    fn update_slice(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u32;
+
+            for _ in 0..8 {
+                let mask = (self.0 & 1).wrapping_neg();
+                self.0 = self.0 >> 1 ^ (0xEDB8_8320 & mask);
+            }
+        }
+    }
+
+    fn finalize(self) -> u32 {
+        !self.0
+    }
+}
+
+/// FNV-1a with 64-bit state, the hash behind [`ByteString::short_id`].
+///
+/// The state starts at the offset basis 0xCBF2_9CE4_8422_2325. Each byte is XORed into the low
+/// byte of the state, then the state is multiplied by the prime 0x0000_0100_0000_01B3 modulo
+/// 2^64. The final state is the result, an empty input hashes to the offset basis.
+///
+/// ```
+/// use rebite::{bytes::ByteString, checksum::Fnv1a64};
+///
+/// let mut raw = *b"foobar";
+///
+/// assert_eq!(ByteString::new(&mut raw).checksum(Fnv1a64::default()), 0x8594_4171_F739_67E8);
+/// assert_eq!(ByteString::new(&mut []).checksum(Fnv1a64::default()), 0xCBF2_9CE4_8422_2325);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Fnv1a64(u64);
+
+impl Default for Fnv1a64 {
+    fn default() -> Self {
+        Self(0xCBF2_9CE4_8422_2325)
+    }
+}
+
+impl Checksum for Fnv1a64 {
+    type Output = u64;
+
+    fn update_slice(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ *byte as u64).wrapping_mul(0x0000_0100_0000_01B3);
+        }
+    }
+
+    fn finalize(self) -> u64 {
+        self.0
+    }
+}
+
+/// Base used by [`ByteString::rolling_hashes`].
+///
+/// ```
+/// use rebite::{bytes::ByteString, checksum::{RollingHash, ROLLING_DEFAULT_BASE, ROLLING_DEFAULT_MODULUS}};
+///
+/// let mut raw = [1u8, 2];
+/// let bytes = ByteString::new(&mut raw);
+///
+/// let mut hash = RollingHash::new(2, ROLLING_DEFAULT_BASE, ROLLING_DEFAULT_MODULUS);
+/// hash.push(1);
+/// assert_eq!(bytes.rolling_hashes(2).next(), Some(hash.push(2)));
+/// assert_eq!(hash.value(), 257 + 2);
+/// ```
+pub const ROLLING_DEFAULT_BASE: u64 = 257;
+
+/// Mersenne prime 2^61 - 1, modulus used by [`ByteString::rolling_hashes`].
+///
+/// ```
+/// use rebite::{bytes::ByteString, checksum::ROLLING_DEFAULT_MODULUS};
+///
+/// // Every hash is reduced below the modulus
+/// let mut raw = [0xFFu8; 32];
+/// let bytes = ByteString::new(&mut raw);
+///
+/// assert!(bytes.rolling_hashes(16).all(|e| e < ROLLING_DEFAULT_MODULUS));
+/// ```
+pub const ROLLING_DEFAULT_MODULUS: u64 = (1 << 61) - 1;
+
+/// Rabin-Karp polynomial hash over a sliding window of bytes.
+///
+/// The hash of `b_0 .. b_(w-1)` is `sum(b_i * base^(w-1-i)) mod modulus`.
+///
+/// ```
+/// use rebite::checksum::RollingHash;
+///
+/// // Find the window "cd" in a stream without rehashing every window from scratch
+/// let mut target = RollingHash::new(2, 257, 1_000_000_007);
+/// target.push(b'c');
+/// let target = target.push(b'd');
+///
+/// let stream = b"abcde";
+/// let mut hash = RollingHash::new(2, 257, 1_000_000_007);
+/// hash.push(stream[0]);
+/// hash.push(stream[1]);
+///
+/// let found = (2..stream.len()).find(|&ix| hash.roll(stream[ix - 2], stream[ix]) == target);
+/// assert_eq!(found, Some(3));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingHash {
+    base: u64,
+    modulus: u64,
+    /// base^(window - 1) mod modulus, the weight of the outgoing byte
+    top_weight: u64,
+    hash: u64,
+}
+
+impl RollingHash {
+
+    /// Panics if `window_size` or `modulus` is zero.
+    ///
+    /// ```
+    /// use rebite::checksum::RollingHash;
+    ///
+    /// let mut hash = RollingHash::new(2, 10, 1000);
+    /// hash.push(1);
+    /// assert_eq!(hash.push(2), 12);
+    /// ```
+    #[track_caller]
+    pub fn new(window_size: usize, base: u64, modulus: u64) -> Self {
+        Self::try_new(window_size, base, modulus).expect("window size and modulus must be nonzero")
+    }
+
+    /// Non-panicking form of [`RollingHash::new`].
+    ///
+    /// ```
+    /// use rebite::{checksum::RollingHash, error::Error};
+    ///
+    /// assert!(matches!(RollingHash::try_new(0, 257, 1_000_000_007), Err(Error::ZeroLength)));
+    /// assert!(matches!(RollingHash::try_new(4, 257, 0), Err(Error::ZeroLength)));
+    /// ```
+    pub fn try_new(window_size: usize, base: u64, modulus: u64) -> Result<Self, Error> {
+        if window_size == 0 || modulus == 0 {
+            return Err(Error::ZeroLength);
+        }
+
+        let top_weight = (1..window_size).fold(1 % modulus, |acc, _| mul_mod(acc, base, modulus));
+
+        Ok(Self { base: base % modulus, modulus, top_weight, hash: 0 })
+    }
+
+    /// Append a byte while the window fills up, returns the hash so far.
+    ///
+    /// ```
+    /// use rebite::checksum::RollingHash;
+    ///
+    /// let mut hash = RollingHash::new(3, 10, 1000);
+    ///
+    /// // Pushing past the window keeps every byte, use roll for a full window
+    /// assert_eq!([1, 2, 3, 4].map(|e| hash.push(e)), [1, 12, 123, 234]);
+    /// ```
+    pub fn push(&mut self, incoming: u8) -> u64 {
+        let hash = self.hash as u128 * self.base as u128 + incoming as u128;
+        self.hash = (hash % self.modulus as u128) as u64;
+        self.hash
+    }
+
+    /// Slide a full window by one byte in O(1), returns the new hash.
+    ///
+    /// ```
+    /// use rebite::checksum::RollingHash;
+    ///
+    /// let mut hash = RollingHash::new(3, 10, 1000);
+    /// [1, 2, 3].map(|e| hash.push(e));
+    ///
+    /// // The outgoing byte is the one pushed window_size bytes earlier
+    /// assert_eq!(hash.roll(1, 4), 234);
+    /// ```
+    pub fn roll(&mut self, outgoing: u8, incoming: u8) -> u64 {
+        let removed = mul_mod(outgoing as u64, self.top_weight, self.modulus);
+        self.hash = ((self.hash as u128 + self.modulus as u128 - removed as u128) % self.modulus as u128) as u64;
+        self.push(incoming)
+    }
+
+    /// Hash of the current window.
+    ///
+    /// ```
+    /// use rebite::checksum::RollingHash;
+    ///
+    /// let mut hash = RollingHash::new(4, 257, 1_000_000_007);
+    /// assert_eq!(hash.value(), 0);
+    /// hash.push(7);
+    /// assert_eq!(hash.value(), 7);
+    /// ```
+    pub fn value(&self) -> u64 {
+        self.hash
+    }
+}
+
+fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    (a as u128 * b as u128 % modulus as u128) as u64
+}
+
+impl<'a> ByteString<'a> {
+
+    /// Rolling hash of every `window` long run of logical bytes, in order.
+    ///
+    /// Uses [`ROLLING_DEFAULT_BASE`] and [`ROLLING_DEFAULT_MODULUS`], yields nothing if
+    /// the buffer is shorter than the window. Panics if `window` is zero.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = *b"abcab";
+    /// let hashes: Vec<u64> = ByteString::new(&mut raw).rolling_hashes(2).collect();
+    ///
+    /// // "ab" occurs at 0 and 3
+    /// assert_eq!(hashes.len(), 4);
+    /// assert_eq!(hashes[0], hashes[3]);
+    /// assert_ne!(hashes[0], hashes[1]);
+    /// ```
+    #[track_caller]
+    pub fn rolling_hashes(&self, window: usize) -> impl Iterator<Item = u64> + '_ {
+        self.try_rolling_hashes(window).expect("window must be nonzero")
+    }
+
+    /// Non-panicking form of [`ByteString::rolling_hashes`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x01u8, 0x02, 0x01, 0x02];
+    /// let bytes = ByteString::new(&mut raw);
+    ///
+    /// let hashes: Vec<u64> = bytes.try_rolling_hashes(2).unwrap().collect();
+    /// assert_eq!(hashes.len(), 3);
+    /// assert_eq!(hashes[0], hashes[2]);
+    ///
+    /// // A window longer than the buffer yields nothing
+    /// assert_eq!(bytes.try_rolling_hashes(5).unwrap().count(), 0);
+    /// ```
+    pub fn try_rolling_hashes(&self, window: usize) -> Result<impl Iterator<Item = u64> + '_, Error> {
+
+        let mut hash = RollingHash::try_new(window, ROLLING_DEFAULT_BASE, ROLLING_DEFAULT_MODULUS)?;
+        let mut incoming = self.iter().copied();
+        let mut outgoing = self.iter().copied();
+        let mut warmed_up = false;
+
+        Ok(std::iter::from_fn(move || {
+            if !warmed_up {
+                warmed_up = true;
+                for _ in 0..window {
+                    hash.push(incoming.next()?);
+                }
+
+                return Some(hash.value());
+            }
+
+            let next = incoming.next()?;
+            Some(hash.roll(outgoing.next()?, next))
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::{view, XorShift};
+
+    #[test]
+    fn test_known_vectors() {
+        let mut a = *b"123456789";
+        let bytes = ByteString::new(&mut a);
+
+        assert_eq!(bytes.checksum(Crc32::default()), 0xCBF4_3926);
+        assert_eq!(bytes.checksum(Adler32::default()), 0x091E_01DE);
+        assert_eq!(bytes.checksum(XorFold::default()), 0x31);
+
+        let mut a = *b"abcde";
+        assert_eq!(ByteString::new(&mut a).checksum(Fletcher16::default()), 0xC8F0);
+    }
+
+    // Frozen: short ids are a cross-version contract, a failure here is a breaking change
+    #[test]
+    fn test_short_id_frozen_vectors() {
+        let vectors: [(&[u8], &str); 7] = [
+            (b"", "cbf29ce484222325"),
+            (b"a", "af63dc4c8601ec8c"),
+            (b"foobar", "85944171f73967e8"),
+            (&[0x12, 0x34], "086f8007b51f1073"),
+            (&[0x34, 0x12], "07ee9e07b4b1c883"),
+            (&[0x00; 8], "a8c7f832281a39c5"),
+            (&[0xDE, 0xAD, 0xBE, 0xEF], "277045760cdd0993"),
+        ];
+
+        for (input, expected) in vectors {
+            let mut raw = input.to_vec();
+            let bytes = ByteString::new(&mut raw);
+            assert_eq!(bytes.short_id_hex().to_string(), expected);
+            assert_eq!(format!("{:?}", bytes.short_id_hex()), expected);
+            assert_eq!(u64::from_be_bytes(bytes.short_id()), u64::from_str_radix(expected, 16).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_incremental_matches_one_shot() {
+        let mut a = *b"The quick brown fox";
+        let mut whole = a;
+        let (head, tail) = a.split_at_mut(7);
+
+        let mut crc = Crc32::default();
+        crc.update(&ByteString::new(head));
+        crc.update_slice(tail);
+
+        assert_eq!(crc.finalize(), ByteString::new(&mut whole).checksum(Crc32::default()));
+    }
+
+    fn scratch_hash(window: &[u8]) -> u64 {
+        let mut hash = RollingHash::new(window.len(), ROLLING_DEFAULT_BASE, ROLLING_DEFAULT_MODULUS);
+        window.iter().fold(0, |_, e| hash.push(*e))
+    }
+
+    #[test]
+    fn test_rolling_matches_scratch() {
+        let mut rng = XorShift::new(0x219);
+
+        for _ in 0..50 {
+            let mut raw = vec![0u8; 1 + rng.below(100)];
+            rng.fill(&mut raw);
+            let reversed = rng.next_bool();
+            let logical: Vec<u8> = if reversed { raw.iter().rev().copied().collect() } else { raw.clone() };
+
+            let bytes = view(&mut raw, reversed);
+
+            for window in [1, 2, 7, logical.len()] {
+                let expected: Vec<u64> = logical.windows(window).map(scratch_hash).collect();
+                assert_eq!(bytes.rolling_hashes(window).collect::<Vec<_>>(), expected);
+            }
+
+            assert_eq!(bytes.rolling_hashes(logical.len() + 1).count(), 0);
+        }
+    }
+
+    #[test]
+    fn test_rolling_hash_small_modulus() {
+        let mut hash = RollingHash::new(3, 10, 1000);
+
+        hash.push(1);
+        hash.push(2);
+        assert_eq!(hash.push(3), 123);
+        assert_eq!(hash.roll(1, 4), 234);
+        assert_eq!(hash.roll(2, 9), 349);
+        assert_eq!(RollingHash::new(1, 10, 1000).push(7), 7);
+    }
+
+    fn assert_canonical_agrees<C: Checksum + Default>(logical: &[u8])
+    where C::Output: PartialEq + std::fmt::Debug {
+
+        let mut canonical = logical.to_vec();
+        let mut reversed: Vec<u8> = logical.iter().rev().copied().collect();
+
+        let mut bytes_reversed = ByteString::new(&mut reversed);
+        bytes_reversed.interpret_reverse_endian();
+
+        assert_eq!(bytes_reversed.checksum(C::default()), ByteString::new(&mut canonical).checksum(C::default()));
+    }
+
+    #[test]
+    fn test_reversed_matches_canonical() {
+        let mut rng = XorShift::new(0x213);
+
+        for _ in 0..100 {
+            let mut logical = vec![0u8; rng.below(300)];
+            rng.fill(&mut logical);
+
+            assert_canonical_agrees::<XorFold>(&logical);
+            assert_canonical_agrees::<Fletcher16>(&logical);
+            assert_canonical_agrees::<Adler32>(&logical);
+            assert_canonical_agrees::<Crc32>(&logical);
+            assert_canonical_agrees::<Fnv1a64>(&logical);
+
+            // Flag-equivalent layouts share their id
+            let mut canonical = logical.clone();
+            let mut reversed: Vec<u8> = logical.iter().rev().copied().collect();
+            let mut bytes_reversed = ByteString::new(&mut reversed);
+            bytes_reversed.interpret_reverse_endian();
+            assert_eq!(bytes_reversed.short_id(), ByteString::new(&mut canonical).short_id());
+        }
+    }
+}
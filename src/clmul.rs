@@ -0,0 +1,171 @@
+//! Carry-less multiplication, polynomial products over GF(2) and GF(2^8)
+
+use crate::{
+    bytes::ByteString,
+    error::Error,
+    util
+};
+
+impl<'a> ByteString<'a> {
+
+    /// out = self clmul rhs, the XOR of the partial products with no carries between bits.
+    ///
+    /// Both inputs are read as values, bit `i` being the coefficient of `x^i`, and `out` must be
+    /// exactly `self.byte_len() + rhs.byte_len()` bytes, else [`Error::LengthMismatch`] is returned
+    /// without writing.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let (mut a, mut b, mut out) = ([0x03u8], [0x03u8], [0xAAu8; 2]);
+    /// let (a, b) = (ByteString::new(&mut a), ByteString::new(&mut b));
+    ///
+    /// // (x + 1)^2 = x^2 + 1, the middle terms cancel instead of carrying
+    /// a.clmul(&b, &mut ByteString::new(&mut out)).unwrap();
+    /// assert_eq!(out, [0x00, 0x05]);
+    ///
+    /// assert_eq!(a.clmul(&b, &mut ByteString::new(&mut out[..1])), Err(Error::LengthMismatch));
+    /// ```
+    pub fn clmul(&self, rhs: &ByteString, out: &mut ByteString) -> Result<(), Error> {
+        if out.byte_len() != self.byte_len() + rhs.byte_len() {
+            return Err(Error::LengthMismatch);
+        }
+
+        out.set_zero();
+
+        // Byte significances add, a byte product covers the two bytes from the sum upwards
+        let len = out.byte_len();
+        for (i, a) in self.iter().rev().enumerate().filter(|(_, a)| **a != 0) {
+            for (j, b) in rhs.iter().rev().enumerate() {
+                let product = clmul8(*a, *b);
+                let low = out.physical_index(len - 1 - (i + j));
+                let high = out.physical_index(len - 2 - (i + j));
+                out.bytes[low] ^= product as u8;
+                out.bytes[high] ^= (product >> 8) as u8;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Multiply every byte by `scalar` in GF(2^8) reduced by `x^8 + poly`.
+    ///
+    /// `poly` holds the low terms of the polynomial, 0x1B for AES and 0x1D for the usual Reed-Solomon
+    /// field. Bytes are independent, so the interpretation flag does not matter.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// // The AES field example, 0x57 * 0x83 = 0xC1
+    /// let mut raw = [0x57u8, 0x01, 0x00];
+    /// ByteString::new(&mut raw).gf256_mul_assign(0x83, 0x1B);
+    /// assert_eq!(raw, [0xC1, 0x83, 0x00]);
+    /// ```
+    pub fn gf256_mul_assign(&mut self, scalar: u8, poly: u8) {
+        for byte in self.iter_mut() {
+            *byte = util::gf256_mul(*byte, scalar, poly);
+        }
+    }
+}
+
+// Carry-less product of two bytes
+fn clmul8(a: u8, b: u8) -> u16 {
+    (0..8).filter(|i| b >> i & 1 == 1).fold(0, |acc, i| acc ^ (a as u16) << i)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::{view, XorShift};
+
+    // Bit by bit reference over u128
+    fn naive(a: u64, b: u64) -> u128 {
+        (0..64).filter(|i| b >> i & 1 == 1).fold(0, |acc, i| acc ^ (a as u128) << i)
+    }
+
+    fn product(a: &[u8], b: &[u8], reversed: (bool, bool, bool)) -> Vec<u8> {
+        let (mut a, mut b) = (a.to_vec(), b.to_vec());
+        let mut out = vec![0xEEu8; a.len() + b.len()];
+
+        let mut bytes_a = ByteString::new(&mut a);
+        let mut bytes_b = ByteString::new(&mut b);
+        let mut bytes_out = view(&mut out, reversed.2);
+        if reversed.0 { bytes_a.reverse_byte_endianness(); bytes_a.interpret_reverse_endian(); }
+        if reversed.1 { bytes_b.reverse_byte_endianness(); bytes_b.interpret_reverse_endian(); }
+
+        bytes_a.clmul(&bytes_b, &mut bytes_out).unwrap();
+        bytes_out.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_known_products() {
+        // The carry-less product, an integer product would be 0x417E
+        assert_eq!(product(&[0x53], &[0xCA], (false, false, false)), [0x3F, 0x7E]);
+        assert_eq!(product(&[0xFF], &[0xFF], (true, false, true)), [0x55, 0x55]);
+        assert_eq!(product(&[0x12, 0x34], &[0x56, 0x78], (false, true, false)), [0x05, 0xC5, 0x81, 0x60]);
+        assert_eq!(product(&[0x80, 0x00], &[0x02], (true, true, true)), [0x01, 0x00, 0x00]);
+        assert_eq!(product(&[0x01], &[], (false, false, false)), [0x00]);
+        assert!(product(&[], &[], (true, true, true)).is_empty());
+    }
+
+    #[test]
+    fn test_against_naive_reference() {
+        let mut rng = XorShift::new(0x265);
+
+        for _ in 0..1000 {
+            let (a_len, b_len) = (1 + rng.below(8), 1 + rng.below(8));
+            let a = rng.next_u64() >> (64 - a_len * 8);
+            let b = rng.next_u64() >> (64 - b_len * 8);
+            let reversed = (rng.next_bool(), rng.next_bool(), rng.next_bool());
+
+            let out = product(&a.to_be_bytes()[8 - a_len..], &b.to_be_bytes()[8 - b_len..], reversed);
+            let expected = naive(a, b).to_be_bytes();
+            assert_eq!(out, expected[16 - a_len - b_len..], "{:#x} {:#x}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_output_length() {
+        let mut a = [0x01u8, 0x02];
+        let mut b = [0x03u8];
+        let mut out = [0xEEu8; 4];
+
+        let bytes_a = ByteString::new(&mut a);
+        assert_eq!(bytes_a.clmul(&ByteString::new(&mut b), &mut ByteString::new(&mut out)), Err(Error::LengthMismatch));
+        assert_eq!(bytes_a.clmul(&ByteString::new(&mut b), &mut ByteString::new(&mut out[..2])), Err(Error::LengthMismatch));
+        assert_eq!(out, [0xEE; 4]);
+    }
+
+    #[test]
+    fn test_gf256_mul_assign() {
+        // AES xtime, the top bit wraps into the polynomial
+        let mut raw = [0x80u8, 0x57, 0x00, 0x01];
+        let mut bytes = ByteString::new(&mut raw);
+        bytes.interpret_reverse_endian();
+        bytes.gf256_mul_assign(0x02, 0x1B);
+        assert_eq!(raw, [0x1B, 0xAE, 0x00, 0x02]);
+
+        let mut bytes = ByteString::new(&mut raw);
+        bytes.gf256_mul_assign(0x01, 0x1B);
+        assert_eq!(bytes.bytes(), [0x1B, 0xAE, 0x00, 0x02]);
+        bytes.gf256_mul_assign(0x00, 0x1B);
+        assert_eq!(raw, [0x00; 4]);
+    }
+
+    #[test]
+    fn test_gf256_mul_assign_reed_solomon() {
+        // Powers of the generator 0x02 in the field of x^8 + x^4 + x^3 + x^2 + 1
+        let powers = [0x01u8, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1D, 0x3A, 0x74];
+
+        let mut raw = powers[..10].to_vec();
+        let mut bytes = ByteString::new(&mut raw);
+        bytes.gf256_mul_assign(0x02, 0x1D);
+        assert_eq!(raw, powers[1..]);
+
+        // 0x8E is the inverse of 0x02
+        let mut bytes = ByteString::new(&mut raw);
+        bytes.gf256_mul_assign(0x8E, 0x1D);
+        assert_eq!(raw, powers[..10]);
+    }
+}
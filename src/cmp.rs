@@ -0,0 +1,520 @@
+//! Byte-wise and numeric ordering, approximate equality and memcmp sortable encodings
+
+use std::cmp::Ordering;
+
+use crate::{
+    bytes::ByteString,
+    error::LengthError
+};
+
+/// Number of logical bytes compared per chunk by the chunked comparison paths.
+///
+/// ```
+/// use rebite::{bytes::ByteString, cmp::COMPARE_CHUNK_LEN};
+///
+/// // Buffers longer than one chunk are compared chunk by chunk with the same result
+/// let mut a = vec![0u8; COMPARE_CHUNK_LEN + 3];
+/// let mut b = a.clone();
+/// b[COMPARE_CHUNK_LEN + 1] = 1;
+///
+/// let (a, b) = (ByteString::new(&mut a), ByteString::new(&mut b));
+/// assert_eq!(a.first_mismatch_chunked(&b), Some(COMPARE_CHUNK_LEN + 1));
+/// ```
+pub const COMPARE_CHUNK_LEN: usize = 64 * 1024;
+
+impl<'a> ByteString<'a> {
+
+    /// Logical index of the first byte in which `self` and `other` differ.
+    ///
+    /// If one string is a prefix of the other, the index is the length of the shorter one.
+    /// Returns `None` if both strings hold the same logical bytes.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let (mut a, mut b, mut prefix) = ([1u8, 2, 3], [3u8, 9, 1], [1u8, 2]);
+    /// let a = ByteString::new(&mut a);
+    /// let mut b = ByteString::new(&mut b);
+    /// b.interpret_reverse_endian();
+    ///
+    /// assert_eq!(a.first_mismatch_chunked(&b), Some(1));
+    /// assert_eq!(a.first_mismatch_chunked(&ByteString::new(&mut prefix)), Some(2));
+    /// assert_eq!(a.first_mismatch_chunked(&a), None);
+    /// ```
+    pub fn first_mismatch_chunked(&self, other: &ByteString) -> Option<usize> {
+        first_mismatch(self, other, COMPARE_CHUNK_LEN)
+    }
+
+    /// Lexicographic comparison of the logical byte sequences, a shorter prefix orders first.
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let (mut a, mut b) = ([0x00u8, 0x02], [0x01u8]);
+    /// let (a, b) = (ByteString::new(&mut a), ByteString::new(&mut b));
+    ///
+    /// // Unlike the numeric order, the leading zero byte decides
+    /// assert_eq!(a.cmp_bytes(&b), Ordering::Less);
+    /// assert_eq!(a.cmp_numeric(&b), Ordering::Greater);
+    /// ```
+    pub fn cmp_bytes(&self, other: &ByteString) -> Ordering {
+
+        let common = self.byte_len().min(other.byte_len());
+
+        match self.first_mismatch_chunked(other) {
+            None => Ordering::Equal,
+            Some(ix) if ix == common => self.byte_len().cmp(&other.byte_len()),
+            Some(ix) => logical_byte(self, ix).cmp(&logical_byte(other, ix))
+        }
+    }
+
+    /// Compare the logical bytes as unsigned big-endian integers, leading zero bytes do not count.
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let (mut a, mut b) = ([0x00u8, 0x00, 0x07], [0x07u8, 0x00]);
+    /// let a = ByteString::new(&mut a);
+    /// let mut b = ByteString::new(&mut b);
+    ///
+    /// assert_eq!(a.cmp_numeric(&b), Ordering::Less);
+    /// b.interpret_reverse_endian();
+    /// assert_eq!(a.cmp_numeric(&b), Ordering::Equal);
+    /// ```
+    pub fn cmp_numeric(&self, other: &ByteString) -> Ordering {
+
+        let a = self.iter().skip(self.leading_zero_len());
+        let b = other.iter().skip(other.leading_zero_len());
+
+        a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+    }
+
+    /// Rewrite the value so that memcmp order of the physical bytes equals numeric order.
+    ///
+    /// The bytes are stored big-endian physically and the interpretation flag is cleared.
+    /// For `signed` two's complement values the sign bit is flipped as well.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// // -1 and 1 as 16 bit little-endian values
+    /// let (mut minus_one, mut one) = ([0xFFu8, 0xFF], [0x01u8, 0x00]);
+    /// for raw in [&mut minus_one, &mut one] {
+    ///     let mut bytes = ByteString::new(raw);
+    ///     bytes.interpret_reverse_endian();
+    ///     bytes.to_lexicographic(true);
+    /// }
+    ///
+    /// assert_eq!((minus_one, one), ([0x7F, 0xFF], [0x80, 0x01]));
+    /// assert!(minus_one < one);
+    /// ```
+    pub fn to_lexicographic(&mut self, signed: bool) {
+
+        if self.interpret_reverse_endian {
+            self.reverse_byte_endianness();
+            self.interpret_reverse_endian();
+        }
+
+        if signed {
+            self.flip_sign_bit();
+        }
+    }
+
+    /// Undo [`ByteString::to_lexicographic`], the value stays in big-endian physical order.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0xFFu8, 0xFE];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    ///
+    /// bytes.to_lexicographic(true);
+    /// bytes.from_lexicographic(true);
+    /// assert_eq!(raw, [0xFE, 0xFF]);
+    /// ```
+    pub fn from_lexicographic(&mut self, signed: bool) {
+
+        if signed {
+            self.flip_sign_bit();
+        }
+    }
+
+    /// Whether the logical bytes differ in at most `max_differing_bits` bits.
+    ///
+    /// Stops as soon as the running count exceeds the threshold, so very different buffers are
+    /// rejected early. A threshold of 0 is exact equality. Fails if the lengths differ.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::LengthError};
+    ///
+    /// let (mut a, mut b, mut short) = ([0x0Fu8, 0x00], [0x0Cu8, 0x00], [0x0Fu8]);
+    /// let (a, b) = (ByteString::new(&mut a), ByteString::new(&mut b));
+    ///
+    /// assert_eq!(a.approx_eq(&b, 2), Ok(true));
+    /// assert_eq!(a.approx_eq(&b, 1), Ok(false));
+    /// assert_eq!(a.approx_eq(&ByteString::new(&mut short), 8), Err(LengthError { index: 0, expected: 2, actual: 1 }));
+    /// ```
+    pub fn approx_eq(&self, other: &ByteString, max_differing_bits: usize) -> Result<bool, LengthError> {
+        if other.byte_len() != self.byte_len() {
+            return Err(LengthError { index: 0, expected: self.byte_len(), actual: other.byte_len() });
+        }
+
+        Ok(within_distance(self.iter().copied().zip(other.iter().copied()), max_differing_bits))
+    }
+
+    /// Whether every logical byte is within `max_abs_delta` of the byte at the same position in `other`.
+    ///
+    /// The bytes are compared as unsigned samples, fails if the lengths differ.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let (mut a, mut b) = ([0x00u8, 0x80], [0x02u8, 0x7F]);
+    /// let (a, b) = (ByteString::new(&mut a), ByteString::new(&mut b));
+    ///
+    /// // The largest difference decides, here 0x00 against 0x02
+    /// assert_eq!(a.within_byte_tolerance(&b, 2), Ok(true));
+    /// assert_eq!(a.within_byte_tolerance(&b, 1), Ok(false));
+    /// ```
+    pub fn within_byte_tolerance(&self, other: &ByteString, max_abs_delta: u8) -> Result<bool, LengthError> {
+        if other.byte_len() != self.byte_len() {
+            return Err(LengthError { index: 0, expected: self.byte_len(), actual: other.byte_len() });
+        }
+
+        Ok(self.iter().zip(other.iter()).all(|(a, b)| a.abs_diff(*b) <= max_abs_delta))
+    }
+
+    fn flip_sign_bit(&mut self) {
+        if let Some(msb) = self.iter_mut().next() {
+            *msb ^= 0x80;
+        }
+    }
+}
+
+impl<'a> PartialOrd for ByteString<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> Ord for ByteString<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_bytes(other)
+    }
+}
+
+// Hamming distance of the pairs is at most `max`, consuming no pair past the one that exceeds it
+fn within_distance(pairs: impl Iterator<Item = (u8, u8)>, max: usize) -> bool {
+    let mut distance = 0;
+    for (a, b) in pairs {
+        distance += (a ^ b).count_ones() as usize;
+        if distance > max {
+            return false;
+        }
+    }
+
+    true
+}
+
+pub(crate) fn leading_zero_bytes<'a>(iter: impl Iterator<Item = &'a u8>) -> usize {
+    iter.take_while(|e| **e == 0).count()
+}
+
+fn logical_byte(bytes: &ByteString, ix: usize) -> u8 {
+    bytes.bytes[bytes.physical_index(ix)]
+}
+
+// Physical slice holding the logical bytes start..start + len
+fn logical_window(bytes: &[u8], reversed: bool, start: usize, len: usize) -> &[u8] {
+    if reversed {
+        let end = bytes.len() - start;
+        return &bytes[end - len..end];
+    }
+
+    &bytes[start..start + len]
+}
+
+// Logical offset of the first differing byte of two equally long windows
+fn window_mismatch(a: &[u8], a_reversed: bool, b: &[u8], b_reversed: bool) -> Option<usize> {
+
+    let ne = |(x, y): (&u8, &u8)| x != y;
+
+    match (a_reversed, b_reversed) {
+        // Identical flags: memcmp the whole window and only scan if it differs
+        (false, false) => {
+            if a == b { return None; }
+            a.iter().zip(b).position(ne)
+        },
+        (true, true) => {
+            if a == b { return None; }
+            a.iter().zip(b).rposition(ne).map(|ix| a.len() - 1 - ix)
+        },
+        // Differing flags: pair each window with the reversal of the other
+        (false, true) => a.iter().zip(b.iter().rev()).position(ne),
+        (true, false) => a.iter().rev().zip(b.iter()).position(ne)
+    }
+}
+
+pub(crate) fn first_mismatch(a: &ByteString, b: &ByteString, chunk_len: usize) -> Option<usize> {
+
+    let common = a.byte_len().min(b.byte_len());
+
+    let mut start = 0;
+    while start < common {
+
+        let len = chunk_len.min(common - start);
+        let a_window = logical_window(a.bytes, a.interpret_reverse_endian, start, len);
+        let b_window = logical_window(b.bytes, b.interpret_reverse_endian, start, len);
+
+        if let Some(ix) = window_mismatch(a_window, a.interpret_reverse_endian, b_window, b.interpret_reverse_endian) {
+            return Some(start + ix);
+        }
+
+        start += len;
+    }
+
+    if a.byte_len() != b.byte_len() {
+        return Some(common);
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::{view, view_logical, XorShift};
+
+    fn reference_mismatch(a: &ByteString, b: &ByteString) -> Option<usize> {
+        let common = a.byte_len().min(b.byte_len());
+
+        match a.iter().zip(b.iter()).position(|(x, y)| x != y) {
+            Some(ix) => Some(ix),
+            None if a.byte_len() != b.byte_len() => Some(common),
+            None => None
+        }
+    }
+
+    #[test]
+    fn test_cmp_bytes_simple() {
+        let mut a = [1u8, 2, 3];
+        let mut b = [1u8, 2, 4];
+        let mut c = [1u8, 2];
+
+        let bytes_a = ByteString::new(&mut a);
+        let bytes_b = ByteString::new(&mut b);
+        let bytes_c = ByteString::new(&mut c);
+
+        assert_eq!(bytes_a.cmp_bytes(&bytes_b), Ordering::Less);
+        assert_eq!(bytes_b.cmp_bytes(&bytes_a), Ordering::Greater);
+        assert_eq!(bytes_c.cmp_bytes(&bytes_a), Ordering::Less);
+        assert_eq!(bytes_a.cmp_bytes(&bytes_a), Ordering::Equal);
+        assert!(bytes_c < bytes_a);
+    }
+
+    #[test]
+    fn test_cmp_numeric() {
+        let mut a = [0u8, 0, 5];
+        let mut b = [4u8, 0];
+        let mut c = [5u8];
+
+        let bytes_a = ByteString::new(&mut a);
+        let mut bytes_b = ByteString::new(&mut b);
+        bytes_b.interpret_reverse_endian();
+        let bytes_c = ByteString::new(&mut c);
+
+        assert_eq!(bytes_a.cmp_numeric(&bytes_b), Ordering::Greater);
+        assert_eq!(bytes_a.cmp_numeric(&bytes_c), Ordering::Equal);
+        assert_eq!(bytes_a.cmp_bytes(&bytes_c), Ordering::Less);
+    }
+
+    #[test]
+    fn test_first_mismatch_reversed() {
+        let mut a = [4u8, 3, 2, 1];
+        let mut b = [1u8, 2, 9, 4];
+
+        let mut bytes_a = ByteString::new(&mut a);
+        bytes_a.interpret_reverse_endian();
+        let bytes_b = ByteString::new(&mut b);
+
+        assert_eq!(bytes_a.first_mismatch_chunked(&bytes_b), Some(2));
+        assert_eq!(bytes_a.cmp_bytes(&bytes_b), Ordering::Less);
+    }
+
+    fn signed_value(logical: &[u8]) -> i128 {
+        let mut padded = [if logical[0] & 0x80 != 0 { 0xFF } else { 0 }; 16];
+        padded[16 - logical.len()..].copy_from_slice(logical);
+        i128::from_be_bytes(padded)
+    }
+
+    #[test]
+    fn test_lexicographic_preserves_order() {
+        let mut rng = XorShift::new(0x218);
+
+        for _ in 0..2000 {
+            let len = 1 + rng.below(16);
+            let mut a = vec![0u8; len];
+            let mut b = vec![0u8; len];
+            rng.fill(&mut a);
+            rng.fill(&mut b);
+            if rng.below(8) == 0 { b.copy_from_slice(&a); }
+
+            let (a_reversed, b_reversed) = (rng.next_bool(), rng.next_bool());
+            let signed = rng.next_bool();
+
+            let mut bytes_a = view(&mut a, a_reversed);
+            let mut bytes_b = view(&mut b, b_reversed);
+
+            let logical_a: Vec<u8> = bytes_a.iter().copied().collect();
+            let logical_b: Vec<u8> = bytes_b.iter().copied().collect();
+
+            let expected = if signed { signed_value(&logical_a).cmp(&signed_value(&logical_b)) }
+            else { bytes_a.cmp_bytes(&bytes_b) };
+
+            bytes_a.to_lexicographic(signed);
+            bytes_b.to_lexicographic(signed);
+            assert_eq!(bytes_a.bytes().cmp(bytes_b.bytes()), expected);
+
+            bytes_a.from_lexicographic(signed);
+            assert!(!bytes_a.interpret_reverse_endian);
+            assert_eq!(bytes_a.bytes(), &logical_a[..]);
+        }
+    }
+
+    #[test]
+    fn test_lexicographic_signed_example() {
+        let mut minus_one = [0xFFu8, 0xFF];
+        let mut one = [0x01u8, 0x00];
+
+        let mut bytes_minus_one = ByteString::new(&mut minus_one);
+        let mut bytes_one = ByteString::new(&mut one);
+        bytes_one.interpret_reverse_endian();
+
+        bytes_minus_one.to_lexicographic(true);
+        bytes_one.to_lexicographic(true);
+
+        assert_eq!(minus_one, [0x7F, 0xFF]);
+        assert_eq!(one, [0x80, 0x01]);
+        assert!(minus_one < one);
+    }
+
+    #[test]
+    fn test_first_mismatch_differential() {
+        let mut rng = XorShift::new(0x207);
+
+        for _ in 0..2000 {
+            let len_a = rng.below(40);
+            let len_b = if rng.next_bool() { len_a } else { rng.below(40) };
+
+            let mut a = vec![0u8; len_a];
+            rng.fill(&mut a);
+
+            // Mostly equal logical contents so mismatches land anywhere, including not at all
+            let mut b: Vec<u8> = a.iter().copied().chain(std::iter::repeat(0)).take(len_b).collect();
+            if len_b > 0 && rng.next_bool() {
+                let ix = rng.below(len_b);
+                b[ix] = rng.next_u8();
+            }
+
+            // Store the logical contents physically reversed when the flag is set
+            let (a_reversed, b_reversed) = (rng.next_bool(), rng.next_bool());
+            if a_reversed { a.reverse(); }
+            if b_reversed { b.reverse(); }
+
+            let bytes_a = view(&mut a, a_reversed);
+            let bytes_b = view(&mut b, b_reversed);
+
+            let expected = reference_mismatch(&bytes_a, &bytes_b);
+            for chunk_len in [1, 3, 7, COMPARE_CHUNK_LEN] {
+                assert_eq!(first_mismatch(&bytes_a, &bytes_b, chunk_len), expected);
+            }
+
+            assert_eq!(bytes_a.cmp_bytes(&bytes_b), bytes_a.iter().cmp(bytes_b.iter()));
+            assert_eq!(bytes_a == bytes_b, len_a == len_b && bytes_a.iter().eq(bytes_b.iter()));
+        }
+    }
+
+    #[test]
+    fn test_approx_eq_thresholds() {
+        for (a_reversed, b_reversed) in [(false, false), (false, true), (true, false), (true, true)] {
+            // Distance 3: one bit in the first byte, two in the last
+            let mut a = [0x00u8, 0xFF, 0x10];
+            let mut b = [0x01u8, 0xFF, 0x13];
+            let mut c = [0x00u8, 0xFF, 0x10];
+            let bytes_a = view_logical(&mut a, a_reversed);
+            let bytes_b = view_logical(&mut b, b_reversed);
+            let bytes_c = view_logical(&mut c, b_reversed);
+
+            assert_eq!(bytes_a.approx_eq(&bytes_c, 0), Ok(true));
+            assert_eq!(bytes_a.approx_eq(&bytes_b, 0), Ok(false));
+            assert_eq!(bytes_a.approx_eq(&bytes_b, 3), Ok(true));
+            assert_eq!(bytes_a.approx_eq(&bytes_b, 2), Ok(false));
+        }
+    }
+
+    #[test]
+    fn test_approx_eq_zero_threshold_is_eq() {
+        let mut rng = XorShift::new(0x273);
+
+        for _ in 0..500 {
+            let len = rng.below(12);
+            let mut a = vec![0u8; len];
+            rng.fill(&mut a);
+            let mut b = a.clone();
+            if len > 0 && rng.next_bool() {
+                b[rng.below(len)] ^= 1 << rng.below(8);
+            }
+
+            let bytes_a = view_logical(&mut a, rng.next_bool());
+            let bytes_b = view_logical(&mut b, rng.next_bool());
+            assert_eq!(bytes_a.approx_eq(&bytes_b, 0), Ok(bytes_a == bytes_b));
+        }
+    }
+
+    #[test]
+    fn test_approx_eq_exits_early() {
+        // Every pair differs in all eight bits, a threshold of 10 is exceeded by the second pair
+        let mut visited = 0;
+        let pairs = std::iter::repeat_n((0x00u8, 0xFFu8), 1000).inspect(|_| visited += 1);
+        assert!(!within_distance(pairs, 10));
+        assert_eq!(visited, 2);
+
+        let mut visited = 0;
+        let pairs = std::iter::repeat_n((0x00u8, 0x01u8), 1000).inspect(|_| visited += 1);
+        assert!(within_distance(pairs, 1000));
+        assert_eq!(visited, 1000);
+    }
+
+    #[test]
+    fn test_within_byte_tolerance() {
+        for (a_reversed, b_reversed) in [(false, false), (false, true), (true, false), (true, true)] {
+            let mut a = [0x10u8, 0x80, 0xFF];
+            let mut b = [0x13u8, 0x7D, 0xFF];
+            let bytes_a = view_logical(&mut a, a_reversed);
+            let bytes_b = view_logical(&mut b, b_reversed);
+
+            assert_eq!(bytes_a.within_byte_tolerance(&bytes_b, 3), Ok(true));
+            assert_eq!(bytes_a.within_byte_tolerance(&bytes_b, 2), Ok(false));
+            assert_eq!(bytes_b.within_byte_tolerance(&bytes_a, 3), Ok(true));
+        }
+
+        let mut a = [0x00u8, 0xFF];
+        let mut b = [0xFFu8, 0x00];
+        assert_eq!(ByteString::new(&mut a).within_byte_tolerance(&ByteString::new(&mut b), 255), Ok(true));
+    }
+
+    #[test]
+    fn test_tolerance_length_mismatch() {
+        let mut a = [0u8; 3];
+        let mut b = [0u8; 2];
+        let (bytes_a, bytes_b) = (ByteString::new(&mut a), ByteString::new(&mut b));
+        let err = Err(LengthError { index: 0, expected: 3, actual: 2 });
+
+        assert_eq!(bytes_a.approx_eq(&bytes_b, 100), err);
+        assert_eq!(bytes_a.within_byte_tolerance(&bytes_b, 255), err);
+    }
+}
@@ -0,0 +1,234 @@
+//! Consistent Overhead Byte Stuffing framing
+
+use crate::{
+    bytes::ByteString,
+    error::Error
+};
+
+/// Longest code block, 254 data bytes followed by no implicit zero
+const MAX_CODE: u8 = 0xFF;
+
+/// Worst-case COBS encoded size of `len` input bytes, excluding any frame delimiter.
+///
+/// Every started run of 254 bytes costs one overhead byte, an empty input encodes to a single byte.
+///
+/// ```
+/// use rebite::cobs::cobs_max_encoded_len;
+///
+/// assert_eq!(cobs_max_encoded_len(0), 1);
+/// assert_eq!(cobs_max_encoded_len(254), 256);
+/// ```
+pub const fn cobs_max_encoded_len(len: usize) -> usize {
+    len + len / 254 + 1
+}
+
+impl<'a> ByteString<'a> {
+
+    /// Consistent Overhead Byte Stuffing of the logical bytes into `out`.
+    ///
+    /// The output contains no zero bytes and no trailing delimiter, callers frame it themselves.
+    /// `out` needs at most [`cobs_max_encoded_len`] bytes. Returns the encoded length.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let mut raw = [0x11u8, 0x00, 0x22];
+    /// let bytes = ByteString::new(&mut raw);
+    /// let mut out = [0u8; 4];
+    ///
+    /// assert_eq!(bytes.cobs_encode_into(&mut out), Ok(4));
+    /// assert_eq!(out, [0x02, 0x11, 0x02, 0x22]);
+    /// assert_eq!(bytes.cobs_encode_into(&mut out[..3]), Err(Error::OutputTooSmall));
+    /// ```
+    pub fn cobs_encode_into(&self, out: &mut [u8]) -> Result<usize, Error> {
+
+        let mut put = |ix: usize, value: u8| -> Result<(), Error> {
+            *out.get_mut(ix).ok_or(Error::OutputTooSmall)? = value;
+            Ok(())
+        };
+
+        let mut code_ix = 0;
+        let mut code = 1;
+        let mut written = 1;
+
+        let mut iter = self.iter().peekable();
+        while let Some(&byte) = iter.next() {
+
+            if byte != 0 {
+                put(written, byte)?;
+                written += 1;
+                code += 1;
+            }
+
+            // Close the block on a zero or when it is full and more data follows
+            if byte == 0 || (code == MAX_CODE && iter.peek().is_some()) {
+                put(code_ix, code)?;
+                code_ix = written;
+                code = 1;
+                written += 1;
+            }
+        }
+
+        put(code_ix, code)?;
+        Ok(written)
+    }
+
+    /// Decode COBS encoded logical bytes of `self` into the front of `out`, returns the decoded length.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let mut encoded = [0x02u8, 0x11, 0x02, 0x22];
+    /// let mut out = [0xEEu8; 5];
+    ///
+    /// // Bytes of `out` past the decoded length are left alone
+    /// assert_eq!(ByteString::new(&mut encoded).cobs_decode_into(&mut ByteString::new(&mut out)), Ok(3));
+    /// assert_eq!(out, [0x11, 0x00, 0x22, 0xEE, 0xEE]);
+    ///
+    /// // A zero byte can never appear in an encoding
+    /// let mut bad = [0x02u8, 0x00];
+    /// assert_eq!(ByteString::new(&mut bad).cobs_decode_into(&mut ByteString::new(&mut out)), Err(Error::MalformedInput));
+    /// ```
+    pub fn cobs_decode_into(&self, out: &mut ByteString) -> Result<usize, Error> {
+
+        let mut dst = out.iter_mut();
+        let mut written = 0;
+        let mut put = |value: u8| -> Result<(), Error> {
+            *dst.next().ok_or(Error::OutputTooSmall)? = value;
+            written += 1;
+            Ok(())
+        };
+
+        let mut src = self.iter().copied().peekable();
+        while let Some(code) = src.next() {
+
+            if code == 0 {
+                return Err(Error::MalformedInput);
+            }
+
+            for _ in 1..code {
+                match src.next() {
+                    Some(byte) if byte != 0 => put(byte)?,
+                    _ => return Err(Error::MalformedInput)
+                }
+            }
+
+            // Each block except a full one and the last stands for a trailing zero
+            if code != MAX_CODE && src.peek().is_some() {
+                put(0)?;
+            }
+        }
+
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::XorShift;
+
+    fn encode(data: &[u8]) -> Vec<u8> {
+        let mut raw = data.to_vec();
+        let bytes = ByteString::new(&mut raw);
+
+        let mut out = vec![0u8; cobs_max_encoded_len(data.len())];
+        let n = bytes.cobs_encode_into(&mut out).unwrap();
+        out.truncate(n);
+        out
+    }
+
+    fn decode(encoded: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut raw = encoded.to_vec();
+        let bytes = ByteString::new(&mut raw);
+
+        let mut out = vec![0u8; encoded.len()];
+        let n = bytes.cobs_decode_into(&mut ByteString::new(&mut out))?;
+        out.truncate(n);
+        Ok(out)
+    }
+
+    #[test]
+    fn test_cobs_known_vectors() {
+        assert_eq!(encode(&[]), [0x01]);
+        assert_eq!(encode(&[0x00]), [0x01, 0x01]);
+        assert_eq!(encode(&[0x00, 0x00]), [0x01, 0x01, 0x01]);
+        assert_eq!(encode(&[0x11, 0x22, 0x00, 0x33]), [0x03, 0x11, 0x22, 0x02, 0x33]);
+        assert_eq!(encode(&[0x11, 0x00, 0x00, 0x00]), [0x02, 0x11, 0x01, 0x01, 0x01]);
+    }
+
+    #[test]
+    fn test_cobs_block_boundary() {
+        let full: Vec<u8> = (1..=254).collect();
+
+        let encoded = encode(&full);
+        assert_eq!(encoded.len(), 255);
+        assert_eq!(encoded[0], 0xFF);
+        assert_eq!(decode(&encoded).unwrap(), full);
+
+        let mut longer = full.clone();
+        longer.push(0xAA);
+        let encoded = encode(&longer);
+        assert_eq!(&encoded[255..], [0x02, 0xAA]);
+        assert_eq!(decode(&encoded).unwrap(), longer);
+
+        let mut zero_after = full.clone();
+        zero_after.push(0);
+        let encoded = encode(&zero_after);
+        assert_eq!(&encoded[255..], [0x01, 0x01]);
+        assert_eq!(decode(&encoded).unwrap(), zero_after);
+    }
+
+    #[test]
+    fn test_cobs_round_trip() {
+        let mut rng = XorShift::new(0x211);
+
+        for _ in 0..500 {
+            let len = rng.below(800);
+            let mut data = vec![0u8; len];
+            rng.fill(&mut data);
+
+            // Sprinkle runs of zeros and long nonzero stretches
+            for byte in data.iter_mut() {
+                if rng.below(4) == 0 { *byte = 0; }
+            }
+            if rng.next_bool() {
+                data.iter_mut().filter(|e| **e == 0).for_each(|e| *e = 1);
+            }
+
+            let encoded = encode(&data);
+            assert!(encoded.len() <= cobs_max_encoded_len(len));
+            assert!(!encoded.contains(&0));
+            assert_eq!(decode(&encoded).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_cobs_reversed_interpretation() {
+        let mut raw = [0x33u8, 0x00, 0x22, 0x11];
+        let mut bytes = ByteString::new(&mut raw);
+        bytes.interpret_reverse_endian();
+
+        let mut out = [0u8; 5];
+        assert_eq!(bytes.cobs_encode_into(&mut out), Ok(5));
+        assert_eq!(out, [0x03, 0x11, 0x22, 0x02, 0x33]);
+    }
+
+    #[test]
+    fn test_cobs_errors() {
+        let mut raw = [0u8; 4];
+        let bytes = ByteString::new(&mut raw);
+        let mut out = [0u8; 4];
+        assert_eq!(bytes.cobs_encode_into(&mut out), Err(Error::OutputTooSmall));
+
+        assert_eq!(decode(&[0x03, 0x11]), Err(Error::MalformedInput));
+        assert_eq!(decode(&[0x03, 0x11, 0x00]), Err(Error::MalformedInput));
+        assert_eq!(decode(&[0x00]), Err(Error::MalformedInput));
+
+        let mut raw = [0x01u8, 0x01, 0x01];
+        let bytes = ByteString::new(&mut raw);
+        let mut out = [0u8; 1];
+        assert_eq!(bytes.cobs_decode_into(&mut ByteString::new(&mut out)), Err(Error::OutputTooSmall));
+    }
+}
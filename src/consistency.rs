@@ -0,0 +1,138 @@
+//! Cross-checks between related APIs over a matrix of lengths and interpretation flags
+
+use std::cmp::Ordering;
+
+use crate::{
+    bit_order::BitOrder,
+    bytes::ByteString,
+    error::Overflow,
+    test_util::{view, XorShift}
+};
+
+const LENGTHS: [usize; 8] = [0, 1, 2, 3, 7, 8, 9, 33];
+
+// Run `f` on random buffers of every length under both interpretations
+fn for_each_case<F: FnMut(&mut XorShift, &mut ByteString)>(seed: u64, mut f: F) {
+    let mut rng = XorShift::new(seed);
+
+    for len in LENGTHS {
+        for reversed in [false, true] {
+            for _ in 0..20 {
+                let mut raw = vec![0u8; len];
+                rng.fill(&mut raw);
+
+                let mut bytes = view(&mut raw, reversed);
+
+                bytes.debug_validate();
+                f(&mut rng, &mut bytes);
+                bytes.debug_validate();
+            }
+        }
+    }
+}
+
+#[test]
+fn eq_matches_first_mismatch_and_cmp() {
+    for_each_case(0x214, |rng, bytes| {
+        let mut other: Vec<u8> = bytes.iter().copied().collect();
+        if !other.is_empty() && rng.next_bool() {
+            let ix = rng.below(other.len());
+            other[ix] ^= 1 << rng.below(8);
+        }
+        let other = ByteString::new(&mut other);
+
+        let mismatch = bytes.first_mismatch_chunked(&other);
+        assert_eq!(*bytes == other, mismatch.is_none());
+        assert_eq!(bytes.cmp_bytes(&other) == Ordering::Equal, mismatch.is_none());
+        assert_eq!(bytes.cmp_bytes(&other), bytes.iter().cmp(other.iter()));
+    });
+}
+
+#[test]
+fn bit_matches_logical_bytes() {
+    for_each_case(0x2140, |_, bytes| {
+        let logical: Vec<u8> = bytes.iter().copied().collect();
+
+        for offset in 0..bytes.bit_len() {
+            let byte = logical[logical.len() - 1 - offset / 8];
+            assert_eq!(bytes.bit(offset), byte >> (offset % 8) & 1 == 1);
+        }
+    });
+}
+
+#[test]
+fn shl_shr_round_trip() {
+    for_each_case(0x2141, |rng, bytes| {
+        if bytes.bit_len() == 0 { return; }
+
+        let n = rng.below(bytes.bit_len());
+        let before: Vec<u8> = bytes.iter().copied().collect();
+
+        // Clear the bits a left shift would lose so the round trip is exact
+        for offset in bytes.bit_len() - n..bytes.bit_len() {
+            if bytes.bit(offset) {
+                let ix = bytes.physical_index(bytes.byte_len() - 1 - offset / 8);
+                bytes.bytes_mut()[ix] ^= 1 << (offset % 8);
+            }
+        }
+        let masked: Vec<u8> = bytes.iter().copied().collect();
+
+        *bytes <<= n;
+        bytes.shr_bits(n);
+        assert!(bytes.iter().eq(masked.iter()), "{:?} << {} >> {}", before, n, n);
+    });
+}
+
+#[test]
+fn count_ones_matches_iter_bits() {
+    for_each_case(0x2144, |_, bytes| {
+        for order in [BitOrder::Msb0, BitOrder::Lsb0] {
+            assert_eq!(bytes.count_ones(), bytes.iter_bits(order).filter(|e| *e).count());
+        }
+    });
+}
+
+#[test]
+fn to_u64_matches_extract_bits() {
+    for_each_case(0x2145, |rng, bytes| {
+        // Half the wide buffers get clear high bytes so both outcomes of to_u64 occur
+        if rng.next_bool() {
+            let high = bytes.byte_len().saturating_sub(8);
+            bytes.iter_mut().take(high).for_each(|e| *e = 0);
+        }
+
+        // The low 64 bits are the last ones of the stream, the bits above them must be clear
+        let low = bytes.bit_len().min(64);
+        let value = bytes.extract_bits(bytes.bit_len() - low, low, BitOrder::Msb0).unwrap();
+        let high_clear = (0..bytes.bit_len() - low).all(|e| !bytes.stream_bit(e, BitOrder::Msb0));
+
+        assert_eq!(bytes.to_u64(), if high_clear { Ok(value) } else { Err(Overflow) });
+    });
+}
+
+#[test]
+fn iter_range_matches_iter() {
+    for_each_case(0x2142, |rng, bytes| {
+        let end = rng.below(bytes.byte_len() + 1);
+        let start = rng.below(end + 1);
+
+        assert!(bytes.iter_range(start..end).eq(bytes.iter().skip(start).take(end - start)));
+        assert!(bytes.iter_range(start..end).rev().eq(bytes.iter().skip(start).take(end - start).rev()));
+    });
+}
+
+#[test]
+fn radix_round_trip_preserves_value() {
+    for_each_case(0x2143, |rng, bytes| {
+        if bytes.byte_len() == 0 { return; }
+
+        let radix = 2 + rng.below(35) as u32;
+        let mut text = vec![0u8; bytes.bit_len() + 1];
+        let n = bytes.write_radix(&mut text, radix, rng.next_bool()).unwrap();
+
+        let mut copy = vec![0u8; bytes.byte_len()];
+        let mut parsed = ByteString::new(&mut copy);
+        parsed.assign_from_radix_str(std::str::from_utf8(&text[..n]).unwrap(), radix).unwrap();
+        assert!(parsed.iter().eq(bytes.iter()));
+    });
+}
@@ -0,0 +1,268 @@
+//! Constant time comparison and selection
+//!
+//! Constant time is best-effort. The code avoids branches and indexing that depend on the secret
+//! data and passes the selection mask through [`core::hint::black_box`] so the optimizer cannot
+//! turn it back into a branch, but Rust gives no guarantee about the machine code it emits.
+//! Check the generated code on the target where timing matters.
+
+use std::hint;
+
+use crate::{
+    bytes::ByteString,
+    error::{Error, LengthError}
+};
+
+/// Byte assumed at logical positions past the end of a buffer in padded comparisons.
+///
+/// ```
+/// use rebite::{bytes::ByteString, ct::CT_PADDING_BYTE};
+///
+/// // A buffer and its padded extension differ only in length, which never compares equal
+/// let (mut short, mut long) = ([0x01u8], [0x01u8, CT_PADDING_BYTE]);
+/// let (short, long) = (ByteString::new(&mut short), ByteString::new(&mut long));
+///
+/// assert!(!short.ct_eq_padded(&long, 4));
+/// ```
+pub const CT_PADDING_BYTE: u8 = 0x00;
+
+// Logical byte at `ix`, or the padding byte past the end, selected without branching on the data
+fn padded_byte(bytes: &ByteString, ix: usize) -> u8 {
+    if bytes.byte_len() == 0 {
+        return CT_PADDING_BYTE;
+    }
+
+    let inside = ((ix < bytes.byte_len()) as u8).wrapping_neg();
+    let value = bytes.bytes[bytes.physical_index(ix.min(bytes.byte_len() - 1))];
+
+    (value & inside) | (CT_PADDING_BYTE & !inside)
+}
+
+// 0xFF when `choice` is set and 0x00 otherwise, by arithmetic rather than a branch. black_box
+// hides that the result has only two values, which would let the compiler select with a branch.
+fn ct_mask(choice: bool) -> u8 {
+    hint::black_box((hint::black_box(choice) as u8).wrapping_neg())
+}
+
+// `b` under a 0xFF mask and `a` under 0x00
+const fn ct_select(a: u8, b: u8, mask: u8) -> u8 {
+    a ^ ((a ^ b) & mask)
+}
+
+// Accumulate differences over exactly `compare_len` fetched pairs plus the length difference
+fn ct_fold<F: FnMut(usize) -> (u8, u8)>(compare_len: usize, len_a: usize, len_b: usize, mut fetch: F) -> bool {
+
+    let mut diff = 0u8;
+    for ix in 0..compare_len {
+        let (a, b) = fetch(ix);
+        diff |= a ^ b;
+    }
+
+    let len_diff = (len_a ^ len_b) as u64;
+    diff |= ((len_diff | len_diff.wrapping_neg()) >> 63) as u8;
+
+    diff == 0
+}
+
+impl<'a> ByteString<'a> {
+
+    /// Constant-time equality of the logical bytes that also hides both lengths.
+    ///
+    /// Always performs exactly `compare_len` byte comparisons, positions past the end of a buffer
+    /// read as [`CT_PADDING_BYTE`]. Buffers of different length never compare equal.
+    /// Panics if `compare_len` is smaller than either length, which is a caller error.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let (mut a, mut b) = ([0x01u8, 0x02], [0x02u8, 0x01]);
+    /// let a = ByteString::new(&mut a);
+    /// let mut b = ByteString::new(&mut b);
+    /// b.interpret_reverse_endian();
+    ///
+    /// // A fixed compare_len makes the time the same for every key up to 32 bytes
+    /// assert!(a.ct_eq_padded(&b, 32));
+    /// ```
+    #[track_caller]
+    pub fn ct_eq_padded(&self, other: &ByteString, compare_len: usize) -> bool {
+        self.try_ct_eq_padded(other, compare_len).expect("compare_len must cover both buffers")
+    }
+
+    /// Non-panicking form of [`ByteString::ct_eq_padded`].
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let (mut a, mut b) = ([0x01u8, 0x02], [0x01u8, 0x02, 0x00]);
+    /// let a = ByteString::new(&mut a);
+    /// let b = ByteString::new(&mut b);
+    ///
+    /// // Padding does not make a shorter buffer equal
+    /// assert_eq!(a.try_ct_eq_padded(&b, 4), Ok(false));
+    /// assert_eq!(a.try_ct_eq_padded(&b, 2), Err(Error::OutOfBounds));
+    /// ```
+    pub fn try_ct_eq_padded(&self, other: &ByteString, compare_len: usize) -> Result<bool, Error> {
+
+        if compare_len < self.byte_len() || compare_len < other.byte_len() {
+            return Err(Error::OutOfBounds);
+        }
+
+        Ok(ct_fold(compare_len, self.byte_len(), other.byte_len(), |ix| (padded_byte(self, ix), padded_byte(other, ix))))
+    }
+
+    /// Copy the logical bytes of `other` into self if `choice` is set, without branching on it.
+    ///
+    /// Every byte of self is read, masked and written whatever `choice` is, so timing and memory
+    /// accesses do not depend on it, as far as the compiler allows, see the [module docs](crate::ct).
+    /// Fails if the lengths differ, before touching self.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let (mut a, mut b) = ([0x01u8, 0x02], [0xAAu8, 0xBB]);
+    /// let mut bytes = ByteString::new(&mut a);
+    /// let other = ByteString::new(&mut b);
+    ///
+    /// bytes.conditional_assign(&other, false).unwrap();
+    /// assert_eq!(bytes.to_u64(), Ok(0x0102));
+    /// bytes.conditional_assign(&other, true).unwrap();
+    /// assert_eq!(bytes.to_u64(), Ok(0xAABB));
+    /// ```
+    pub fn conditional_assign(&mut self, other: &ByteString, choice: bool) -> Result<(), LengthError> {
+        if other.byte_len() != self.byte_len() {
+            return Err(LengthError { index: 0, expected: self.byte_len(), actual: other.byte_len() });
+        }
+
+        let mask = ct_mask(choice);
+        for (a, b) in self.iter_mut().zip(other.iter()) {
+            *a = ct_select(*a, *b, hint::black_box(mask));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::test_util::view;
+
+    #[test]
+    fn test_ct_eq_padded_equal() {
+        let mut a = [1u8, 2, 3];
+        let mut b = [3u8, 2, 1];
+
+        let bytes_a = ByteString::new(&mut a);
+        let mut bytes_b = ByteString::new(&mut b);
+        bytes_b.interpret_reverse_endian();
+
+        assert!(bytes_a.ct_eq_padded(&bytes_b, 3));
+        assert!(bytes_a.ct_eq_padded(&bytes_b, 64));
+    }
+
+    #[test]
+    fn test_ct_eq_padded_different() {
+        let mut a = [1u8, 2, 3];
+        let mut b = [1u8, 2, 4];
+        let mut prefix = [1u8, 2];
+        let mut padded = [1u8, 2, 3, 0];
+
+        let bytes_a = ByteString::new(&mut a);
+
+        assert!(!bytes_a.ct_eq_padded(&ByteString::new(&mut b), 8));
+        assert!(!bytes_a.ct_eq_padded(&ByteString::new(&mut prefix), 8));
+        // Same bytes once padded, but the lengths still differ
+        assert!(!bytes_a.ct_eq_padded(&ByteString::new(&mut padded), 8));
+        assert!(ByteString::new(&mut []).ct_eq_padded(&ByteString::new(&mut []), 8));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_ct_eq_padded_short_compare_len() {
+        let mut a = [1u8, 2, 3];
+        let mut b = [1u8, 2, 3];
+
+        ByteString::new(&mut a).ct_eq_padded(&ByteString::new(&mut b), 2);
+    }
+
+    // Byte source that counts every byte it hands out
+    struct CountingSource {
+        bytes: Vec<u8>,
+        reads: Cell<usize>,
+    }
+
+    impl CountingSource {
+        fn new(bytes: Vec<u8>) -> Self {
+            Self { bytes, reads: Cell::new(0) }
+        }
+
+        fn read(&self, ix: usize) -> u8 {
+            self.reads.set(self.reads.get() + 1);
+            self.bytes.get(ix).copied().unwrap_or(CT_PADDING_BYTE)
+        }
+    }
+
+    #[test]
+    fn test_ct_fold_access_count_is_length_independent() {
+        let len = 48;
+
+        // Differing at the first byte, the last byte, nowhere, and only in length
+        let cases = [
+            (vec![0x5Au8; 32], { let mut e = vec![0x5Au8; 32]; e[0] = 0xA5; e }, false),
+            (vec![0x5Au8; 32], { let mut e = vec![0x5Au8; 32]; e[31] = 0xA5; e }, false),
+            (vec![0x5Au8; 32], vec![0x5Au8; 32], true),
+            (vec![0x5Au8; 3], vec![0x5Au8; 16], false),
+            (Vec::new(), Vec::new(), true),
+        ];
+
+        for (a, b, equal) in cases {
+            let (len_a, len_b) = (a.len(), b.len());
+            let (a, b) = (CountingSource::new(a), CountingSource::new(b));
+
+            assert_eq!(ct_fold(len, len_a, len_b, |ix| (a.read(ix), b.read(ix))), equal);
+            assert_eq!((a.reads.get(), b.reads.get()), (len, len), "lengths {} and {}", len_a, len_b);
+        }
+    }
+
+    #[test]
+    fn test_ct_select_is_mask_arithmetic() {
+        assert_eq!((ct_mask(false), ct_mask(true)), (0x00, 0xFF));
+
+        for a in 0..=255u8 {
+            for b in 0..=255u8 {
+                assert_eq!(ct_select(a, b, ct_mask(false)), a);
+                assert_eq!(ct_select(a, b, ct_mask(true)), b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_conditional_assign_both_choices() {
+        for (self_reversed, other_reversed) in [(false, false), (false, true), (true, false), (true, true)] {
+            let mut a = [0x01u8, 0x02, 0x03];
+            let mut b = [0xAAu8, 0xBB, 0xCC];
+            let mut bytes = view(&mut a, self_reversed);
+            let other = view(&mut b, other_reversed);
+
+            let before: Vec<u8> = bytes.iter().copied().collect();
+            bytes.conditional_assign(&other, false).unwrap();
+            assert!(bytes.iter().eq(before.iter()));
+
+            bytes.conditional_assign(&other, true).unwrap();
+            assert_eq!(bytes, other);
+        }
+    }
+
+    #[test]
+    fn test_conditional_assign_length_mismatch() {
+        let mut a = [0x01u8, 0x02];
+        let mut b = [0xAAu8];
+        let mut bytes = ByteString::new(&mut a);
+
+        let err = bytes.conditional_assign(&ByteString::new(&mut b), true);
+        assert_eq!(err, Err(LengthError { index: 0, expected: 2, actual: 1 }));
+        assert_eq!(a, [0x01, 0x02]);
+    }
+}
@@ -0,0 +1,101 @@
+//! Detection of repeated fixed size blocks
+
+use std::collections::HashMap;
+
+use crate::{
+    bytes::ByteString,
+    checksum::{Checksum, Crc32},
+    error::Error
+};
+
+impl<'a> ByteString<'a> {
+
+    /// Append `(first, later)` logical block indices of every block identical to an earlier one.
+    ///
+    /// Blocks are `block_size` logical bytes long, trailing bytes that do not fill a block are ignored.
+    /// Each repeated block is paired with the first block holding the same contents, so three equal
+    /// blocks `a < b < c` are reported as `(a, b)` and `(a, c)`. Pairs are ordered by `later`.
+    /// Candidates are found by hash and confirmed by comparing the bytes.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// // Blocks 12 34 12 12 and a trailing partial block
+    /// let mut raw = [1u8, 2, 3, 4, 1, 2, 1, 2, 9];
+    /// let mut pairs = vec![(9, 9)];
+    ///
+    /// ByteString::new(&mut raw).find_duplicate_blocks(2, &mut pairs).unwrap();
+    /// assert_eq!(pairs, [(9, 9), (0, 2), (0, 3)]);
+    /// ```
+    pub fn find_duplicate_blocks(&self, block_size: usize, out: &mut Vec<(usize, usize)>) -> Result<(), Error> {
+
+        if block_size == 0 {
+            return Err(Error::ZeroLength);
+        }
+
+        let logical: Vec<u8> = self.iter().copied().collect();
+        let blocks: Vec<&[u8]> = logical.chunks_exact(block_size).collect();
+
+        // Hash to the first block of every distinct content seen with that hash
+        let mut firsts: HashMap<u32, Vec<usize>> = HashMap::new();
+
+        for (ix, block) in blocks.iter().enumerate() {
+            let mut crc = Crc32::default();
+            crc.update_slice(block);
+
+            let candidates = firsts.entry(crc.finalize()).or_default();
+            match candidates.iter().find(|first| blocks[**first] == *block) {
+                Some(first) => out.push((*first, ix)),
+                None => candidates.push(ix)
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::view;
+
+    fn duplicates(raw: &mut [u8], reversed: bool, block_size: usize) -> Vec<(usize, usize)> {
+        let bytes = view(raw, reversed);
+
+        let mut out = Vec::new();
+        bytes.find_duplicate_blocks(block_size, &mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn test_known_duplicates_with_triple() {
+        let mut raw = *b"AAAABBBBAAAACCCCBBBBAAAAxy";
+        assert_eq!(duplicates(&mut raw, false, 4), [(0, 2), (1, 4), (0, 5)]);
+
+        // Reversed interpretation numbers blocks from the other physical end
+        let mut raw = *b"yxAAAABBBBCCCCAAAABBBBAAAA";
+        assert_eq!(duplicates(&mut raw, true, 4), [(0, 2), (1, 4), (0, 5)]);
+    }
+
+    #[test]
+    fn test_no_duplicates() {
+        let mut raw: Vec<u8> = (0..64).collect();
+        assert!(duplicates(&mut raw, false, 8).is_empty());
+        assert!(duplicates(&mut raw, false, 65).is_empty());
+    }
+
+    #[test]
+    fn test_all_identical() {
+        let mut raw = [7u8; 20];
+        assert_eq!(duplicates(&mut raw, false, 5), [(0, 1), (0, 2), (0, 3)]);
+        assert_eq!(duplicates(&mut raw, false, 1).len(), 19);
+    }
+
+    #[test]
+    fn test_zero_block_size() {
+        let mut raw = [0u8; 4];
+        let mut out = Vec::new();
+        assert_eq!(ByteString::new(&mut raw).find_duplicate_blocks(0, &mut out), Err(Error::ZeroLength));
+    }
+}
@@ -0,0 +1,185 @@
+//! Change detection between successive states of a buffer
+
+use std::ops::Range;
+
+use crate::{
+    bytes::ByteString,
+    error::LengthError
+};
+
+impl<'a> ByteString<'a> {
+
+    /// Write 0xFF into `out` for every logical byte that differs from `previous` and 0x00 otherwise,
+    /// returning the number of changed bytes.
+    ///
+    /// All three lengths must match, error indices count `previous` as 0 and `out` as 1. The three
+    /// buffers are walked once in logical order, so their interpretation flags may differ.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::LengthError};
+    ///
+    /// let (mut now, mut before, mut mask) = ([1u8, 2, 3], [1u8, 0, 0], [0u8; 3]);
+    /// let now = ByteString::new(&mut now);
+    /// let before = ByteString::new(&mut before);
+    ///
+    /// assert_eq!(now.changed_mask_into(&before, &mut ByteString::new(&mut mask)), Ok(2));
+    /// assert_eq!(mask, [0x00, 0xFF, 0xFF]);
+    ///
+    /// let result = now.changed_mask_into(&before, &mut ByteString::new(&mut mask[..2]));
+    /// assert_eq!(result, Err(LengthError { index: 1, expected: 3, actual: 2 }));
+    /// ```
+    pub fn changed_mask_into(&self, previous: &ByteString, out: &mut ByteString) -> Result<usize, LengthError> {
+
+        for (index, len) in [previous.byte_len(), out.byte_len()].into_iter().enumerate() {
+            if len != self.byte_len() {
+                return Err(LengthError { index, expected: self.byte_len(), actual: len });
+            }
+        }
+
+        let mut count = 0;
+        for ((dst, now), before) in out.iter_mut().zip(self.iter()).zip(previous.iter()) {
+            let changed = now != before;
+            *dst = if changed { 0xFF } else { 0x00 };
+            count += changed as usize;
+        }
+
+        Ok(count)
+    }
+
+    /// Append the logical ranges that differ from `previous` to `out`, ascending.
+    ///
+    /// Changes separated by at most `max_gap` unchanged bytes share a range, so a gap of 0 only
+    /// joins adjacent changes. Fails without appending if the lengths differ.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let (mut now, mut before) = ([1u8, 0, 1, 0, 0, 1], [0u8; 6]);
+    /// let now = ByteString::new(&mut now);
+    /// let before = ByteString::new(&mut before);
+    ///
+    /// let mut ranges = Vec::new();
+    /// now.changed_ranges(&before, 0, &mut ranges).unwrap();
+    /// assert_eq!(ranges, [0..1, 2..3, 5..6]);
+    ///
+    /// // A gap of one unchanged byte joins the first two
+    /// ranges.clear();
+    /// now.changed_ranges(&before, 1, &mut ranges).unwrap();
+    /// assert_eq!(ranges, [0..3, 5..6]);
+    /// ```
+    pub fn changed_ranges(&self, previous: &ByteString, max_gap: usize, out: &mut Vec<Range<usize>>) -> Result<(), LengthError> {
+
+        if previous.byte_len() != self.byte_len() {
+            return Err(LengthError { index: 0, expected: self.byte_len(), actual: previous.byte_len() });
+        }
+
+        // Only ranges pushed by this call may be extended
+        let first = out.len();
+        for (ix, (now, before)) in self.iter().zip(previous.iter()).enumerate() {
+            if now == before {
+                continue;
+            }
+
+            match out[first..].last_mut() {
+                Some(last) if ix - last.end <= max_gap => last.end = ix + 1,
+                _ => out.push(ix..ix + 1),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::view;
+
+    #[test]
+    fn test_no_changes_and_all_changes() {
+        let mut now = [0x10u8, 0x20, 0x30, 0x40];
+        let mut before = now;
+        let mut mask = [0xAAu8; 4];
+        let mut ranges = Vec::new();
+
+        let bytes = ByteString::new(&mut now);
+        assert_eq!(bytes.changed_mask_into(&ByteString::new(&mut before), &mut ByteString::new(&mut mask)), Ok(0));
+        bytes.changed_ranges(&ByteString::new(&mut before), 0, &mut ranges).unwrap();
+        assert_eq!(mask, [0x00; 4]);
+        assert!(ranges.is_empty());
+
+        let mut other = [0u8; 4];
+        assert_eq!(bytes.changed_mask_into(&ByteString::new(&mut other), &mut ByteString::new(&mut mask)), Ok(4));
+        bytes.changed_ranges(&ByteString::new(&mut other), 0, &mut ranges).unwrap();
+        assert_eq!(mask, [0xFF; 4]);
+        assert_eq!(ranges, vec![0..4]);
+    }
+
+    #[test]
+    fn test_gap_threshold() {
+        // Changes at 0, 3, 5, 9 and 14
+        let mut now = [1u8, 0, 0, 1, 0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 1];
+        let mut before = [0u8; 15];
+        let bytes = ByteString::new(&mut now);
+        let previous = ByteString::new(&mut before);
+
+        let cases: [(usize, &[Range<usize>]); 4] = [
+            (0, &[0..1, 3..4, 5..6, 9..10, 14..15]),
+            (1, &[0..1, 3..6, 9..10, 14..15]),
+            (2, &[0..6, 9..10, 14..15]),
+            (3, &[0..10, 14..15]),
+        ];
+        for (max_gap, expected) in cases {
+            let mut ranges = Vec::new();
+            bytes.changed_ranges(&previous, max_gap, &mut ranges).unwrap();
+            assert_eq!(ranges, expected, "max_gap={}", max_gap);
+        }
+
+        // Earlier contents of `out` are kept and never merged into
+        let mut ranges = vec![0..1, 2..3];
+        bytes.changed_ranges(&previous, 4, &mut ranges).unwrap();
+        assert_eq!(ranges, vec![0..1, 2..3, 0..15]);
+    }
+
+    #[test]
+    fn test_mixed_flags() {
+        // Logical states 11 22 33 44 and 11 00 33 00 under every combination of flags
+        for (now_rev, before_rev, mask_rev) in (0..8).map(|e| (e & 1 != 0, e & 2 != 0, e & 4 != 0)) {
+            let mut now = [0x11u8, 0x22, 0x33, 0x44];
+            let mut before = [0x11u8, 0x00, 0x33, 0x00];
+            if now_rev { now.reverse(); }
+            if before_rev { before.reverse(); }
+
+            let mut mask = [0u8; 4];
+            let bytes = view(&mut now, now_rev);
+            let previous = view(&mut before, before_rev);
+            let mut mask_bytes = view(&mut mask, mask_rev);
+
+            assert_eq!(bytes.changed_mask_into(&previous, &mut mask_bytes), Ok(2));
+            assert!(mask_bytes.iter().eq([0x00, 0xFF, 0x00, 0xFF].iter()));
+
+            let mut ranges = Vec::new();
+            bytes.changed_ranges(&previous, 0, &mut ranges).unwrap();
+            assert_eq!(ranges, vec![1..2, 3..4]);
+        }
+    }
+
+    #[test]
+    fn test_length_mismatch() {
+        let mut now = [0u8; 3];
+        let mut before = [0u8; 2];
+        let mut same = [0u8; 3];
+        let mut mask = [0x55u8; 4];
+        let bytes = ByteString::new(&mut now);
+
+        let err = bytes.changed_mask_into(&ByteString::new(&mut before), &mut ByteString::new(&mut mask));
+        assert_eq!(err, Err(LengthError { index: 0, expected: 3, actual: 2 }));
+        let err = bytes.changed_mask_into(&ByteString::new(&mut same), &mut ByteString::new(&mut mask));
+        assert_eq!(err, Err(LengthError { index: 1, expected: 3, actual: 4 }));
+        assert_eq!(mask, [0x55; 4]);
+
+        let mut ranges = Vec::new();
+        assert!(bytes.changed_ranges(&ByteString::new(&mut before), 0, &mut ranges).is_err());
+    }
+}
@@ -0,0 +1,358 @@
+//! SECDED Hamming codes that correct single and detect double bit errors
+
+use crate::{
+    bytes::ByteString,
+    error::Error
+};
+
+/// Data bytes protected by one SECDED code word.
+///
+/// ```
+/// use rebite::{bytes::ByteString, ecc::{ECC_DATA_LEN, ECC_WORD_LEN}};
+///
+/// let mut data = [0x5Au8; 2 * ECC_DATA_LEN];
+/// let mut encoded = [0u8; 2 * ECC_WORD_LEN];
+///
+/// // Every group of ECC_DATA_LEN data bytes gets its own code word
+/// ByteString::new(&mut data).secded_encode_into(&mut ByteString::new(&mut encoded)).unwrap();
+/// assert_eq!(encoded[ECC_WORD_LEN..ECC_WORD_LEN + ECC_DATA_LEN], data[ECC_DATA_LEN..]);
+/// ```
+pub const ECC_DATA_LEN: usize = 8;
+
+/// Encoded size of one code word, the data bytes followed by one check byte.
+///
+/// ```
+/// use rebite::ecc::{secded_encoded_len, ECC_DATA_LEN, ECC_WORD_LEN};
+///
+/// assert_eq!(secded_encoded_len(ECC_DATA_LEN), ECC_WORD_LEN);
+/// ```
+pub const ECC_WORD_LEN: usize = ECC_DATA_LEN + 1;
+
+/// Outcome of decoding a single code word.
+///
+/// ```
+/// use rebite::{bytes::ByteString, ecc::WordStatus};
+///
+/// let mut data = [0u8; 16];
+/// let mut encoded = [0u8; 18];
+/// ByteString::new(&mut data).secded_encode_into(&mut ByteString::new(&mut encoded)).unwrap();
+///
+/// // One flip in the first word, two in the second
+/// encoded[0] ^= 0x01;
+/// encoded[9] ^= 0x03;
+///
+/// let report = ByteString::new(&mut data).secded_decode_from(&ByteString::new(&mut encoded)).unwrap();
+/// assert_eq!(report.words, [WordStatus::Corrected, WordStatus::Uncorrectable]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordStatus {
+    /// No bit flipped
+    Clean,
+    /// A single flipped bit was repaired
+    Corrected,
+    /// Two or more bits flipped, the data bytes were copied unrepaired
+    Uncorrectable,
+}
+
+/// Per word results of [`ByteString::secded_decode_from`].
+///
+/// ```
+/// use rebite::bytes::ByteString;
+///
+/// let mut data = *b"12345678";
+/// let mut encoded = [0u8; 9];
+/// ByteString::new(&mut data).secded_encode_into(&mut ByteString::new(&mut encoded)).unwrap();
+///
+/// let report = ByteString::new(&mut data).secded_decode_from(&ByteString::new(&mut encoded)).unwrap();
+/// assert!(report.is_clean());
+/// assert_eq!(report.words.len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EccReport {
+    /// Status of every code word, in order
+    pub words: Vec<WordStatus>,
+}
+
+impl EccReport {
+
+    /// Number of words with a repaired single bit error.
+    ///
+    /// ```
+    /// use rebite::ecc::{EccReport, WordStatus};
+    ///
+    /// let report = EccReport { words: vec![WordStatus::Corrected, WordStatus::Clean, WordStatus::Corrected] };
+    /// assert_eq!(report.corrected(), 2);
+    /// ```
+    pub fn corrected(&self) -> usize {
+        self.words.iter().filter(|e| **e == WordStatus::Corrected).count()
+    }
+
+    /// Number of words that could not be repaired.
+    ///
+    /// ```
+    /// use rebite::ecc::{EccReport, WordStatus};
+    ///
+    /// let report = EccReport { words: vec![WordStatus::Corrected, WordStatus::Uncorrectable] };
+    /// assert_eq!(report.uncorrectable(), 1);
+    /// ```
+    pub fn uncorrectable(&self) -> usize {
+        self.words.iter().filter(|e| **e == WordStatus::Uncorrectable).count()
+    }
+
+    /// Whether no word had any error.
+    ///
+    /// ```
+    /// use rebite::ecc::{EccReport, WordStatus};
+    ///
+    /// // A repaired word still counts as an error
+    /// assert!(!EccReport { words: vec![WordStatus::Corrected] }.is_clean());
+    /// assert!(EccReport { words: Vec::new() }.is_clean());
+    /// ```
+    pub fn is_clean(&self) -> bool {
+        self.words.iter().all(|e| *e == WordStatus::Clean)
+    }
+}
+
+/// Encoded size for `len` data bytes, a trailing partial word is zero padded.
+///
+/// ```
+/// use rebite::ecc::secded_encoded_len;
+///
+/// assert_eq!(secded_encoded_len(8), 9);
+/// assert_eq!(secded_encoded_len(9), 18);
+/// assert_eq!(secded_encoded_len(0), 0);
+/// ```
+pub const fn secded_encoded_len(len: usize) -> usize {
+    len.div_ceil(ECC_DATA_LEN) * ECC_WORD_LEN
+}
+
+// Hamming position (3..=71, skipping powers of two) of each of the 64 data bits
+fn data_positions() -> [u8; 64] {
+    let mut positions = [0u8; 64];
+    let mut candidates = (3u8..).filter(|e| !e.is_power_of_two());
+
+    for position in positions.iter_mut() {
+        *position = candidates.next().unwrap();
+    }
+
+    positions
+}
+
+// Seven Hamming check bits of a data word
+fn hamming_checks(word: u64, positions: &[u8; 64]) -> u8 {
+    (0..64)
+        .filter(|bit| word >> bit & 1 == 1)
+        .fold(0, |acc, bit| acc ^ positions[bit])
+}
+
+// Check byte layout: bits 0..7 are the Hamming checks, bit 7 is the overall parity
+fn check_byte(word: u64, positions: &[u8; 64]) -> u8 {
+    let checks = hamming_checks(word, positions);
+    let parity = (word.count_ones() + checks.count_ones()) as u8 & 1;
+
+    checks | parity << 7
+}
+
+impl<'a> ByteString<'a> {
+
+    /// SECDED encode the logical bytes into `out` as Hamming(72, 64) words.
+    ///
+    /// Each group of eight data bytes is followed by one check byte, `out` needs
+    /// [`secded_encoded_len`] bytes. Bits are numbered LSB first within each byte.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, ecc::secded_encoded_len};
+    ///
+    /// let mut data = *b"rebite!";
+    /// let mut encoded = [0u8; secded_encoded_len(7)];
+    ///
+    /// // Seven data bytes are padded with a zero to fill the word
+    /// ByteString::new(&mut data).secded_encode_into(&mut ByteString::new(&mut encoded)).unwrap();
+    /// assert_eq!(encoded[..8], *b"rebite!\0");
+    /// ```
+    pub fn secded_encode_into(&self, out: &mut ByteString) -> Result<(), Error> {
+
+        if out.byte_len() < secded_encoded_len(self.byte_len()) {
+            return Err(Error::OutputTooSmall);
+        }
+
+        let positions = data_positions();
+        let data: Vec<u8> = self.iter().copied().collect();
+        let mut dst = out.iter_mut();
+
+        for chunk in data.chunks(ECC_DATA_LEN) {
+            let mut word = [0u8; ECC_DATA_LEN];
+            word[..chunk.len()].copy_from_slice(chunk);
+
+            // Zip from the bounded side so no destination byte is skipped
+            for (value, byte) in word.into_iter().zip(dst.by_ref()) {
+                *byte = value;
+            }
+
+            if let Some(byte) = dst.next() {
+                *byte = check_byte(u64::from_le_bytes(word), &positions);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Decode SECDED words from `input` into `self`, repairing single bit errors.
+    ///
+    /// Words with uncorrectable errors are still copied and flagged in the report.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, ecc::WordStatus};
+    ///
+    /// let mut data = *b"12345678";
+    /// let mut encoded = [0u8; 9];
+    /// ByteString::new(&mut data).secded_encode_into(&mut ByteString::new(&mut encoded)).unwrap();
+    ///
+    /// // One flipped bit is repaired
+    /// encoded[3] ^= 0x10;
+    /// let mut decoded = [0u8; 8];
+    /// let report = ByteString::new(&mut decoded).secded_decode_from(&ByteString::new(&mut encoded)).unwrap();
+    /// assert_eq!((report.words, decoded), (vec![WordStatus::Corrected], data));
+    ///
+    /// // A second one is detected but not repaired
+    /// encoded[5] ^= 0x01;
+    /// let report = ByteString::new(&mut decoded).secded_decode_from(&ByteString::new(&mut encoded)).unwrap();
+    /// assert_eq!(report.uncorrectable(), 1);
+    /// ```
+    pub fn secded_decode_from(&mut self, input: &ByteString) -> Result<EccReport, Error> {
+
+        if input.byte_len() < secded_encoded_len(self.byte_len()) {
+            return Err(Error::MalformedInput);
+        }
+
+        let positions = data_positions();
+        let encoded: Vec<u8> = input.iter().copied().collect();
+        let len = self.byte_len();
+        let mut dst = self.iter_mut();
+        let mut words = Vec::new();
+
+        for (ix, chunk) in encoded.chunks(ECC_WORD_LEN).take(len.div_ceil(ECC_DATA_LEN)).enumerate() {
+            let mut word = u64::from_le_bytes(chunk[..ECC_DATA_LEN].try_into().unwrap());
+            let received = chunk[ECC_DATA_LEN];
+
+            let syndrome = hamming_checks(word, &positions) ^ (received & 0x7F);
+            let odd = (word.count_ones() + received.count_ones()) & 1 == 1;
+
+            let status = match (syndrome, odd) {
+                (0, false) => WordStatus::Clean,
+                // Single error in the check byte itself
+                (s, true) if s == 0 || s.is_power_of_two() => WordStatus::Corrected,
+                (s, true) => match positions.iter().position(|e| *e == s) {
+                    Some(bit) => {
+                        word ^= 1 << bit;
+                        WordStatus::Corrected
+                    },
+                    None => WordStatus::Uncorrectable
+                },
+                (_, false) => WordStatus::Uncorrectable
+            };
+            words.push(status);
+
+            let keep = ECC_DATA_LEN.min(len - ix * ECC_DATA_LEN);
+            for (value, byte) in word.to_le_bytes().into_iter().take(keep).zip(dst.by_ref()) {
+                *byte = value;
+            }
+        }
+
+        Ok(EccReport { words })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::XorShift;
+
+    fn encode(data: &[u8]) -> Vec<u8> {
+        let mut raw = data.to_vec();
+        let mut out = vec![0u8; secded_encoded_len(data.len())];
+
+        ByteString::new(&mut raw).secded_encode_into(&mut ByteString::new(&mut out)).unwrap();
+        out
+    }
+
+    fn decode(encoded: &[u8], len: usize) -> (Vec<u8>, EccReport) {
+        let mut raw = encoded.to_vec();
+        let mut out = vec![0u8; len];
+
+        let report = ByteString::new(&mut out).secded_decode_from(&ByteString::new(&mut raw)).unwrap();
+        (out, report)
+    }
+
+    #[test]
+    fn test_secded_clean_round_trip() {
+        let mut rng = XorShift::new(0x212);
+
+        for _ in 0..200 {
+            let mut data = vec![0u8; rng.below(40)];
+            rng.fill(&mut data);
+
+            let (decoded, report) = decode(&encode(&data), data.len());
+            assert_eq!(decoded, data);
+            assert!(report.is_clean());
+            assert_eq!(report.words.len(), data.len().div_ceil(ECC_DATA_LEN));
+        }
+    }
+
+    #[test]
+    fn test_secded_single_bit_errors() {
+        let data = [0xDEu8, 0xAD, 0xBE, 0xEF, 0x01, 0x23, 0x45, 0x67, 0x89, 0xAB];
+        let encoded = encode(&data);
+
+        for bit in 0..ECC_WORD_LEN * 8 {
+            let mut corrupted = encoded.clone();
+            corrupted[ECC_WORD_LEN + bit / 8] ^= 1 << (bit % 8);
+
+            let (decoded, report) = decode(&corrupted, data.len());
+            assert_eq!(decoded, data, "bit {}", bit);
+            assert_eq!(report.words, [WordStatus::Clean, WordStatus::Corrected]);
+        }
+    }
+
+    #[test]
+    fn test_secded_double_bit_errors() {
+        let data = [0x5Au8; 8];
+        let encoded = encode(&data);
+
+        for a in 0..ECC_WORD_LEN * 8 {
+            for b in a + 1..ECC_WORD_LEN * 8 {
+                let mut corrupted = encoded.clone();
+                corrupted[a / 8] ^= 1 << (a % 8);
+                corrupted[b / 8] ^= 1 << (b % 8);
+
+                let (_, report) = decode(&corrupted, data.len());
+                assert_eq!(report.uncorrectable(), 1, "bits {} {}", a, b);
+                assert_eq!(report.corrected(), 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_secded_reversed_interpretation() {
+        let mut data = [1u8, 2, 3, 4, 5, 6, 7, 8, 9];
+        let mut bytes = ByteString::new(&mut data);
+        bytes.interpret_reverse_endian();
+
+        let mut out = [0u8; 18];
+        bytes.secded_encode_into(&mut ByteString::new(&mut out)).unwrap();
+        assert_eq!(out.to_vec(), encode(&[9, 8, 7, 6, 5, 4, 3, 2, 1]));
+    }
+
+    #[test]
+    fn test_secded_size_errors() {
+        let mut data = [0u8; 9];
+        let mut out = [0u8; 17];
+        let bytes = ByteString::new(&mut data);
+
+        assert_eq!(bytes.secded_encode_into(&mut ByteString::new(&mut out)), Err(Error::OutputTooSmall));
+
+        let mut dst = [0u8; 9];
+        assert_eq!(ByteString::new(&mut dst).secded_decode_from(&ByteString::new(&mut out)), Err(Error::MalformedInput));
+    }
+}
@@ -0,0 +1,580 @@
+//! Error types of the fallible operations
+
+use std::fmt;
+
+/// General error for fallible ByteString operations.
+///
+/// ```
+/// use rebite::{bytes::ByteString, error::Error, typed::Endianness};
+///
+/// let mut raw = [0u8; 2];
+/// let mut bytes = ByteString::new(&mut raw);
+///
+/// let err = bytes.write_u32_at(0, 1, Endianness::Big).unwrap_err();
+/// assert_eq!(err, Error::OutOfBounds);
+/// assert_eq!(err.to_string(), "range out of bounds");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The output buffer cannot hold the result
+    OutputTooSmall,
+    /// The radix is outside of 2..=36
+    InvalidRadix(u32),
+    /// The requested logical range does not lie within the buffer
+    OutOfBounds,
+    /// The input is not a valid encoding
+    MalformedInput,
+    /// A size argument that must be nonzero was zero
+    ZeroLength,
+    /// The operation needs a buffer whose length is a power of two
+    LengthNotPowerOfTwo,
+    /// Reading from or writing to an I/O stream failed
+    Io(std::io::ErrorKind),
+    /// Two windows that must have the same length do not
+    LengthMismatch,
+    /// Two regions that must be disjoint share the positions `start..end`
+    Overlap {
+        /// First shared position
+        start: usize,
+        /// One past the last shared position
+        end: usize,
+    },
+    /// No field with the requested name exists
+    UnknownField,
+    /// The divisor is zero
+    DivisionByZero,
+    /// The value would be above the bound of a [`BoundedBytes`](crate::bounded::BoundedBytes)
+    ExceedsBound,
+    /// The write would touch the locked logical range `start..end` of a [`GuardedByteString`](crate::guard::GuardedByteString)
+    ProtectedRegion {
+        /// First locked position
+        start: usize,
+        /// One past the last locked position
+        end: usize,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::OutputTooSmall => write!(f, "output buffer too small"),
+            Error::InvalidRadix(radix) => write!(f, "invalid radix {}", radix),
+            Error::OutOfBounds => write!(f, "range out of bounds"),
+            Error::MalformedInput => write!(f, "malformed input"),
+            Error::ZeroLength => write!(f, "length must be nonzero"),
+            Error::LengthNotPowerOfTwo => write!(f, "length must be a power of two"),
+            Error::Io(kind) => write!(f, "i/o error: {}", kind),
+            Error::LengthMismatch => write!(f, "length mismatch"),
+            Error::Overlap { start, end } => write!(f, "regions overlap at {}..{}", start, end),
+            Error::UnknownField => write!(f, "unknown field"),
+            Error::DivisionByZero => write!(f, "division by zero"),
+            Error::ExceedsBound => write!(f, "value exceeds bound"),
+            Error::ProtectedRegion { start, end } => write!(f, "write touches protected region {}..{}", start, end),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err.kind())
+    }
+}
+
+/// Nonzero bits would have been lost by the operation.
+///
+/// ```
+/// use rebite::{bytes::ByteString, error::{Overflow}};
+///
+/// let mut raw = [0x01u8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+/// let bytes = ByteString::new(&mut raw);
+///
+/// assert_eq!(bytes.to_u64(), Err(Overflow));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "value overflows buffer")
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+/// A source buffer did not have the required byte length.
+///
+/// ```
+/// use rebite::{bytes::ByteString, error::{LengthError}};
+///
+/// let (mut a, mut b) = ([0u8; 2], [0u8; 3]);
+/// let b = ByteString::new(&mut b);
+///
+/// let err = ByteString::new(&mut a).assign_xor_of([&b]).unwrap_err();
+/// assert_eq!(err, LengthError { index: 0, expected: 2, actual: 3 });
+/// assert_eq!(err.to_string(), "source 0 has length 3, expected 2");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LengthError {
+    /// Position of the offending source in the input sequence
+    pub index: usize,
+    /// Length every source must have
+    pub expected: usize,
+    /// Length of the offending source
+    pub actual: usize,
+}
+
+impl fmt::Display for LengthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "source {} has length {}, expected {}", self.index, self.actual, self.expected)
+    }
+}
+
+impl std::error::Error for LengthError {}
+
+/// A logical region `offset..offset + len` does not lie within the buffer.
+///
+/// ```
+/// use rebite::{bytes::ByteString, error::{RegionError}};
+///
+/// let mut raw = [0u8; 4];
+/// let bytes = ByteString::new(&mut raw);
+///
+/// let err = bytes.check_region(3, 2).unwrap_err();
+/// assert_eq!(err, RegionError { offset: 3, len: 2, byte_len: 4 });
+/// assert_eq!(err.to_string(), "region of 2 bytes at 3 overruns the 4 byte buffer by 1 bytes");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegionError {
+    /// Logical offset of the first byte of the region
+    pub offset: usize,
+    /// Length of the region in bytes
+    pub len: usize,
+    /// Byte length of the buffer the region was checked against
+    pub byte_len: usize,
+}
+
+impl RegionError {
+    /// Number of bytes the region extends past the end of the buffer, saturating if its end overflows.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0u8; 8];
+    /// let error = ByteString::new(&mut raw).check_region(6, 4).unwrap_err();
+    /// assert_eq!(error.overrun(), 2);
+    ///
+    /// let error = ByteString::new(&mut raw).check_region(usize::MAX, 2).unwrap_err();
+    /// assert_eq!(error.overrun(), usize::MAX - 8);
+    /// ```
+    pub fn overrun(&self) -> usize {
+        self.offset.saturating_add(self.len) - self.byte_len
+    }
+}
+
+impl fmt::Display for RegionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "region of {} bytes at {} overruns the {} byte buffer by {} bytes", self.len, self.offset, self.byte_len, self.overrun())
+    }
+}
+
+impl std::error::Error for RegionError {}
+
+impl From<RegionError> for Error {
+    fn from(_: RegionError) -> Self {
+        Error::OutOfBounds
+    }
+}
+
+/// Why a patch list was rejected.
+///
+/// ```
+/// use rebite::{bytes::ByteString, error::PatchErrorKind, patch::OverlapPolicy};
+///
+/// let mut raw = [0u8; 4];
+/// let bytes = ByteString::new(&mut raw);
+///
+/// let err = bytes.check_patches(&[(0, &[1]), (4, &[1])], OverlapPolicy::Reject).unwrap_err();
+/// assert!(matches!(err.kind, PatchErrorKind::OutOfBounds(region) if region.offset == 4));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatchErrorKind {
+    /// The patch does not lie within the buffer
+    OutOfBounds(RegionError),
+    /// The patch overlaps the earlier patch with this index
+    Overlaps(usize),
+}
+
+/// A patch list failed validation at the patch with position `index` in the list.
+///
+/// ```
+/// use rebite::{bytes::ByteString, error::{PatchError, PatchErrorKind}, patch::OverlapPolicy};
+///
+/// let mut raw = [0u8; 4];
+/// let bytes = ByteString::new(&mut raw);
+///
+/// let err = bytes.check_patches(&[(0, &[1, 2]), (1, &[3])], OverlapPolicy::Reject).unwrap_err();
+/// assert_eq!(err, PatchError { index: 1, kind: PatchErrorKind::Overlaps(0) });
+/// assert_eq!(err.to_string(), "patch 1 overlaps patch 0");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PatchError {
+    /// Position of the offending patch in the list
+    pub index: usize,
+    /// Why the patch was rejected
+    pub kind: PatchErrorKind,
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            PatchErrorKind::OutOfBounds(region) => write!(f, "patch {}: {}", self.index, region),
+            PatchErrorKind::Overlaps(other) => write!(f, "patch {} overlaps patch {}", self.index, other),
+        }
+    }
+}
+
+impl std::error::Error for PatchError {}
+
+/// A scratch arena cannot satisfy an allocation.
+///
+/// ```
+/// use rebite::error::OutOfScratch;
+///
+/// let err = OutOfScratch { requested: 5, available: 4 };
+/// assert_eq!(err.to_string(), "scratch exhausted: requested 5 bytes, 4 available");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfScratch {
+    /// Bytes asked for
+    pub requested: usize,
+    /// Bytes left in the arena
+    pub available: usize,
+}
+
+impl fmt::Display for OutOfScratch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "scratch exhausted: requested {} bytes, {} available", self.requested, self.available)
+    }
+}
+
+impl std::error::Error for OutOfScratch {}
+
+/// A write cursor has fewer bytes left than a write needs.
+///
+/// ```
+/// use rebite::{bytes::ByteString, error::OutOfSpace, writer::ByteStringWriter};
+///
+/// let mut raw = [0u8; 1];
+/// let mut bytes = ByteString::new(&mut raw);
+/// let mut writer = ByteStringWriter::new(&mut bytes);
+///
+/// let err = writer.write_slice(&[1, 2]).unwrap_err();
+/// assert_eq!(err, OutOfSpace { needed: 2, remaining: 1 });
+/// assert_eq!(err.to_string(), "out of space: needed 2 bytes, 1 remaining");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfSpace {
+    /// Bytes the write needs
+    pub needed: usize,
+    /// Bytes left after the cursor
+    pub remaining: usize,
+}
+
+impl fmt::Display for OutOfSpace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "out of space: needed {} bytes, {} remaining", self.needed, self.remaining)
+    }
+}
+
+impl std::error::Error for OutOfSpace {}
+
+impl From<OutOfSpace> for Error {
+    fn from(_: OutOfSpace) -> Self {
+        Error::OutputTooSmall
+    }
+}
+
+/// Why a [`FieldReader`](crate::reader::FieldReader) read failed.
+///
+/// ```
+/// use rebite::{bytes::ByteString, error::ReadErrorKind, reader::FieldReader};
+///
+/// let mut raw = [0u8; 2];
+/// let bytes = ByteString::new(&mut raw);
+/// let mut r = FieldReader::new(&bytes);
+///
+/// assert_eq!(r.take_bits(65).unwrap_err().kind, ReadErrorKind::TooWide(65));
+/// assert_eq!(r.take_bits(17).unwrap_err().kind, ReadErrorKind::Exhausted { needed: 17, remaining: 16 });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadErrorKind {
+    /// The field needs more bits than are left
+    Exhausted {
+        /// Bits the field needs
+        needed: usize,
+        /// Bits left from where the field would start
+        remaining: usize,
+    },
+    /// A bit field wider than 64 bits was requested
+    TooWide(usize),
+}
+
+/// A field read failed at logical bit `position`, tagged with the reader's current context.
+///
+/// ```
+/// use rebite::{bytes::ByteString, reader::FieldReader};
+///
+/// let mut raw = [0u8; 2];
+/// let bytes = ByteString::new(&mut raw);
+/// let mut r = FieldReader::new(&bytes);
+///
+/// r.take_bits(4).unwrap();
+/// let err = r.context("flags").take_bits(16).unwrap_err();
+/// assert_eq!((err.position, err.context), (4, Some("flags")));
+/// assert_eq!(err.to_string(), "flags: needed 16 bits at bit 4, 12 remaining");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadError {
+    /// Logical bit offset where the field would start
+    pub position: usize,
+    /// Why the read failed
+    pub kind: ReadErrorKind,
+    /// Name set with [`FieldReader::context`](crate::reader::FieldReader::context) before the read
+    pub context: Option<&'static str>,
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(context) = self.context {
+            write!(f, "{}: ", context)?;
+        }
+
+        match self.kind {
+            ReadErrorKind::Exhausted { needed, remaining } => write!(f, "needed {} bits at bit {}, {} remaining", needed, self.position, remaining),
+            ReadErrorKind::TooWide(width) => write!(f, "bit field of {} bits at bit {} is wider than 64", width, self.position),
+        }
+    }
+}
+
+impl std::error::Error for ReadError {}
+
+/// Why a [`FieldWriter`](crate::writer::FieldWriter) append failed.
+///
+/// ```
+/// use rebite::{bytes::ByteString, error::WriteErrorKind, writer::FieldWriter};
+///
+/// let mut raw = [0u8; 2];
+/// let mut bytes = ByteString::new(&mut raw);
+/// let mut w = FieldWriter::new(&mut bytes);
+///
+/// assert_eq!(w.put_bits(0, 65).unwrap_err().kind, WriteErrorKind::TooWide(65));
+/// assert_eq!(w.put_bits(0, 17).unwrap_err().kind, WriteErrorKind::Full { needed: 17, remaining: 16 });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteErrorKind {
+    /// The field needs more bits than are left
+    Full {
+        /// Bits the field needs
+        needed: usize,
+        /// Bits left from where the field would start
+        remaining: usize,
+    },
+    /// A bit field wider than 64 bits was given
+    TooWide(usize),
+}
+
+/// A field append failed at logical bit `position`.
+///
+/// ```
+/// use rebite::{bytes::ByteString, typed::Endianness, writer::FieldWriter};
+///
+/// let mut raw = [0u8; 2];
+/// let mut bytes = ByteString::new(&mut raw);
+/// let mut w = FieldWriter::new(&mut bytes);
+///
+/// w.put_bits(0, 4).unwrap();
+/// let err = w.put_u16(0, Endianness::Big).unwrap_err();
+/// assert_eq!(err.position, 8);
+/// assert_eq!(err.to_string(), "needed 16 bits at bit 8, 8 remaining");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteError {
+    /// Logical bit offset where the field would start
+    pub position: usize,
+    /// Why the append failed
+    pub kind: WriteErrorKind,
+}
+
+impl fmt::Display for WriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            WriteErrorKind::Full { needed, remaining } => write!(f, "needed {} bits at bit {}, {} remaining", needed, self.position, remaining),
+            WriteErrorKind::TooWide(width) => write!(f, "bit field of {} bits at bit {} is wider than 64", width, self.position),
+        }
+    }
+}
+
+impl std::error::Error for WriteError {}
+
+impl From<WriteError> for Error {
+    fn from(err: WriteError) -> Self {
+        match err.kind {
+            WriteErrorKind::Full { .. } => Error::OutputTooSmall,
+            WriteErrorKind::TooWide(_) => Error::OutOfBounds,
+        }
+    }
+}
+
+/// Why [`ByteString::resync`](crate::bytes::ByteString::resync) found nothing to align to.
+///
+/// ```
+/// use rebite::{bytes::ByteString, error::{SyncError}};
+///
+/// let (mut raw, mut sync) = ([0x00u8, 0x0A], [0xABu8]);
+/// let mut bytes = ByteString::new(&mut raw);
+/// let sync = ByteString::new(&mut sync);
+///
+/// // 0xA at the very end could be the start of 0xAB
+/// assert_eq!(bytes.resync(&sync, 8), Err(SyncError::NotFound { partial_at: Some(12) }));
+/// assert_eq!(bytes.resync(&sync, 9), Err(SyncError::InvalidPattern));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncError {
+    /// The sync length is zero or longer than the sync word
+    InvalidPattern,
+    /// No complete occurrence of the sync pattern
+    NotFound {
+        /// Stream offset of the earliest prefix of the pattern that runs into the end of the buffer
+        partial_at: Option<usize>,
+    },
+}
+
+impl fmt::Display for SyncError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SyncError::InvalidPattern => write!(f, "invalid sync pattern"),
+            SyncError::NotFound { partial_at: None } => write!(f, "sync pattern not found"),
+            SyncError::NotFound { partial_at: Some(offset) } => write!(f, "sync pattern not found, partial match at bit {}", offset),
+        }
+    }
+}
+
+impl std::error::Error for SyncError {}
+
+/// Reasons an exported frame cannot be imported.
+///
+/// ```
+/// use rebite::{bytes::ByteString, error::{ImportError}};
+///
+/// let mut storage = [0u8; 4];
+///
+/// assert!(matches!(ByteString::import(b"RBTS", &mut storage), Err(ImportError::Truncated)));
+/// assert!(matches!(ByteString::import(b"XXXX\x01\x00\x00\x00\x00\x00\x00", &mut storage), Err(ImportError::BadMagic)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportError {
+    /// The input ends before the header or payload is complete
+    Truncated,
+    /// The frame does not start with the export magic
+    BadMagic,
+    /// The frame was written by a format version this build cannot read
+    UnsupportedVersion(u8),
+    /// The flags byte has bits set that this version does not know
+    UnknownFlags(u8),
+    /// The storage cannot hold the payload
+    StorageTooSmall,
+    /// The payload does not match the checksum in the header
+    ChecksumMismatch,
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Truncated => write!(f, "truncated frame"),
+            ImportError::BadMagic => write!(f, "bad magic"),
+            ImportError::UnsupportedVersion(version) => write!(f, "unsupported version {}", version),
+            ImportError::UnknownFlags(flags) => write!(f, "unknown flags {:#04x}", flags),
+            ImportError::StorageTooSmall => write!(f, "storage too small"),
+            ImportError::ChecksumMismatch => write!(f, "checksum mismatch"),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+/// What went wrong while parsing text into a ByteString.
+///
+/// ```
+/// use rebite::{bytes::ByteString, error::{ParseErrorKind}};
+///
+/// let mut raw = [0u8; 1];
+/// let mut bytes = ByteString::new(&mut raw);
+///
+/// assert_eq!(bytes.assign_from_radix_str("", 10).unwrap_err().kind, ParseErrorKind::Empty);
+/// assert_eq!(bytes.assign_from_radix_str("1g", 16).unwrap_err().kind, ParseErrorKind::InvalidDigit('g'));
+/// assert_eq!(bytes.assign_from_radix_str("256", 10).unwrap_err().kind, ParseErrorKind::Overflow);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The input contained no digits
+    Empty,
+    /// The character is not a digit of the requested notation
+    InvalidDigit(char),
+    /// The value does not fit into the destination
+    Overflow,
+    /// The radix is outside of 2..=36
+    InvalidRadix(u32),
+    /// The input does not continue as the notation requires, holds what was expected instead
+    Expected(&'static str),
+}
+
+/// Parse failure with the byte position in the input where it occurred.
+///
+/// ```
+/// use rebite::{bytes::ByteString, error::{ParseError, ParseErrorKind}};
+///
+/// let mut raw = [0u8; 2];
+/// let mut bytes = ByteString::new(&mut raw);
+///
+/// let err = bytes.assign_from_colon_hex("de:xd").unwrap_err();
+/// assert_eq!(err, ParseError { position: 3, kind: ParseErrorKind::InvalidDigit('x') });
+/// assert_eq!(err.to_string(), "invalid digit 'x' at position 3");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseError {
+    /// Byte position in the input
+    pub position: usize,
+    /// What went wrong
+    pub kind: ParseErrorKind,
+}
+
+impl ParseError {
+    /// Error of `kind` at byte `position` of the input.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::{ParseError, ParseErrorKind}};
+    ///
+    /// let mut raw = [0u8; 2];
+    /// let result = ByteString::new(&mut raw).assign_from_radix_str("12g4", 16);
+    /// assert_eq!(result, Err(ParseError::new(2, ParseErrorKind::InvalidDigit('g'))));
+    /// ```
+    pub fn new(position: usize, kind: ParseErrorKind) -> Self {
+        Self { position, kind }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ParseErrorKind::Empty => write!(f, "empty input"),
+            ParseErrorKind::InvalidDigit(c) => write!(f, "invalid digit {:?} at position {}", c, self.position),
+            ParseErrorKind::Overflow => write!(f, "value overflows destination at position {}", self.position),
+            ParseErrorKind::InvalidRadix(radix) => write!(f, "invalid radix {}", radix),
+            ParseErrorKind::Expected(what) => write!(f, "expected {} at position {}", what, self.position),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
@@ -0,0 +1,233 @@
+//! A self describing binary frame for persisting a ByteString
+
+use crate::{
+    bytes::ByteString,
+    checksum::{Checksum, Crc32},
+    error::{Error, ImportError}
+};
+
+/// First bytes of every exported frame.
+///
+/// ```
+/// use rebite::{bytes::ByteString, export::{EXPORT_HEADER_LEN, EXPORT_MAGIC, EXPORT_VERSION}};
+///
+/// let mut raw = [0u8; 1];
+/// let mut frame = [0u8; EXPORT_HEADER_LEN + 1];
+/// ByteString::new(&mut raw).export_into(&mut frame).unwrap();
+///
+/// assert_eq!(frame[..4], EXPORT_MAGIC);
+/// ```
+pub const EXPORT_MAGIC: [u8; 4] = *b"RBTS";
+
+/// Frame layout version written by [`ByteString::export_into`].
+///
+/// ```
+/// use rebite::{bytes::ByteString, export::{EXPORT_HEADER_LEN, EXPORT_MAGIC, EXPORT_VERSION}};
+///
+/// let mut raw = [0u8; 1];
+/// let mut frame = [0u8; EXPORT_HEADER_LEN + 1];
+/// ByteString::new(&mut raw).export_into(&mut frame).unwrap();
+///
+/// assert_eq!(frame[4], EXPORT_VERSION);
+/// ```
+pub const EXPORT_VERSION: u8 = 1;
+
+/// Magic, version, flags, u32 LE payload length and checksum byte.
+///
+/// ```
+/// use rebite::{bytes::ByteString, export::{EXPORT_HEADER_LEN, EXPORT_MAGIC, EXPORT_VERSION}};
+///
+/// let mut raw = [0u8; 5];
+/// let mut frame = [0u8; 64];
+///
+/// assert_eq!(ByteString::new(&mut raw).export_into(&mut frame), Ok(EXPORT_HEADER_LEN + 5));
+/// ```
+pub const EXPORT_HEADER_LEN: usize = 4 + 1 + 1 + 4 + 1;
+
+const FLAG_REVERSE_ENDIAN: u8 = 0b1;
+
+// Low byte of the CRC-32 over the header fields before it and the payload
+fn frame_check(header: &[u8], payload: &[u8]) -> u8 {
+    let mut crc = Crc32::default();
+    crc.update_slice(header);
+    crc.update_slice(payload);
+    crc.finalize() as u8
+}
+
+impl<'a> ByteString<'a> {
+
+    /// Serialize the physical bytes and interpretation into a self describing frame.
+    ///
+    /// `out` needs [`EXPORT_HEADER_LEN`] plus `byte_len()` bytes, returns the frame length.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error, export::EXPORT_HEADER_LEN};
+    ///
+    /// let mut raw = [0x01u8, 0x02];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    ///
+    /// // The payload is the physical bytes, the flag travels in the header
+    /// let mut frame = [0u8; EXPORT_HEADER_LEN + 2];
+    /// assert_eq!(bytes.export_into(&mut frame), Ok(13));
+    /// assert_eq!(frame[..6], [b'R', b'B', b'T', b'S', 1, 1]);
+    /// assert_eq!(frame[11..], [0x01, 0x02]);
+    ///
+    /// assert_eq!(bytes.export_into(&mut frame[..12]), Err(Error::OutputTooSmall));
+    /// ```
+    pub fn export_into(&self, out: &mut [u8]) -> Result<usize, Error> {
+
+        let total = EXPORT_HEADER_LEN + self.byte_len();
+        let len = u32::try_from(self.byte_len()).map_err(|_| Error::OutOfBounds)?;
+
+        if out.len() < total {
+            return Err(Error::OutputTooSmall);
+        }
+
+        let flags = if self.interpret_reverse_endian { FLAG_REVERSE_ENDIAN } else { 0 };
+
+        let (header, payload) = out[..total].split_at_mut(EXPORT_HEADER_LEN);
+        header[..4].copy_from_slice(&EXPORT_MAGIC);
+        header[4] = EXPORT_VERSION;
+        header[5] = flags;
+        header[6..10].copy_from_slice(&len.to_le_bytes());
+        payload.copy_from_slice(self.bytes);
+        header[10] = frame_check(&header[..10], payload);
+
+        Ok(total)
+    }
+
+    /// Rebuild a ByteString from a frame written by [`ByteString::export_into`].
+    ///
+    /// The payload is copied into the front of `storage`, which backs the returned string.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::ImportError};
+    ///
+    /// let mut raw = [0x01u8, 0x02];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    /// let mut frame = [0u8; 13];
+    /// bytes.export_into(&mut frame).unwrap();
+    ///
+    /// let mut storage = [0u8; 4];
+    /// let imported = ByteString::import(&frame, &mut storage).unwrap();
+    /// assert_eq!(imported.to_u64(), Ok(0x0201));
+    ///
+    /// // Any damage shows up as a checksum mismatch
+    /// frame[12] ^= 0x40;
+    /// assert!(matches!(ByteString::import(&frame, &mut storage), Err(ImportError::ChecksumMismatch)));
+    /// ```
+    pub fn import(src: &[u8], storage: &'a mut [u8]) -> Result<ByteString<'a>, ImportError> {
+
+        let header = src.get(..EXPORT_HEADER_LEN).ok_or(ImportError::Truncated)?;
+
+        if header[..4] != EXPORT_MAGIC {
+            return Err(ImportError::BadMagic);
+        }
+
+        if header[4] != EXPORT_VERSION {
+            return Err(ImportError::UnsupportedVersion(header[4]));
+        }
+
+        let flags = header[5];
+        if flags & !FLAG_REVERSE_ENDIAN != 0 {
+            return Err(ImportError::UnknownFlags(flags));
+        }
+
+        let len = u32::from_le_bytes(header[6..10].try_into().unwrap()) as usize;
+        let payload = src[EXPORT_HEADER_LEN..].get(..len).ok_or(ImportError::Truncated)?;
+
+        if frame_check(&header[..10], payload) != header[10] {
+            return Err(ImportError::ChecksumMismatch);
+        }
+
+        let storage = storage.get_mut(..len).ok_or(ImportError::StorageTooSmall)?;
+        storage.copy_from_slice(payload);
+
+        let mut bytes = ByteString::new(storage);
+        if flags & FLAG_REVERSE_ENDIAN != 0 {
+            bytes.interpret_reverse_endian();
+        }
+
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::{view, XorShift};
+
+    fn export(raw: &mut [u8], reversed: bool) -> Vec<u8> {
+        let bytes = view(raw, reversed);
+
+        let mut out = vec![0u8; EXPORT_HEADER_LEN + bytes.byte_len()];
+        let n = bytes.export_into(&mut out).unwrap();
+        assert_eq!(n, out.len());
+        out
+    }
+
+    #[test]
+    fn test_export_round_trip() {
+        let mut rng = XorShift::new(0x215);
+
+        for _ in 0..100 {
+            let mut raw = vec![0u8; rng.below(64)];
+            rng.fill(&mut raw);
+            let reversed = rng.next_bool();
+
+            let frame = export(&mut raw.clone(), reversed);
+
+            let mut storage = vec![0u8; raw.len() + rng.below(4)];
+            let imported = ByteString::import(&frame, &mut storage).unwrap();
+
+            assert_eq!(imported.bytes(), &raw[..]);
+            assert_eq!(imported.interpret_reverse_endian, reversed);
+        }
+    }
+
+    #[test]
+    fn test_export_layout() {
+        let frame = export(&mut [0xAA, 0xBB], true);
+
+        assert_eq!(&frame[..4], b"RBTS");
+        assert_eq!(frame[4], EXPORT_VERSION);
+        assert_eq!(frame[5], 1);
+        assert_eq!(&frame[6..10], [2, 0, 0, 0]);
+        assert_eq!(&frame[11..], [0xAA, 0xBB]);
+    }
+
+    #[test]
+    fn test_import_errors() {
+        let frame = export(&mut [1, 2, 3, 4], false);
+        let mut storage = [0u8; 4];
+
+        for len in 0..frame.len() {
+            assert_eq!(ByteString::import(&frame[..len], &mut storage).err(), Some(ImportError::Truncated));
+        }
+
+        let mut bad = frame.clone();
+        bad[0] = b'X';
+        assert_eq!(ByteString::import(&bad, &mut storage).err(), Some(ImportError::BadMagic));
+
+        let mut bad = frame.clone();
+        bad[4] = 2;
+        assert_eq!(ByteString::import(&bad, &mut storage).err(), Some(ImportError::UnsupportedVersion(2)));
+
+        let mut bad = frame.clone();
+        bad[5] = 0x80;
+        assert_eq!(ByteString::import(&bad, &mut storage).err(), Some(ImportError::UnknownFlags(0x80)));
+
+        let mut bad = frame.clone();
+        bad[12] ^= 0x10;
+        assert_eq!(ByteString::import(&bad, &mut storage).err(), Some(ImportError::ChecksumMismatch));
+
+        let mut small = [0u8; 3];
+        assert_eq!(ByteString::import(&frame, &mut small).err(), Some(ImportError::StorageTooSmall));
+
+        let mut out = [0u8; 14];
+        assert_eq!(ByteString::new(&mut [1, 2, 3, 4]).export_into(&mut out), Err(Error::OutputTooSmall));
+    }
+}
@@ -0,0 +1,296 @@
+//! Scaling by powers of two for fixed point values
+
+use crate::{
+    bytes::ByteString,
+    error::Overflow
+};
+
+/// How bits discarded by a scaling right shift affect the result.
+///
+/// ```
+/// use rebite::{bytes::ByteString, fixed::Rounding};
+///
+/// // 0b1011 / 4 is 2.75
+/// let scale = |mode| {
+///     let mut raw = [0b1011u8];
+///     ByteString::new(&mut raw).scale_shr_round(2, mode);
+///     raw[0]
+/// };
+///
+/// assert_eq!([scale(Rounding::Floor), scale(Rounding::Ceil), scale(Rounding::Nearest)], [2, 3, 3]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    /// Toward negative infinity, the discarded bits are dropped. For unsigned values this is toward zero.
+    Floor,
+    /// Toward positive infinity if any discarded bit is set
+    Ceil,
+    /// To the nearest value, ties go to the even result
+    Nearest,
+}
+
+impl<'a> ByteString<'a> {
+
+    /// Divide the unsigned value by 2^n, rounding according to `mode`.
+    ///
+    /// The top bit counts as a magnitude bit, see [`ByteString::scale_sar_round`] for two's complement values.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, fixed::Rounding};
+    ///
+    /// // 10 / 4 = 2.5 and 14 / 4 = 3.5, ties round to the even result
+    /// let scale = |value: u8, mode| {
+    ///     let mut raw = [value];
+    ///     ByteString::new(&mut raw).scale_shr_round(2, mode);
+    ///     raw[0]
+    /// };
+    ///
+    /// assert_eq!([scale(10, Rounding::Floor), scale(10, Rounding::Ceil), scale(10, Rounding::Nearest)], [2, 3, 2]);
+    /// assert_eq!(scale(14, Rounding::Nearest), 4);
+    /// assert_eq!(scale(1, Rounding::Ceil), 1);
+    /// ```
+    pub fn scale_shr_round(&mut self, n: usize, mode: Rounding) {
+        self.scale_round(n, mode, false);
+    }
+
+    /// Divide the two's complement value by 2^n, rounding according to `mode`.
+    ///
+    /// [`Rounding::Floor`] goes toward negative infinity like an arithmetic shift, so -5 / 2 gives -3.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, fixed::Rounding};
+    ///
+    /// // -10 / 4 = -2.5 and -14 / 4 = -3.5, ties round to the even result
+    /// let scale = |value: i8, mode| {
+    ///     let mut raw = [value as u8];
+    ///     ByteString::new(&mut raw).scale_sar_round(2, mode);
+    ///     raw[0] as i8
+    /// };
+    ///
+    /// assert_eq!([scale(-10, Rounding::Floor), scale(-10, Rounding::Ceil), scale(-10, Rounding::Nearest)], [-3, -2, -2]);
+    /// assert_eq!(scale(-14, Rounding::Nearest), -4);
+    /// assert_eq!(scale(-1, Rounding::Ceil), 0);
+    /// ```
+    pub fn scale_sar_round(&mut self, n: usize, mode: Rounding) {
+        let negative = self.iter().next().is_some_and(|e| e & 0x80 != 0);
+        self.scale_round(n, mode, negative);
+    }
+
+    fn scale_round(&mut self, n: usize, mode: Rounding, negative: bool) {
+
+        if n == 0 {
+            return;
+        }
+
+        // Highest discarded bit decides the half, everything below it is the sticky bit.
+        // Bits past the top are copies of the sign.
+        let round = if n <= self.bit_len() { self.bit(n - 1) } else { negative };
+        let sticky = (0..(n - 1).min(self.bit_len())).any(|offset| self.bit(offset)) || (negative && n > self.bit_len() + 1);
+
+        if negative { self.sar_assign(n); } else { self.shr_bits(n); }
+
+        let round_up = match mode {
+            Rounding::Floor => false,
+            Rounding::Ceil => round || sticky,
+            Rounding::Nearest => round && (sticky || self.bit(0))
+        };
+
+        // The shifted value has at least one leading copy of the sign bit, so this cannot overflow.
+        // A negative value rounding up to zero wraps from all ones, which drops the carry.
+        if round_up {
+            self.mul_add_small(1, 1);
+        }
+    }
+
+    /// Multiply the unsigned value by 2^n, failing without modification if a set bit would be shifted out.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Overflow};
+    ///
+    /// let mut raw = [0x00u8, 0x30];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.scale_shl_checked(10), Ok(()));
+    /// assert_eq!(bytes.to_u64(), Ok(0xC000));
+    /// assert_eq!(bytes.scale_shl_checked(1), Err(Overflow));
+    /// assert_eq!(bytes.to_u64(), Ok(0xC000));
+    ///
+    /// // Shifting a zero value never overflows, however far
+    /// bytes.set_zero();
+    /// assert_eq!(bytes.scale_shl_checked(100), Ok(()));
+    /// ```
+    pub fn scale_shl_checked(&mut self, n: usize) -> Result<(), Overflow> {
+
+        let lost = self.bit_len().saturating_sub(n);
+        if (lost..self.bit_len()).any(|offset| self.bit(offset)) {
+            return Err(Overflow);
+        }
+
+        *self <<= n;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::{view_logical, XorShift};
+
+    fn reference_shr(value: u128, n: usize, mode: Rounding) -> u128 {
+        if n == 0 { return value; }
+
+        let q = if n >= 128 { 0 } else { value >> n };
+        let rem = if n >= 128 { value } else { value & ((1u128 << n) - 1) };
+
+        match mode {
+            Rounding::Floor => q,
+            Rounding::Ceil => q + (rem != 0) as u128,
+            // Beyond the width even the half bit is an implicit zero
+            Rounding::Nearest if n > 128 => q,
+            Rounding::Nearest => {
+                let half = 1u128 << (n - 1);
+                q + (rem > half || (rem == half && q & 1 == 1)) as u128
+            }
+        }
+    }
+
+    fn reference_sar(value: i128, n: usize, mode: Rounding) -> i128 {
+        if n == 0 { return value; }
+
+        // Beyond the width the quotient lies within (-1/2, 1/2), or is exactly -1/2 and ties to even zero
+        if n >= 128 {
+            return match mode {
+                Rounding::Floor => value >> 127,
+                Rounding::Ceil => (value > 0) as i128,
+                Rounding::Nearest => 0
+            };
+        }
+
+        // The remainder of a floor division is never negative
+        let q = value >> n;
+        let rem = value as u128 & ((1u128 << n) - 1);
+
+        match mode {
+            Rounding::Floor => q,
+            Rounding::Ceil => q + (rem != 0) as i128,
+            Rounding::Nearest => {
+                let half = 1u128 << (n - 1);
+                q + (rem > half || (rem == half && q & 1 == 1)) as i128
+            }
+        }
+    }
+
+    fn with_value<F: FnOnce(&mut ByteString)>(value: u128, reversed: bool, f: F) -> u128 {
+        let mut a = value.to_be_bytes();
+        let mut bytes = view_logical(&mut a, reversed);
+        f(&mut bytes);
+
+        if reversed { a.reverse(); }
+        u128::from_be_bytes(a)
+    }
+
+    #[test]
+    fn test_scale_shr_round_differential() {
+        let mut rng = XorShift::new(0x209);
+
+        for _ in 0..2000 {
+            let value = (rng.next_u64() as u128) << 64 | rng.next_u64() as u128;
+            let value = value >> rng.below(128);
+            let n = rng.below(140);
+
+            for mode in [Rounding::Floor, Rounding::Ceil, Rounding::Nearest] {
+                let reversed = rng.next_bool();
+                let result = with_value(value, reversed, |bytes| bytes.scale_shr_round(n, mode));
+                assert_eq!(result, reference_shr(value, n, mode), "{:#x} >> {} {:?}", value, n, mode);
+            }
+        }
+    }
+
+    #[test]
+    fn test_scale_shr_round_ties() {
+        // 0b10.1 ties to 2, 0b11.1 ties to 4, 0b10.11 rounds up to 3
+        let cases = [(0b101, 1, 2), (0b111, 1, 4), (0b1011, 2, 3), (0b1010, 2, 2), (0b1110, 2, 4)];
+
+        for (value, n, expected) in cases {
+            assert_eq!(with_value(value, false, |bytes| bytes.scale_shr_round(n, Rounding::Nearest)), expected);
+        }
+
+        assert_eq!(with_value(0b101, false, |bytes| bytes.scale_shr_round(1, Rounding::Floor)), 2);
+        assert_eq!(with_value(0b101, false, |bytes| bytes.scale_shr_round(1, Rounding::Ceil)), 3);
+
+        // Exactly one half of the whole range rounds to even zero
+        assert_eq!(with_value(1 << 127, false, |bytes| bytes.scale_shr_round(128, Rounding::Nearest)), 0);
+        assert_eq!(with_value(1 << 127 | 1, false, |bytes| bytes.scale_shr_round(128, Rounding::Nearest)), 1);
+    }
+
+    #[test]
+    fn test_scale_sar_round_differential() {
+        let mut rng = XorShift::new(0x2091);
+
+        for _ in 0..2000 {
+            let value = ((rng.next_u64() as u128) << 64 | rng.next_u64() as u128) as i128;
+            let value = value >> rng.below(128);
+            let n = rng.below(140);
+
+            for mode in [Rounding::Floor, Rounding::Ceil, Rounding::Nearest] {
+                let reversed = rng.next_bool();
+                let result = with_value(value as u128, reversed, |bytes| bytes.scale_sar_round(n, mode)) as i128;
+                assert_eq!(result, reference_sar(value, n, mode), "{} >> {} {:?}", value, n, mode);
+            }
+        }
+    }
+
+    #[test]
+    fn test_scale_sar_round_negative() {
+        let scale = |value: i128, n, mode| with_value(value as u128, false, |bytes| bytes.scale_sar_round(n, mode)) as i128;
+
+        // Floor goes toward negative infinity, unlike the truncating division
+        for (value, n) in [(-5, 1), (-1, 1), (-9, 3), (-1, 100), (i128::MIN + 1, 126)] {
+            assert_eq!(scale(value, n, Rounding::Floor), value.div_euclid(1 << n));
+            assert_ne!(scale(value, n, Rounding::Floor), value / (1 << n));
+        }
+
+        // Ceil goes toward positive infinity, which for negative values is toward zero
+        assert_eq!(scale(-5, 1, Rounding::Ceil), -5 / 2);
+        assert_eq!(scale(-1, 200, Rounding::Ceil), 0);
+
+        // -2.5 ties to -2, -3.5 ties to -4, -2.75 rounds to -3
+        let cases = [(-5, 1, -2), (-7, 1, -4), (-11, 2, -3), (-10, 2, -2), (-14, 2, -4)];
+        for (value, n, expected) in cases {
+            assert_eq!(scale(value, n, Rounding::Nearest), expected);
+        }
+
+        // Exactly minus one half of the whole range rounds to even zero
+        assert_eq!(scale(i128::MIN, 128, Rounding::Nearest), 0);
+        assert_eq!(scale(i128::MIN, 128, Rounding::Floor), -1);
+
+        // The unsigned form treats the sign bit as magnitude
+        assert_eq!(with_value(-4i128 as u128, false, |bytes| bytes.scale_shr_round(1, Rounding::Floor)), (-4i128 as u128) >> 1);
+    }
+
+    #[test]
+    fn test_scale_shl_checked() {
+        let mut rng = XorShift::new(0x2090);
+
+        for _ in 0..2000 {
+            let value = ((rng.next_u64() as u128) << 64 | rng.next_u64() as u128) >> rng.below(128);
+            let n = rng.below(140);
+            let reversed = rng.next_bool();
+
+            let fits = n < 128 && (value << n) >> n == value || value == 0;
+
+            let mut result = Ok(());
+            let shifted = with_value(value, reversed, |bytes| result = bytes.scale_shl_checked(n));
+
+            if fits {
+                assert_eq!(result, Ok(()));
+                assert_eq!(shifted, value.checked_shl(n as u32).unwrap_or(0));
+            }
+            else {
+                assert_eq!(result, Err(Overflow));
+                assert_eq!(shifted, value);
+            }
+        }
+    }
+}
@@ -0,0 +1,309 @@
+//! Display and Debug output, redaction of sensitive buffers and summaries
+
+use std::fmt;
+
+use crate::bytes::ByteString;
+
+/// Length-only rendering of a ByteString, see [`ByteString::redacted`].
+///
+/// ```
+/// use rebite::bytes::ByteString;
+///
+/// let mut raw = [0x12u8, 0x34];
+/// let bytes = ByteString::new(&mut raw);
+///
+/// assert_eq!(bytes.redacted().to_string(), "[REDACTED; 2 bytes]");
+/// assert_eq!(format!("{:?}", bytes.redacted()), "[REDACTED; 2 bytes]");
+/// ```
+#[derive(Clone, Copy)]
+pub struct Redacted {
+    pub(crate) len: usize
+}
+
+impl fmt::Display for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[REDACTED; {} bytes]", self.len)
+    }
+}
+
+impl fmt::Debug for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Bytes shown at each end of a [`Summary`].
+///
+/// ```
+/// use rebite::{bytes::ByteString, fmt::SUMMARY_EDGE_BYTES};
+///
+/// // Up to twice the edge length the summary shows every byte
+/// let mut raw = [0xAAu8; 2 * SUMMARY_EDGE_BYTES];
+/// assert!(ByteString::new(&mut raw).summary().to_string().ends_with(" bytes=aaaaaaaaaaaaaaaa"));
+/// ```
+pub const SUMMARY_EDGE_BYTES: usize = 4;
+
+/// One-line fingerprint of a ByteString, see [`ByteString::summary`].
+///
+/// ```
+/// use rebite::bytes::ByteString;
+///
+/// let mut raw = [0x00u8, 0x00, 0xFF];
+/// let bytes = ByteString::new(&mut raw);
+///
+/// assert_eq!(bytes.summary().to_string(), "len=3 ones=33.3% lz=2 tz=0 bytes=0000ff");
+/// ```
+pub struct Summary {
+    len: usize,
+    ones: usize,
+    leading_zero_bytes: usize,
+    trailing_zero_bytes: usize,
+    head: Vec<u8>,
+    tail: Vec<u8>,
+    redacted: bool,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.redacted {
+            return fmt::Display::fmt(&Redacted { len: self.len }, f);
+        }
+
+        write!(f, "len={}", self.len)?;
+        if self.len >= 1024 {
+            write!(f, " ({})", HumanSize(self.len))?;
+        }
+
+        let percent = if self.len == 0 { 0.0 } else { self.ones as f64 * 100.0 / (self.len * 8) as f64 };
+        write!(f, " ones={:.1}% lz={} tz={}", percent, self.leading_zero_bytes, self.trailing_zero_bytes)?;
+
+        if self.tail.is_empty() {
+            write!(f, " bytes=")?;
+            return self.head.iter().try_for_each(|e| write!(f, "{:02x}", e));
+        }
+
+        write!(f, " head=")?;
+        self.head.iter().try_for_each(|e| write!(f, "{:02x}", e))?;
+        write!(f, " tail=")?;
+        self.tail.iter().try_for_each(|e| write!(f, "{:02x}", e))
+    }
+}
+
+// Byte count in binary units with one decimal, "4.0 KiB"
+struct HumanSize(usize);
+
+impl fmt::Display for HumanSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+        let mut value = self.0 as f64;
+        let mut unit = 0;
+        while value >= 1024.0 && unit + 1 < UNITS.len() {
+            value /= 1024.0;
+            unit += 1;
+        }
+
+        if unit == 0 {
+            return write!(f, "{} B", self.0);
+        }
+
+        write!(f, "{:.1} {}", value, UNITS[unit])
+    }
+}
+
+impl<'a> ByteString<'a> {
+
+    /// One-line fingerprint for logging: length, share of set bits, leading and trailing zero bytes
+    /// and the first and last [`SUMMARY_EDGE_BYTES`] logical bytes in hex.
+    ///
+    /// Buffers of up to twice that many bytes are shown whole. Sensitive buffers show only the length.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x00u8, 0x01, 0xFF];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// assert_eq!(bytes.summary().to_string(), "len=3 ones=37.5% lz=1 tz=0 bytes=0001ff");
+    ///
+    /// let mut long = [0xAAu8; 12];
+    /// assert_eq!(ByteString::new(&mut long).summary().to_string(), "len=12 ones=50.0% lz=0 tz=0 head=aaaaaaaa tail=aaaaaaaa");
+    ///
+    /// bytes.mark_sensitive();
+    /// assert_eq!(bytes.summary().to_string(), "[REDACTED; 3 bytes]");
+    /// ```
+    pub fn summary(&self) -> Summary {
+        let len = self.byte_len();
+        if self.sensitive {
+            return Summary { len, ones: 0, leading_zero_bytes: 0, trailing_zero_bytes: 0, head: Vec::new(), tail: Vec::new(), redacted: true };
+        }
+
+        let leading = self.leading_zero_len();
+        let trailing = if leading == len { len } else { self.trailing_zero_len() };
+
+        let (head, tail) = if len <= 2 * SUMMARY_EDGE_BYTES {
+            (self.iter().copied().collect(), Vec::new())
+        }
+        else {
+            (self.iter().take(SUMMARY_EDGE_BYTES).copied().collect(), self.iter_range(len - SUMMARY_EDGE_BYTES..len).copied().collect())
+        };
+
+        Summary {
+            len,
+            ones: self.count_ones(),
+            leading_zero_bytes: leading,
+            trailing_zero_bytes: trailing,
+            head,
+            tail,
+            redacted: false,
+        }
+    }
+
+    /// Formatter that shows the byte length but none of the contents.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = *b"hunter2";
+    /// let bytes = ByteString::new(&mut raw);
+    ///
+    /// // Works on any instance, whether it is marked sensitive or not
+    /// assert_eq!(format!("{}", bytes.redacted()), "[REDACTED; 7 bytes]");
+    /// ```
+    pub fn redacted(&self) -> Redacted {
+        Redacted { len: self.byte_len() }
+    }
+
+    /// Make Debug and Display of this instance print the redacted form, equality and all other behavior is unchanged.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let (mut a, mut b) = ([0x42u8], [0x42u8]);
+    /// let mut secret = ByteString::new(&mut a);
+    /// secret.mark_sensitive();
+    ///
+    /// assert_eq!(format!("{:?}", secret), "[REDACTED; 1 bytes]");
+    /// assert!(secret == ByteString::new(&mut b));
+    /// ```
+    pub fn mark_sensitive(&mut self) {
+        self.sensitive = true;
+    }
+
+    /// Whether [`ByteString::mark_sensitive`] was called on this instance.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0u8; 2];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// assert!(!bytes.is_sensitive());
+    ///
+    /// bytes.mark_sensitive();
+    /// assert!(bytes.is_sensitive());
+    /// ```
+    pub fn is_sensitive(&self) -> bool {
+        self.sensitive
+    }
+}
+
+/// Lowercase hex of the logical bytes
+impl<'a> fmt::Display for ByteString<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.sensitive {
+            return fmt::Display::fmt(&self.redacted(), f);
+        }
+
+        self.iter().try_for_each(|e| write!(f, "{:02x}", e))
+    }
+}
+
+impl<'a> fmt::Debug for ByteString<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.sensitive {
+            return fmt::Debug::fmt(&self.redacted(), f);
+        }
+
+        f.debug_struct("ByteString")
+            .field("bytes", &self.bytes)
+            .field("interpret_reverse_endian", &self.interpret_reverse_endian)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::view;
+
+    #[test]
+    fn test_redacted_hides_contents() {
+        let mut raw = [0xABu8; 32];
+        let bytes = ByteString::new(&mut raw);
+
+        let shown = format!("{} {:?}", bytes.redacted(), bytes.redacted());
+        assert_eq!(shown, "[REDACTED; 32 bytes] [REDACTED; 32 bytes]");
+        assert!(!shown.to_lowercase().contains("ab") && !shown.contains("171"));
+    }
+
+    #[test]
+    fn test_sensitive_switches_default_formatting() {
+        let mut raw = [0x01u8, 0xFE];
+        let mut bytes = ByteString::new(&mut raw);
+        bytes.interpret_reverse_endian();
+
+        assert_eq!(format!("{}", bytes), "fe01");
+        assert_eq!(format!("{:?}", bytes), "ByteString { bytes: [1, 254], interpret_reverse_endian: true }");
+
+        bytes.mark_sensitive();
+        assert!(bytes.is_sensitive());
+        assert_eq!(format!("{}", bytes), "[REDACTED; 2 bytes]");
+        assert_eq!(format!("{:?}", bytes), "[REDACTED; 2 bytes]");
+        assert_eq!(format!("{:#?}", bytes), "[REDACTED; 2 bytes]");
+    }
+
+    fn summary(raw: &mut [u8], reversed: bool) -> String {
+        let bytes = view(raw, reversed);
+        bytes.summary().to_string()
+    }
+
+    #[test]
+    fn test_summary_golden() {
+        assert_eq!(summary(&mut [], false), "len=0 ones=0.0% lz=0 tz=0 bytes=");
+        assert_eq!(summary(&mut [0u8; 6], false), "len=6 ones=0.0% lz=6 tz=6 bytes=000000000000");
+        assert_eq!(summary(&mut [0x00, 0x0F, 0xF0, 0x00], true), "len=4 ones=25.0% lz=1 tz=1 bytes=00f00f00");
+
+        let mut raw = [0x00u8, 0x00, 0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xFF, 0x00];
+        assert_eq!(summary(&mut raw, false), "len=11 ones=40.9% lz=2 tz=1 head=00001234 tail=bcdeff00");
+        assert_eq!(summary(&mut raw, true), "len=11 ones=40.9% lz=1 tz=2 head=00ffdebc tail=34120000");
+
+        let mut large = vec![0xFFu8; 1536];
+        large[0] = 0;
+        assert_eq!(summary(&mut large, false), "len=1536 (1.5 KiB) ones=99.9% lz=1 tz=0 head=00ffffff tail=ffffffff");
+
+        let mut zeros = vec![0u8; 3 << 20];
+        assert_eq!(summary(&mut zeros, false), "len=3145728 (3.0 MiB) ones=0.0% lz=3145728 tz=3145728 head=00000000 tail=00000000");
+    }
+
+    #[test]
+    fn test_summary_sensitive() {
+        let mut raw = [0xABu8; 12];
+        let mut bytes = ByteString::new(&mut raw);
+        bytes.mark_sensitive();
+
+        assert_eq!(bytes.summary().to_string(), "[REDACTED; 12 bytes]");
+    }
+
+    #[test]
+    fn test_sensitive_does_not_affect_equality() {
+        let mut a = [1u8, 2];
+        let mut b = [1u8, 2];
+
+        let mut bytes_a = ByteString::new(&mut a);
+        bytes_a.mark_sensitive();
+        let bytes_b = ByteString::new(&mut b);
+
+        assert!(bytes_a == bytes_b);
+        assert_eq!(bytes_a.cmp_bytes(&bytes_b), std::cmp::Ordering::Equal);
+    }
+}
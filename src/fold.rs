@@ -0,0 +1,127 @@
+//! Folding the buffer into a single u64 word
+
+use crate::{
+    bytes::ByteString,
+    typed::Endianness
+};
+
+impl<'a> ByteString<'a> {
+
+    /// Fold the logical bytes as consecutive u64 words decoded with `endian`.
+    ///
+    /// A trailing partial word is padded with zero bytes after the last logical byte
+    /// before decoding. An empty buffer yields `init`.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, typed::Endianness};
+    ///
+    /// let mut raw = [0x01u8; 10];
+    /// let bytes = ByteString::new(&mut raw);
+    ///
+    /// // The two byte tail is padded after its last byte, so it lands in the high bytes of a big-endian word
+    /// let last = bytes.reduce_words_u64(Endianness::Big, 0, |_, word| word);
+    /// assert_eq!(last, 0x0101_0000_0000_0000);
+    /// assert_eq!(ByteString::new(&mut []).reduce_words_u64(Endianness::Big, 7, |_, _| 0), 7);
+    /// ```
+    pub fn reduce_words_u64<F>(&self, endian: Endianness, init: u64, mut f: F) -> u64
+    where F: FnMut(u64, u64) -> u64 {
+
+        let mut acc = init;
+        let mut iter = self.iter();
+
+        loop {
+            let mut word = [0u8; 8];
+            let n = word.iter_mut().zip(iter.by_ref()).map(|(dst, src)| *dst = *src).count();
+            if n == 0 { break; }
+
+            let value = match endian { Endianness::Big => u64::from_be_bytes(word), Endianness::Little => u64::from_le_bytes(word) };
+            acc = f(acc, value);
+        }
+
+        acc
+    }
+
+    /// XOR of all words, see [`ByteString::reduce_words_u64`].
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, typed::Endianness};
+    ///
+    /// let mut raw = [0xFFu8, 0, 0, 0, 0, 0, 0, 0, 0x0F];
+    /// let bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.xor_fold_u64(Endianness::Big), 0xF0 << 56);
+    /// assert_eq!(bytes.xor_fold_u64(Endianness::Little), 0xF0);
+    /// ```
+    pub fn xor_fold_u64(&self, endian: Endianness) -> u64 {
+        self.reduce_words_u64(endian, 0, |acc, word| acc ^ word)
+    }
+
+    /// Wrapping sum of all words, see [`ByteString::reduce_words_u64`].
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, typed::Endianness};
+    ///
+    /// let mut raw = [0xFFu8; 16];
+    /// raw[15] = 0x01;
+    ///
+    /// // u64::MAX + 0xFF..01 wraps
+    /// assert_eq!(ByteString::new(&mut raw).add_fold_u64(Endianness::Big), 0xFFFF_FFFF_FFFF_FF00);
+    /// ```
+    pub fn add_fold_u64(&self, endian: Endianness) -> u64 {
+        self.reduce_words_u64(endian, 0, |acc, word| acc.wrapping_add(word))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::{view, XorShift};
+
+    // Manual decoding of the logical bytes, independent of the iterator staging
+    fn words(logical: &[u8], endian: Endianness) -> Vec<u64> {
+        logical.chunks(8).map(|chunk| {
+            let mut word = [0u8; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            match endian { Endianness::Big => u64::from_be_bytes(word), Endianness::Little => u64::from_le_bytes(word) }
+        }).collect()
+    }
+
+    #[test]
+    fn test_crafted_partial_tail() {
+        let mut raw = [0x01u8, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0xAA, 0xBB];
+        let bytes = ByteString::new(&mut raw);
+
+        assert_eq!(bytes.xor_fold_u64(Endianness::Big), 0x0102_0304_0506_0708 ^ 0xAABB_0000_0000_0000);
+        assert_eq!(bytes.add_fold_u64(Endianness::Little), 0x0807_0605_0403_0201 + 0xBBAA);
+
+        let mut raw = [0xBBu8, 0xAA, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01];
+        let mut bytes = ByteString::new(&mut raw);
+        bytes.interpret_reverse_endian();
+        assert_eq!(bytes.xor_fold_u64(Endianness::Big), 0x0102_0304_0506_0708 ^ 0xAABB_0000_0000_0000);
+
+        assert_eq!(ByteString::new(&mut []).reduce_words_u64(Endianness::Big, 7, |_, _| 0), 7);
+    }
+
+    #[test]
+    fn test_wrappers_match_manual() {
+        let mut rng = XorShift::new(0x240);
+
+        for _ in 0..500 {
+            let mut raw = vec![0u8; rng.below(40)];
+            rng.fill(&mut raw);
+            let reversed = rng.next_bool();
+            let logical: Vec<u8> = if reversed { raw.iter().rev().copied().collect() } else { raw.clone() };
+
+            let bytes = view(&mut raw, reversed);
+
+            for endian in [Endianness::Big, Endianness::Little] {
+                let expected = words(&logical, endian);
+
+                assert_eq!(bytes.xor_fold_u64(endian), expected.iter().fold(0, |acc, e| acc ^ e));
+                assert_eq!(bytes.add_fold_u64(endian), expected.iter().fold(0u64, |acc, e| acc.wrapping_add(*e)));
+                assert_eq!(bytes.reduce_words_u64(endian, 0, |acc, e| acc.max(e)), expected.iter().copied().max().unwrap_or(0));
+            }
+        }
+    }
+}
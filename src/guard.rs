@@ -0,0 +1,416 @@
+//! Buffers with locked logical ranges that writes must not touch
+
+use std::ops::{
+    Deref,
+    Range
+};
+
+use crate::{
+    bytes::ByteString,
+    error::Error
+};
+
+/// ByteString with locked logical ranges, such as a header or checksum field next to a payload.
+///
+/// Reads go through `Deref`. Every write checks the logical range it affects and fails with
+/// [`Error::ProtectedRegion`] without changing anything if that range overlaps a lock. Shifts
+/// move every byte, so they are rejected while any lock exists.
+///
+/// ```
+/// use rebite::{bytes::ByteString, error::Error, guard::GuardedByteString};
+///
+/// let mut raw = [0u8; 4];
+/// let mut guarded = GuardedByteString::new(ByteString::new(&mut raw));
+///
+/// // Lock the two byte header, the payload stays writable
+/// guarded.lock(0..2).unwrap();
+/// assert_eq!(guarded.fill(0..4, 0xFF), Err(Error::ProtectedRegion { start: 0, end: 2 }));
+/// guarded.fill(2..4, 0xFF).unwrap();
+///
+/// assert!(guarded.shl_assign(8).is_err());
+/// assert_eq!(raw, [0x00, 0x00, 0xFF, 0xFF]);
+/// ```
+pub struct GuardedByteString<'a> {
+    bytes: ByteString<'a>,
+    locks: Vec<Range<usize>>,
+}
+
+impl<'a> GuardedByteString<'a> {
+
+    /// Guard `bytes`, initially without any lock.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, guard::GuardedByteString};
+    ///
+    /// let mut raw = [0x01u8, 0x02];
+    /// let guarded = GuardedByteString::new(ByteString::new(&mut raw));
+    ///
+    /// // Reads go straight to the ByteString
+    /// assert_eq!(guarded.to_u64(), Ok(0x0102));
+    /// assert!(guarded.locks().is_empty());
+    /// ```
+    pub fn new(bytes: ByteString<'a>) -> Self {
+        Self { bytes, locks: Vec::new() }
+    }
+
+    /// Give up the locks and return the ByteString.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, guard::GuardedByteString};
+    ///
+    /// let mut raw = [0x01u8, 0x02];
+    /// let mut guarded = GuardedByteString::new(ByteString::new(&mut raw));
+    /// guarded.lock(0..2).unwrap();
+    ///
+    /// let mut bytes = guarded.into_inner();
+    /// bytes.set_zero();
+    /// assert_eq!(raw, [0x00, 0x00]);
+    /// ```
+    pub fn into_inner(self) -> ByteString<'a> {
+        self.bytes
+    }
+
+    /// Locked logical ranges in the order they were locked.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, guard::GuardedByteString};
+    ///
+    /// let mut raw = [0u8; 8];
+    /// let mut guarded = GuardedByteString::new(ByteString::new(&mut raw));
+    /// guarded.lock(4..8).unwrap();
+    /// guarded.lock(0..2).unwrap();
+    ///
+    /// assert_eq!(guarded.locks(), [4..8, 0..2]);
+    /// ```
+    pub fn locks(&self) -> &[Range<usize>] {
+        &self.locks
+    }
+
+    /// Lock the logical `range` against writes, locks may overlap each other.
+    ///
+    /// Fails with [`Error::ZeroLength`] for an empty range and with [`Error::OutOfBounds`] if it
+    /// does not lie within the buffer.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error, guard::GuardedByteString};
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let mut guarded = GuardedByteString::new(ByteString::new(&mut raw));
+    ///
+    /// assert_eq!(guarded.lock(1..1), Err(Error::ZeroLength));
+    /// assert!(guarded.lock(2..5).is_err());
+    /// assert_eq!(guarded.lock(0..2), Ok(()));
+    /// assert_eq!(guarded.lock(1..3), Ok(()));
+    /// ```
+    pub fn lock(&mut self, range: Range<usize>) -> Result<(), Error> {
+        if range.is_empty() {
+            return Err(Error::ZeroLength);
+        }
+        self.bytes.check_region(range.start, range.len())?;
+
+        self.locks.push(range);
+        Ok(())
+    }
+
+    /// Remove the lock on exactly `range`, returns whether there was one.
+    ///
+    /// Other locks stay in place even where they overlap `range`.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, guard::GuardedByteString};
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let mut guarded = GuardedByteString::new(ByteString::new(&mut raw));
+    /// guarded.lock(0..4).unwrap();
+    ///
+    /// // Only an exact match unlocks
+    /// assert!(!guarded.unlock(0..2));
+    /// assert!(guarded.unlock(0..4));
+    /// assert!(!guarded.unlock(0..4));
+    /// ```
+    pub fn unlock(&mut self, range: Range<usize>) -> bool {
+        match self.locks.iter().position(|e| *e == range) {
+            Some(ix) => {
+                self.locks.remove(ix);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Whether the logical byte at `ix` is covered by a lock.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, guard::GuardedByteString};
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let mut guarded = GuardedByteString::new(ByteString::new(&mut raw));
+    /// guarded.lock(1..3).unwrap();
+    ///
+    /// assert_eq!((0..4).map(|e| guarded.is_locked(e)).collect::<Vec<_>>(), [false, true, true, false]);
+    /// ```
+    pub fn is_locked(&self, ix: usize) -> bool {
+        self.locks.iter().any(|e| e.contains(&ix))
+    }
+
+    /// Set the logical byte at `ix`, fails with [`Error::OutOfBounds`] past the end.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error, guard::GuardedByteString};
+    ///
+    /// let mut raw = [0u8; 3];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    /// let mut guarded = GuardedByteString::new(bytes);
+    /// guarded.lock(0..1).unwrap();
+    ///
+    /// assert_eq!(guarded.set_byte(0, 0xFF), Err(Error::ProtectedRegion { start: 0, end: 1 }));
+    /// assert_eq!(guarded.set_byte(3, 0xFF), Err(Error::OutOfBounds));
+    /// assert_eq!(guarded.set_byte(2, 0xFF), Ok(()));
+    /// assert_eq!(raw, [0xFF, 0x00, 0x00]);
+    /// ```
+    pub fn set_byte(&mut self, ix: usize, value: u8) -> Result<(), Error> {
+        self.writable(ix..ix.saturating_add(1))?;
+        self.bytes.iter_range_mut(ix..ix + 1).for_each(|e| *e = value);
+
+        Ok(())
+    }
+
+    /// Set every byte of the logical `range` to `value`.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error, guard::GuardedByteString};
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let mut guarded = GuardedByteString::new(ByteString::new(&mut raw));
+    /// guarded.lock(3..4).unwrap();
+    ///
+    /// // A range touching a lock is refused as a whole
+    /// assert_eq!(guarded.fill(1..4, 0xAA), Err(Error::ProtectedRegion { start: 3, end: 4 }));
+    /// assert_eq!(guarded.fill(1..3, 0xAA), Ok(()));
+    /// assert_eq!(raw, [0x00, 0xAA, 0xAA, 0x00]);
+    /// ```
+    pub fn fill(&mut self, range: Range<usize>, value: u8) -> Result<(), Error> {
+        self.writable(range.clone())?;
+        self.bytes.iter_range_mut(range).for_each(|e| *e = value);
+
+        Ok(())
+    }
+
+    /// Copy `src` to the logical bytes starting at `offset`.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error, guard::GuardedByteString};
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let mut guarded = GuardedByteString::new(ByteString::new(&mut raw));
+    ///
+    /// assert_eq!(guarded.copy_from(3, &[1, 2]), Err(Error::OutOfBounds));
+    /// assert_eq!(guarded.copy_from(1, &[1, 2]), Ok(()));
+    /// assert_eq!(raw, [0, 1, 2, 0]);
+    /// ```
+    pub fn copy_from(&mut self, offset: usize, src: &[u8]) -> Result<(), Error> {
+        let end = offset.checked_add(src.len()).ok_or(Error::OutOfBounds)?;
+        self.writable(offset..end)?;
+
+        for (dst, value) in self.bytes.iter_range_mut(offset..end).zip(src) {
+            *dst = *value;
+        }
+
+        Ok(())
+    }
+
+    /// self ^= rhs over the whole buffer, fails with [`Error::LengthMismatch`] if the lengths differ.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error, guard::GuardedByteString};
+    ///
+    /// let (mut raw, mut key) = ([0x0Fu8, 0xF0], [0xFFu8, 0xFF]);
+    /// let key = ByteString::new(&mut key);
+    /// let mut guarded = GuardedByteString::new(ByteString::new(&mut raw));
+    ///
+    /// // Even a zero key byte over the locked range would be a write
+    /// guarded.lock(0..1).unwrap();
+    /// assert_eq!(guarded.xor_assign(&key), Err(Error::ProtectedRegion { start: 0, end: 1 }));
+    ///
+    /// guarded.unlock(0..1);
+    /// assert_eq!(guarded.xor_assign(&key), Ok(()));
+    /// assert_eq!(raw, [0xF0, 0x0F]);
+    /// ```
+    pub fn xor_assign(&mut self, rhs: &ByteString) -> Result<(), Error> {
+        if rhs.byte_len() != self.bytes.byte_len() {
+            return Err(Error::LengthMismatch);
+        }
+        self.writable(0..self.bytes.byte_len())?;
+
+        for (dst, value) in self.bytes.iter_mut().zip(rhs.iter()) {
+            *dst ^= *value;
+        }
+
+        Ok(())
+    }
+
+    /// self <<= count, rejected while any lock exists.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error, guard::GuardedByteString};
+    ///
+    /// let mut raw = [0x00u8, 0x01];
+    /// let mut guarded = GuardedByteString::new(ByteString::new(&mut raw));
+    ///
+    /// assert_eq!(guarded.shl_assign(9), Ok(()));
+    /// guarded.lock(1..2).unwrap();
+    /// assert_eq!(guarded.shl_assign(1), Err(Error::ProtectedRegion { start: 1, end: 2 }));
+    /// assert_eq!(raw, [0x02, 0x00]);
+    /// ```
+    pub fn shl_assign(&mut self, count: usize) -> Result<(), Error> {
+        self.unlocked()?;
+        self.bytes <<= count;
+
+        Ok(())
+    }
+
+    /// self >>= count, rejected while any lock exists.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error, guard::GuardedByteString};
+    ///
+    /// let mut raw = [0x80u8, 0x00];
+    /// let mut guarded = GuardedByteString::new(ByteString::new(&mut raw));
+    ///
+    /// // A lock anywhere blocks the shift, even on bytes that would not change
+    /// guarded.lock(0..1).unwrap();
+    /// assert!(guarded.shr_assign(4).is_err());
+    /// guarded.unlock(0..1);
+    /// assert_eq!(guarded.shr_assign(4), Ok(()));
+    /// assert_eq!(raw, [0x08, 0x00]);
+    /// ```
+    pub fn shr_assign(&mut self, count: usize) -> Result<(), Error> {
+        self.unlocked()?;
+        self.bytes >>= count;
+
+        Ok(())
+    }
+
+    // Bounds first, then the first lock overlapping the range. An empty range touches no lock.
+    fn writable(&self, range: Range<usize>) -> Result<(), Error> {
+        if range.end > self.bytes.byte_len() || range.start > range.end {
+            return Err(Error::OutOfBounds);
+        }
+
+        match self.locks.iter().find(|e| e.start < range.end && range.start < e.end) {
+            Some(lock) => Err(Error::ProtectedRegion { start: lock.start, end: lock.end }),
+            None => Ok(()),
+        }
+    }
+
+    // Whole buffer operations whose effect cannot be confined to a range
+    fn unlocked(&self) -> Result<(), Error> {
+        match self.locks.first() {
+            Some(lock) => Err(Error::ProtectedRegion { start: lock.start, end: lock.end }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> Deref for GuardedByteString<'a> {
+    type Target = ByteString<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::view;
+
+    // Four byte header, locked checksum at 4..6, payload after
+    fn frame(raw: &mut [u8], reversed: bool) -> GuardedByteString<'_> {
+        let bytes = view(raw, reversed);
+
+        let mut guarded = GuardedByteString::new(bytes);
+        guarded.lock(4..6).unwrap();
+        guarded
+    }
+
+    #[test]
+    fn test_writes_inside_overlapping_and_outside() {
+        for reversed in [false, true] {
+            let mut raw = [0u8; 10];
+            let mut guarded = frame(&mut raw, reversed);
+            let locked = Err(Error::ProtectedRegion { start: 4, end: 6 });
+
+            // Inside and overlapping
+            assert_eq!(guarded.set_byte(4, 0xFF), locked);
+            assert_eq!(guarded.set_byte(5, 0xFF), locked);
+            assert_eq!(guarded.fill(3..5, 0xFF), locked);
+            assert_eq!(guarded.fill(5..10, 0xFF), locked);
+            assert_eq!(guarded.copy_from(2, &[0xFF; 3]), locked);
+            assert!(guarded.is_zero());
+
+            // Outside, including right up to the lock and an empty range at its start
+            guarded.set_byte(3, 0x11).unwrap();
+            guarded.fill(6..8, 0x22).unwrap();
+            guarded.copy_from(8, &[0x33, 0x44]).unwrap();
+            guarded.fill(4..4, 0xFF).unwrap();
+            assert_eq!(guarded.iter().copied().collect::<Vec<u8>>(), [0, 0, 0, 0x11, 0, 0, 0x22, 0x22, 0x33, 0x44]);
+
+            // Bounds are checked before locks
+            assert_eq!(guarded.set_byte(10, 0), Err(Error::OutOfBounds));
+            assert_eq!(guarded.set_byte(usize::MAX, 0), Err(Error::OutOfBounds));
+            assert_eq!(guarded.copy_from(9, &[0, 0]), Err(Error::OutOfBounds));
+            assert_eq!(guarded.copy_from(usize::MAX, &[0]), Err(Error::OutOfBounds));
+        }
+    }
+
+    #[test]
+    fn test_whole_buffer_ops_rejected() {
+        let mut raw = [0x01u8, 0x02, 0x03, 0x04, 0x05, 0x06];
+        let mut mask = [0xFFu8; 6];
+        let mut short = [0xFFu8; 2];
+        let mut guarded = frame(&mut raw, false);
+        let locked = Err(Error::ProtectedRegion { start: 4, end: 6 });
+
+        assert_eq!(guarded.xor_assign(&ByteString::new(&mut mask)), locked);
+        assert_eq!(guarded.xor_assign(&ByteString::new(&mut short)), Err(Error::LengthMismatch));
+        assert_eq!(guarded.shl_assign(8), locked);
+        assert_eq!(guarded.shr_assign(0), locked);
+        assert_eq!(guarded.bytes(), [0x01, 0x02, 0x03, 0x04, 0x05, 0x06]);
+
+        assert!(guarded.unlock(4..6));
+        guarded.xor_assign(&ByteString::new(&mut mask)).unwrap();
+        guarded.shl_assign(8).unwrap();
+        assert_eq!(guarded.bytes(), [0xFD, 0xFC, 0xFB, 0xFA, 0xF9, 0x00]);
+    }
+
+    #[test]
+    fn test_unlock_and_relock() {
+        let mut raw = [0u8; 8];
+        let mut guarded = frame(&mut raw, false);
+
+        assert_eq!(guarded.lock(0..0), Err(Error::ZeroLength));
+        assert_eq!(guarded.lock(6..9), Err(Error::OutOfBounds));
+        guarded.lock(5..8).unwrap();
+        assert_eq!(guarded.locks(), [4..6, 5..8]);
+        assert!(guarded.is_locked(4) && guarded.is_locked(7) && !guarded.is_locked(3));
+
+        // Only an exact match is removed, the overlapping lock still guards byte 5
+        assert!(!guarded.unlock(4..5));
+        assert!(guarded.unlock(4..6));
+        guarded.set_byte(4, 0xAA).unwrap();
+        assert_eq!(guarded.set_byte(5, 0xAA), Err(Error::ProtectedRegion { start: 5, end: 8 }));
+
+        assert!(guarded.unlock(5..8));
+        assert!(!guarded.unlock(5..8));
+        guarded.fill(5..8, 0xBB).unwrap();
+
+        guarded.lock(4..6).unwrap();
+        assert_eq!(guarded.fill(0..8, 0), Err(Error::ProtectedRegion { start: 4, end: 6 }));
+
+        let bytes = guarded.into_inner();
+        assert_eq!(bytes.bytes(), [0, 0, 0, 0, 0xAA, 0xBB, 0xBB, 0xBB]);
+    }
+}
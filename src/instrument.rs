@@ -0,0 +1,643 @@
+//! Operation counting wrapper for profiling, enabled by the `instrument` feature
+
+use std::{
+    cell::Cell,
+    cmp::Ordering,
+    fmt,
+    ops::{
+        Range,
+        AddAssign,
+        BitAndAssign,
+        BitOrAssign,
+        BitXorAssign,
+        DivAssign,
+        MulAssign,
+        RemAssign,
+        ShlAssign,
+        ShrAssign,
+        SubAssign,
+    }
+};
+
+use crate::{
+    bit_order::BitOrder,
+    bytes::ByteString,
+    bytes_iter::{BytesIter, BytesIterMut},
+    checksum::ShortIdHex,
+    ecc::EccReport,
+    error::{Error, LengthError, Overflow, ParseError, PatchError, RegionError, SyncError},
+    fixed::Rounding,
+    fmt::{Redacted, Summary},
+    matrix::ByteMatrix,
+    patch::OverlapPolicy,
+    resize::Extension,
+    snapshot::Snapshot,
+    text::{CArray, ColonSeparated, Escaped, TextFormat},
+    typed::Endianness
+};
+
+/// Calls of one category and the bytes of the wrapped buffer they covered.
+///
+/// ```
+/// use std::cell::Cell;
+/// use rebite::{bytes::ByteString, instrument::{CountingByteString, OpCount, OpStats}};
+///
+/// let stats = Cell::new(OpStats::default());
+/// let mut raw = [0u8; 4];
+/// let mut bytes = CountingByteString::new(ByteString::new(&mut raw), &stats);
+///
+/// bytes.set_zero();
+/// bytes.set_bit(0, true);
+/// assert_eq!(stats.get().writes, OpCount { calls: 2, bytes: 8 });
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OpCount {
+    /// Number of calls
+    pub calls: u64,
+    /// Sum of the buffer length over all calls
+    pub bytes: u64,
+}
+
+/// Per category operation counts collected by a [`CountingByteString`].
+///
+/// ```
+/// use std::cell::Cell;
+/// use rebite::{bytes::ByteString, instrument::{CountingByteString, OpStats}};
+///
+/// let stats = Cell::new(OpStats::default());
+/// let mut raw = [0u8; 2];
+/// let mut bytes = CountingByteString::new(ByteString::new(&mut raw), &stats);
+///
+/// bytes.to_u64().unwrap();
+/// bytes.rotl_bits(3);
+///
+/// let report = bytes.report();
+/// assert_eq!((report.reads.calls, report.shifts.calls, report.writes.calls), (1, 1, 0));
+/// assert!(report.to_string().starts_with("reads             1 calls            2 bytes"));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OpStats {
+    /// Methods that only read the buffer
+    pub reads: OpCount,
+    /// Methods and operators that modify the buffer
+    pub writes: OpCount,
+    /// Comparisons against another buffer
+    pub compares: OpCount,
+    /// Shifts and rotations
+    pub shifts: OpCount,
+}
+
+impl fmt::Display for OpStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = [("reads", self.reads), ("writes", self.writes), ("compares", self.compares), ("shifts", self.shifts)];
+
+        for (ix, (name, count)) in rows.iter().enumerate() {
+            if ix > 0 { writeln!(f)?; }
+            write!(f, "{:<8} {:>10} calls {:>12} bytes", name, count.calls, count.bytes)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Category {
+    Read,
+    Write,
+    Compare,
+    Shift,
+}
+
+/// ByteString wrapper that counts every operation into a shared [`OpStats`].
+///
+/// Each call adds one to its category and the byte length of the wrapped buffer to the bytes
+/// touched, an upper bound for operations on a subrange. Length and flag queries are not counted.
+/// Generic methods are reached uncounted through [`CountingByteString::inner`].
+///
+/// ```
+/// use std::cell::Cell;
+/// use rebite::{bytes::ByteString, instrument::{CountingByteString, OpStats}};
+///
+/// // Two wrappers counting into the same stats
+/// let stats = Cell::new(OpStats::default());
+/// let (mut a, mut b) = ([0u8; 8], [0u8; 8]);
+/// let mut a = CountingByteString::new(ByteString::new(&mut a), &stats);
+/// let b = CountingByteString::new(ByteString::new(&mut b), &stats);
+///
+/// a.increment();
+/// a.inner().set_zero();
+/// assert!(a.is_zero());
+/// assert!(b.is_zero());
+///
+/// // The inner call was not counted
+/// assert_eq!(stats.get().writes.calls, 1);
+/// assert_eq!(stats.get().reads.calls, 2);
+/// ```
+pub struct CountingByteString<'s, 'a> {
+    bytes: ByteString<'a>,
+    stats: &'s Cell<OpStats>,
+}
+
+impl<'s, 'a> CountingByteString<'s, 'a> {
+
+    /// Count the operations on `bytes` into `stats`, which several wrappers may share.
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    /// use rebite::{bytes::ByteString, instrument::{CountingByteString, OpStats}};
+    ///
+    /// let stats = Cell::new(OpStats::default());
+    /// let mut raw = [0u8; 2];
+    /// let bytes = CountingByteString::new(ByteString::new(&mut raw), &stats);
+    ///
+    /// assert_eq!(bytes.report(), OpStats::default());
+    /// ```
+    pub fn new(bytes: ByteString<'a>, stats: &'s Cell<OpStats>) -> Self {
+        Self { bytes, stats }
+    }
+
+    /// Snapshot of the counts so far, its Display prints one line per category.
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    /// use rebite::{bytes::ByteString, instrument::{CountingByteString, OpStats}};
+    ///
+    /// let stats = Cell::new(OpStats::default());
+    /// let mut raw = [0u8; 2];
+    /// let bytes = CountingByteString::new(ByteString::new(&mut raw), &stats);
+    ///
+    /// bytes.is_zero();
+    /// assert_eq!(bytes.report().reads.calls, 1);
+    /// assert_eq!(bytes.report().to_string().lines().count(), 4);
+    /// ```
+    pub fn report(&self) -> OpStats {
+        self.stats.get()
+    }
+
+    /// The wrapped ByteString, accesses through it are not counted.
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    /// use rebite::{bytes::ByteString, instrument::{CountingByteString, OpStats}};
+    ///
+    /// let stats = Cell::new(OpStats::default());
+    /// let mut raw = [0u8; 2];
+    /// let mut bytes = CountingByteString::new(ByteString::new(&mut raw), &stats);
+    ///
+    /// // Generic methods are only reachable here
+    /// bytes.inner().assign_xor_of([]).unwrap();
+    /// assert_eq!(stats.get(), OpStats::default());
+    /// ```
+    pub fn inner(&mut self) -> &mut ByteString<'a> {
+        &mut self.bytes
+    }
+
+    /// Give back the wrapped ByteString.
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    /// use rebite::{bytes::ByteString, instrument::{CountingByteString, OpStats}};
+    ///
+    /// let stats = Cell::new(OpStats::default());
+    /// let mut raw = [0x00u8, 0x01];
+    /// let mut bytes = CountingByteString::new(ByteString::new(&mut raw), &stats);
+    /// bytes.interpret_reverse_endian();
+    ///
+    /// // The flag stays with the ByteString
+    /// assert_eq!(bytes.into_inner().to_u64(), Ok(0x0100));
+    /// ```
+    pub fn into_inner(self) -> ByteString<'a> {
+        self.bytes
+    }
+
+    /// Not counted, see [`ByteString::byte_len`].
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    /// use rebite::{bytes::ByteString, instrument::{CountingByteString, OpStats}};
+    ///
+    /// let stats = Cell::new(OpStats::default());
+    /// let mut raw = [0u8; 3];
+    /// let bytes = CountingByteString::new(ByteString::new(&mut raw), &stats);
+    ///
+    /// assert_eq!(bytes.byte_len(), 3);
+    /// assert_eq!(bytes.report(), OpStats::default());
+    /// ```
+    pub fn byte_len(&self) -> usize {
+        self.bytes.byte_len()
+    }
+
+    /// Not counted, see [`ByteString::bit_len`].
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    /// use rebite::{bytes::ByteString, instrument::{CountingByteString, OpStats}};
+    ///
+    /// let stats = Cell::new(OpStats::default());
+    /// let mut raw = [0u8; 3];
+    /// let bytes = CountingByteString::new(ByteString::new(&mut raw), &stats);
+    ///
+    /// assert_eq!(bytes.bit_len(), 24);
+    /// assert_eq!(bytes.report(), OpStats::default());
+    /// ```
+    pub fn bit_len(&self) -> usize {
+        self.bytes.bit_len()
+    }
+
+    /// Not counted, see [`ByteString::is_sensitive`].
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    /// use rebite::{bytes::ByteString, instrument::{CountingByteString, OpStats}};
+    ///
+    /// let stats = Cell::new(OpStats::default());
+    /// let mut raw = [0u8; 2];
+    /// let bytes = CountingByteString::new(ByteString::new(&mut raw), &stats);
+    ///
+    /// assert!(!bytes.is_sensitive());
+    /// ```
+    pub fn is_sensitive(&self) -> bool {
+        self.bytes.is_sensitive()
+    }
+
+    /// Not counted, see [`ByteString::mark_sensitive`].
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    /// use rebite::{bytes::ByteString, instrument::{CountingByteString, OpStats}};
+    ///
+    /// let stats = Cell::new(OpStats::default());
+    /// let mut raw = [0u8; 2];
+    /// let mut bytes = CountingByteString::new(ByteString::new(&mut raw), &stats);
+    ///
+    /// bytes.mark_sensitive();
+    /// assert!(bytes.is_sensitive());
+    /// assert_eq!(bytes.report(), OpStats::default());
+    /// ```
+    pub fn mark_sensitive(&mut self) {
+        self.bytes.mark_sensitive()
+    }
+
+    /// Not counted, see [`ByteString::interpret_reverse_endian`].
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    /// use rebite::{bytes::ByteString, instrument::{CountingByteString, OpStats}};
+    ///
+    /// let stats = Cell::new(OpStats::default());
+    /// let mut raw = [0x00u8, 0x01];
+    /// let mut bytes = CountingByteString::new(ByteString::new(&mut raw), &stats);
+    ///
+    /// bytes.interpret_reverse_endian();
+    /// assert_eq!(bytes.to_u64(), Ok(0x0100));
+    /// assert_eq!(bytes.report().reads.calls, 1);
+    /// ```
+    pub fn interpret_reverse_endian(&mut self) {
+        self.bytes.interpret_reverse_endian()
+    }
+
+    /// Not counted, see [`ByteString::redacted`].
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    /// use rebite::{bytes::ByteString, instrument::{CountingByteString, OpStats}};
+    ///
+    /// let stats = Cell::new(OpStats::default());
+    /// let mut raw = [0u8; 2];
+    /// let bytes = CountingByteString::new(ByteString::new(&mut raw), &stats);
+    ///
+    /// assert_eq!(bytes.redacted().to_string(), "[REDACTED; 2 bytes]");
+    /// assert_eq!(bytes.report(), OpStats::default());
+    /// ```
+    pub fn redacted(&self) -> Redacted {
+        self.bytes.redacted()
+    }
+
+    fn record(&self, category: Category) {
+        let mut stats = self.stats.get();
+        let count = match category {
+            Category::Read => &mut stats.reads,
+            Category::Write => &mut stats.writes,
+            Category::Compare => &mut stats.compares,
+            Category::Shift => &mut stats.shifts,
+        };
+
+        count.calls += 1;
+        count.bytes += self.bytes.byte_len() as u64;
+        self.stats.set(stats);
+    }
+}
+
+// Counted forwarding of inherent methods, one line per method keeps the list easy to extend with the API.
+// Entries are munched one at a time since `&self` and `&mut self` need separate rules.
+macro_rules! delegate {
+    () => {};
+    ($category:ident fn $name:ident(&self $(, $arg:ident: $ty:ty)*) $(-> $ret:ty)?; $($rest:tt)*) => {
+        impl<'s, 'a> CountingByteString<'s, 'a> {
+            #[doc = concat!("Counted [`ByteString::", stringify!($name), "`]")]
+            #[track_caller]
+            pub fn $name(&self $(, $arg: $ty)*) $(-> $ret)? {
+                self.record(Category::$category);
+                self.bytes.$name($($arg),*)
+            }
+        }
+
+        delegate!($($rest)*);
+    };
+    ($category:ident fn $name:ident(&mut self $(, $arg:ident: $ty:ty)*) $(-> $ret:ty)?; $($rest:tt)*) => {
+        impl<'s, 'a> CountingByteString<'s, 'a> {
+            #[doc = concat!("Counted [`ByteString::", stringify!($name), "`]")]
+            #[track_caller]
+            pub fn $name(&mut self $(, $arg: $ty)*) $(-> $ret)? {
+                self.record(Category::$category);
+                self.bytes.$name($($arg),*)
+            }
+        }
+
+        delegate!($($rest)*);
+    };
+}
+
+delegate! {
+    Read fn bytes(&self) -> &[u8];
+    Read fn iter(&self) -> BytesIter<'_>;
+    Read fn iter_range(&self, range: Range<usize>) -> BytesIter<'_>;
+    Read fn try_iter_range(&self, range: Range<usize>) -> Result<BytesIter<'_>, Error>;
+    Read fn bit(&self, offset: usize) -> bool;
+    Read fn try_bit(&self, offset: usize) -> Result<bool, Error>;
+    Read fn read_bit_range(&self, range: Range<usize>) -> Result<u64, Error>;
+    Read fn iter_bits(&self, order: BitOrder) -> impl Iterator<Item = bool> + '_;
+    Read fn extract_bits(&self, start: usize, len: usize, order: BitOrder) -> Result<u64, Error>;
+    Read fn collect_bits_into(&self, out: &mut Vec<bool>);
+    Read fn for_each_bit(&self, f: impl FnMut(bool));
+    Read fn count_ones(&self) -> usize;
+    Read fn is_zero(&self) -> bool;
+    Read fn to_u64(&self) -> Result<u64, Overflow>;
+    Read fn read_u16_at(&self, offset: usize, endian: Endianness) -> Result<u16, Error>;
+    Read fn read_u32_at(&self, offset: usize, endian: Endianness) -> Result<u32, Error>;
+    Read fn read_u64_at(&self, offset: usize, endian: Endianness) -> Result<u64, Error>;
+    Read fn read_f32_at(&self, offset: usize, endian: Endianness) -> Result<f32, Error>;
+    Read fn read_f64_at(&self, offset: usize, endian: Endianness) -> Result<f64, Error>;
+    Read fn xor_fold_u64(&self, endian: Endianness) -> u64;
+    Read fn add_fold_u64(&self, endian: Endianness) -> u64;
+    Read fn rolling_hashes(&self, window: usize) -> impl Iterator<Item = u64> + '_;
+    Read fn try_rolling_hashes(&self, window: usize) -> Result<impl Iterator<Item = u64> + '_, Error>;
+    Read fn iter_bit_reversed_order(&self) -> impl Iterator<Item = &u8> + '_;
+    Read fn find_duplicate_blocks(&self, block_size: usize, out: &mut Vec<(usize, usize)>) -> Result<(), Error>;
+    Read fn export_into(&self, out: &mut [u8]) -> Result<usize, Error>;
+    Read fn write_radix(&self, out: &mut [u8], radix: u32, uppercase: bool) -> Result<usize, Error>;
+    Read fn cobs_encode_into(&self, out: &mut [u8]) -> Result<usize, Error>;
+    Read fn cobs_decode_into(&self, out: &mut ByteString) -> Result<usize, Error>;
+    Read fn to_cbor_bytes_into(&self, out: &mut [u8]) -> Result<usize, Error>;
+    Read fn secded_encode_into(&self, out: &mut ByteString) -> Result<(), Error>;
+    Read fn extend_into(&self, out: &mut ByteString, extension: Extension) -> Result<(), Error>;
+    Read fn summary(&self) -> Summary;
+    Read fn short_id(&self) -> [u8; 8];
+    Read fn short_id_hex(&self) -> ShortIdHex;
+    Read fn clmul(&self, rhs: &ByteString, out: &mut ByteString) -> Result<(), Error>;
+    Read fn changed_mask_into(&self, previous: &ByteString, out: &mut ByteString) -> Result<usize, LengthError>;
+    Read fn changed_ranges(&self, previous: &ByteString, max_gap: usize, out: &mut Vec<Range<usize>>) -> Result<(), LengthError>;
+    Read fn snapshot(&self) -> Snapshot;
+    Read fn snapshot_from(&self, previous: &Snapshot) -> Snapshot;
+    Read fn display_colon_separated(&self) -> ColonSeparated<'_, 'a>;
+    Read fn display_c_array(&self, per_line: usize) -> CArray<'_, 'a>;
+    Read fn display_escaped(&self) -> Escaped<'_, 'a>;
+    Read fn check_region(&self, offset: usize, len: usize) -> Result<(), RegionError>;
+    Read fn region(&self, offset: usize, len: usize) -> Result<&[u8], RegionError>;
+    Read fn check_patches(&self, patches: &[(usize, &[u8])], policy: OverlapPolicy) -> Result<(), PatchError>;
+    Read fn diff_after_patches(&self, patches: &[(usize, &[u8])], policy: OverlapPolicy) -> Result<Vec<Range<usize>>, PatchError>;
+    Read fn regions(&self, descriptors: &[(usize, usize)]) -> Result<(), Vec<(usize, RegionError)>>;
+}
+
+delegate! {
+    Write fn bytes_mut(&mut self) -> &mut [u8];
+    Write fn trim_start_matches(&mut self, pred: impl Fn(u8) -> bool) -> ByteString<'_>;
+    Write fn trim_end_matches(&mut self, pred: impl Fn(u8) -> bool) -> ByteString<'_>;
+    Write fn trim_matches(&mut self, pred: impl Fn(u8) -> bool) -> ByteString<'_>;
+    Write fn assign_from_bits(&mut self, bits: impl IntoIterator<Item = bool>, zero_rest: bool) -> Result<usize, Error>;
+    Write fn as_matrix_mut(&mut self, width: usize) -> Result<ByteMatrix<'_>, Error>;
+    Write fn apply_patches(&mut self, patches: &[(usize, &[u8])], policy: OverlapPolicy) -> Result<(), PatchError>;
+    Write fn region_mut(&mut self, offset: usize, len: usize) -> Result<&mut [u8], RegionError>;
+    Write fn iter_mut(&mut self) -> BytesIterMut<'_>;
+    Write fn iter_range_mut(&mut self, range: Range<usize>) -> BytesIterMut<'_>;
+    Write fn try_iter_range_mut(&mut self, range: Range<usize>) -> Result<BytesIterMut<'_>, Error>;
+    Write fn set_bit(&mut self, offset: usize, value: bool);
+    Write fn try_set_bit(&mut self, offset: usize, value: bool) -> Result<(), Error>;
+    Write fn write_bit_range(&mut self, range: Range<usize>, value: u64) -> Result<(), Error>;
+    Write fn move_bits_within(&mut self, src_bit: usize, dst_bit: usize, len_bits: usize) -> Result<(), Error>;
+    Write fn set_bytes_with_value(&mut self, value: u8);
+    Write fn set_zero(&mut self);
+    Write fn reverse_byte_endianness(&mut self);
+    Write fn reverse_bit_endianness(&mut self);
+    Write fn to_network_order(&mut self);
+    Write fn from_network_order(&mut self, host_is_little_endian: bool);
+    Write fn to_host(&mut self);
+    Write fn from_host(&mut self);
+    Write fn to_lexicographic(&mut self, signed: bool);
+    Write fn from_lexicographic(&mut self, signed: bool);
+    Write fn permute_bit_reversed(&mut self) -> Result<(), Error>;
+    Write fn assign_from_u64(&mut self, value: u64) -> Result<(), Overflow>;
+    Write fn write_u16_at(&mut self, offset: usize, value: u16, endian: Endianness) -> Result<(), Error>;
+    Write fn write_u32_at(&mut self, offset: usize, value: u32, endian: Endianness) -> Result<(), Error>;
+    Write fn write_u64_at(&mut self, offset: usize, value: u64, endian: Endianness) -> Result<(), Error>;
+    Write fn write_f32_at(&mut self, offset: usize, value: f32, endian: Endianness) -> Result<(), Error>;
+    Write fn write_f64_at(&mut self, offset: usize, value: f64, endian: Endianness) -> Result<(), Error>;
+    Write fn assign_from_radix_str(&mut self, s: &str, radix: u32) -> Result<(), ParseError>;
+    Write fn assign_from_text_auto(&mut self, s: &str) -> Result<TextFormat, ParseError>;
+    Write fn assign_from_colon_hex(&mut self, s: &str) -> Result<(), ParseError>;
+    Write fn assign_from_spaced_hex(&mut self, s: &str) -> Result<(), ParseError>;
+    Write fn assign_from_c_array(&mut self, s: &str) -> Result<(), ParseError>;
+    Write fn assign_from_escaped(&mut self, s: &str) -> Result<(), ParseError>;
+    Write fn secded_decode_from(&mut self, input: &ByteString) -> Result<EccReport, Error>;
+    Write fn restore(&mut self, snapshot: &Snapshot) -> Result<(), LengthError>;
+    Write fn take_into(&mut self, out: &mut ByteString) -> Result<(), LengthError>;
+    Write fn replace_from(&mut self, src: &mut ByteString) -> Result<(), LengthError>;
+    Write fn overflowing_add_assign(&mut self, rhs: &ByteString) -> bool;
+    Write fn add_assign_scalar(&mut self, value: u64) -> bool;
+    Write fn increment(&mut self) -> bool;
+    Write fn decrement(&mut self) -> bool;
+    Write fn negate(&mut self) -> bool;
+    Write fn checked_sub_assign(&mut self, rhs: &ByteString) -> bool;
+    Write fn nand_assign(&mut self, rhs: &ByteString);
+    Write fn try_nand_assign(&mut self, rhs: &ByteString) -> Result<(), LengthError>;
+    Write fn nor_assign(&mut self, rhs: &ByteString);
+    Write fn try_nor_assign(&mut self, rhs: &ByteString) -> Result<(), LengthError>;
+    Write fn xnor_assign(&mut self, rhs: &ByteString);
+    Write fn try_xnor_assign(&mut self, rhs: &ByteString) -> Result<(), LengthError>;
+    Write fn conditional_assign(&mut self, other: &ByteString, choice: bool) -> Result<(), LengthError>;
+    Write fn merge_bits(&mut self, other: &ByteString, mask: &ByteString);
+    Write fn try_merge_bits(&mut self, other: &ByteString, mask: &ByteString) -> Result<(), LengthError>;
+    Write fn gf256_mul_assign(&mut self, scalar: u8, poly: u8);
+    Write fn saturating_add_assign(&mut self, rhs: &ByteString) -> bool;
+    Write fn saturating_sub_assign(&mut self, rhs: &ByteString) -> bool;
+    Write fn abs_diff_assign(&mut self, rhs: &ByteString) -> bool;
+    Write fn try_div_assign(&mut self, divisor: &ByteString) -> Result<(), Error>;
+    Write fn try_rem_assign(&mut self, divisor: &ByteString) -> Result<(), Error>;
+    Write fn div_rem(&mut self, divisor: &ByteString, remainder: &mut ByteString) -> Result<(), Error>;
+    Write fn reconstruct_missing(&mut self, parity: &ByteString, present: &[&ByteString]) -> Result<(), LengthError>;
+    Write fn scramble_additive(&mut self, seed: u64);
+    Write fn scramble_multiplicative(&mut self, poly: u32);
+    Write fn descramble_multiplicative(&mut self, poly: u32);
+}
+
+delegate! {
+    Compare fn cmp_numeric(&self, other: &ByteString) -> Ordering;
+    Compare fn cmp_bytes(&self, other: &ByteString) -> Ordering;
+    Compare fn first_mismatch_chunked(&self, other: &ByteString) -> Option<usize>;
+    Compare fn approx_eq(&self, other: &ByteString, max_differing_bits: usize) -> Result<bool, LengthError>;
+    Compare fn within_byte_tolerance(&self, other: &ByteString, max_abs_delta: u8) -> Result<bool, LengthError>;
+    Compare fn ct_eq_padded(&self, other: &ByteString, compare_len: usize) -> bool;
+    Compare fn try_ct_eq_padded(&self, other: &ByteString, compare_len: usize) -> Result<bool, Error>;
+    Compare fn is_subset_of(&self, other: &ByteString) -> Result<bool, LengthError>;
+    Compare fn is_superset_of(&self, other: &ByteString) -> Result<bool, LengthError>;
+    Compare fn is_disjoint_from(&self, other: &ByteString) -> Result<bool, LengthError>;
+    Compare fn intersects(&self, other: &ByteString) -> Result<bool, LengthError>;
+}
+
+delegate! {
+    Shift fn rotl_bytes(&mut self, count: usize);
+    Shift fn try_rotl_bytes(&mut self, count: usize) -> Result<(), Error>;
+    Shift fn rotr_bytes(&mut self, count: usize);
+    Shift fn try_shl(&mut self, count: usize) -> Result<(), Error>;
+    Shift fn sar_assign(&mut self, count: usize);
+    Shift fn shl_carry(&mut self, count: usize) -> u64;
+    Shift fn try_shl_carry(&mut self, count: usize) -> Result<u64, Error>;
+    Shift fn shr_carry(&mut self, count: usize) -> u64;
+    Shift fn try_shr_carry(&mut self, count: usize) -> Result<u64, Error>;
+    Shift fn shl_assign_with_carry_in(&mut self, count: usize, carry_in: u64) -> u64;
+    Shift fn try_shl_assign_with_carry_in(&mut self, count: usize, carry_in: u64) -> Result<u64, Error>;
+    Shift fn shr_assign_with_carry_in(&mut self, count: usize, carry_in: u64) -> u64;
+    Shift fn try_shr_assign_with_carry_in(&mut self, count: usize, carry_in: u64) -> Result<u64, Error>;
+    Shift fn funnel_shl(&mut self, src: &ByteString, count: usize) -> Result<(), Error>;
+    Shift fn funnel_shr(&mut self, src: &ByteString, count: usize) -> Result<(), Error>;
+    Shift fn rotl_bit_range(&mut self, range: Range<usize>, count: usize) -> Result<(), Error>;
+    Shift fn rotl_bits(&mut self, count: usize);
+    Shift fn resync(&mut self, sync_word: &ByteString, sync_bits: usize) -> Result<usize, SyncError>;
+    Shift fn rotr_bits(&mut self, count: usize);
+    Shift fn scale_shr_round(&mut self, n: usize, mode: Rounding);
+    Shift fn scale_sar_round(&mut self, n: usize, mode: Rounding);
+    Shift fn scale_shl_checked(&mut self, n: usize) -> Result<(), Overflow>;
+}
+
+// Counted forwarding of the compound assignment operators
+macro_rules! delegate_assign {
+    ($($category:ident $trait:ident::$name:ident($rhs:ty);)*) => {
+        $(
+            impl<'s, 'a, 'b: 'a> $trait<$rhs> for CountingByteString<'s, 'a> {
+                #[track_caller]
+                fn $name(&mut self, rhs: $rhs) {
+                    self.record(Category::$category);
+                    self.bytes.$name(rhs);
+                }
+            }
+        )*
+    };
+}
+
+delegate_assign! {
+    Shift ShlAssign::shl_assign(usize);
+    Shift ShrAssign::shr_assign(usize);
+    Write BitAndAssign::bitand_assign(&'b ByteString<'b>);
+    Write BitOrAssign::bitor_assign(&'b ByteString<'b>);
+    Write BitXorAssign::bitxor_assign(&'b ByteString<'b>);
+    Write AddAssign::add_assign(&'b ByteString<'b>);
+    Write AddAssign::add_assign(u64);
+    Write SubAssign::sub_assign(&'b ByteString<'b>);
+    Write MulAssign::mul_assign(&'b ByteString<'b>);
+    Write MulAssign::mul_assign(u8);
+    Write MulAssign::mul_assign(u64);
+    Write DivAssign::div_assign(&'b ByteString<'b>);
+    Write RemAssign::rem_assign(&'b ByteString<'b>);
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::view;
+    use crate::testing::fill_lcg;
+
+    // The same script on a plain and a counted view must leave identical bytes
+    fn script_plain(bytes: &mut ByteString, other: &ByteString) -> (Ordering, bool, u64) {
+        bytes.set_bit(3, true);
+        bytes.write_u16_at(1, 0xBEEF, Endianness::Little).unwrap();
+        *bytes <<= 5;
+        bytes.rotl_bytes(2);
+        let ordering = bytes.cmp_numeric(other);
+        let zero = bytes.is_zero();
+        let fold = bytes.xor_fold_u64(Endianness::Big);
+        bytes.try_div_assign(other).unwrap();
+        (ordering, zero, fold)
+    }
+
+    fn script_counted(bytes: &mut CountingByteString, other: &ByteString) -> (Ordering, bool, u64) {
+        bytes.set_bit(3, true);
+        bytes.write_u16_at(1, 0xBEEF, Endianness::Little).unwrap();
+        *bytes <<= 5;
+        bytes.rotl_bytes(2);
+        let ordering = bytes.cmp_numeric(other);
+        let zero = bytes.is_zero();
+        let fold = bytes.xor_fold_u64(Endianness::Big);
+        bytes.try_div_assign(other).unwrap();
+        (ordering, zero, fold)
+    }
+
+    #[test]
+    fn test_counts_scripted_sequence() {
+        let mut raw = [0u8; 6];
+        let mut divisor = [0x03u8];
+        let divisor = ByteString::new(&mut divisor);
+
+        let stats = Cell::new(OpStats::default());
+        let mut counted = CountingByteString::new(ByteString::new(&mut raw), &stats);
+        script_counted(&mut counted, &divisor);
+
+        let (len, expected) = (6, |calls| OpCount { calls, bytes: calls * 6 });
+        assert_eq!(counted.byte_len(), len);
+        assert_eq!(counted.report(), OpStats { reads: expected(2), writes: expected(3), compares: expected(1), shifts: expected(2) });
+
+        // Uncounted access and a second wrapper sharing the same cell
+        counted.inner().set_zero();
+        let mut other = [0u8; 2];
+        let mut second = CountingByteString::new(ByteString::new(&mut other), &stats);
+        second.iter_mut().for_each(|e| *e = 1);
+        assert_eq!(stats.get().writes, OpCount { calls: 4, bytes: 20 });
+    }
+
+    #[test]
+    fn test_results_match_plain() {
+        for seed in 1..50 {
+            let (mut plain_raw, mut counted_raw) = ([0u8; 9], [0u8; 9]);
+            let mut divisor_raw = [0u8; 2];
+            fill_lcg(&mut ByteString::new(&mut plain_raw), seed);
+            fill_lcg(&mut ByteString::new(&mut counted_raw), seed);
+            fill_lcg(&mut ByteString::new(&mut divisor_raw), !seed);
+            divisor_raw[1] |= 1;
+
+            let divisor = view(&mut divisor_raw, seed % 3 == 0);
+
+            let mut plain = view(&mut plain_raw, seed % 2 == 0);
+            let plain_result = script_plain(&mut plain, &divisor);
+
+            let stats = Cell::new(OpStats::default());
+            let mut counted = CountingByteString::new(ByteString::new(&mut counted_raw), &stats);
+            if seed % 2 == 0 { counted.interpret_reverse_endian(); }
+            let counted_result = script_counted(&mut counted, &divisor);
+
+            assert_eq!(plain_result, counted_result);
+            assert_eq!(plain_raw, counted_raw);
+        }
+    }
+
+    #[test]
+    fn test_report_golden() {
+        let stats = OpStats { reads: OpCount { calls: 3, bytes: 48 }, shifts: OpCount { calls: 1, bytes: 16 }, ..OpStats::default() };
+
+        assert_eq!(stats.to_string(), concat!(
+            "reads             3 calls           48 bytes\n",
+            "writes            0 calls            0 bytes\n",
+            "compares          0 calls            0 bytes\n",
+            "shifts            1 calls           16 bytes",
+        ));
+    }
+}
@@ -0,0 +1,150 @@
+//! Filling a ByteString from text streams
+
+use std::io::{BufRead, ErrorKind};
+
+use crate::{
+    bytes::ByteString,
+    error::Error
+};
+
+/// Stream ASCII hex from `reader` into the logical bytes of `out`, returns the number of decoded bytes.
+///
+/// Whitespace, including newlines, is skipped anywhere. Reading stops at EOF or before the first
+/// byte that is neither hex nor whitespace, which is left unconsumed in the reader.
+/// Fails with [`Error::MalformedInput`] on an odd number of hex digits and
+/// [`Error::OutputTooSmall`] if `out` cannot hold the data.
+///
+/// ```
+/// use std::io::{BufRead, Cursor};
+/// use rebite::{bytes::ByteString, error::Error, io::read_hex_into};
+///
+/// let mut reader = Cursor::new("de ad\nbe ef;rest");
+/// let mut raw = [0u8; 8];
+///
+/// assert_eq!(read_hex_into(&mut reader, &mut ByteString::new(&mut raw)), Ok(4));
+/// assert_eq!(raw[..4], [0xDE, 0xAD, 0xBE, 0xEF]);
+///
+/// // The stop byte is still in the reader
+/// assert_eq!(reader.fill_buf().unwrap(), b";rest");
+///
+/// assert_eq!(read_hex_into(Cursor::new("abc"), &mut ByteString::new(&mut raw)), Err(Error::MalformedInput));
+/// ```
+pub fn read_hex_into<R: BufRead>(mut reader: R, out: &mut ByteString) -> Result<usize, Error> {
+
+    let mut dst = out.iter_mut();
+    let mut written = 0;
+    let mut high: Option<u8> = None;
+
+    'stream: loop {
+        let chunk = match reader.fill_buf() {
+            Ok(chunk) => chunk,
+            Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err.into())
+        };
+
+        if chunk.is_empty() {
+            break;
+        }
+
+        let mut used = 0;
+        for c in chunk {
+            if c.is_ascii_whitespace() {
+                used += 1;
+                continue;
+            }
+
+            let Some(nibble) = (*c as char).to_digit(16) else {
+                reader.consume(used);
+                break 'stream;
+            };
+
+            match high.take() {
+                None => high = Some(nibble as u8),
+                Some(h) => {
+                    *dst.next().ok_or(Error::OutputTooSmall)? = h << 4 | nibble as u8;
+                    written += 1;
+                }
+            }
+            used += 1;
+        }
+
+        reader.consume(used);
+    }
+
+    if high.is_some() {
+        return Err(Error::MalformedInput);
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::{Cursor, Read};
+
+    use super::*;
+    use crate::test_util::view;
+
+    fn read(input: &str, len: usize, reversed: bool) -> (Result<usize, Error>, Vec<u8>) {
+        let mut raw = vec![0u8; len];
+        let mut out = view(&mut raw, reversed);
+
+        let result = read_hex_into(Cursor::new(input.as_bytes()), &mut out);
+        (result, raw)
+    }
+
+    #[test]
+    fn test_multi_line() {
+        let input = "0011 2233\n44556677\r\n  8899aAbB\nccddeeff\n";
+        let (result, raw) = read(input, 16, false);
+
+        assert_eq!(result, Ok(16));
+        assert_eq!(raw, [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]);
+    }
+
+    #[test]
+    fn test_logical_order() {
+        let (result, raw) = read("0102", 3, true);
+        assert_eq!(result, Ok(2));
+        assert_eq!(raw, [0x00, 0x02, 0x01]);
+    }
+
+    #[test]
+    fn test_trailing_garbage_left_in_reader() {
+        let mut raw = [0u8; 4];
+        let mut out = ByteString::new(&mut raw);
+        let mut reader = Cursor::new(&b"ab cd\n; xyz"[..]);
+
+        assert_eq!(read_hex_into(&mut reader, &mut out), Ok(2));
+
+        let mut rest = String::new();
+        reader.read_to_string(&mut rest).unwrap();
+        assert_eq!(rest, "; xyz");
+        assert_eq!(raw, [0xAB, 0xCD, 0, 0]);
+    }
+
+    #[test]
+    fn test_small_buffered_reader() {
+        let input = "de ad\nbe ef".repeat(3);
+        let mut raw = [0u8; 12];
+        let reader = std::io::BufReader::with_capacity(3, input.as_bytes());
+
+        assert_eq!(read_hex_into(reader, &mut ByteString::new(&mut raw)), Ok(12));
+        assert_eq!(raw, [0xDE, 0xAD, 0xBE, 0xEF].repeat(3)[..]);
+    }
+
+    #[test]
+    fn test_exact_fit_and_overflow() {
+        assert_eq!(read("aabb", 2, false), (Ok(2), vec![0xAA, 0xBB]));
+        assert_eq!(read("aabbcc", 2, false).0, Err(Error::OutputTooSmall));
+    }
+
+    #[test]
+    fn test_odd_nibbles_and_empty() {
+        assert_eq!(read("abc", 4, false).0, Err(Error::MalformedInput));
+        assert_eq!(read("a b\nc x", 4, false).0, Err(Error::MalformedInput));
+        assert_eq!(read("", 4, false), (Ok(0), vec![0; 4]));
+        assert_eq!(read(" \n\t", 0, false).0, Ok(0));
+    }
+}
@@ -0,0 +1,368 @@
+//! Named bit fields over a buffer, for register maps and packet headers
+
+use crate::{
+    bytes::ByteString,
+    error::Error,
+    typed::Endianness
+};
+
+/// One named field of a [`Layout`].
+///
+/// `bit_offset` counts in wire order, from the most significant bit of logical byte 0, as
+/// protocol diagrams do. Little-endian fields must be a whole number of bytes wide.
+///
+/// ```
+/// use rebite::{bytes::ByteString, layout::{FieldSpec, Layout}, typed::Endianness};
+///
+/// // A 4 bit version, a 12 bit big-endian length and a little-endian u16
+/// const FIELDS: [FieldSpec; 3] = [
+///     FieldSpec::bits("version", 0, 4),
+///     FieldSpec::bits("length", 4, 12),
+///     FieldSpec::new("id", 16, 16, Endianness::Little),
+/// ];
+///
+/// let mut raw = [0x41u8, 0x23, 0x34, 0x12];
+/// let layout = Layout::new(&FIELDS, 4).unwrap();
+/// let bytes = ByteString::new(&mut raw);
+/// assert_eq!(layout.read(&bytes, "version"), Ok(4));
+/// assert_eq!(layout.read(&bytes, "length"), Ok(0x123));
+/// assert_eq!(layout.read(&bytes, "id"), Ok(0x1234));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldSpec {
+    /// Name used to read and write the field
+    pub name: &'static str,
+    /// Position of the first bit in wire order
+    pub bit_offset: usize,
+    /// Width in bits, 1 to 64
+    pub bit_width: usize,
+    /// Byte order of the field value
+    pub endian: Endianness,
+}
+
+impl FieldSpec {
+
+    /// Field of `bit_width` bits at wire order `bit_offset`.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, layout::{FieldSpec, Layout}, typed::Endianness};
+    ///
+    /// const FIELDS: [FieldSpec; 1] = [FieldSpec::new("port", 8, 16, Endianness::Little)];
+    /// let layout = Layout::new(&FIELDS, 3).unwrap();
+    ///
+    /// let mut raw = [0x00u8, 0x50, 0x00];
+    /// assert_eq!(layout.read(&ByteString::new(&mut raw), "port"), Ok(80));
+    /// ```
+    pub const fn new(name: &'static str, bit_offset: usize, bit_width: usize, endian: Endianness) -> Self {
+        Self { name, bit_offset, bit_width, endian }
+    }
+
+    /// Big-endian field, the common case for bit fields.
+    ///
+    /// ```
+    /// use rebite::{layout::FieldSpec, typed::Endianness};
+    ///
+    /// assert_eq!(FieldSpec::bits("flag", 7, 1), FieldSpec::new("flag", 7, 1, Endianness::Big));
+    /// ```
+    pub const fn bits(name: &'static str, bit_offset: usize, bit_width: usize) -> Self {
+        Self::new(name, bit_offset, bit_width, Endianness::Big)
+    }
+}
+
+/// Field descriptors validated against a buffer length, read and written by name.
+///
+/// ```
+/// use rebite::{bytes::ByteString, error::Error, layout::{FieldSpec, Layout}};
+///
+/// const FIELDS: [FieldSpec; 2] = [FieldSpec::bits("kind", 0, 3), FieldSpec::bits("count", 3, 5)];
+/// let layout = Layout::new_disjoint(&FIELDS, 1).unwrap();
+///
+/// let mut raw = [0u8];
+/// let mut bytes = ByteString::new(&mut raw);
+/// layout.write(&mut bytes, "kind", 0b101).unwrap();
+/// layout.write(&mut bytes, "count", 3).unwrap();
+/// assert_eq!(raw, [0b101_00011]);
+///
+/// assert_eq!(layout.read(&ByteString::new(&mut raw), "size"), Err(Error::UnknownField));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Layout<'f> {
+    fields: &'f [FieldSpec],
+    byte_len: usize,
+}
+
+impl<'f> Layout<'f> {
+
+    /// Check that every field fits into `byte_len` bytes, is 1..=64 bits wide and has a valid endianness.
+    ///
+    /// ```
+    /// use rebite::{error::Error, layout::{FieldSpec, Layout}, typed::Endianness};
+    ///
+    /// assert!(matches!(Layout::new(&[FieldSpec::bits("a", 12, 5)], 2), Err(Error::OutOfBounds)));
+    /// assert!(matches!(Layout::new(&[FieldSpec::bits("a", 0, 0)], 2), Err(Error::ZeroLength)));
+    ///
+    /// // Byte swapping needs whole bytes
+    /// assert!(matches!(Layout::new(&[FieldSpec::new("a", 0, 12, Endianness::Little)], 2), Err(Error::MalformedInput)));
+    ///
+    /// // Overlapping fields are allowed, see new_disjoint
+    /// assert!(Layout::new(&[FieldSpec::bits("a", 0, 8), FieldSpec::bits("b", 4, 8)], 2).is_ok());
+    /// ```
+    pub fn new(fields: &'f [FieldSpec], byte_len: usize) -> Result<Self, Error> {
+
+        for field in fields {
+            if field.bit_width == 0 {
+                return Err(Error::ZeroLength);
+            }
+
+            let end = field.bit_offset.checked_add(field.bit_width).ok_or(Error::OutOfBounds)?;
+            if field.bit_width > 64 || end > byte_len * 8 {
+                return Err(Error::OutOfBounds);
+            }
+
+            if field.endian == Endianness::Little && !field.bit_width.is_multiple_of(8) {
+                return Err(Error::MalformedInput);
+            }
+        }
+
+        Ok(Self { fields, byte_len })
+    }
+
+    /// Like [`Layout::new`], and additionally reject fields sharing bits, reported as a wire order bit range.
+    ///
+    /// ```
+    /// use rebite::{error::Error, layout::{FieldSpec, Layout}};
+    ///
+    /// let fields = [FieldSpec::bits("a", 0, 8), FieldSpec::bits("b", 4, 8)];
+    /// assert!(matches!(Layout::new_disjoint(&fields, 2), Err(Error::Overlap { start: 4, end: 8 })));
+    /// ```
+    pub fn new_disjoint(fields: &'f [FieldSpec], byte_len: usize) -> Result<Self, Error> {
+        let layout = Self::new(fields, byte_len)?;
+
+        let mut spans: Vec<(usize, usize)> = fields.iter().map(|e| (e.bit_offset, e.bit_offset + e.bit_width)).collect();
+        spans.sort_unstable();
+
+        for pair in spans.windows(2) {
+            if pair[1].0 < pair[0].1 {
+                return Err(Error::Overlap { start: pair[1].0, end: pair[0].1.min(pair[1].1) });
+            }
+        }
+
+        Ok(layout)
+    }
+
+    /// The field descriptors, in the order given.
+    ///
+    /// ```
+    /// use rebite::layout::{FieldSpec, Layout};
+    ///
+    /// let fields = [FieldSpec::bits("b", 8, 8), FieldSpec::bits("a", 0, 8)];
+    /// let layout = Layout::new(&fields, 2).unwrap();
+    ///
+    /// assert_eq!(layout.fields().iter().map(|e| e.name).collect::<Vec<_>>(), ["b", "a"]);
+    /// ```
+    pub fn fields(&self) -> &'f [FieldSpec] {
+        self.fields
+    }
+
+    /// Buffer length the layout was validated against.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error, layout::{FieldSpec, Layout}};
+    ///
+    /// let fields = [FieldSpec::bits("a", 0, 4)];
+    /// let layout = Layout::new(&fields, 4).unwrap();
+    /// assert_eq!(layout.byte_len(), 4);
+    ///
+    /// // Shorter buffers are refused even if the field itself would fit
+    /// let mut raw = [0xF0u8];
+    /// assert_eq!(layout.read(&ByteString::new(&mut raw), "a"), Err(Error::OutOfBounds));
+    /// ```
+    pub fn byte_len(&self) -> usize {
+        self.byte_len
+    }
+
+    /// Value of the named field, fails with [`Error::UnknownField`] if there is none.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error, layout::{FieldSpec, Layout}};
+    ///
+    /// const FIELDS: [FieldSpec; 2] = [FieldSpec::bits("version", 0, 4), FieldSpec::bits("length", 4, 12)];
+    /// let layout = Layout::new(&FIELDS, 2).unwrap();
+    ///
+    /// // Bit offsets count from the most significant bit of logical byte 0
+    /// let mut raw = [0x41u8, 0x23];
+    /// let bytes = ByteString::new(&mut raw);
+    /// assert_eq!(layout.read(&bytes, "version"), Ok(0x4));
+    /// assert_eq!(layout.read(&bytes, "length"), Ok(0x123));
+    /// assert_eq!(layout.read(&bytes, "flags"), Err(Error::UnknownField));
+    /// ```
+    pub fn read(&self, bytes: &ByteString, name: &str) -> Result<u64, Error> {
+        self.read_field(bytes, self.field(name)?)
+    }
+
+    /// Store `value` into the named field, bits above the field width are ignored.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, layout::{FieldSpec, Layout}};
+    ///
+    /// let fields = [FieldSpec::bits("version", 0, 4), FieldSpec::bits("length", 4, 12)];
+    /// let layout = Layout::new(&fields, 2).unwrap();
+    ///
+    /// let mut raw = [0x00u8, 0x00];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// layout.write(&mut bytes, "version", 0x16).unwrap();
+    /// layout.write(&mut bytes, "length", 0xABC).unwrap();
+    ///
+    /// assert_eq!(raw, [0x6A, 0xBC]);
+    /// ```
+    pub fn write(&self, bytes: &mut ByteString, name: &str, value: u64) -> Result<(), Error> {
+        let field = self.field(name)?;
+        let value = match field.endian {
+            Endianness::Big => value,
+            Endianness::Little => swap_width(value, field.bit_width)
+        };
+
+        bytes.write_bit_range(self.value_range(bytes, field)?, value)
+    }
+
+    /// Every field with its value, in declaration order.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, layout::{FieldSpec, Layout}};
+    ///
+    /// let fields = [FieldSpec::bits("high", 0, 4), FieldSpec::bits("low", 4, 4)];
+    /// let layout = Layout::new(&fields, 1).unwrap();
+    ///
+    /// let mut raw = [0xA5u8];
+    /// let bytes = ByteString::new(&mut raw);
+    /// let values: Vec<_> = layout.read_all(&bytes).collect();
+    /// assert_eq!(values, [("high", Ok(0xA)), ("low", Ok(0x5))]);
+    /// ```
+    pub fn read_all<'b>(&'b self, bytes: &'b ByteString) -> impl Iterator<Item = (&'static str, Result<u64, Error>)> + 'b {
+        self.fields.iter().map(move |field| (field.name, self.read_field(bytes, field)))
+    }
+
+    fn field(&self, name: &str) -> Result<&'f FieldSpec, Error> {
+        self.fields.iter().find(|e| e.name == name).ok_or(Error::UnknownField)
+    }
+
+    fn read_field(&self, bytes: &ByteString, field: &FieldSpec) -> Result<u64, Error> {
+        let value = bytes.read_bit_range(self.value_range(bytes, field)?)?;
+
+        Ok(match field.endian {
+            Endianness::Big => value,
+            Endianness::Little => swap_width(value, field.bit_width)
+        })
+    }
+
+    // Wire order bit offsets to offsets from the least significant bit of the value
+    fn value_range(&self, bytes: &ByteString, field: &FieldSpec) -> Result<std::ops::Range<usize>, Error> {
+        if bytes.byte_len() < self.byte_len {
+            return Err(Error::OutOfBounds);
+        }
+
+        let end = bytes.bit_len() - field.bit_offset;
+        Ok(end - field.bit_width..end)
+    }
+}
+
+// Reverse the byte order of the low `width` bits, `width` is a multiple of 8
+const fn swap_width(value: u64, width: usize) -> u64 {
+    value.swap_bytes() >> (64 - width)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::{view, XorShift};
+
+    // IPv4 header with one option word
+    const IPV4: [FieldSpec; 14] = [
+        FieldSpec::bits("version", 0, 4),
+        FieldSpec::bits("ihl", 4, 4),
+        FieldSpec::bits("dscp", 8, 6),
+        FieldSpec::bits("ecn", 14, 2),
+        FieldSpec::bits("total_length", 16, 16),
+        FieldSpec::bits("identification", 32, 16),
+        FieldSpec::bits("flags", 48, 3),
+        FieldSpec::bits("fragment_offset", 51, 13),
+        FieldSpec::bits("ttl", 64, 8),
+        FieldSpec::bits("protocol", 72, 8),
+        FieldSpec::bits("checksum", 80, 16),
+        FieldSpec::bits("source", 96, 32),
+        FieldSpec::bits("destination", 128, 32),
+        FieldSpec::bits("options", 160, 32),
+    ];
+
+    #[test]
+    fn test_ipv4_known_header() {
+        let mut raw = [
+            0x46, 0x00, 0x00, 0x2C, 0x1C, 0x46, 0x40, 0x00, 0x40, 0x06, 0xB1, 0xE6,
+            0xC0, 0xA8, 0x00, 0x68, 0xC0, 0xA8, 0x00, 0x01, 0x94, 0x04, 0x00, 0x00,
+        ];
+        let bytes = ByteString::new(&mut raw);
+        let layout = Layout::new_disjoint(&IPV4, 24).unwrap();
+
+        assert_eq!(layout.read(&bytes, "version"), Ok(4));
+        assert_eq!(layout.read(&bytes, "ihl"), Ok(6));
+        assert_eq!(layout.read(&bytes, "flags"), Ok(0b010));
+        assert_eq!(layout.read(&bytes, "fragment_offset"), Ok(0));
+        assert_eq!(layout.read(&bytes, "protocol"), Ok(6));
+        assert_eq!(layout.read(&bytes, "source"), Ok(0xC0A8_0068));
+        assert_eq!(layout.read(&bytes, "options"), Ok(0x9404_0000));
+        assert_eq!(layout.read(&bytes, "padding"), Err(Error::UnknownField));
+    }
+
+    #[test]
+    fn test_ipv4_round_trip() {
+        let mut rng = XorShift::new(0x248);
+        let layout = Layout::new_disjoint(&IPV4, 24).unwrap();
+
+        for _ in 0..100 {
+            let values: Vec<u64> = IPV4.iter().map(|e| rng.next_u64() & (u64::MAX >> (64 - e.bit_width))).collect();
+            let reversed = rng.next_bool();
+
+            let mut raw = [0u8; 24];
+            let mut bytes = view(&mut raw, reversed);
+
+            for (field, value) in IPV4.iter().zip(&values) {
+                layout.write(&mut bytes, field.name, *value).unwrap();
+            }
+
+            let read: Vec<(&str, u64)> = layout.read_all(&bytes).map(|(name, value)| (name, value.unwrap())).collect();
+            let expected: Vec<(&str, u64)> = IPV4.iter().map(|e| e.name).zip(values).collect();
+            assert_eq!(read, expected);
+        }
+    }
+
+    #[test]
+    fn test_little_endian_field() {
+        let fields = [FieldSpec::bits("kind", 0, 8), FieldSpec::new("length", 8, 16, Endianness::Little)];
+        let layout = Layout::new(&fields, 3).unwrap();
+
+        let mut raw = [0x01u8, 0x34, 0x12];
+        let mut bytes = ByteString::new(&mut raw);
+        assert_eq!(layout.read(&bytes, "length"), Ok(0x1234));
+
+        layout.write(&mut bytes, "length", 0xBEEF).unwrap();
+        assert_eq!(raw, [0x01, 0xEF, 0xBE]);
+    }
+
+    #[test]
+    fn test_construction_errors() {
+        let overlapping = [FieldSpec::bits("a", 0, 12), FieldSpec::bits("b", 8, 8)];
+        assert!(Layout::new(&overlapping, 2).is_ok());
+        assert_eq!(Layout::new_disjoint(&overlapping, 2).unwrap_err(), Error::Overlap { start: 8, end: 12 });
+
+        assert_eq!(Layout::new(&[FieldSpec::bits("a", 10, 8)], 2).unwrap_err(), Error::OutOfBounds);
+        assert_eq!(Layout::new(&[FieldSpec::bits("a", 0, 65)], 16).unwrap_err(), Error::OutOfBounds);
+        assert_eq!(Layout::new(&[FieldSpec::bits("a", usize::MAX, 2)], 2).unwrap_err(), Error::OutOfBounds);
+        assert_eq!(Layout::new(&[FieldSpec::bits("a", 0, 0)], 2).unwrap_err(), Error::ZeroLength);
+        assert_eq!(Layout::new(&[FieldSpec::new("a", 0, 12, Endianness::Little)], 2).unwrap_err(), Error::MalformedInput);
+
+        let layout = Layout::new(&IPV4, 24).unwrap();
+        let mut short = [0u8; 20];
+        assert_eq!(layout.read(&ByteString::new(&mut short), "version"), Err(Error::OutOfBounds));
+    }
+}
@@ -1,3 +1,104 @@
+//! Views over mutable byte slices with a switchable logical byte order.
+//!
+//! # Panics
+//!
+//! Operations that can fail on bad arguments come in pairs. The plain method panics with the
+//! caller's location (`#[track_caller]`) and documents when, its `try_` twin returns the same
+//! failure as an error and leaves the buffer untouched. Operations with no invalid arguments,
+//! such as `<<=` which saturates to zero, never panic and have no twin.
+//!
+//! # Documentation
+//!
+//! Every public item is documented, `#![deny(missing_docs)]` keeps it that way. The examples
+//! show the edge cases of the documented semantics and run as tests with `cargo test --doc`.
+//!
+//! # Features
+//!
+//! `forbid-unsafe` builds the crate without any unsafe code, enforced by `#![forbid(unsafe_code)]`.
+//! Internal fast paths switch to safe equivalents and the public API stays the same, except for the
+//! `unsafe fn`s of [`raw`] which rebuild a ByteString from a pointer. [`bytes::ByteString::with_words_mut`]
+//! then works on a copy of the words, [`scratch::Scratch::reset`] cannot reclaim allocated regions,
+//! and wiping secrets falls back to plain stores passed through `std::hint::black_box`, which is
+//! best-effort rather than guaranteed to survive optimization.
+//!
+//! `debug-strict` makes `&=`, `|=`, `^=` and the nand, nor and xnor methods panic in debug builds
+//! when the operands differ in length rather than silently leaving the excess bytes alone. Tests can
+//! install a handler with `set_mismatch_handler` to record the mismatches instead. Release builds
+//! are unaffected.
+//!
+//! `instrument` adds [`instrument::CountingByteString`], a wrapper counting operations for profiling.
+//!
+//! `testing` exposes the fixture generators and assertions of [`testing`] to other crates' tests.
+
+#![cfg_attr(feature = "forbid-unsafe", forbid(unsafe_code))]
+#![deny(missing_docs)]
+
 mod util;
+mod arith;
+mod strict;
+mod word_split;
+pub mod accumulate;
+pub mod bit_order;
+pub mod bitfield;
+pub mod bitset;
+pub mod bounded;
 pub mod bytes;
 pub mod bytes_iter;
+pub mod cbor;
+pub mod checksum;
+pub mod clmul;
+pub mod cmp;
+pub mod cobs;
+pub mod ct;
+pub mod dedup;
+pub mod delta;
+pub mod ecc;
+pub mod error;
+pub mod export;
+pub mod fixed;
+pub mod fmt;
+pub mod fold;
+pub mod guard;
+#[cfg(any(test, feature = "instrument"))]
+pub mod instrument;
+pub mod io;
+pub mod layout;
+pub mod literal;
+pub mod matrix;
+pub mod network;
+pub mod ops;
+pub mod parity;
+pub mod patch;
+pub mod permute;
+pub mod radix;
+pub mod raw;
+pub mod reader;
+pub mod region;
+pub mod resize;
+pub mod rotate;
+pub mod scramble;
+pub mod scratch;
+pub mod snapshot;
+pub mod sort;
+pub mod sync;
+pub mod table;
+pub mod text;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod transfer;
+pub mod trim;
+pub mod typed;
+pub mod window;
+pub mod words;
+pub mod writer;
+
+pub use sort::{sort_byte_strings, sort_slices_numeric, binary_search_numeric};
+#[cfg(all(feature = "debug-strict", debug_assertions))]
+pub use strict::{set_mismatch_handler, clear_mismatch_handler};
+
+#[cfg(test)]
+mod test_util;
+#[cfg(test)]
+mod consistency;
+#[cfg(test)]
+mod word_paths;
@@ -0,0 +1,143 @@
+//! Const construction of fixed width byte arrays from integer literals, see [`byte_value!`](crate::byte_value)
+
+/// `N` byte encoding of an unsigned value, panics (at compile time in const contexts) if it does not fit.
+///
+/// ```
+/// use rebite::literal::encode_unsigned;
+///
+/// const MAGIC: [u8; 4] = encode_unsigned(0xCAFE, true);
+/// assert_eq!(MAGIC, [0x00, 0x00, 0xCA, 0xFE]);
+///
+/// // Wider than a u128, the extra bytes are zero
+/// assert_eq!(encode_unsigned::<18>(1, false)[16..], [0, 0]);
+/// ```
+pub const fn encode_unsigned<const N: usize>(value: u128, big_endian: bool) -> [u8; N] {
+
+    if N < 16 && value >> (N * 8) != 0 {
+        panic!("value does not fit into the requested width");
+    }
+
+    encode(value, 0x00, big_endian)
+}
+
+/// `N` byte two's complement encoding of a signed value, panics if it does not fit.
+///
+/// ```
+/// use rebite::literal::encode_signed;
+///
+/// assert_eq!(encode_signed::<2>(-128, false), [0x80, 0xFF]);
+///
+/// // 128 needs a ninth bit for the sign
+/// assert!(std::panic::catch_unwind(|| encode_signed::<1>(128, false)).is_err());
+/// ```
+pub const fn encode_signed<const N: usize>(value: i128, big_endian: bool) -> [u8; N] {
+
+    let fits = if N == 0 { value == 0 }
+    else if N >= 16 { true }
+    else {
+        let rest = value >> (N * 8 - 1);
+        rest == 0 || rest == -1
+    };
+
+    if !fits {
+        panic!("value does not fit into the requested width");
+    }
+
+    encode(value as u128, if value < 0 { 0xFF } else { 0x00 }, big_endian)
+}
+
+const fn encode<const N: usize>(value: u128, fill: u8, big_endian: bool) -> [u8; N] {
+
+    let mut out = [fill; N];
+    let mut ix = 0;
+
+    // Least significant byte first, beyond 16 bytes only the fill remains
+    while ix < N && ix < 16 {
+        let byte = (value >> (ix * 8)) as u8;
+        if big_endian { out[N - 1 - ix] = byte; } else { out[ix] = byte; }
+        ix += 1;
+    }
+
+    out
+}
+
+/// Fixed width byte array holding an integer literal, evaluated at compile time.
+///
+/// `u` zero-extends an unsigned value, `i` sign-extends a signed one. A literal that does not
+/// fit into the width is a compile error.
+///
+/// ```
+/// use rebite::byte_value;
+///
+/// const ONE: [u8; 16] = byte_value!(u, 0x01, 16 bytes, be);
+/// assert_eq!(ONE[15], 0x01);
+///
+/// let key = byte_value!(u, 0x1234_5678_9ABC, 8 bytes, le);
+/// assert_eq!(key, [0xBC, 0x9A, 0x78, 0x56, 0x34, 0x12, 0x00, 0x00]);
+///
+/// assert_eq!(byte_value!(i, -2, 3 bytes, be), [0xFF, 0xFF, 0xFE]);
+/// ```
+///
+/// ```compile_fail
+/// use rebite::byte_value;
+///
+/// let too_wide = byte_value!(u, 0x1_0000, 2 bytes, be);
+/// ```
+#[macro_export]
+macro_rules! byte_value {
+    (u, $value:expr, $width:literal bytes, be) => {
+        const { $crate::literal::encode_unsigned::<$width>($value, true) }
+    };
+    (u, $value:expr, $width:literal bytes, le) => {
+        const { $crate::literal::encode_unsigned::<$width>($value, false) }
+    };
+    (i, $value:expr, $width:literal bytes, be) => {
+        const { $crate::literal::encode_signed::<$width>($value, true) }
+    };
+    (i, $value:expr, $width:literal bytes, le) => {
+        const { $crate::literal::encode_signed::<$width>($value, false) }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::bytes::ByteString;
+
+    const KEY: [u8; 16] = crate::byte_value!(u, 0x1234_5678_9ABC, 16 bytes, be);
+
+    #[test]
+    fn test_const_item() {
+        let mut expected = [0u8; 16];
+        expected[10..].copy_from_slice(&[0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC]);
+        assert_eq!(KEY, expected);
+    }
+
+    #[test]
+    fn test_local() {
+        let mut le = crate::byte_value!(u, 0x0102, 4 bytes, le);
+        assert_eq!(le, [0x02, 0x01, 0x00, 0x00]);
+
+        let mut bytes = ByteString::new(&mut le);
+        bytes.interpret_reverse_endian();
+        assert!(bytes.iter().eq(crate::byte_value!(u, 0x0102, 4 bytes, be).iter()));
+    }
+
+    #[test]
+    fn test_signed_and_wide() {
+        assert_eq!(crate::byte_value!(i, -1, 2 bytes, le), [0xFF, 0xFF]);
+        assert_eq!(crate::byte_value!(i, 127, 1 bytes, be), [0x7F]);
+        assert_eq!(crate::byte_value!(i, -128, 1 bytes, be), [0x80]);
+        assert_eq!(crate::byte_value!(i, -1, 18 bytes, be), [0xFF; 18]);
+        assert_eq!(crate::byte_value!(u, u128::MAX, 17 bytes, be)[0], 0x00);
+        assert_eq!(crate::byte_value!(u, 0, 0 bytes, be), []);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_runtime_overflow_panics() {
+        let value = 0x100;
+        let _ = encode_signed::<1>(value, true);
+    }
+}
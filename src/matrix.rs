@@ -0,0 +1,413 @@
+//! Row-major two dimensional views for images and LED frames
+
+use crate::{
+    bytes::ByteString,
+    error::Error
+};
+
+/// Row-major matrix over the logical bytes of a ByteString, see [`ByteString::as_matrix_mut`].
+///
+/// Element `(x, y)` is logical byte `y * width + x`, so with the flag set the rows run from the
+/// physical end of the buffer.
+///
+/// ```
+/// use rebite::bytes::ByteString;
+///
+/// let mut raw = [1u8, 2, 3, 4, 5, 6];
+/// let mut bytes = ByteString::new(&mut raw);
+/// let mut matrix = bytes.as_matrix_mut(3).unwrap();
+///
+/// // Row 1, column 2
+/// assert_eq!(matrix.get(2, 1), 6);
+/// matrix.set(0, 1, 0);
+/// assert_eq!(raw, [1, 2, 3, 0, 5, 6]);
+/// ```
+pub struct ByteMatrix<'m> {
+    bytes: ByteString<'m>,
+    width: usize,
+}
+
+impl<'a> ByteString<'a> {
+
+    /// View the buffer as rows of `width` bytes.
+    ///
+    /// Fails with [`Error::ZeroLength`] for a zero width and with [`Error::LengthMismatch`] if the
+    /// byte length is not a multiple of it.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0u8; 6];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// let matrix = bytes.as_matrix_mut(3).unwrap();
+    /// assert_eq!((matrix.width(), matrix.height()), (3, 2));
+    ///
+    /// assert!(bytes.as_matrix_mut(4).is_err());
+    /// ```
+    pub fn as_matrix_mut(&mut self, width: usize) -> Result<ByteMatrix<'_>, Error> {
+        if width == 0 {
+            return Err(Error::ZeroLength);
+        }
+        if !self.byte_len().is_multiple_of(width) {
+            return Err(Error::LengthMismatch);
+        }
+
+        Ok(ByteMatrix { bytes: self.sub_view(0..self.byte_len()), width })
+    }
+}
+
+impl<'m> ByteMatrix<'m> {
+
+    /// Bytes per row.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0u8; 8];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.as_matrix_mut(4).unwrap().width(), 4);
+    /// ```
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of rows.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0u8; 8];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.as_matrix_mut(4).unwrap().height(), 2);
+    /// ```
+    pub fn height(&self) -> usize {
+        self.bytes.byte_len() / self.width
+    }
+
+    /// Row `y` as a ByteString, panics if `y` is not below the height.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.as_matrix_mut(2).unwrap().row_mut(1).set_bytes_with_value(0xFF);
+    /// assert_eq!(raw, [0x00, 0x00, 0xFF, 0xFF]);
+    /// ```
+    #[track_caller]
+    pub fn row_mut(&mut self, y: usize) -> ByteString<'_> {
+        self.try_row_mut(y).expect("row out of range")
+    }
+
+    /// Non-panicking form of [`ByteMatrix::row_mut`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// let mut matrix = bytes.as_matrix_mut(2).unwrap();
+    ///
+    /// assert!(matrix.try_row_mut(1).is_ok());
+    /// assert!(matrix.try_row_mut(2).is_err());
+    /// ```
+    pub fn try_row_mut(&mut self, y: usize) -> Result<ByteString<'_>, Error> {
+        if y >= self.height() {
+            return Err(Error::OutOfBounds);
+        }
+
+        Ok(self.bytes.sub_view(y * self.width..(y + 1) * self.width))
+    }
+
+    /// Element at column `x` of row `y`, panics if either is out of range.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [1u8, 2, 3, 4, 5, 6];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// let matrix = bytes.as_matrix_mut(3).unwrap();
+    ///
+    /// assert_eq!(matrix.get(0, 1), 4);
+    ///
+    /// // With the flag set the first row starts at the physical end
+    /// bytes.interpret_reverse_endian();
+    /// assert_eq!(bytes.as_matrix_mut(3).unwrap().get(0, 0), 6);
+    /// ```
+    #[track_caller]
+    pub fn get(&self, x: usize, y: usize) -> u8 {
+        self.try_get(x, y).expect("element out of range")
+    }
+
+    /// Non-panicking form of [`ByteMatrix::get`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [1u8, 2, 3, 4];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// let matrix = bytes.as_matrix_mut(2).unwrap();
+    ///
+    /// assert_eq!(matrix.try_get(1, 1), Ok(4));
+    /// assert!(matrix.try_get(2, 0).is_err());
+    /// ```
+    pub fn try_get(&self, x: usize, y: usize) -> Result<u8, Error> {
+        let ix = self.physical_index(x, y)?;
+        Ok(self.bytes.bytes[ix])
+    }
+
+    /// Set the element at column `x` of row `y`, panics if either is out of range.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.as_matrix_mut(2).unwrap().set(1, 0, 0xAA);
+    /// assert_eq!(raw, [0x00, 0xAA, 0x00, 0x00]);
+    /// ```
+    #[track_caller]
+    pub fn set(&mut self, x: usize, y: usize, value: u8) {
+        self.try_set(x, y, value).expect("element out of range")
+    }
+
+    /// Non-panicking form of [`ByteMatrix::set`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// let mut matrix = bytes.as_matrix_mut(2).unwrap();
+    ///
+    /// assert_eq!(matrix.try_set(0, 1, 7), Ok(()));
+    /// assert!(matrix.try_set(0, 2, 7).is_err());
+    /// ```
+    pub fn try_set(&mut self, x: usize, y: usize, value: u8) -> Result<(), Error> {
+        let ix = self.physical_index(x, y)?;
+        self.bytes.bytes[ix] = value;
+        Ok(())
+    }
+
+    /// Elements of column `x` from the top row down, panics if `x` is not below the width.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [1u8, 2, 3, 4, 5, 6];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// let matrix = bytes.as_matrix_mut(2).unwrap();
+    ///
+    /// assert!(matrix.iter_column(1).copied().eq([2, 4, 6]));
+    /// ```
+    #[track_caller]
+    pub fn iter_column(&self, x: usize) -> impl Iterator<Item = &u8> + '_ {
+        self.try_iter_column(x).expect("column out of range")
+    }
+
+    /// Non-panicking form of [`ByteMatrix::iter_column`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [1u8, 2, 3, 4];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// let matrix = bytes.as_matrix_mut(2).unwrap();
+    ///
+    /// assert!(matrix.try_iter_column(0).unwrap().copied().eq([1, 3]));
+    /// assert!(matrix.try_iter_column(2).is_err());
+    /// ```
+    pub fn try_iter_column(&self, x: usize) -> Result<impl Iterator<Item = &u8> + '_, Error> {
+        if x >= self.width {
+            return Err(Error::OutOfBounds);
+        }
+
+        // Strided walk over the logical indices, each mapped to its physical position
+        Ok((0..self.height()).map(move |y| &self.bytes.bytes[self.bytes.physical_index(y * self.width + x)]))
+    }
+
+    /// Write the transpose into `out`, which must have the same length and becomes `height` bytes wide.
+    ///
+    /// Fails with [`Error::LengthMismatch`] without writing if the lengths differ.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [1u8, 2, 3, 4, 5, 6];
+    /// let mut out = [0u8; 6];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.as_matrix_mut(3).unwrap().transpose_into(&mut ByteString::new(&mut out)).unwrap();
+    /// assert_eq!(out, [1, 4, 2, 5, 3, 6]);
+    /// ```
+    pub fn transpose_into(&self, out: &mut ByteString) -> Result<(), Error> {
+        if out.byte_len() != self.bytes.byte_len() {
+            return Err(Error::LengthMismatch);
+        }
+
+        let height = self.height();
+        for (ix, dst) in out.iter_mut().enumerate() {
+            // Output element (y, x) sits at logical x * height + y
+            let (x, y) = (ix / height, ix % height);
+            *dst = self.bytes.bytes[self.bytes.physical_index(y * self.width + x)];
+        }
+
+        Ok(())
+    }
+
+    /// Mirror every row, column `x` swaps with column `width - 1 - x`.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [1u8, 2, 3, 4, 5, 6];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.as_matrix_mut(3).unwrap().flip_horizontal();
+    /// assert_eq!(raw, [3, 2, 1, 6, 5, 4]);
+    /// ```
+    pub fn flip_horizontal(&mut self) {
+        for y in 0..self.height() {
+            for x in 0..self.width / 2 {
+                let a = self.bytes.physical_index(y * self.width + x);
+                let b = self.bytes.physical_index(y * self.width + self.width - 1 - x);
+                self.bytes.bytes.swap(a, b);
+            }
+        }
+    }
+
+    /// Mirror the rows, row `y` swaps with row `height - 1 - y`.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [1u8, 2, 3, 4, 5, 6];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.as_matrix_mut(3).unwrap().flip_vertical();
+    /// assert_eq!(raw, [4, 5, 6, 1, 2, 3]);
+    /// ```
+    pub fn flip_vertical(&mut self) {
+        let height = self.height();
+        for y in 0..height / 2 {
+            for x in 0..self.width {
+                let a = self.bytes.physical_index(y * self.width + x);
+                let b = self.bytes.physical_index((height - 1 - y) * self.width + x);
+                self.bytes.bytes.swap(a, b);
+            }
+        }
+    }
+
+    // Physical position of element (x, y)
+    fn physical_index(&self, x: usize, y: usize) -> Result<usize, Error> {
+        if x >= self.width || y >= self.height() {
+            return Err(Error::OutOfBounds);
+        }
+
+        Ok(self.bytes.physical_index(y * self.width + x))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::{view, view_logical};
+
+    #[test]
+    fn test_shape_errors() {
+        let mut raw = [0u8; 6];
+        let mut bytes = ByteString::new(&mut raw);
+
+        assert!(matches!(bytes.as_matrix_mut(4), Err(Error::LengthMismatch)));
+        assert!(matches!(bytes.as_matrix_mut(0), Err(Error::ZeroLength)));
+
+        let mut matrix = bytes.as_matrix_mut(3).unwrap();
+        assert_eq!((matrix.width(), matrix.height()), (3, 2));
+        assert_eq!(matrix.try_get(3, 0), Err(Error::OutOfBounds));
+        assert_eq!(matrix.try_set(0, 2, 1), Err(Error::OutOfBounds));
+        assert!(matrix.try_row_mut(2).is_err());
+        assert!(matrix.try_iter_column(3).is_err());
+    }
+
+    #[test]
+    fn test_column_iteration_3x5() {
+        for reversed in [false, true] {
+            // Logical bytes 0..15, three rows of five
+            let mut raw: Vec<u8> = (0..15).collect();
+            let mut bytes = view_logical(&mut raw, reversed);
+            let matrix = bytes.as_matrix_mut(5).unwrap();
+
+            assert_eq!(matrix.height(), 3);
+            assert!(matrix.iter_column(0).eq([0, 5, 10].iter()));
+            assert!(matrix.iter_column(4).eq([4, 9, 14].iter()));
+            assert_eq!(matrix.get(2, 1), 7);
+        }
+    }
+
+    #[test]
+    fn test_transpose_twice_is_identity() {
+        for reversed in [false, true] {
+            let original: Vec<u8> = (0..15).map(|e| e * 3 + 1).collect();
+            let mut raw = original.clone();
+            let mut bytes = view(&mut raw, reversed);
+            let matrix = bytes.as_matrix_mut(5).unwrap();
+
+            // The output interpretation is independent of the input's
+            let mut once = [0u8; 15];
+            let mut once_bytes = view(&mut once, !reversed);
+            matrix.transpose_into(&mut once_bytes).unwrap();
+
+            let transposed = once_bytes.as_matrix_mut(3).unwrap();
+            assert_eq!(transposed.height(), 5);
+            // Columns of the transpose are the rows of the input
+            assert!(transposed.iter_column(1).copied().eq((0..5).map(|x| matrix.get(x, 1))));
+
+            let mut twice = [0u8; 15];
+            let mut twice_bytes = view(&mut twice, reversed);
+            transposed.transpose_into(&mut twice_bytes).unwrap();
+            assert_eq!(&twice[..], &original[..]);
+        }
+    }
+
+    #[test]
+    fn test_flips_hand_computed() {
+        for reversed in [false, true] {
+            // Logical rows [1 2 3] [4 5 6]
+            let mut raw = [1u8, 2, 3, 4, 5, 6];
+            let mut bytes = view_logical(&mut raw, reversed);
+
+            let mut matrix = bytes.as_matrix_mut(3).unwrap();
+            matrix.flip_horizontal();
+            assert_eq!((matrix.get(0, 0), matrix.get(2, 1)), (3, 4));
+            matrix.flip_vertical();
+            matrix.set(1, 1, 0xAA);
+            assert!(matrix.row_mut(1).iter().eq([3, 0xAA, 1].iter()));
+            assert!(bytes.iter().eq([6, 5, 4, 3, 0xAA, 1].iter()));
+        }
+
+        // An odd width leaves the middle column, a single row is its own vertical flip
+        let mut raw = [1u8, 2, 3, 4, 5];
+        let mut bytes = ByteString::new(&mut raw);
+        let mut matrix = bytes.as_matrix_mut(5).unwrap();
+        matrix.flip_vertical();
+        matrix.flip_horizontal();
+        assert_eq!(raw, [5, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_transpose_length_mismatch() {
+        let mut raw = [0u8; 6];
+        let mut out = [0xEEu8; 5];
+        let mut bytes = ByteString::new(&mut raw);
+        let mut out_bytes = ByteString::new(&mut out);
+
+        assert_eq!(bytes.as_matrix_mut(2).unwrap().transpose_into(&mut out_bytes), Err(Error::LengthMismatch));
+        assert_eq!(out, [0xEE; 5]);
+    }
+}
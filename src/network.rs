@@ -0,0 +1,132 @@
+//! Conversion between host and network byte order
+
+use crate::bytes::ByteString;
+
+impl<'a> ByteString<'a> {
+
+    /// Make the physical layout big-endian, keeping the value, and clear the interpretation flag.
+    ///
+    /// Afterwards `bytes()` can be sent as is. Calling it again does nothing.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// // 0x1234 as read from a little-endian register
+    /// let mut raw = [0x34u8, 0x12];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    ///
+    /// bytes.to_network_order();
+    /// assert_eq!(bytes.bytes(), [0x12, 0x34]);
+    /// assert_eq!(bytes.to_u64(), Ok(0x1234));
+    /// ```
+    pub fn to_network_order(&mut self) {
+        self.set_physical_little_endian(false);
+    }
+
+    /// Lay a buffer out in the host convention, keeping the value.
+    ///
+    /// With `host_is_little_endian` the bytes are stored back to front and the flag is set, so
+    /// `bytes()` matches the host's native integer layout. Calling it again does nothing, and
+    /// [`ByteString::to_network_order`] undoes it.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x12u8, 0x34];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.from_network_order(true);
+    /// assert_eq!(bytes.bytes(), [0x34, 0x12]);
+    /// assert_eq!(bytes.to_u64(), Ok(0x1234));
+    ///
+    /// // A big-endian host needs no change
+    /// bytes.from_network_order(false);
+    /// assert_eq!(bytes.bytes(), [0x12, 0x34]);
+    /// ```
+    pub fn from_network_order(&mut self, host_is_little_endian: bool) {
+        self.set_physical_little_endian(host_is_little_endian);
+    }
+
+    /// [`ByteString::from_network_order`] for the target this crate is compiled for.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x12u8, 0x34, 0x56, 0x78];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.to_host();
+    /// let native: [u8; 4] = bytes.bytes().try_into().unwrap();
+    /// assert_eq!(u32::from_ne_bytes(native), 0x1234_5678);
+    /// ```
+    pub fn to_host(&mut self) {
+        self.from_network_order(cfg!(target_endian = "little"));
+    }
+
+    /// Undo [`ByteString::to_host`], same as [`ByteString::to_network_order`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = 0x1234_5678u32.to_ne_bytes();
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// if cfg!(target_endian = "little") { bytes.interpret_reverse_endian(); }
+    ///
+    /// bytes.from_host();
+    /// assert_eq!(raw, [0x12, 0x34, 0x56, 0x78]);
+    /// ```
+    pub fn from_host(&mut self) {
+        self.to_network_order();
+    }
+
+    // Physically reverse when the layout differs, toggling the flag keeps the logical value
+    fn set_physical_little_endian(&mut self, little: bool) {
+        if self.interpret_reverse_endian != little {
+            self.reverse_byte_endianness();
+            self.interpret_reverse_endian();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::view;
+
+    #[test]
+    fn test_network_order_layout() {
+        for start_reversed in [false, true] {
+            let mut raw = [0x12u8, 0x34, 0x56];
+            let mut bytes = view(&mut raw, start_reversed);
+            let logical: Vec<u8> = bytes.iter().copied().collect();
+
+            bytes.to_network_order();
+            bytes.to_network_order();
+            assert_eq!(bytes.bytes(), &logical[..]);
+            assert!(bytes.iter().eq(logical.iter()));
+
+            bytes.from_network_order(true);
+            bytes.from_network_order(true);
+            assert!(bytes.bytes().iter().eq(logical.iter().rev()));
+            assert!(bytes.iter().eq(logical.iter()));
+
+            bytes.from_network_order(false);
+            assert_eq!(bytes.bytes(), &logical[..]);
+        }
+    }
+
+    #[test]
+    fn test_host_round_trip() {
+        let value = 0x0102_0304u32;
+        let mut raw = value.to_be_bytes();
+        let mut bytes = ByteString::new(&mut raw);
+
+        bytes.to_host();
+        assert_eq!(bytes.bytes(), &value.to_ne_bytes()[..]);
+
+        bytes.from_host();
+        assert_eq!(bytes.bytes(), &value.to_be_bytes()[..]);
+    }
+}
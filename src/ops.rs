@@ -0,0 +1,396 @@
+//! A chaining builder over the in-place mutations
+
+use crate::{
+    bytes::ByteString,
+    error::Error,
+    fixed::Rounding
+};
+
+/// Chainable view over the mutating operations of a ByteString, see [`ByteString::ops`].
+///
+/// ```
+/// use rebite::bytes::ByteString;
+///
+/// let mut raw = [0x00u8, 0xFF];
+/// let mut bytes = ByteString::new(&mut raw);
+///
+/// bytes.ops().invert().and_all(0x0F).rotl_bytes(1);
+/// assert_eq!(raw, [0x00, 0x0F]);
+/// ```
+pub struct ByteStringOps<'b, 'a> {
+    bytes: &'b mut ByteString<'a>
+}
+
+impl<'a> ByteString<'a> {
+
+    /// Chain several mutations in one expression.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0xFFu8; 4];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.ops()
+    ///     .set_zero()
+    ///     .set_bit(3, true)
+    ///     .xor_all(0x5A)
+    ///     .shl_bits(4)
+    ///     .rotl_bytes(1);
+    ///
+    /// assert_eq!(raw, [0xA5, 0xA5, 0x20, 0xA5]);
+    /// ```
+    pub fn ops(&mut self) -> ByteStringOps<'_, 'a> {
+        ByteStringOps { bytes: self }
+    }
+}
+
+impl<'b, 'a> ByteStringOps<'b, 'a> {
+
+    /// Give back the underlying ByteString to continue with non-chainable calls.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x0Fu8];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// let bytes = bytes.ops().invert().done();
+    /// assert!(!bytes.is_zero());
+    /// assert_eq!(raw, [0xF0]);
+    /// ```
+    pub fn done(self) -> &'b mut ByteString<'a> {
+        self.bytes
+    }
+
+    /// See [`ByteString::interpret_reverse_endian`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x00u8, 0x00];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// // The bit offsets of later steps count from the physical end
+    /// bytes.ops().interpret_reverse_endian().set_bit(0, true);
+    /// assert_eq!(raw, [0x01, 0x00]);
+    /// ```
+    pub fn interpret_reverse_endian(self) -> Self {
+        self.bytes.interpret_reverse_endian();
+        self
+    }
+
+    /// See [`ByteString::set_zero`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0xFFu8; 2];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.ops().set_zero().set_bit(8, true);
+    /// assert_eq!(raw, [0x01, 0x00]);
+    /// ```
+    pub fn set_zero(self) -> Self {
+        self.bytes.set_zero();
+        self
+    }
+
+    /// See [`ByteString::set_bytes_with_value`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0u8; 2];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.ops().set_bytes_with_value(0xF0).set_bit(0, true);
+    /// assert_eq!(raw, [0xF0, 0xF1]);
+    /// ```
+    pub fn set_bytes_with_value(self, value: u8) -> Self {
+        self.bytes.set_bytes_with_value(value);
+        self
+    }
+
+    /// Set the bit at `offset`, counted from the least significant bit, panics if out of range.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0u8; 2];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.ops().set_bit(0, true).set_bit(15, true);
+    /// assert_eq!(raw, [0x80, 0x01]);
+    /// ```
+    #[track_caller]
+    pub fn set_bit(self, offset: usize, value: bool) -> Self {
+        self.bytes.set_bit(offset, value);
+        self
+    }
+
+    /// Non-panicking form of [`ByteStringOps::set_bit`], the chain ends at the first error.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let mut raw = [0u8];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// assert!(bytes.ops().try_set_bit(7, true).is_ok());
+    /// assert_eq!(bytes.ops().try_set_bit(8, true).map(|e| e.done().is_zero()), Err(Error::OutOfBounds));
+    /// assert_eq!(raw, [0x80]);
+    /// ```
+    pub fn try_set_bit(self, offset: usize, value: bool) -> Result<Self, Error> {
+        self.bytes.try_set_bit(offset, value)?;
+        Ok(self)
+    }
+
+    /// XOR every byte with `value`.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x0Fu8, 0xF0];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.ops().xor_all(0xFF);
+    /// assert_eq!(raw, [0xF0, 0x0F]);
+    /// ```
+    pub fn xor_all(self, value: u8) -> Self {
+        self.bytes.bytes_mut().iter_mut().for_each(|e| *e ^= value);
+        self
+    }
+
+    /// AND every byte with `value`.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x3Cu8, 0xFF];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.ops().and_all(0x0F);
+    /// assert_eq!(raw, [0x0C, 0x0F]);
+    /// ```
+    pub fn and_all(self, value: u8) -> Self {
+        self.bytes.bytes_mut().iter_mut().for_each(|e| *e &= value);
+        self
+    }
+
+    /// OR every byte with `value`.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x30u8, 0x00];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.ops().or_all(0x01);
+    /// assert_eq!(raw, [0x31, 0x01]);
+    /// ```
+    pub fn or_all(self, value: u8) -> Self {
+        self.bytes.bytes_mut().iter_mut().for_each(|e| *e |= value);
+        self
+    }
+
+    /// Complement every bit.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x00u8, 0x5A];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.ops().invert();
+    /// assert_eq!(raw, [0xFF, 0xA5]);
+    /// ```
+    pub fn invert(self) -> Self {
+        self.bytes.bytes_mut().iter_mut().for_each(|e| *e = !*e);
+        self
+    }
+
+    /// See [`ByteString::reverse_byte_endianness`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x01u8, 0x02, 0x03];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.ops().reverse_byte_endianness();
+    /// assert_eq!(raw, [0x03, 0x02, 0x01]);
+    /// ```
+    pub fn reverse_byte_endianness(self) -> Self {
+        self.bytes.reverse_byte_endianness();
+        self
+    }
+
+    /// See [`ByteString::reverse_bit_endianness`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x01u8, 0xC0];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.ops().reverse_bit_endianness();
+    /// assert_eq!(raw, [0x80, 0x03]);
+    /// ```
+    pub fn reverse_bit_endianness(self) -> Self {
+        self.bytes.reverse_bit_endianness();
+        self
+    }
+
+    /// See [`ByteString::rotl_bytes`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x01u8, 0x02, 0x03];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.ops().rotl_bytes(1).rotl_bytes(1);
+    /// assert_eq!(raw, [0x03, 0x01, 0x02]);
+    /// ```
+    pub fn rotl_bytes(self, count: usize) -> Self {
+        self.bytes.rotl_bytes(count);
+        self
+    }
+
+    /// See [`ByteString::try_rotl_bytes`], which rejects counts past the byte length that `rotl_bytes` reduces.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x01u8, 0x02];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// assert!(bytes.ops().try_rotl_bytes(1).is_ok());
+    /// assert!(bytes.ops().try_rotl_bytes(3).is_err());
+    /// assert_eq!(raw, [0x02, 0x01]);
+    /// ```
+    pub fn try_rotl_bytes(self, count: usize) -> Result<Self, Error> {
+        self.bytes.try_rotl_bytes(count)?;
+        Ok(self)
+    }
+
+    /// See [`ByteString::rotr_bytes`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x01u8, 0x02, 0x03];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.ops().rotr_bytes(1);
+    /// assert_eq!(raw, [0x03, 0x01, 0x02]);
+    /// ```
+    pub fn rotr_bytes(self, count: usize) -> Self {
+        self.bytes.rotr_bytes(count);
+        self
+    }
+
+    /// `<<=`, saturating to zero.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x00u8, 0x81];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.ops().shl_bits(1);
+    /// assert_eq!(bytes.bytes(), [0x01, 0x02]);
+    ///
+    /// bytes.ops().shl_bits(16);
+    /// assert_eq!(raw, [0x00, 0x00]);
+    /// ```
+    pub fn shl_bits(self, count: usize) -> Self {
+        *self.bytes <<= count;
+        self
+    }
+
+    /// `>>=`, saturating to zero.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x81u8, 0x00];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.ops().shr_bits(1);
+    /// assert_eq!(raw, [0x40, 0x80]);
+    /// ```
+    pub fn shr_bits(self, count: usize) -> Self {
+        *self.bytes >>= count;
+        self
+    }
+
+    /// See [`ByteString::scale_shr_round`].
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, fixed::Rounding};
+    ///
+    /// let mut raw = [0x0Eu8];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// // 14 / 4 = 3.5, a tie rounded to the even 4
+    /// bytes.ops().scale_shr_round(2, Rounding::Nearest);
+    /// assert_eq!(raw, [0x04]);
+    /// ```
+    pub fn scale_shr_round(self, count: usize, mode: Rounding) -> Self {
+        self.bytes.scale_shr_round(count, mode);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_chain_matches_individual_calls() {
+        type Chained = for<'b, 'a> fn(ByteStringOps<'b, 'a>) -> ByteStringOps<'b, 'a>;
+        type Single = fn(&mut ByteString);
+
+        let steps: [(Chained, Single); 12] = [
+            (|o| o.interpret_reverse_endian(), |b| b.interpret_reverse_endian()),
+            (|o| o.or_all(0x01), |b| b.bytes_mut().iter_mut().for_each(|e| *e |= 0x01)),
+            (|o| o.and_all(0xF7), |b| b.bytes_mut().iter_mut().for_each(|e| *e &= 0xF7)),
+            (|o| o.shl_bits(3), |b| *b <<= 3),
+            (|o| o.shr_bits(1), |b| *b >>= 1),
+            (|o| o.invert(), |b| b.bytes_mut().iter_mut().for_each(|e| *e = !*e)),
+            (|o| o.reverse_bit_endianness(), |b| b.reverse_bit_endianness()),
+            (|o| o.reverse_byte_endianness(), |b| b.reverse_byte_endianness()),
+            (|o| o.set_bytes_with_value(0x0F), |b| b.set_bytes_with_value(0x0F)),
+            (|o| o.set_bit(23, true), |b| b.set_bit(23, true)),
+            (|o| o.scale_shr_round(2, Rounding::Ceil), |b| b.scale_shr_round(2, Rounding::Ceil)),
+            (|o| o.xor_all(0xFF), |b| b.bytes_mut().iter_mut().for_each(|e| *e ^= 0xFF)),
+        ];
+
+        // Compare after every step, a later step such as set_bytes_with_value would hide earlier ones
+        for len in 1..=steps.len() {
+            let mut chained = [0x12u8, 0x34, 0x56];
+            let mut single = chained;
+
+            let mut bytes = ByteString::new(&mut chained);
+            steps[..len].iter().fold(bytes.ops(), |ops, (step, _)| step(ops));
+
+            let mut bytes = ByteString::new(&mut single);
+            steps[..len].iter().for_each(|(_, step)| step(&mut bytes));
+
+            assert_eq!(chained, single, "after {} steps", len);
+        }
+    }
+
+    #[test]
+    fn test_done_returns_underlying() {
+        let mut raw = [0u8; 2];
+        let mut bytes = ByteString::new(&mut raw);
+
+        let inner = bytes.ops().set_bit(15, true).done();
+        assert!(!inner.is_zero());
+        assert_eq!(raw, [0x80, 0x00]);
+    }
+}
@@ -0,0 +1,164 @@
+//! XOR parity across several buffers and reconstruction of a missing one
+
+use crate::{
+    bytes::ByteString,
+    error::LengthError
+};
+
+impl<'a> ByteString<'a> {
+
+    /// self = XOR of all `sources`, which must match the length of self.
+    ///
+    /// Sources combine in logical order, so interpretation flags may differ between them.
+    /// Lengths are checked before self is touched.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut a = [0x0Fu8, 0x01];
+    /// let mut b = [0xF0u8, 0x02];
+    /// let mut raw = [0u8; 2];
+    /// let (a, b) = (ByteString::new(&mut a), ByteString::new(&mut b));
+    ///
+    /// ByteString::new(&mut raw).assign_xor_of([&a, &b]).unwrap();
+    /// assert_eq!(raw, [0xFF, 0x03]);
+    ///
+    /// // The short source is reported by its position
+    /// let mut short = [0u8];
+    /// let short = ByteString::new(&mut short);
+    /// let err = ByteString::new(&mut raw).assign_xor_of([&a, &short]).unwrap_err();
+    /// assert_eq!((err.index, err.actual), (1, 1));
+    /// ```
+    pub fn assign_xor_of<'x, I>(&mut self, sources: I) -> Result<(), LengthError>
+    where I: IntoIterator<Item = &'x ByteString<'x>> {
+
+        let sources: Vec<&ByteString> = sources.into_iter().collect();
+
+        if let Some((index, source)) = sources.iter().enumerate().find(|(_, e)| e.byte_len() != self.byte_len()) {
+            return Err(LengthError { index, expected: self.byte_len(), actual: source.byte_len() });
+        }
+
+        self.set_zero();
+
+        for source in sources {
+            for (a, b) in self.iter_mut().zip(source.iter()) {
+                *a ^= *b;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Recover the one missing member of a stripe from its XOR `parity` and the `present` members.
+    ///
+    /// Error indices count `parity` as 0 and `present[i]` as `i + 1`.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let (mut a, mut b, mut c) = ([0x12u8, 0x34], [0x56u8, 0x78], [0x9Au8, 0xBC]);
+    /// let (a, b, c) = (ByteString::new(&mut a), ByteString::new(&mut b), ByteString::new(&mut c));
+    ///
+    /// let mut parity = [0u8; 2];
+    /// let mut parity = ByteString::new(&mut parity);
+    /// parity.assign_xor_of([&a, &b, &c]).unwrap();
+    ///
+    /// // Lose b and rebuild it from the others
+    /// let mut lost = [0u8; 2];
+    /// ByteString::new(&mut lost).reconstruct_missing(&parity, &[&a, &c]).unwrap();
+    /// assert_eq!(lost, [0x56, 0x78]);
+    /// ```
+    pub fn reconstruct_missing(&mut self, parity: &ByteString, present: &[&ByteString]) -> Result<(), LengthError> {
+        self.assign_xor_of(std::iter::once(parity).chain(present.iter().copied()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::{view, XorShift};
+
+    #[test]
+    fn test_parity_recovers_any_missing() {
+        let mut rng = XorShift::new(0x217);
+
+        for _ in 0..50 {
+            let count = 2 + rng.below(6);
+            let len = rng.below(24);
+
+            let mut raws: Vec<Vec<u8>> = (0..count).map(|_| {
+                let mut raw = vec![0u8; len];
+                rng.fill(&mut raw);
+                raw
+            }).collect();
+            let reversed: Vec<bool> = (0..count).map(|_| rng.next_bool()).collect();
+            let logical: Vec<Vec<u8>> = raws.iter().zip(&reversed)
+                .map(|(raw, reversed)| if *reversed { raw.iter().rev().copied().collect() } else { raw.clone() })
+                .collect();
+
+            let stripes: Vec<ByteString> = raws.iter_mut().zip(&reversed).map(|(raw, reversed)| {
+                let bytes = view(raw, *reversed);
+                bytes
+            }).collect();
+
+            let mut parity_raw = vec![0u8; len];
+            let mut parity = ByteString::new(&mut parity_raw);
+            parity.assign_xor_of(&stripes).unwrap();
+
+            for (missing, expected) in logical.iter().enumerate() {
+                let present: Vec<&ByteString> = stripes.iter().enumerate()
+                    .filter(|(ix, _)| *ix != missing)
+                    .map(|(_, e)| e)
+                    .collect();
+
+                let mut recovered_raw = vec![0u8; len];
+                let mut recovered = ByteString::new(&mut recovered_raw);
+                recovered.reconstruct_missing(&parity, &present).unwrap();
+
+                assert!(recovered.iter().eq(expected.iter()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_assign_xor_of_simple() {
+        let mut a = [0x0Fu8, 0xF0];
+        let mut b = [0xFFu8, 0x00];
+        let mut out = [0xAAu8, 0xAA];
+
+        let bytes_a = ByteString::new(&mut a);
+        let mut bytes_b = ByteString::new(&mut b);
+        bytes_b.interpret_reverse_endian();
+
+        ByteString::new(&mut out).assign_xor_of([&bytes_a, &bytes_b]).unwrap();
+        assert_eq!(out, [0x0F, 0x0F]);
+
+        let mut empty = [0xAAu8, 0xAA];
+        ByteString::new(&mut empty).assign_xor_of([]).unwrap();
+        assert_eq!(empty, [0, 0]);
+    }
+
+    #[test]
+    fn test_length_mismatch_reports_index() {
+        let mut a = [1u8, 2];
+        let mut b = [1u8, 2];
+        let mut c = [1u8, 2, 3];
+        let mut out = [9u8, 9];
+
+        let bytes_a = ByteString::new(&mut a);
+        let bytes_b = ByteString::new(&mut b);
+        let bytes_c = ByteString::new(&mut c);
+        let mut bytes_out = ByteString::new(&mut out);
+
+        let err = bytes_out.assign_xor_of([&bytes_a, &bytes_b, &bytes_c]).unwrap_err();
+        assert_eq!(err, LengthError { index: 2, expected: 2, actual: 3 });
+
+        let err = bytes_out.reconstruct_missing(&bytes_c, &[&bytes_a]).unwrap_err();
+        assert_eq!(err.index, 0);
+
+        let err = bytes_out.reconstruct_missing(&bytes_a, &[&bytes_b, &bytes_c]).unwrap_err();
+        assert_eq!(err.index, 2);
+        assert_eq!(out, [9, 9]);
+    }
+}
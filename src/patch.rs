@@ -0,0 +1,226 @@
+//! Validated application of patch lists
+
+use std::ops::Range;
+
+use crate::{
+    bytes::ByteString,
+    error::{PatchError, PatchErrorKind}
+};
+
+/// How a patch list treats patches that write the same logical bytes.
+///
+/// ```
+/// use rebite::{bytes::ByteString, patch::OverlapPolicy};
+///
+/// let mut raw = [0u8; 3];
+/// let mut bytes = ByteString::new(&mut raw);
+/// let patches: &[(usize, &[u8])] = &[(0, &[1, 1]), (1, &[2, 2])];
+///
+/// assert!(bytes.apply_patches(patches, OverlapPolicy::Reject).is_err());
+///
+/// // Later patches win on the shared byte 1
+/// bytes.apply_patches(patches, OverlapPolicy::LaterWins).unwrap();
+/// assert_eq!(raw, [1, 2, 2]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlapPolicy {
+    /// Fail with [`PatchErrorKind::Overlaps`]
+    Reject,
+    /// Apply in list order, so the later patch wins on the shared bytes
+    LaterWins,
+}
+
+impl<'a> ByteString<'a> {
+
+    /// Validate a list of `(offset, bytes)` patches without writing anything.
+    ///
+    /// Offsets are logical and `bytes` is written in logical order from there. Out of bounds patches
+    /// are reported first, in list order, then overlaps if `policy` rejects them.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::PatchErrorKind, patch::OverlapPolicy};
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let bytes = ByteString::new(&mut raw);
+    /// let patches: &[(usize, &[u8])] = &[(0, &[1, 2]), (1, &[3])];
+    ///
+    /// assert!(bytes.check_patches(patches, OverlapPolicy::LaterWins).is_ok());
+    ///
+    /// let err = bytes.check_patches(patches, OverlapPolicy::Reject).unwrap_err();
+    /// assert_eq!((err.index, err.kind), (1, PatchErrorKind::Overlaps(0)));
+    ///
+    /// assert!(bytes.check_patches(&[(3, &[1, 2])], OverlapPolicy::Reject).is_err());
+    /// ```
+    pub fn check_patches(&self, patches: &[(usize, &[u8])], policy: OverlapPolicy) -> Result<(), PatchError> {
+
+        for (index, &(offset, bytes)) in patches.iter().enumerate() {
+            self.check_region(offset, bytes.len())
+                .map_err(|region| PatchError { index, kind: PatchErrorKind::OutOfBounds(region) })?;
+        }
+
+        if policy == OverlapPolicy::LaterWins {
+            return Ok(());
+        }
+
+        // Sweep by offset, remembering the patch reaching furthest so far; empty patches never overlap
+        let mut order: Vec<usize> = (0..patches.len()).filter(|ix| !patches[*ix].1.is_empty()).collect();
+        order.sort_by_key(|ix| patches[*ix].0);
+
+        let mut reach: Option<(usize, usize)> = None;
+        for ix in order {
+            let (offset, bytes) = patches[ix];
+            match reach {
+                Some((end, other)) if offset < end => {
+                    return Err(PatchError { index: ix.max(other), kind: PatchErrorKind::Overlaps(ix.min(other)) });
+                }
+                Some((end, _)) if end >= offset + bytes.len() => (),
+                _ => reach = Some((offset + bytes.len(), ix)),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write every patch after validating all of them, see [`ByteString::check_patches`].
+    ///
+    /// A rejected list leaves the buffer untouched.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, patch::OverlapPolicy};
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.apply_patches(&[(0, &[1, 2]), (1, &[3])], OverlapPolicy::LaterWins).unwrap();
+    /// assert_eq!(bytes.bytes(), [1, 3, 0, 0]);
+    ///
+    /// assert!(bytes.apply_patches(&[(2, &[9]), (4, &[9])], OverlapPolicy::LaterWins).is_err());
+    /// assert_eq!(raw, [1, 3, 0, 0]);
+    /// ```
+    pub fn apply_patches(&mut self, patches: &[(usize, &[u8])], policy: OverlapPolicy) -> Result<(), PatchError> {
+        self.check_patches(patches, policy)?;
+
+        for &(offset, bytes) in patches {
+            for (dst, src) in self.iter_range_mut(offset..offset + bytes.len()).zip(bytes) {
+                *dst = *src;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Logical ranges whose bytes [`ByteString::apply_patches`] would change, ascending and merged.
+    ///
+    /// Patched bytes that already hold their new value do not count.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, patch::OverlapPolicy};
+    ///
+    /// let mut raw = [1u8, 2, 3, 4, 5];
+    /// let bytes = ByteString::new(&mut raw);
+    ///
+    /// // Byte 1 already holds 2, so only 0 and 3..5 change
+    /// let changed = bytes.diff_after_patches(&[(0, &[9, 2]), (3, &[7, 7])], OverlapPolicy::Reject).unwrap();
+    /// assert_eq!(changed, [0..1, 3..5]);
+    /// ```
+    pub fn diff_after_patches(&self, patches: &[(usize, &[u8])], policy: OverlapPolicy) -> Result<Vec<Range<usize>>, PatchError> {
+        self.check_patches(patches, policy)?;
+
+        // Final value of every patched position, up to the furthest patch end
+        let len = patches.iter().map(|(offset, bytes)| offset + bytes.len()).max().unwrap_or(0);
+        let mut overlay = vec![None; len];
+        for &(offset, bytes) in patches {
+            for (dst, src) in overlay[offset..].iter_mut().zip(bytes) {
+                *dst = Some(*src);
+            }
+        }
+
+        let mut changed: Vec<Range<usize>> = Vec::new();
+        for (ix, (new, old)) in overlay.iter().zip(self.iter()).enumerate() {
+            if new.is_none_or(|new| new == *old) {
+                continue;
+            }
+
+            match changed.last_mut() {
+                Some(last) if last.end == ix => last.end += 1,
+                _ => changed.push(ix..ix + 1),
+            }
+        }
+
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::error::RegionError;
+
+    #[test]
+    fn test_multi_patch_application() {
+        let mut raw = [0u8; 10];
+        let mut bytes = ByteString::new(&mut raw);
+
+        let patches: [(usize, &[u8]); 3] = [(8, &[0xAA, 0xBB]), (0, &[0x01]), (3, &[0x02, 0x03, 0x04])];
+        bytes.apply_patches(&patches, OverlapPolicy::Reject).unwrap();
+        assert_eq!(raw, [0x01, 0x00, 0x00, 0x02, 0x03, 0x04, 0x00, 0x00, 0xAA, 0xBB]);
+    }
+
+    #[test]
+    fn test_reversed_offsets_are_logical() {
+        let mut raw = [0u8; 4];
+        let mut bytes = ByteString::new(&mut raw);
+        bytes.interpret_reverse_endian();
+
+        bytes.apply_patches(&[(1, &[0x11, 0x22])], OverlapPolicy::Reject).unwrap();
+        assert_eq!(raw, [0x00, 0x22, 0x11, 0x00]);
+    }
+
+    #[test]
+    fn test_overlap_policy() {
+        let mut raw = [0u8; 6];
+        let mut bytes = ByteString::new(&mut raw);
+        let patches: [(usize, &[u8]); 3] = [(0, &[0x01, 0x01, 0x01]), (4, &[0x03]), (2, &[0x02, 0x02])];
+
+        let err = bytes.apply_patches(&patches, OverlapPolicy::Reject).unwrap_err();
+        assert_eq!(err, PatchError { index: 2, kind: PatchErrorKind::Overlaps(0) });
+        assert_eq!(err.to_string(), "patch 2 overlaps patch 0");
+        assert_eq!(raw, [0u8; 6]);
+
+        let mut bytes = ByteString::new(&mut raw);
+        bytes.apply_patches(&patches, OverlapPolicy::LaterWins).unwrap();
+        assert_eq!(raw, [0x01, 0x01, 0x02, 0x02, 0x03, 0x00]);
+
+        // Adjacent and empty patches do not overlap, containment does
+        let bytes = ByteString::new(&mut raw);
+        assert!(bytes.check_patches(&[(0, &[0x00, 0x00]), (2, &[0x00]), (1, &[])], OverlapPolicy::Reject).is_ok());
+        let err = bytes.check_patches(&[(0, &[0x00; 6]), (1, &[0x00]), (4, &[0x00])], OverlapPolicy::Reject).unwrap_err();
+        assert_eq!(err.kind, PatchErrorKind::Overlaps(0));
+    }
+
+    #[test]
+    fn test_out_of_range_leaves_buffer_untouched() {
+        let mut raw = [0x55u8; 4];
+        let mut bytes = ByteString::new(&mut raw);
+        let patches: [(usize, &[u8]); 2] = [(0, &[0x00]), (3, &[0x00, 0x00])];
+
+        for policy in [OverlapPolicy::Reject, OverlapPolicy::LaterWins] {
+            let err = bytes.apply_patches(&patches, policy).unwrap_err();
+            assert_eq!(err, PatchError { index: 1, kind: PatchErrorKind::OutOfBounds(RegionError { offset: 3, len: 2, byte_len: 4 }) });
+        }
+        assert_eq!(raw, [0x55; 4]);
+    }
+
+    #[test]
+    fn test_diff_after_patches() {
+        let mut raw = [0x00u8, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66];
+        let bytes = ByteString::new(&mut raw);
+
+        // The second patch rewrites 0x33 with itself and the third is undone by the last
+        let patches: [(usize, &[u8]); 4] = [(0, &[0xFF, 0xFF]), (2, &[0xEE, 0x33, 0xEE]), (6, &[0xEE]), (6, &[0x66])];
+        assert_eq!(bytes.diff_after_patches(&patches, OverlapPolicy::LaterWins), Ok(vec![0..3, 4..5]));
+        assert!(bytes.diff_after_patches(&patches, OverlapPolicy::Reject).is_err());
+        assert_eq!(bytes.diff_after_patches(&[], OverlapPolicy::Reject), Ok(vec![]));
+    }
+}
@@ -0,0 +1,141 @@
+//! Bit-reversed index permutation of the bytes
+
+use crate::{
+    bytes::ByteString,
+    error::Error
+};
+
+// Reverse the lowest `bits` bits of `ix`
+const fn reverse_index(ix: usize, bits: u32) -> usize {
+    if bits == 0 { 0 } else { ix.reverse_bits() >> (usize::BITS - bits) }
+}
+
+// Index width ⌈log2(len)⌉
+const fn index_bits(len: usize) -> u32 {
+    len.next_power_of_two().trailing_zeros()
+}
+
+impl<'a> ByteString<'a> {
+
+    /// Logical bytes in bit-reversed index order, with indices reversed within ⌈log2(len)⌉ bits.
+    ///
+    /// For lengths that are not a power of two, reversed indices past the end are skipped.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0u8, 1, 2, 3, 4, 5, 6, 7];
+    /// let bytes = ByteString::new(&mut raw);
+    /// assert!(bytes.iter_bit_reversed_order().copied().eq([0, 4, 2, 6, 1, 5, 3, 7]));
+    ///
+    /// // Indices 4 and 6 of the 3 bit order lie past the end
+    /// let mut raw = [0u8, 1, 2, 3, 4, 5];
+    /// let bytes = ByteString::new(&mut raw);
+    /// assert!(bytes.iter_bit_reversed_order().copied().eq([0, 4, 2, 1, 5, 3]));
+    /// ```
+    pub fn iter_bit_reversed_order(&self) -> impl Iterator<Item = &u8> + '_ {
+        let bits = index_bits(self.byte_len());
+
+        (0..self.byte_len().next_power_of_two())
+            .map(move |ix| reverse_index(ix, bits))
+            .filter(|ix| *ix < self.byte_len())
+            .map(|ix| &self.bytes[self.physical_index(ix)])
+    }
+
+    /// Reorder the logical bytes into bit-reversed index order, the length must be a power of two.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let mut raw = [0u8, 1, 2, 3];
+    /// ByteString::new(&mut raw).permute_bit_reversed().unwrap();
+    /// assert_eq!(raw, [0, 2, 1, 3]);
+    ///
+    /// let mut raw = [0u8; 3];
+    /// assert_eq!(ByteString::new(&mut raw).permute_bit_reversed(), Err(Error::LengthNotPowerOfTwo));
+    /// ```
+    pub fn permute_bit_reversed(&mut self) -> Result<(), Error> {
+
+        let len = self.byte_len();
+        if !len.is_power_of_two() {
+            return Err(Error::LengthNotPowerOfTwo);
+        }
+
+        // The permutation is an involution, swapping each pair once leaves every index in place
+        let bits = index_bits(len);
+        for ix in 0..len {
+            let partner = reverse_index(ix, bits);
+            if ix < partner {
+                let (a, b) = (self.physical_index(ix), self.physical_index(partner));
+                self.bytes.swap(a, b);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::view;
+
+    fn reference(len: usize) -> Vec<usize> {
+        let bits = (len as f64).log2().ceil() as u32;
+        (0..1 << bits)
+            .map(|ix: usize| (0..bits).fold(0, |acc, bit| acc << 1 | (ix >> bit & 1)))
+            .filter(|ix| *ix < len)
+            .collect()
+    }
+
+    #[test]
+    fn test_iter_matches_reference() {
+        for len in [8, 16] {
+            let mut raw: Vec<u8> = (0..len as u8).collect();
+            let bytes = ByteString::new(&mut raw);
+
+            let expected: Vec<u8> = reference(len).into_iter().map(|e| e as u8).collect();
+            assert_eq!(bytes.iter_bit_reversed_order().copied().collect::<Vec<_>>(), expected);
+        }
+
+        let mut raw: Vec<u8> = (0..8).collect();
+        assert!(ByteString::new(&mut raw).iter_bit_reversed_order().eq([0, 4, 2, 6, 1, 5, 3, 7].iter()));
+    }
+
+    #[test]
+    fn test_iter_non_power_of_two() {
+        let mut raw: Vec<u8> = (0..6).collect();
+        let mut bytes = ByteString::new(&mut raw);
+        assert!(bytes.iter_bit_reversed_order().eq([0, 4, 2, 1, 5, 3].iter()));
+
+        bytes.interpret_reverse_endian();
+        assert!(bytes.iter_bit_reversed_order().eq([5, 1, 3, 4, 0, 2].iter()));
+        assert_eq!(ByteString::new(&mut []).iter_bit_reversed_order().count(), 0);
+    }
+
+    #[test]
+    fn test_permute_twice_is_identity() {
+        for len in [1, 2, 4, 8, 16, 64] {
+            for reversed in [false, true] {
+                let original: Vec<u8> = (0..len as u8).collect();
+                let mut raw = original.clone();
+                let mut bytes = view(&mut raw, reversed);
+
+                let expected: Vec<u8> = bytes.iter_bit_reversed_order().copied().collect();
+                bytes.permute_bit_reversed().unwrap();
+                assert!(bytes.iter().eq(expected.iter()));
+
+                bytes.permute_bit_reversed().unwrap();
+                assert_eq!(raw, original);
+            }
+        }
+    }
+
+    #[test]
+    fn test_permute_requires_power_of_two() {
+        let mut raw = [0u8; 6];
+        assert_eq!(ByteString::new(&mut raw).permute_bit_reversed(), Err(Error::LengthNotPowerOfTwo));
+        assert_eq!(ByteString::new(&mut []).permute_bit_reversed(), Err(Error::LengthNotPowerOfTwo));
+    }
+}
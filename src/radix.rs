@@ -0,0 +1,359 @@
+//! Parsing and printing the value in radix 2 to 36
+
+use crate::{
+    bytes::ByteString,
+    error::{Error, ParseError, ParseErrorKind}
+};
+
+/// Smallest radix accepted by the textual conversions.
+///
+/// ```
+/// use rebite::{bytes::ByteString, radix::{MAX_RADIX, MIN_RADIX}};
+///
+/// let mut raw = [0u8; 1];
+/// let mut bytes = ByteString::new(&mut raw);
+///
+/// bytes.assign_from_radix_str("101", MIN_RADIX).unwrap();
+/// assert_eq!(raw, [5]);
+/// assert!(ByteString::new(&mut raw).assign_from_radix_str("1", MIN_RADIX - 1).is_err());
+/// ```
+pub const MIN_RADIX: u32 = 2;
+
+/// Largest radix accepted by the textual conversions.
+///
+/// ```
+/// use rebite::{bytes::ByteString, radix::{MAX_RADIX, MIN_RADIX}};
+///
+/// let mut raw = [35u8];
+/// let bytes = ByteString::new(&mut raw);
+/// let mut out = [0u8; 2];
+///
+/// let written = bytes.write_radix(&mut out, MAX_RADIX, false).unwrap();
+/// assert_eq!(&out[..written], b"z");
+/// assert!(bytes.write_radix(&mut out, MAX_RADIX + 1, false).is_err());
+/// ```
+pub const MAX_RADIX: u32 = 36;
+
+impl<'a> ByteString<'a> {
+
+    /// Parse `s` as an unsigned number in `radix` and store it in the buffer.
+    ///
+    /// Digits above 9 are the letters `a..=z`, case insensitive. The value is
+    /// placed according to the current interpretation, most significant logical byte first.
+    /// An invalid radix, an empty string or a bad digit is rejected before the buffer is touched,
+    /// a value too large for the buffer leaves it zeroed.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::ParseErrorKind};
+    ///
+    /// let mut raw = [0u8; 2];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.assign_from_radix_str("4660", 10).unwrap();
+    /// assert_eq!(bytes.bytes(), [0x12, 0x34]);
+    ///
+    /// bytes.assign_from_radix_str("ZZ", 36).unwrap();
+    /// assert_eq!(bytes.bytes(), [0x05, 0x0F]);
+    ///
+    /// // A bad digit keeps the previous value, an overflow does not
+    /// assert!(bytes.assign_from_radix_str("12g4", 16).is_err());
+    /// assert_eq!(bytes.bytes(), [0x05, 0x0F]);
+    ///
+    /// // 0x10000 needs a third byte
+    /// let err = bytes.assign_from_radix_str("10000", 16).unwrap_err();
+    /// assert_eq!(err.kind, ParseErrorKind::Overflow);
+    /// assert_eq!(bytes.bytes(), [0x00, 0x00]);
+    /// ```
+    pub fn assign_from_radix_str(&mut self, s: &str, radix: u32) -> Result<(), ParseError> {
+
+        if !(MIN_RADIX..=MAX_RADIX).contains(&radix) {
+            return Err(ParseError::new(0, ParseErrorKind::InvalidRadix(radix)));
+        }
+
+        if s.is_empty() {
+            return Err(ParseError::new(0, ParseErrorKind::Empty));
+        }
+
+        // Reject bad digits before touching the buffer
+        if let Some((pos, c)) = s.char_indices().find(|(_, c)| !c.is_digit(radix)) {
+            return Err(ParseError::new(pos, ParseErrorKind::InvalidDigit(c)));
+        }
+
+        self.set_zero();
+
+        let result = if radix.is_power_of_two() {
+            self.pack_pow2_digits(s, radix.trailing_zeros() as usize)
+        }
+        else {
+            self.accumulate_digits(s, radix)
+        };
+
+        if result.is_err() {
+            self.set_zero();
+        }
+
+        result
+    }
+
+    /// Write the value as ASCII digits in `radix` to the front of `out`, without leading zeros.
+    ///
+    /// Returns the number of digits written, a zero value is written as `"0"`. Fails with
+    /// [`Error::OutputTooSmall`] if `out` cannot hold all digits, `out` is then left untouched.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x01u8, 0xFF];
+    /// let bytes = ByteString::new(&mut raw);
+    /// let mut out = [0u8; 16];
+    ///
+    /// let written = bytes.write_radix(&mut out, 16, true).unwrap();
+    /// assert_eq!(&out[..written], b"1FF");
+    ///
+    /// let written = bytes.write_radix(&mut out, 10, false).unwrap();
+    /// assert_eq!(&out[..written], b"511");
+    ///
+    /// let mut short = [b'-'; 2];
+    /// assert!(bytes.write_radix(&mut short, 10, false).is_err());
+    /// assert_eq!(&short, b"--");
+    /// ```
+    pub fn write_radix(&self, out: &mut [u8], radix: u32, uppercase: bool) -> Result<usize, Error> {
+
+        if !(MIN_RADIX..=MAX_RADIX).contains(&radix) {
+            return Err(Error::InvalidRadix(radix));
+        }
+
+        // Digits are produced least significant first and flipped at the end
+        let written = if radix.is_power_of_two() {
+            self.unpack_pow2_digits(out, radix.trailing_zeros() as usize, uppercase)?
+        }
+        else {
+            self.extract_digits(out, radix, uppercase)?
+        };
+
+        out[..written].reverse();
+        Ok(written)
+    }
+
+    fn accumulate_digits(&mut self, s: &str, radix: u32) -> Result<(), ParseError> {
+
+        for (pos, c) in s.char_indices() {
+            let digit = c.to_digit(radix).unwrap();
+
+            if self.mul_add_small(radix, digit) != 0 {
+                return Err(ParseError::new(pos, ParseErrorKind::Overflow));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn pack_pow2_digits(&mut self, s: &str, bits: usize) -> Result<(), ParseError> {
+
+        let mut significant = 0;
+        for (pos, c) in s.char_indices() {
+            let digit = c.to_digit(1 << bits).unwrap();
+
+            significant = if significant == 0 { 32 - digit.leading_zeros() as usize }
+            else { significant + bits };
+
+            if significant > self.bit_len() {
+                return Err(ParseError::new(pos, ParseErrorKind::Overflow));
+            }
+        }
+
+        // Fill from the least significant end, every digit lands at a fixed bit offset and spans
+        // at most two bytes. Bits past the buffer are zero, the check above saw to that.
+        let len = self.byte_len();
+        for (k, c) in s.chars().rev().enumerate() {
+            let offset = k * bits;
+            if offset >= self.bit_len() { break; }
+
+            let window = (c.to_digit(1 << bits).unwrap() as u16) << (offset % 8);
+            let ix = self.physical_index(len - 1 - offset / 8);
+            self.bytes[ix] |= window as u8;
+
+            if window > 0xFF && offset / 8 + 1 < len {
+                let ix = self.physical_index(len - 2 - offset / 8);
+                self.bytes[ix] |= (window >> 8) as u8;
+            }
+        }
+
+        Ok(())
+    }
+
+    // The digit count is only known once the divisions are done, so they go to a buffer first
+    // and `out` is written only if they all fit
+    fn extract_digits(&self, out: &mut [u8], radix: u32, uppercase: bool) -> Result<usize, Error> {
+
+        let mut scratch: Vec<u8> = self.iter().copied().collect();
+        let mut value = ByteString::new(&mut scratch);
+
+        let mut digits = Vec::with_capacity(self.bit_len() + 1);
+        loop {
+            digits.push(ascii_digit(value.div_rem_small(radix), uppercase));
+            if value.is_zero() { break; }
+        }
+
+        let slots = out.get_mut(..digits.len()).ok_or(Error::OutputTooSmall)?;
+        slots.copy_from_slice(&digits);
+
+        Ok(digits.len())
+    }
+
+    fn unpack_pow2_digits(&self, out: &mut [u8], bits: usize, uppercase: bool) -> Result<usize, Error> {
+
+        let len = self.byte_len();
+        let significant = self.iter().position(|e| *e != 0)
+            .map_or(0, |ix| (len - ix) * 8 - self.bytes[self.physical_index(ix)].leading_zeros() as usize);
+        let count = significant.div_ceil(bits).max(1);
+
+        if count > out.len() {
+            return Err(Error::OutputTooSmall);
+        }
+
+        // Each digit spans at most two bytes, read them as one window from the least significant end
+        let byte = |k: usize| if k < len { self.bytes[self.physical_index(len - 1 - k)] as u16 } else { 0 };
+        let mask = (1u16 << bits) - 1;
+
+        for (k, slot) in out.iter_mut().take(count).enumerate() {
+            let offset = k * bits;
+            let window = byte(offset / 8) | byte(offset / 8 + 1) << 8;
+
+            *slot = ascii_digit((window >> (offset % 8) & mask) as u32, uppercase);
+        }
+
+        Ok(count)
+    }
+}
+
+fn ascii_digit(digit: u32, uppercase: bool) -> u8 {
+    let c = char::from_digit(digit, MAX_RADIX).unwrap() as u8;
+
+    if uppercase { c.to_ascii_uppercase() } else { c }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::{view, XorShift};
+
+    fn format_u128(mut value: u128, radix: u32) -> String {
+        let mut digits = Vec::new();
+        loop {
+            digits.push(char::from_digit((value % radix as u128) as u32, radix).unwrap());
+            value /= radix as u128;
+            if value == 0 { break; }
+        }
+
+        digits.iter().rev().collect()
+    }
+
+    fn write_to_string(bytes: &ByteString, radix: u32, uppercase: bool) -> String {
+        let mut out = [0u8; 130];
+        let n = bytes.write_radix(&mut out, radix, uppercase).unwrap();
+        String::from_utf8(out[..n].to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_agrees_with_u128() {
+        let mut rng = XorShift::new(0x208);
+
+        for _ in 0..500 {
+            let len = 1 + rng.below(16);
+            let mut a = vec![0u8; 16];
+            rng.fill(&mut a[16 - len..]);
+            let value = u128::from_be_bytes(a.clone().try_into().unwrap());
+
+            let bytes = ByteString::new(&mut a[16 - len..]);
+
+            for radix in [2, 8, 10, 16, 36] {
+                assert_eq!(write_to_string(&bytes, radix, false), format_u128(value, radix));
+            }
+        }
+    }
+
+    #[test]
+    fn test_cross_radix_round_trip() {
+        let mut rng = XorShift::new(0x2080);
+
+        for _ in 0..200 {
+            let mut a = [0u8; 12];
+            rng.fill(&mut a);
+            let reversed = rng.next_bool();
+
+            let bytes = view(&mut a, reversed);
+
+            for radix in MIN_RADIX..=MAX_RADIX {
+                let text = write_to_string(&bytes, radix, rng.next_bool());
+
+                let mut b = [0u8; 12];
+                let mut parsed = view(&mut b, reversed);
+
+                parsed.assign_from_radix_str(&text, radix).unwrap();
+                assert!(parsed == bytes);
+            }
+        }
+    }
+
+    #[test]
+    fn test_radix_36_letters() {
+        let mut a = [0u8; 4];
+        let mut bytes = ByteString::new(&mut a);
+
+        bytes.assign_from_radix_str("Zz", 36).unwrap();
+        assert_eq!(a, (35u32 * 36 + 35).to_be_bytes());
+
+        let bytes = ByteString::new(&mut a);
+        assert_eq!(write_to_string(&bytes, 36, true), "ZZ");
+        assert_eq!(write_to_string(&bytes, 36, false), "zz");
+    }
+
+    #[test]
+    fn test_invalid_digit_position() {
+        let mut a = [0xEEu8; 4];
+        let mut bytes = ByteString::new(&mut a);
+
+        let err = bytes.assign_from_radix_str("1012", 2).unwrap_err();
+        assert_eq!(err, ParseError::new(3, ParseErrorKind::InvalidDigit('2')));
+
+        let err = bytes.assign_from_radix_str("12g4", 16).unwrap_err();
+        assert_eq!(err, ParseError::new(2, ParseErrorKind::InvalidDigit('g')));
+
+        let err = bytes.assign_from_radix_str("", 10).unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::Empty);
+
+        let err = bytes.assign_from_radix_str("1", 37).unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::InvalidRadix(37));
+
+        // Rejected before the buffer is touched
+        assert_eq!(a, [0xEE; 4]);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let mut a = [0u8; 1];
+        let mut bytes = ByteString::new(&mut a);
+
+        assert!(bytes.assign_from_radix_str("255", 10).is_ok());
+        assert!(bytes.assign_from_radix_str("0000ff", 16).is_ok());
+        assert_eq!(bytes.assign_from_radix_str("256", 10).unwrap_err(), ParseError::new(2, ParseErrorKind::Overflow));
+        assert_eq!(bytes.assign_from_radix_str("1ff", 16).unwrap_err(), ParseError::new(2, ParseErrorKind::Overflow));
+        assert!(bytes.is_zero());
+    }
+
+    #[test]
+    fn test_write_output_too_small() {
+        let mut a = [0x01u8, 0x00];
+        let bytes = ByteString::new(&mut a);
+
+        // A failed write leaves the output untouched
+        let mut out = [b'-'; 2];
+        assert_eq!(bytes.write_radix(&mut out, 10, false), Err(Error::OutputTooSmall));
+        assert_eq!(bytes.write_radix(&mut out, 16, false), Err(Error::OutputTooSmall));
+        assert_eq!(&out, b"--");
+        assert_eq!(bytes.write_radix(&mut out, 36, false), Ok(2));
+        assert_eq!(bytes.write_radix(&mut out, 1, false), Err(Error::InvalidRadix(1)));
+    }
+}
@@ -0,0 +1,188 @@
+//! Conversion to and from raw pointer parts, for FFI
+//!
+//! Rebuilding a ByteString from raw parts is `unsafe fn`, so `ByteString::from_raw_parts` and
+//! `RawByteString::into_byte_string` are left out with `forbid-unsafe`. Taking a ByteString
+//! apart stays available.
+
+#[cfg(not(feature = "forbid-unsafe"))]
+use std::slice;
+
+use crate::bytes::ByteString;
+
+/// C compatible mirror of a ByteString for passing buffers across an FFI boundary.
+///
+/// Only the byte range and interpretation flag are carried, the sensitive mark is not.
+///
+/// ```
+/// use rebite::{bytes::ByteString, raw::RawByteString};
+///
+/// let mut raw = [1u8, 2];
+/// let mut bytes = ByteString::new(&mut raw);
+/// bytes.interpret_reverse_endian();
+///
+/// let parts = RawByteString::from(bytes);
+/// assert_eq!((parts.len, parts.reversed), (2, true));
+/// assert_eq!(parts.ptr.cast_const(), raw.as_ptr());
+/// ```
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawByteString {
+    /// First physical byte of the buffer
+    pub ptr: *mut u8,
+    /// Length of the buffer in bytes
+    pub len: usize,
+    /// The interpretation flag
+    pub reversed: bool,
+}
+
+impl<'a> ByteString<'a> {
+
+    /// Decompose into the buffer pointer, byte length and interpretation flag.
+    ///
+    /// The pointer stays valid for as long as the original borrow would have.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [1u8, 2, 3];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    ///
+    /// let (ptr, len, reversed) = bytes.into_raw_parts();
+    /// assert_eq!((len, reversed), (3, true));
+    /// assert_eq!(ptr.cast_const(), raw.as_ptr());
+    /// ```
+    pub fn into_raw_parts(self) -> (*mut u8, usize, bool) {
+        (self.bytes.as_mut_ptr(), self.bytes.len(), self.interpret_reverse_endian)
+    }
+
+    /// Rebuild a ByteString from the parts returned by [`ByteString::into_raw_parts`].
+    ///
+    /// # Safety
+    ///
+    /// The same contract as [`slice::from_raw_parts_mut`]:
+    ///
+    /// * `ptr` must be non-null, and valid for reads and writes of `len` bytes, which must all be
+    ///   initialized and lie within a single allocation. For `len == 0` a dangling aligned
+    ///   pointer such as `NonNull::dangling()` is fine.
+    /// * The memory must not be accessed through any other pointer for the lifetime `'a`,
+    ///   which the caller chooses and must not outlive the allocation.
+    /// * `len` must not exceed `isize::MAX`.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [1u8, 2, 3];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    /// let (ptr, len, reversed) = bytes.into_raw_parts();
+    ///
+    /// // This is ok, the parts come straight from a live ByteString
+    /// let bytes = unsafe { ByteString::from_raw_parts(ptr, len, reversed) };
+    /// assert_eq!(bytes.to_string(), "030201");
+    /// ```
+    #[cfg(not(feature = "forbid-unsafe"))]
+    pub unsafe fn from_raw_parts(ptr: *mut u8, len: usize, reversed: bool) -> ByteString<'a> {
+        /*
+            This is ok, the caller upholds the slice::from_raw_parts_mut contract
+            documented above
+        */
+        let mut bytes = ByteString::new(unsafe { slice::from_raw_parts_mut(ptr, len) });
+        if reversed { bytes.interpret_reverse_endian(); }
+        bytes
+    }
+}
+
+impl<'a> From<ByteString<'a>> for RawByteString {
+    fn from(bytes: ByteString<'a>) -> Self {
+        let (ptr, len, reversed) = bytes.into_raw_parts();
+        Self { ptr, len, reversed }
+    }
+}
+
+#[cfg(not(feature = "forbid-unsafe"))]
+impl RawByteString {
+
+    /// Reconstruct the ByteString this was made from.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`ByteString::from_raw_parts`] for the fields of `self`.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, raw::RawByteString};
+    ///
+    /// let mut raw = [1u8, 2];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    /// let parts = RawByteString::from(bytes);
+    ///
+    /// // This is ok, the parts come from a live ByteString whose borrow ended with the conversion
+    /// let bytes = unsafe { parts.into_byte_string() };
+    /// assert_eq!(bytes.to_u64(), Ok(0x0201));
+    /// ```
+    pub unsafe fn into_byte_string<'a>(self) -> ByteString<'a> {
+        /* This is ok, forwarded contract */
+        unsafe { ByteString::from_raw_parts(self.ptr, self.len, self.reversed) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[cfg(not(feature = "forbid-unsafe"))]
+    use std::ptr::NonNull;
+
+    use super::*;
+
+    #[test]
+    fn test_parts_cover_the_buffer() {
+        let mut raw = [0u8; 5];
+        let start = raw.as_ptr();
+        let mut bytes = ByteString::new(&mut raw[1..4]);
+        bytes.interpret_reverse_endian();
+
+        let parts = RawByteString::from(bytes);
+        assert_eq!(parts.ptr.cast_const(), start.wrapping_add(1));
+        assert_eq!((parts.len, parts.reversed), (3, true));
+    }
+
+    #[test]
+    #[cfg(not(feature = "forbid-unsafe"))]
+    fn test_raw_parts_round_trip() {
+        let mut raw = [0x01u8, 0x02, 0x03, 0x04];
+        let mut bytes = ByteString::new(&mut raw);
+        bytes.interpret_reverse_endian();
+
+        let (ptr, len, reversed) = bytes.into_raw_parts();
+        assert_eq!((len, reversed), (4, true));
+
+        let mut rebuilt = unsafe { ByteString::from_raw_parts(ptr, len, reversed) };
+        assert!(rebuilt.iter().eq([0x04, 0x03, 0x02, 0x01].iter()));
+
+        rebuilt <<= 8;
+        assert_eq!(raw, [0x00, 0x01, 0x02, 0x03]);
+    }
+
+    #[test]
+    #[cfg(not(feature = "forbid-unsafe"))]
+    fn test_repr_c_mirror() {
+        let mut raw = [0xAAu8, 0xBB];
+        let c_side = RawByteString::from(ByteString::new(&mut raw));
+
+        // What a C callback would do with the struct
+        unsafe { *c_side.ptr.add(1) = 0xCC; }
+
+        let mut bytes = unsafe { c_side.into_byte_string() };
+        bytes.interpret_reverse_endian();
+        assert!(bytes.iter().eq([0xCC, 0xAA].iter()));
+    }
+
+    #[test]
+    #[cfg(not(feature = "forbid-unsafe"))]
+    fn test_empty_dangling() {
+        let bytes = unsafe { ByteString::from_raw_parts(NonNull::dangling().as_ptr(), 0, false) };
+        assert!(bytes.is_zero());
+        assert_eq!(bytes.byte_len(), 0);
+    }
+}
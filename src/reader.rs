@@ -0,0 +1,462 @@
+//! A cursor reading typed fields and bit fields, the read side of [`FieldWriter`](crate::writer::FieldWriter)
+
+use crate::{
+    bit_order::BitOrder,
+    bytes::ByteString,
+    bytes_iter::BytesIter,
+    error::{ReadError, ReadErrorKind},
+    typed::Endianness
+};
+
+/// Sequential reader of typed fields and bit fields, in logical order.
+///
+/// Follows the padding rules of [`FieldWriter`](crate::writer::FieldWriter): bits are taken most
+/// significant first and a byte-level read after bits skips the rest of the partial byte. A read
+/// that does not fit fails with a [`ReadError`] and leaves the cursor where it was.
+/// [`FieldReader::bit_order`] switches to formats packing bit fields from the low bit of each byte.
+///
+/// ```
+/// use rebite::{bytes::ByteString, typed::Endianness, reader::FieldReader};
+///
+/// let mut raw = [0x45u8, 0x00, 0x00, 0x54];
+/// let bytes = ByteString::new(&mut raw);
+/// let mut r = FieldReader::new(&bytes);
+///
+/// assert_eq!(r.take_bits(4), Ok(4));
+/// assert_eq!(r.take_bits(4), Ok(5));
+/// r.skip(1).unwrap();
+/// assert_eq!(r.take_u16(Endianness::Big), Ok(84));
+///
+/// let err = r.context("ttl").take_u8().unwrap_err();
+/// assert_eq!(err.to_string(), "ttl: needed 8 bits at bit 32, 0 remaining");
+/// ```
+#[derive(Debug)]
+pub struct FieldReader<'r, 'a> {
+    bytes: &'r ByteString<'a>,
+    bit_position: usize,
+    bit_order: BitOrder,
+    context: Option<&'static str>,
+}
+
+impl<'r, 'a> FieldReader<'r, 'a> {
+
+    /// Cursor at logical bit 0, the most significant bit of the first logical byte.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, reader::FieldReader};
+    ///
+    /// let mut raw = [0x80u8, 0x01];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    ///
+    /// // The first logical byte is the physical last one
+    /// assert_eq!(FieldReader::new(&bytes).take_bits(8), Ok(0x01));
+    /// ```
+    pub fn new(bytes: &'r ByteString<'a>) -> Self {
+        Self { bytes, bit_position: 0, bit_order: BitOrder::Msb0, context: None }
+    }
+
+    /// Take the following bit fields in `order`, until the next call.
+    ///
+    /// With [`BitOrder::Lsb0`] the bits of a byte are taken from its least significant one up and
+    /// each field is read least significant bit first, as DEFLATE and most register formats pack
+    /// them. Either way a byte-aligned 8 bit field is the byte itself. Byte-level reads are not
+    /// affected. Change the order on a byte boundary, the orders take a partial byte from opposite ends.
+    ///
+    /// ```
+    /// use rebite::{bit_order::BitOrder, bytes::ByteString, reader::FieldReader};
+    ///
+    /// // A DEFLATE block header: BFINAL = 1 and BTYPE = 2 in the low three bits
+    /// let mut raw = [0b1111_0101u8];
+    /// let bytes = ByteString::new(&mut raw);
+    /// let mut r = FieldReader::new(&bytes);
+    ///
+    /// r.bit_order(BitOrder::Lsb0);
+    /// assert_eq!(r.take_bits(1), Ok(1));
+    /// assert_eq!(r.take_bits(2), Ok(2));
+    /// assert_eq!(r.take_bits(5), Ok(0b11110));
+    /// ```
+    pub fn bit_order(&mut self, order: BitOrder) -> &mut Self {
+        self.bit_order = order;
+        self
+    }
+
+    /// Tag errors of the following reads with `name`, until the next call.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, reader::FieldReader, typed::Endianness};
+    ///
+    /// let mut raw = [0x00u8];
+    /// let bytes = ByteString::new(&mut raw);
+    /// let mut r = FieldReader::new(&bytes);
+    ///
+    /// let err = r.context("length").take_u16(Endianness::Big).unwrap_err();
+    /// assert_eq!(err.context, Some("length"));
+    /// ```
+    pub fn context(&mut self, name: &'static str) -> &mut Self {
+        self.context = Some(name);
+        self
+    }
+
+    /// Bits taken or skipped so far.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, reader::FieldReader};
+    ///
+    /// let mut raw = [0u8; 2];
+    /// let bytes = ByteString::new(&mut raw);
+    /// let mut r = FieldReader::new(&bytes);
+    ///
+    /// r.take_bits(3).unwrap();
+    /// assert_eq!(r.bit_position(), 3);
+    ///
+    /// // A byte-level read first skips the rest of the partial byte
+    /// r.take_u8().unwrap();
+    /// assert_eq!(r.bit_position(), 16);
+    /// ```
+    pub fn bit_position(&self) -> usize {
+        self.bit_position
+    }
+
+    /// Logical offset of the next byte-level read, past any partially read byte.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, reader::FieldReader};
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let bytes = ByteString::new(&mut raw);
+    /// let mut r = FieldReader::new(&bytes);
+    ///
+    /// assert_eq!(r.position(), 0);
+    /// r.take_bits(1).unwrap();
+    /// assert_eq!(r.position(), 1);
+    /// ```
+    pub fn position(&self) -> usize {
+        self.bit_position.div_ceil(8)
+    }
+
+    /// Whole bytes left for byte-level reads.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, reader::FieldReader};
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let bytes = ByteString::new(&mut raw);
+    /// let mut r = FieldReader::new(&bytes);
+    ///
+    /// r.skip(1).unwrap();
+    /// r.take_bits(2).unwrap();
+    /// assert_eq!(r.remaining(), 2);
+    /// ```
+    pub fn remaining(&self) -> usize {
+        self.bytes.byte_len().saturating_sub(self.position())
+    }
+
+    /// Take a byte.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, reader::FieldReader};
+    ///
+    /// let mut raw = [0xABu8];
+    /// let bytes = ByteString::new(&mut raw);
+    /// let mut r = FieldReader::new(&bytes);
+    ///
+    /// assert_eq!(r.take_u8(), Ok(0xAB));
+    /// assert!(r.take_u8().is_err());
+    /// ```
+    pub fn take_u8(&mut self) -> Result<u8, ReadError> {
+        let start = self.reserve(1)?;
+        Ok(self.bytes.iter_range(start..start + 1).next().copied().unwrap())
+    }
+
+    /// Take a u16 in the given byte order.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, reader::FieldReader, typed::Endianness};
+    ///
+    /// let mut raw = [0x12u8, 0x34, 0x12, 0x34];
+    /// let bytes = ByteString::new(&mut raw);
+    /// let mut r = FieldReader::new(&bytes);
+    ///
+    /// assert_eq!(r.take_u16(Endianness::Big), Ok(0x1234));
+    /// assert_eq!(r.take_u16(Endianness::Little), Ok(0x3412));
+    /// ```
+    pub fn take_u16(&mut self, endian: Endianness) -> Result<u16, ReadError> {
+        let start = self.reserve(2)?;
+        Ok(self.bytes.read_u16_at(start, endian).unwrap())
+    }
+
+    /// Take a u32 in the given byte order.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, reader::FieldReader, typed::Endianness};
+    ///
+    /// let mut raw = [0x00u8, 0x00, 0x01, 0x00];
+    /// let bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(FieldReader::new(&bytes).take_u32(Endianness::Big), Ok(256));
+    /// ```
+    pub fn take_u32(&mut self, endian: Endianness) -> Result<u32, ReadError> {
+        let start = self.reserve(4)?;
+        Ok(self.bytes.read_u32_at(start, endian).unwrap())
+    }
+
+    /// Take a u64 in the given byte order.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, reader::FieldReader, typed::Endianness};
+    ///
+    /// let mut raw = [0x01u8, 0, 0, 0, 0, 0, 0, 0];
+    /// let bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(FieldReader::new(&bytes).take_u64(Endianness::Little), Ok(1));
+    /// ```
+    pub fn take_u64(&mut self, endian: Endianness) -> Result<u64, ReadError> {
+        let start = self.reserve(8)?;
+        Ok(self.bytes.read_u64_at(start, endian).unwrap())
+    }
+
+    /// Take `n` bytes, iterated in logical order.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, reader::FieldReader};
+    ///
+    /// let mut raw = [1u8, 2, 3, 4];
+    /// let bytes = ByteString::new(&mut raw);
+    /// let mut r = FieldReader::new(&bytes);
+    ///
+    /// r.skip(1).unwrap();
+    /// assert!(r.take_bytes(2).unwrap().copied().eq([2, 3]));
+    /// assert!(r.take_bytes(2).is_err());
+    /// ```
+    pub fn take_bytes(&mut self, n: usize) -> Result<BytesIter<'r>, ReadError> {
+        let start = self.reserve(n)?;
+        let bytes: &'r ByteString<'a> = self.bytes;
+        Ok(bytes.iter_range(start..start + n))
+    }
+
+    /// Take `width` bits as the low bits of the result, most significant first unless the bit order is [`BitOrder::Lsb0`].
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, reader::FieldReader};
+    ///
+    /// let mut raw = [0b1011_0010u8];
+    /// let bytes = ByteString::new(&mut raw);
+    /// let mut r = FieldReader::new(&bytes);
+    ///
+    /// assert_eq!(r.take_bits(3), Ok(0b101));
+    /// assert_eq!(r.take_bits(5), Ok(0b10010));
+    /// assert!(r.take_bits(1).is_err());
+    /// ```
+    pub fn take_bits(&mut self, width: usize) -> Result<u64, ReadError> {
+        if width > 64 {
+            return Err(self.error(self.bit_position, ReadErrorKind::TooWide(width)));
+        }
+
+        let remaining = self.bytes.bit_len() - self.bit_position;
+        if width > remaining {
+            return Err(self.error(self.bit_position, ReadErrorKind::Exhausted { needed: width, remaining }));
+        }
+
+        let positions = self.bit_position..self.bit_position + width;
+        let bit = |p| self.bytes.stream_bit(p, self.bit_order) as u64;
+        let value = match self.bit_order {
+            BitOrder::Msb0 => positions.fold(0, |acc, p| acc << 1 | bit(p)),
+            BitOrder::Lsb0 => positions.rev().fold(0, |acc, p| acc << 1 | bit(p)),
+        };
+        self.bit_position += width;
+
+        Ok(value)
+    }
+
+    /// Skip `n` bytes.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, reader::FieldReader};
+    ///
+    /// let mut raw = [1u8, 2, 3];
+    /// let bytes = ByteString::new(&mut raw);
+    /// let mut r = FieldReader::new(&bytes);
+    ///
+    /// r.skip(2).unwrap();
+    /// assert_eq!(r.take_u8(), Ok(3));
+    ///
+    /// // A failed skip leaves the cursor in place
+    /// assert!(r.skip(1).is_err());
+    /// assert_eq!(r.position(), 3);
+    /// ```
+    pub fn skip(&mut self, n: usize) -> Result<(), ReadError> {
+        self.reserve(n).map(|_| ())
+    }
+
+    // Offset of the next whole byte, the cursor stays put if `len` bytes do not fit after it
+    fn reserve(&mut self, len: usize) -> Result<usize, ReadError> {
+        let start = self.position();
+        match start.checked_add(len) {
+            Some(end) if end <= self.bytes.byte_len() => {
+                self.bit_position = end * 8;
+                Ok(start)
+            },
+            _ => {
+                let remaining = self.bytes.bit_len().saturating_sub(start * 8);
+                Err(self.error(start * 8, ReadErrorKind::Exhausted { needed: len.saturating_mul(8), remaining }))
+            },
+        }
+    }
+
+    fn error(&self, position: usize, kind: ReadErrorKind) -> ReadError {
+        ReadError { position, kind, context: self.context }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{
+        test_util::{view, view_logical, XorShift},
+        writer::FieldWriter
+    };
+
+    // Field kinds shared by the writer and reader sides of the round trip
+    #[derive(Debug, Clone, Copy)]
+    enum Field {
+        U8(u8),
+        U16(u16, Endianness),
+        U32(u32, Endianness),
+        U64(u64, Endianness),
+        Bytes([u8; 3]),
+        Bits(u64, usize),
+    }
+
+    fn random_field(rng: &mut XorShift) -> Field {
+        let endian = if rng.next_bool() { Endianness::Big } else { Endianness::Little };
+        match rng.below(6) {
+            0 => Field::U8(rng.next_u8()),
+            1 => Field::U16(rng.next_u64() as u16, endian),
+            2 => Field::U32(rng.next_u64() as u32, endian),
+            3 => Field::U64(rng.next_u64(), endian),
+            4 => Field::Bytes([rng.next_u8(), rng.next_u8(), rng.next_u8()]),
+            _ => {
+                let width = rng.below(65);
+                let value = if width == 64 { rng.next_u64() } else { rng.next_u64() & ((1 << width) - 1) };
+                Field::Bits(value, width)
+            },
+        }
+    }
+
+    #[test]
+    fn test_reader_recovers_writer_fields() {
+        let mut rng = XorShift::new(0x275);
+
+        for _ in 0..200 {
+            let fields: Vec<Field> = (0..rng.below(12)).map(|_| random_field(&mut rng)).collect();
+            let reversed = rng.next_bool();
+            let order = if rng.next_bool() { BitOrder::Lsb0 } else { BitOrder::Msb0 };
+
+            let mut raw = vec![0u8; 128];
+            let mut bytes = view(&mut raw, reversed);
+
+            let mut w = FieldWriter::new(&mut bytes);
+            w.bit_order(order);
+            for field in &fields {
+                match *field {
+                    Field::U8(v) => w.put_u8(v),
+                    Field::U16(v, e) => w.put_u16(v, e),
+                    Field::U32(v, e) => w.put_u32(v, e),
+                    Field::U64(v, e) => w.put_u64(v, e),
+                    Field::Bytes(v) => w.put_bytes(&v),
+                    Field::Bits(v, width) => w.put_bits(v, width),
+                }.unwrap();
+            }
+            let written = w.finish();
+
+            let mut r = FieldReader::new(&bytes);
+            r.bit_order(order);
+            for field in &fields {
+                let ok = match *field {
+                    Field::U8(v) => r.take_u8() == Ok(v),
+                    Field::U16(v, e) => r.take_u16(e) == Ok(v),
+                    Field::U32(v, e) => r.take_u32(e) == Ok(v),
+                    Field::U64(v, e) => r.take_u64(e) == Ok(v),
+                    Field::Bytes(v) => r.take_bytes(3).unwrap().eq(v.iter()),
+                    Field::Bits(v, width) => r.take_bits(width) == Ok(v),
+                };
+                assert!(ok, "{:?} in {:?}", field, fields);
+            }
+            assert_eq!(r.position(), written);
+        }
+    }
+
+    #[test]
+    fn test_exhaustion_carries_position_and_context() {
+        let mut raw = [0x01u8, 0x02, 0x03];
+        let bytes = ByteString::new(&mut raw);
+        let mut r = FieldReader::new(&bytes);
+
+        assert_eq!(r.take_bits(3), Ok(0));
+        let err = r.context("length").take_u32(Endianness::Big).unwrap_err();
+        assert_eq!(err, ReadError { position: 8, kind: ReadErrorKind::Exhausted { needed: 32, remaining: 16 }, context: Some("length") });
+
+        // The failed read left the cursor alone, the context sticks until replaced
+        assert_eq!((r.bit_position(), r.position(), r.remaining()), (3, 1, 2));
+        assert_eq!(r.take_bits(65).unwrap_err(), ReadError { position: 3, kind: ReadErrorKind::TooWide(65), context: Some("length") });
+        assert_eq!(r.take_bits(22).unwrap_err().kind, ReadErrorKind::Exhausted { needed: 22, remaining: 21 });
+        assert_eq!(r.context("payload").take_bytes(3).err().unwrap().to_string(), "payload: needed 24 bits at bit 8, 16 remaining");
+        assert_eq!(r.skip(usize::MAX).unwrap_err().kind, ReadErrorKind::Exhausted { needed: usize::MAX, remaining: 16 });
+
+        assert_eq!(r.take_u16(Endianness::Little), Ok(0x0302));
+        assert_eq!(r.remaining(), 0);
+        assert_eq!(r.take_bits(0), Ok(0));
+    }
+
+    #[test]
+    fn test_interleaved_bits_and_bytes_follow_padding() {
+        for reversed in [false, true] {
+            let mut raw = [0xA5u8, 0xFF, 0xFF, 0x80, 0xC3];
+            let bytes = view_logical(&mut raw, reversed);
+            let mut r = FieldReader::new(&bytes);
+
+            // A byte read skips the rest of a partial byte, whatever its bits
+            assert_eq!(r.take_bits(3), Ok(0b101));
+            assert_eq!(r.take_u8(), Ok(0xFF));
+
+            // Bits spanning a byte boundary
+            assert_eq!(r.take_bits(9), Ok(0b1_1111_1111));
+            assert_eq!((r.bit_position(), r.position()), (25, 4));
+            assert_eq!(r.take_bytes(1).unwrap().copied().collect::<Vec<u8>>(), [0xC3]);
+        }
+    }
+
+    #[test]
+    fn test_lsb_first_bits() {
+        for reversed in [false, true] {
+            let mut raw = [0b1000_0110u8, 0b0101_0011, 0x1F];
+            let bytes = view_logical(&mut raw, reversed);
+
+            // Single bits come in the reverse order within each byte
+            let mut msb = FieldReader::new(&bytes);
+            let mut lsb = FieldReader::new(&bytes);
+            lsb.bit_order(BitOrder::Lsb0);
+            let msb: Vec<u64> = (0..16).map(|_| msb.take_bits(1).unwrap()).collect();
+            let lsb: Vec<u64> = (0..16).map(|_| lsb.take_bits(1).unwrap()).collect();
+            for (m, l) in msb.chunks(8).zip(lsb.chunks(8)) {
+                assert!(m.iter().eq(l.iter().rev()));
+            }
+
+            // Fields run from the low bits up and across the byte boundary, low bits first
+            let mut r = FieldReader::new(&bytes);
+            r.bit_order(BitOrder::Lsb0);
+            assert_eq!(r.take_bits(3), Ok(0b110));
+            assert_eq!(r.take_bits(7), Ok(0b11_10000));
+            assert_eq!(r.take_bits(6), Ok(0b010100));
+            assert_eq!(r.bit_position(), 16);
+
+            // An aligned byte reads the same in both orders
+            assert_eq!(r.take_bits(8), Ok(0x1F));
+            let mut r = FieldReader::new(&bytes);
+            r.skip(2).unwrap();
+            assert_eq!(r.take_bits(8), Ok(0x1F));
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Bounds checked access to logical regions
+
+use crate::{
+    bytes::ByteString,
+    error::RegionError
+};
+
+impl<'a> ByteString<'a> {
+
+    /// Check that the logical region `offset..offset + len` lies within the buffer.
+    ///
+    /// A zero length region is valid up to and including `offset == byte_len()`.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let bytes = ByteString::new(&mut raw);
+    ///
+    /// assert!(bytes.check_region(1, 3).is_ok());
+    /// assert!(bytes.check_region(4, 0).is_ok());
+    ///
+    /// let err = bytes.check_region(3, 2).unwrap_err();
+    /// assert_eq!((err.offset, err.len, err.byte_len), (3, 2, 4));
+    /// ```
+    pub fn check_region(&self, offset: usize, len: usize) -> Result<(), RegionError> {
+        match offset.checked_add(len) {
+            Some(end) if end <= self.byte_len() => Ok(()),
+            _ => Err(RegionError { offset, len, byte_len: self.byte_len() }),
+        }
+    }
+
+    /// Physical bytes backing the logical region `offset..offset + len`.
+    ///
+    /// With the flag set the slice holds the region's bytes in reverse logical order.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [1u8, 2, 3, 4];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// assert_eq!(bytes.region(1, 2), Ok(&[2u8, 3][..]));
+    ///
+    /// // Logical bytes 0..2 are the physical bytes 2..4
+    /// bytes.interpret_reverse_endian();
+    /// assert_eq!(bytes.region(0, 2), Ok(&[3u8, 4][..]));
+    /// ```
+    pub fn region(&self, offset: usize, len: usize) -> Result<&[u8], RegionError> {
+        self.check_region(offset, len)?;
+        let physical = self.physical_range(offset..offset + len).unwrap();
+
+        Ok(&self.bytes[physical])
+    }
+
+    /// Mutable variant of [`ByteString::region`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.region_mut(2, 2).unwrap().fill(0xFF);
+    /// assert_eq!(raw, [0x00, 0x00, 0xFF, 0xFF]);
+    /// ```
+    pub fn region_mut(&mut self, offset: usize, len: usize) -> Result<&mut [u8], RegionError> {
+        self.check_region(offset, len)?;
+        let physical = self.physical_range(offset..offset + len).unwrap();
+
+        Ok(&mut self.bytes[physical])
+    }
+
+    /// Check every `(offset, len)` descriptor, reporting all violations paired with their index
+    /// in `descriptors` rather than stopping at the first.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let bytes = ByteString::new(&mut raw);
+    ///
+    /// assert!(bytes.regions(&[(0, 2), (2, 2)]).is_ok());
+    ///
+    /// let violations = bytes.regions(&[(5, 0), (0, 4), (3, 2)]).unwrap_err();
+    /// assert!(violations.iter().map(|(ix, _)| *ix).eq([0, 2]));
+    /// ```
+    pub fn regions(&self, descriptors: &[(usize, usize)]) -> Result<(), Vec<(usize, RegionError)>> {
+        let violations: Vec<_> = descriptors.iter().enumerate()
+            .filter_map(|(ix, &(offset, len))| self.check_region(offset, len).err().map(|err| (ix, err)))
+            .collect();
+
+        if violations.is_empty() { Ok(()) } else { Err(violations) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_exact_fit() {
+        let mut raw = [0x01u8, 0x02, 0x03, 0x04];
+        let bytes = ByteString::new(&mut raw);
+
+        assert_eq!(bytes.region(0, 4), Ok(&[0x01u8, 0x02, 0x03, 0x04][..]));
+        assert_eq!(bytes.region(1, 3), Ok(&[0x02u8, 0x03, 0x04][..]));
+        assert!(bytes.regions(&[(0, 1), (1, 2), (3, 1)]).is_ok());
+    }
+
+    #[test]
+    fn test_off_by_one_overruns() {
+        let mut raw = [0u8; 4];
+        let mut bytes = ByteString::new(&mut raw);
+
+        let err = bytes.check_region(1, 4).unwrap_err();
+        assert_eq!(err, RegionError { offset: 1, len: 4, byte_len: 4 });
+        assert_eq!(err.overrun(), 1);
+        assert_eq!(err.to_string(), "region of 4 bytes at 1 overruns the 4 byte buffer by 1 bytes");
+
+        assert_eq!(bytes.region(5, 0).unwrap_err().overrun(), 1);
+        assert!(bytes.region_mut(0, 5).is_err());
+        assert_eq!(bytes.check_region(usize::MAX, 2).unwrap_err().overrun(), usize::MAX - 4);
+    }
+
+    #[test]
+    fn test_zero_length_at_end() {
+        let mut raw = [0u8; 3];
+        let bytes = ByteString::new(&mut raw);
+
+        assert_eq!(bytes.region(3, 0), Ok(&[][..]));
+
+        let mut empty: [u8; 0] = [];
+        let bytes = ByteString::new(&mut empty);
+        assert!(bytes.check_region(0, 0).is_ok());
+        assert!(bytes.check_region(0, 1).is_err());
+    }
+
+    #[test]
+    fn test_bulk_reports_all_violations() {
+        let mut raw = [0u8; 8];
+        let bytes = ByteString::new(&mut raw);
+
+        // A length-prefixed frame whose last two regions are truncated
+        let violations = bytes.regions(&[(0, 2), (2, 4), (6, 3), (9, 0)]).unwrap_err();
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0], (2, RegionError { offset: 6, len: 3, byte_len: 8 }));
+        assert_eq!(violations[1].0, 3);
+        assert_eq!(violations[1].1.overrun(), 1);
+    }
+
+    #[test]
+    fn test_reversed_offset_mapping() {
+        let mut raw = [0x01u8, 0x02, 0x03, 0x04, 0x05];
+        let mut bytes = ByteString::new(&mut raw);
+        bytes.interpret_reverse_endian();
+
+        // Logical bytes 05 04 03 02 01, the first two live at the physical end
+        assert_eq!(bytes.region(0, 2), Ok(&[0x04u8, 0x05][..]));
+        assert_eq!(bytes.region(3, 2), Ok(&[0x01u8, 0x02][..]));
+        assert_eq!(bytes.region(5, 0), Ok(&[][..]));
+
+        bytes.region_mut(1, 3).unwrap().fill(0xAA);
+        assert!(bytes.iter().eq([0x05u8, 0xAA, 0xAA, 0xAA, 0x01].iter()));
+    }
+}
@@ -0,0 +1,141 @@
+//! Widening a value into a larger buffer
+
+use crate::{
+    bytes::ByteString,
+    error::Error
+};
+
+/// Fill for the extra most significant bytes when widening a value.
+///
+/// ```
+/// use rebite::{bytes::ByteString, resize::Extension};
+///
+/// let mut raw = [0x12u8];
+/// let bytes = ByteString::new(&mut raw);
+/// let mut wide = [0u8; 3];
+///
+/// bytes.extend_into(&mut ByteString::new(&mut wide), Extension::Byte(0xAA)).unwrap();
+/// assert_eq!(wide, [0xAA, 0xAA, 0x12]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Extension {
+    /// Fill with zero bytes, for unsigned values
+    Zero,
+    /// Replicate the sign bit of the two's complement source
+    Sign,
+    /// Fill with the given byte
+    Byte(u8),
+}
+
+impl<'a> ByteString<'a> {
+
+    /// Copy the value into the wider or equally wide `out`, filling its extra most significant logical bytes.
+    ///
+    /// Fails with [`Error::OutputTooSmall`] if `out` is shorter than self.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, resize::Extension};
+    ///
+    /// let mut raw = [0x80u8, 0x01];
+    /// let bytes = ByteString::new(&mut raw);
+    /// let mut wide = [0u8; 4];
+    ///
+    /// bytes.extend_into(&mut ByteString::new(&mut wide), Extension::Sign).unwrap();
+    /// assert_eq!(wide, [0xFF, 0xFF, 0x80, 0x01]);
+    ///
+    /// bytes.extend_into(&mut ByteString::new(&mut wide), Extension::Zero).unwrap();
+    /// assert_eq!(wide, [0x00, 0x00, 0x80, 0x01]);
+    /// ```
+    pub fn extend_into(&self, out: &mut ByteString, extension: Extension) -> Result<(), Error> {
+
+        let extra = out.byte_len().checked_sub(self.byte_len()).ok_or(Error::OutputTooSmall)?;
+
+        let fill = match extension {
+            Extension::Zero => 0x00,
+            Extension::Sign => match self.iter().next() {
+                Some(msb) if msb & 0x80 != 0 => 0xFF,
+                _ => 0x00
+            },
+            Extension::Byte(value) => value
+        };
+
+        let mut dst = out.iter_mut();
+        for byte in dst.by_ref().take(extra) {
+            *byte = fill;
+        }
+
+        for (dst, src) in dst.zip(self.iter()) {
+            *dst = *src;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::{view, view_logical, XorShift};
+
+    fn extend(value: &[u8], src_reversed: bool, out_len: usize, out_reversed: bool, extension: Extension) -> Vec<u8> {
+        let mut raw = value.to_vec();
+        let src = view_logical(&mut raw, src_reversed);
+
+        let mut out_raw = vec![0xEEu8; out_len];
+        let mut out = view(&mut out_raw, out_reversed);
+
+        src.extend_into(&mut out, extension).unwrap();
+        out.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_extend_matches_u128() {
+        let mut rng = XorShift::new(0x225);
+
+        for _ in 0..1000 {
+            let len = rng.below(9);
+            let out_len = len + rng.below(17 - len);
+
+            let mut value = vec![0u8; len];
+            rng.fill(&mut value);
+            let (src_reversed, out_reversed) = (rng.next_bool(), rng.next_bool());
+
+            let mut padded = [0u8; 16];
+            padded[16 - len..].copy_from_slice(&value);
+            let unsigned = u128::from_be_bytes(padded);
+            let signed = if len > 0 && value[0] & 0x80 != 0 { unsigned | (u128::MAX << (len * 8)) } else { unsigned };
+
+            let expected = |wide: u128| wide.to_be_bytes()[16 - out_len..].to_vec();
+            let fill_byte = rng.next_u8();
+            let filled = if len == 16 { unsigned } else { unsigned | (u128::from_be_bytes([fill_byte; 16]) << (len * 8)) };
+
+            assert_eq!(extend(&value, src_reversed, out_len, out_reversed, Extension::Zero), expected(unsigned));
+            assert_eq!(extend(&value, src_reversed, out_len, out_reversed, Extension::Sign), expected(signed));
+            assert_eq!(extend(&value, src_reversed, out_len, out_reversed, Extension::Byte(fill_byte)), expected(filled));
+        }
+    }
+
+    #[test]
+    fn test_extend_physical_placement() {
+        let mut raw = [0x80u8, 0x01];
+        let src = ByteString::new(&mut raw);
+
+        let mut out_raw = [0u8; 4];
+        let mut out = ByteString::new(&mut out_raw);
+        out.interpret_reverse_endian();
+        src.extend_into(&mut out, Extension::Sign).unwrap();
+
+        // The fill lands at the physical end of a reversed destination
+        assert_eq!(out_raw, [0x01, 0x80, 0xFF, 0xFF]);
+    }
+
+    #[test]
+    fn test_extend_into_shorter_fails() {
+        let mut raw = [1u8, 2, 3];
+        let mut out_raw = [0u8; 2];
+
+        let result = ByteString::new(&mut raw).extend_into(&mut ByteString::new(&mut out_raw), Extension::Zero);
+        assert_eq!(result, Err(Error::OutputTooSmall));
+    }
+}
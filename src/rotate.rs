@@ -0,0 +1,240 @@
+//! Bit rotation of the whole buffer or of a bit range
+
+use std::ops::Range;
+
+use crate::{
+    bytes::ByteString,
+    error::Error
+};
+
+impl<'a> ByteString<'a> {
+
+    /// Rotate the value toward the most significant end by `count` bits, reduced modulo the bit length.
+    ///
+    /// Bits leaving the top re-enter at the bottom. Like [`ByteString::rotl_bytes`] the rotation
+    /// is logical, with the flag set the bits move the other way through the physical bytes.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x80u8, 0x00, 0x01];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.rotl_bits(1);
+    /// assert_eq!(raw, [0x00, 0x00, 0x03]);
+    /// ```
+    pub fn rotl_bits(&mut self, count: usize) {
+        let bit_len = self.bit_len();
+        if bit_len == 0 {
+            return;
+        }
+
+        let count = count % bit_len;
+        self.rotl_bytes(count / 8);
+
+        let shift = count % 8;
+        if shift == 0 {
+            return;
+        }
+
+        // Each logical byte takes its low bits from the top of the next, the last from the first
+        let mut carry = self.iter().next().copied().unwrap() >> (8 - shift);
+        for byte in self.iter_mut().rev() {
+            let out = *byte >> (8 - shift);
+            *byte = *byte << shift | carry;
+            carry = out;
+        }
+    }
+
+    /// Rotate the value toward the least significant end by `count` bits, the inverse of [`ByteString::rotl_bits`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x00u8, 0x01];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.rotr_bits(1);
+    /// assert_eq!(bytes.bytes(), [0x80, 0x00]);
+    ///
+    /// // Reduced modulo the bit length
+    /// bytes.rotr_bits(17);
+    /// assert_eq!(raw, [0x40, 0x00]);
+    /// ```
+    pub fn rotr_bits(&mut self, count: usize) {
+        let bit_len = self.bit_len();
+        if bit_len == 0 {
+            return;
+        }
+
+        self.rotl_bits(bit_len - count % bit_len);
+    }
+
+    /// Rotate the bits of `range` toward the most significant end by `count`, leaving all other bits untouched.
+    ///
+    /// Bits are numbered from the least significant bit of the value, as with shifts.
+    /// `count` is reduced modulo the range length and empty ranges are a no-op.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let mut raw = [0b1001_0110u8];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// // Bits 2..6 hold 0101, rotated by one they become 1010
+    /// bytes.rotl_bit_range(2..6, 1).unwrap();
+    /// assert_eq!(bytes.bytes(), [0b1010_1010]);
+    ///
+    /// assert_eq!(bytes.rotl_bit_range(4..9, 1), Err(Error::OutOfBounds));
+    /// ```
+    pub fn rotl_bit_range(&mut self, range: Range<usize>, count: usize) -> Result<(), Error> {
+
+        if range.start > range.end || range.end > self.bit_len() {
+            return Err(Error::OutOfBounds);
+        }
+
+        let width = range.len();
+        if width == 0 || count.is_multiple_of(width) {
+            return Ok(());
+        }
+
+        // Field bits from least significant up, rotating left moves them to higher offsets
+        let mut field: Vec<bool> = range.clone().map(|offset| self.bit(offset)).collect();
+        field.rotate_right(count % width);
+
+        for (offset, value) in range.zip(field) {
+            self.set_bit(offset, value);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::{view_logical, XorShift};
+
+    fn reference(value: u32, range: Range<usize>, count: usize) -> u32 {
+        let width = range.len() as u32;
+        if width == 0 { return value; }
+
+        let mask = if width == 32 { u32::MAX } else { ((1u32 << width) - 1) << range.start };
+        let field = (value & mask) >> range.start;
+        let count = (count as u32) % width;
+        let rotated = if count == 0 { field } else { (field << count | field >> (width - count)) & (mask >> range.start) };
+
+        value & !mask | rotated << range.start
+    }
+
+    fn rotate(value: u32, reversed: bool, range: Range<usize>, count: usize) -> u32 {
+        let mut raw = value.to_be_bytes();
+        let mut bytes = view_logical(&mut raw, reversed);
+        bytes.rotl_bit_range(range, count).unwrap();
+
+        if reversed { raw.reverse(); }
+        u32::from_be_bytes(raw)
+    }
+
+    #[test]
+    fn test_rotl_bit_range_within_byte() {
+        // Field 0b1101 at bits 2..6 rotates to 0b1011
+        assert_eq!(rotate(0b1111_0000_1111_0100, false, 2..6, 1), 0b1111_0000_1110_1100);
+        assert_eq!(rotate(0xFFFF_FF00, false, 2..6, 3), 0xFFFF_FF00);
+    }
+
+    #[test]
+    fn test_rotl_bit_range_spanning_three_bytes() {
+        let value = 0x12_34_56_78;
+        for reversed in [false, true] {
+            assert_eq!(rotate(value, reversed, 4..20, 5), reference(value, 4..20, 5));
+        }
+    }
+
+    #[test]
+    fn test_rotl_bit_range_whole_buffer() {
+        let value = 0x8000_0001u32;
+        for reversed in [false, true] {
+            assert_eq!(rotate(value, reversed, 0..32, 1), value.rotate_left(1));
+            assert_eq!(rotate(value, reversed, 0..32, 33), value.rotate_left(1));
+        }
+    }
+
+    #[test]
+    fn test_rotl_bit_range_differential() {
+        let mut rng = XorShift::new(0x221);
+
+        for _ in 0..2000 {
+            let value = rng.next_u64() as u32;
+            let end = rng.below(33);
+            let start = rng.below(end + 1);
+            let count = rng.below(70);
+
+            assert_eq!(rotate(value, rng.next_bool(), start..end, count), reference(value, start..end, count));
+        }
+    }
+
+    #[test]
+    fn test_rotl_bits_wraps_top_bit() {
+        // With the flag set the last physical byte is the most significant
+        let mut raw = [0x00u8, 0x00, 0x80];
+        let mut bytes = ByteString::new(&mut raw);
+        bytes.interpret_reverse_endian();
+        bytes.rotl_bits(1);
+        assert_eq!(raw, [0x01, 0x00, 0x00]);
+
+        let mut raw = [0x80u8, 0x00, 0x00];
+        ByteString::new(&mut raw).rotl_bits(1);
+        assert_eq!(raw, [0x00, 0x00, 0x01]);
+
+        let mut raw = [0x00u8, 0x00, 0x01];
+        ByteString::new(&mut raw).rotr_bits(1);
+        assert_eq!(raw, [0x80, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_rotl_bits_full_turns() {
+        let mut raw = [0x12u8, 0x34, 0x56];
+        let mut bytes = ByteString::new(&mut raw);
+
+        for count in [0, 24, 48] {
+            bytes.rotl_bits(count);
+            bytes.rotr_bits(count);
+        }
+        assert_eq!(raw, [0x12, 0x34, 0x56]);
+
+        let mut empty = [0u8; 0];
+        let mut bytes = ByteString::new(&mut empty);
+        bytes.rotl_bits(3);
+        bytes.rotr_bits(3);
+    }
+
+    #[test]
+    fn test_rotl_rotr_bits_against_u32() {
+        let mut rng = XorShift::new(0x271);
+
+        for _ in 0..2000 {
+            let value = rng.next_u64() as u32;
+            let (count, reversed) = (rng.below(100), rng.next_bool());
+
+            let mut raw = value.to_be_bytes();
+            let mut bytes = view_logical(&mut raw, reversed);
+
+            bytes.rotl_bits(count);
+            assert_eq!(bytes.to_u64(), Ok(value.rotate_left(count as u32) as u64));
+            bytes.rotr_bits(count + 5);
+            assert_eq!(bytes.to_u64(), Ok(value.rotate_right(5) as u64));
+        }
+    }
+
+    #[test]
+    fn test_rotl_bit_range_out_of_bounds() {
+        let mut raw = [0xA5u8; 2];
+        let mut bytes = ByteString::new(&mut raw);
+
+        assert_eq!(bytes.rotl_bit_range(8..17, 1), Err(Error::OutOfBounds));
+        assert_eq!(bytes.rotl_bit_range(5..5, 1), Ok(()));
+        assert_eq!(raw, [0xA5; 2]);
+    }
+}
@@ -0,0 +1,227 @@
+//! Reversible whitening of the logical bytes, additive and self-synchronizing multiplicative
+
+use crate::bytes::ByteString;
+
+/// Register state replacing a zero seed of [`ByteString::scramble_additive`], which xorshift cannot leave.
+///
+/// ```
+/// use rebite::{bytes::ByteString, scramble::ADDITIVE_ZERO_SEED};
+///
+/// let (mut a, mut b) = ([0u8; 8], [0u8; 8]);
+/// ByteString::new(&mut a).scramble_additive(0);
+/// ByteString::new(&mut b).scramble_additive(ADDITIVE_ZERO_SEED);
+///
+/// assert_eq!(a, b);
+/// ```
+pub const ADDITIVE_ZERO_SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// Scrambler polynomial 1 + x^-18 + x^-23 of the V.34 calling modem.
+///
+/// ```
+/// use rebite::{bytes::ByteString, scramble::V34_CALLING};
+///
+/// // An impulse echoes 18 and 23 bits later
+/// let mut raw = [0x80u8, 0x00, 0x00, 0x00];
+/// ByteString::new(&mut raw).scramble_multiplicative(V34_CALLING);
+///
+/// assert_eq!(raw, [0x80, 0x00, 0x21, 0x00]);
+/// ```
+pub const V34_CALLING: u32 = 1 | 1 << 18 | 1 << 23;
+
+/// Scrambler polynomial 1 + x^-5 + x^-23 of the V.34 answering modem.
+///
+/// ```
+/// use rebite::{bytes::ByteString, scramble::V34_ANSWERING};
+///
+/// // The first echo of an impulse follows 5 bits later
+/// let mut raw = [0x80u8, 0x00, 0x00, 0x00];
+/// ByteString::new(&mut raw).scramble_multiplicative(V34_ANSWERING);
+///
+/// assert_eq!(raw[0], 0x84);
+/// ```
+pub const V34_ANSWERING: u32 = 1 | 1 << 5 | 1 << 23;
+
+// Scrambler output bit: the input bit XOR the parity of the tapped history bits
+fn feedback(history: u32, taps: u32) -> u8 {
+    ((history & taps).count_ones() & 1) as u8
+}
+
+impl<'a> ByteString<'a> {
+
+    /// XOR an xorshift64 keystream over the logical bytes, applying it twice restores the data.
+    ///
+    /// The register starts at `seed`, or at [`ADDITIVE_ZERO_SEED`] for a zero seed. Each step
+    /// applies `x ^= x << 13; x ^= x >> 7; x ^= x << 17` and yields the register as 8 big-endian
+    /// keystream bytes, so logical byte `i` is XORed with byte `i % 8` of step `i / 8 + 1`. This
+    /// keystream is frozen, data whitened by one version unwhitens with any other.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0u8; 12];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.scramble_additive(1);
+    /// assert_eq!(raw, [0x00, 0x00, 0x00, 0x00, 0x40, 0x82, 0x20, 0x41, 0x10, 0x00, 0x41, 0x06]);
+    /// ```
+    pub fn scramble_additive(&mut self, seed: u64) {
+        let mut state = if seed == 0 { ADDITIVE_ZERO_SEED } else { seed };
+        let mut block = [0u8; 8];
+
+        for (ix, byte) in self.iter_mut().enumerate() {
+            if ix.is_multiple_of(8) {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                block = state.to_be_bytes();
+            }
+            *byte ^= block[ix % 8];
+        }
+    }
+
+    /// Self-synchronizing multiplicative scrambler over the bits of the logical bytes, most significant first.
+    ///
+    /// Bit `k` of `poly` is the coefficient of x^-k, so [`V34_CALLING`] makes every output bit the
+    /// input bit XOR the outputs 18 and 23 bits earlier. Bit 0 is the implied 1 and is ignored,
+    /// delays reach up to 31 bits. The register starts at zero for each call. Undone by
+    /// [`ByteString::descramble_multiplicative`] with the same polynomial.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, scramble::V34_CALLING};
+    ///
+    /// let mut raw = [0x80u8, 0x00, 0x00, 0x00];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.scramble_multiplicative(V34_CALLING);
+    /// assert_eq!(bytes.bytes(), [0x80, 0x00, 0x21, 0x00]);
+    ///
+    /// bytes.descramble_multiplicative(V34_CALLING);
+    /// assert_eq!(bytes.bytes(), [0x80, 0x00, 0x00, 0x00]);
+    /// ```
+    pub fn scramble_multiplicative(&mut self, poly: u32) {
+        let taps = poly >> 1;
+        let mut history = 0u32;
+
+        for byte in self.iter_mut() {
+            let mut out = 0u8;
+            for shift in (0..8).rev() {
+                let bit = *byte >> shift & 1 ^ feedback(history, taps);
+                history = history << 1 | bit as u32;
+                out = out << 1 | bit;
+            }
+            *byte = out;
+        }
+    }
+
+    /// Inverse of [`ByteString::scramble_multiplicative`].
+    ///
+    /// The register is fed from the scrambled bits, so it recovers from any starting state or
+    /// transmission error once the longest delay of `poly` has passed.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, scramble::V34_ANSWERING};
+    ///
+    /// let mut raw = *b"payload";
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.scramble_multiplicative(V34_ANSWERING);
+    /// assert_ne!(&raw, b"payload");
+    ///
+    /// ByteString::new(&mut raw).descramble_multiplicative(V34_ANSWERING);
+    /// assert_eq!(&raw, b"payload");
+    /// ```
+    pub fn descramble_multiplicative(&mut self, poly: u32) {
+        let taps = poly >> 1;
+        let mut history = 0u32;
+
+        for byte in self.iter_mut() {
+            let mut out = 0u8;
+            for shift in (0..8).rev() {
+                let bit = *byte >> shift & 1;
+                out = out << 1 | bit ^ feedback(history, taps);
+                history = history << 1 | bit as u32;
+            }
+            *byte = out;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::{view_logical, XorShift};
+
+    fn apply(raw: &[u8], reversed: bool, f: impl FnOnce(&mut ByteString)) -> Vec<u8> {
+        let mut physical = raw.to_vec();
+        let mut bytes = view_logical(&mut physical, reversed);
+
+        f(&mut bytes);
+        bytes.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_additive_keystream_frozen() {
+        // Must never change, whitened data would no longer unwhiten
+        for reversed in [false, true] {
+            assert_eq!(apply(&[0u8; 12], reversed, |b| b.scramble_additive(0)), [0xDC, 0x1B, 0x77, 0xAE, 0x0B, 0xF3, 0x4D, 0xAD, 0x64, 0xF0, 0xEE, 0xB9]);
+            assert_eq!(apply(&[0u8; 12], reversed, |b| b.scramble_additive(ADDITIVE_ZERO_SEED)), apply(&[0u8; 12], reversed, |b| b.scramble_additive(0)));
+
+            let keystream = [
+                0x3F, 0x28, 0x00, 0xD6, 0x56, 0x9E, 0x01, 0xB4, 0x60, 0x6F,
+                0x94, 0x9A, 0x3C, 0xEB, 0xD0, 0xB7, 0xC6, 0x9B, 0xBA, 0x40,
+            ];
+            let data: Vec<u8> = (0..20).collect();
+            let expected: Vec<u8> = data.iter().zip(keystream).map(|(a, b)| a ^ b).collect();
+            assert_eq!(apply(&data, reversed, |b| b.scramble_additive(0x0123_4567_89AB_CDEF)), expected);
+        }
+    }
+
+    #[test]
+    fn test_multiplicative_frozen() {
+        for reversed in [false, true] {
+            // An impulse echoes at the tap delays 18 and 23, then at 36 and 46 with 41 cancelling
+            assert_eq!(apply(&[0x80, 0, 0, 0, 0, 0], reversed, |b| b.scramble_multiplicative(V34_CALLING)), [0x80, 0x00, 0x21, 0x00, 0x08, 0x02]);
+            assert_eq!(apply(&[0xFF; 6], reversed, |b| b.scramble_multiplicative(V34_CALLING)), [0xFF, 0xFF, 0xC1, 0xFF, 0xF0, 0x03]);
+        }
+    }
+
+    #[test]
+    fn test_round_trips() {
+        let mut rng = XorShift::new(0x278);
+
+        for _ in 0..300 {
+            let mut data = vec![0u8; rng.below(40)];
+            rng.fill(&mut data);
+            let reversed = rng.next_bool();
+            let seed = rng.next_u64();
+            let poly = match rng.below(3) {
+                0 => V34_CALLING,
+                1 => V34_ANSWERING,
+                _ => rng.next_u64() as u32,
+            };
+
+            let whitened = apply(&data, reversed, |b| b.scramble_additive(seed));
+            assert_eq!(apply(&whitened, reversed, |b| b.scramble_additive(seed)), data);
+
+            let scrambled = apply(&data, reversed, |b| b.scramble_multiplicative(poly));
+            assert_eq!(apply(&scrambled, !reversed, |b| b.descramble_multiplicative(poly)), data, "poly={:#x}", poly);
+        }
+    }
+
+    #[test]
+    fn test_descrambler_self_synchronizes() {
+        let data: Vec<u8> = (0..16u8).map(|e| e.wrapping_mul(37)).collect();
+        let mut scrambled = apply(&data, false, |b| b.scramble_multiplicative(V34_ANSWERING));
+
+        // A flipped bit at stream position 10 corrupts only positions 10, 15 and 33
+        scrambled[1] ^= 0x20;
+        let descrambled = apply(&scrambled, false, |b| b.descramble_multiplicative(V34_ANSWERING));
+        let errors: Vec<usize> = (0..128).filter(|&p| (descrambled[p / 8] ^ data[p / 8]) >> (7 - p % 8) & 1 == 1).collect();
+        assert_eq!(errors, [10, 15, 33]);
+
+        // Without the first bytes the register starts wrong, yet everything past 23 bits decodes
+        let tail = apply(&scrambled[4..], false, |b| b.descramble_multiplicative(V34_ANSWERING));
+        assert_eq!(tail[3..], data[7..]);
+    }
+}
@@ -0,0 +1,266 @@
+//! A bump arena handing out temporary ByteStrings from caller storage
+//!
+//! Rejoining handed out regions needs unsafe code, so with `forbid-unsafe` the arena splits its
+//! storage safely instead and [`Scratch::reset`] cannot reclaim what was allocated.
+
+use std::cell::Cell;
+
+#[cfg(not(feature = "forbid-unsafe"))]
+use std::{
+    marker::PhantomData,
+    slice
+};
+
+use crate::{
+    bytes::ByteString,
+    error::OutOfScratch
+};
+
+/// Bump arena handing out temporary ByteStrings from one caller provided buffer.
+///
+/// Allocations borrow the arena shared, so any number of them can be alive at once.
+/// [`Scratch::reset`] needs exclusive access, which guarantees they are all gone before memory is reused.
+///
+/// ```
+/// use rebite::scratch::Scratch;
+///
+/// let mut storage = [0u8; 16];
+/// let scratch = Scratch::new(&mut storage);
+///
+/// // Several temporaries alive at once
+/// let mut a = scratch.alloc(8).unwrap();
+/// let b = scratch.alloc(8).unwrap();
+/// a.set_bit(0, true);
+/// assert!(!a.is_zero() && b.is_zero());
+/// assert_eq!(scratch.available(), 0);
+/// ```
+pub struct Scratch<'a> {
+    #[cfg(not(feature = "forbid-unsafe"))]
+    ptr: *mut u8,
+    #[cfg(not(feature = "forbid-unsafe"))]
+    used: Cell<usize>,
+    #[cfg(not(feature = "forbid-unsafe"))]
+    _storage: PhantomData<&'a mut [u8]>,
+    // Storage not handed out yet, allocations are split off its front
+    #[cfg(feature = "forbid-unsafe")]
+    rest: Cell<&'a mut [u8]>,
+    len: usize
+}
+
+impl<'a> Scratch<'a> {
+
+    /// Arena over `storage`, with all of it available.
+    ///
+    /// ```
+    /// use rebite::scratch::Scratch;
+    ///
+    /// let mut storage = [0u8; 64];
+    /// let scratch = Scratch::new(&mut storage);
+    ///
+    /// assert_eq!(scratch.available(), 64);
+    /// ```
+    pub fn new(storage: &'a mut [u8]) -> Self {
+        #[cfg(not(feature = "forbid-unsafe"))]
+        {
+            Self { ptr: storage.as_mut_ptr(), len: storage.len(), used: Cell::new(0), _storage: PhantomData }
+        }
+
+        #[cfg(feature = "forbid-unsafe")]
+        {
+            Self { len: storage.len(), rest: Cell::new(storage) }
+        }
+    }
+
+    /// Carve out `len` zeroed bytes that do not overlap any other live allocation.
+    ///
+    /// ```
+    /// use rebite::scratch::Scratch;
+    ///
+    /// let mut storage = [0xFFu8; 8];
+    /// let scratch = Scratch::new(&mut storage);
+    ///
+    /// let mut a = scratch.alloc(4).unwrap();
+    /// let b = scratch.alloc(4).unwrap();
+    /// a.set_bytes_with_value(1);
+    /// assert!(b.is_zero());
+    ///
+    /// let err = scratch.alloc(1).unwrap_err();
+    /// assert_eq!((err.requested, err.available), (1, 0));
+    /// ```
+    pub fn alloc(&self, len: usize) -> Result<ByteString<'_>, OutOfScratch> {
+
+        if len > self.available() {
+            return Err(OutOfScratch { requested: len, available: self.available() });
+        }
+
+        #[cfg(not(feature = "forbid-unsafe"))]
+        let bytes = {
+            let used = self.used.get();
+            self.used.set(used + len);
+
+            /*
+                This is ok, the region used..used + len lies within the storage and
+                is handed out exactly once until reset, which requires &mut self
+            */
+            unsafe { slice::from_raw_parts_mut(self.ptr.add(used), len) }
+        };
+
+        #[cfg(feature = "forbid-unsafe")]
+        let bytes = {
+            let (bytes, rest) = self.rest.take().split_at_mut(len);
+            self.rest.set(rest);
+            bytes
+        };
+
+        bytes.fill(0);
+        Ok(ByteString::new(bytes))
+    }
+
+    /// Make the whole storage available again.
+    ///
+    /// ```
+    /// use rebite::scratch::Scratch;
+    ///
+    /// let mut storage = [0u8; 8];
+    /// let mut scratch = Scratch::new(&mut storage);
+    ///
+    /// scratch.alloc(8).unwrap();
+    /// scratch.reset();
+    /// assert_eq!(scratch.available(), 8);
+    /// ```
+    #[cfg(not(feature = "forbid-unsafe"))]
+    pub fn reset(&mut self) {
+        self.used.set(0);
+    }
+
+    /// Keep the bytes never handed out available, the allocated regions cannot be rejoined without
+    /// unsafe code. Build a new arena over the storage once the allocations are gone to reuse all of it.
+    ///
+    /// ```
+    /// use rebite::scratch::Scratch;
+    ///
+    /// let mut storage = [0u8; 8];
+    /// let mut scratch = Scratch::new(&mut storage);
+    ///
+    /// scratch.alloc(6).unwrap();
+    /// scratch.reset();
+    /// assert_eq!(scratch.available(), 2);
+    ///
+    /// assert_eq!(Scratch::new(&mut storage).available(), 8);
+    /// ```
+    #[cfg(feature = "forbid-unsafe")]
+    pub fn reset(&mut self) {}
+
+    /// Length of the storage in bytes.
+    ///
+    /// ```
+    /// use rebite::scratch::Scratch;
+    ///
+    /// let mut storage = [0u8; 8];
+    /// let scratch = Scratch::new(&mut storage);
+    ///
+    /// scratch.alloc(3).unwrap();
+    /// assert_eq!(scratch.capacity(), 8);
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.len
+    }
+
+    /// Bytes left for allocation until the next reset.
+    ///
+    /// ```
+    /// use rebite::scratch::Scratch;
+    ///
+    /// let mut storage = [0u8; 8];
+    /// let scratch = Scratch::new(&mut storage);
+    ///
+    /// scratch.alloc(3).unwrap();
+    /// assert_eq!(scratch.available(), 5);
+    /// ```
+    pub fn available(&self) -> usize {
+        #[cfg(not(feature = "forbid-unsafe"))]
+        {
+            self.len - self.used.get()
+        }
+
+        #[cfg(feature = "forbid-unsafe")]
+        {
+            let rest = self.rest.take();
+            let available = rest.len();
+            self.rest.set(rest);
+            available
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_allocations_do_not_overlap() {
+        let mut storage = [0xEEu8; 16];
+        let scratch = Scratch::new(&mut storage);
+
+        let mut a = scratch.alloc(4).unwrap();
+        let mut b = scratch.alloc(8).unwrap();
+        let mut c = scratch.alloc(4).unwrap();
+
+        assert!(a.is_zero() && b.is_zero() && c.is_zero());
+
+        a.set_bytes_with_value(0xAA);
+        b.set_bytes_with_value(0xBB);
+        c.set_bytes_with_value(0xCC);
+
+        assert!(a.bytes().iter().all(|e| *e == 0xAA));
+        assert!(b.bytes().iter().all(|e| *e == 0xBB));
+        assert!(c.bytes().iter().all(|e| *e == 0xCC));
+        assert_eq!(scratch.available(), 0);
+
+        assert_eq!(storage, [0xAA, 0xAA, 0xAA, 0xAA, 0xBB, 0xBB, 0xBB, 0xBB, 0xBB, 0xBB, 0xBB, 0xBB, 0xCC, 0xCC, 0xCC, 0xCC]);
+    }
+
+    #[test]
+    fn test_exhaustion() {
+        let mut storage = [0u8; 8];
+        let scratch = Scratch::new(&mut storage);
+
+        let _a = scratch.alloc(5).unwrap();
+        assert_eq!(scratch.alloc(4).err(), Some(OutOfScratch { requested: 4, available: 3 }));
+        assert_eq!(scratch.alloc(3).unwrap().byte_len(), 3);
+        assert_eq!(scratch.alloc(0).unwrap().byte_len(), 0);
+        assert!(scratch.alloc(1).is_err());
+    }
+
+    #[test]
+    #[cfg(not(feature = "forbid-unsafe"))]
+    fn test_reset_allows_reuse() {
+        let mut storage = [0u8; 4];
+        let mut scratch = Scratch::new(&mut storage);
+        let first = scratch.alloc(4).unwrap().into_raw_parts().0;
+        assert!(scratch.alloc(1).is_err());
+
+        scratch.reset();
+        assert_eq!(scratch.available(), scratch.capacity());
+
+        // The same region comes back, zeroed again
+        let mut reused = scratch.alloc(4).unwrap();
+        assert!(reused.is_zero());
+        reused.set_bytes_with_value(0x11);
+        assert_eq!(reused.into_raw_parts().0, first);
+        assert_eq!(storage, [0x11; 4]);
+    }
+
+    #[test]
+    #[cfg(feature = "forbid-unsafe")]
+    fn test_reset_keeps_the_remainder() {
+        let mut storage = [0u8; 4];
+        let mut scratch = Scratch::new(&mut storage);
+
+        scratch.alloc(3).unwrap().set_bytes_with_value(0x11);
+        scratch.reset();
+        assert_eq!(scratch.available(), 1);
+        assert!(scratch.alloc(1).unwrap().is_zero());
+    }
+}
@@ -0,0 +1,317 @@
+//! Cheap copies of the buffer for later comparison and restore
+
+use std::{
+    fmt,
+    sync::Arc
+};
+
+use crate::{
+    bytes::ByteString,
+    error::LengthError,
+    fmt::Redacted,
+    util
+};
+
+/// Bytes per independently shared chunk of a [`Snapshot`].
+///
+/// ```
+/// use rebite::{bytes::ByteString, snapshot::SNAPSHOT_CHUNK_LEN};
+///
+/// let mut raw = vec![0u8; 4 * SNAPSHOT_CHUNK_LEN];
+/// let mut bytes = ByteString::new(&mut raw);
+///
+/// // Only the chunk holding the change is compared byte by byte
+/// let before = bytes.snapshot();
+/// bytes.set_bit(0, true);
+/// assert_eq!(before.diff(&bytes.snapshot_from(&before)), [(4 * SNAPSHOT_CHUNK_LEN - 1, 0x00, 0x01)]);
+/// ```
+pub const SNAPSHOT_CHUNK_LEN: usize = 256;
+
+/// Immutable copy of the logical bytes of a ByteString.
+///
+/// Storage is split into reference counted chunks, snapshots taken with
+/// [`ByteString::snapshot_from`] share every chunk that did not change. A snapshot of a
+/// sensitive buffer is sensitive too: Debug shows only its length and the last owner of each
+/// chunk wipes it on drop.
+///
+/// ```
+/// use rebite::bytes::ByteString;
+///
+/// let mut raw = [0x12u8, 0x34];
+/// let mut bytes = ByteString::new(&mut raw);
+/// bytes.mark_sensitive();
+///
+/// let saved = bytes.snapshot();
+/// assert_eq!(format!("{:?}", saved), "[REDACTED; 2 bytes]");
+///
+/// bytes.set_zero();
+/// bytes.restore(&saved).unwrap();
+/// assert_eq!(raw, [0x12, 0x34]);
+/// ```
+#[derive(Clone)]
+pub struct Snapshot {
+    chunks: Vec<Arc<[u8]>>,
+    len: usize,
+    sensitive: bool,
+}
+
+impl fmt::Debug for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.sensitive {
+            return fmt::Debug::fmt(&Redacted { len: self.len }, f);
+        }
+
+        f.debug_struct("Snapshot").field("chunks", &self.chunks).field("len", &self.len).finish()
+    }
+}
+
+impl Drop for Snapshot {
+    fn drop(&mut self) {
+        if !self.sensitive {
+            return;
+        }
+
+        // Chunks still shared with another snapshot are wiped when that one drops
+        for chunk in &mut self.chunks {
+            if let Some(bytes) = Arc::get_mut(chunk) {
+                util::wipe(bytes);
+            }
+        }
+    }
+}
+
+impl Snapshot {
+
+    /// Length of the copied buffer in bytes.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0u8; 5];
+    ///
+    /// assert_eq!(ByteString::new(&mut raw).snapshot().byte_len(), 5);
+    /// ```
+    pub fn byte_len(&self) -> usize {
+        self.len
+    }
+
+    /// Logical bytes in order.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [1u8, 2, 3];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    ///
+    /// assert!(bytes.snapshot().iter().copied().eq([3, 2, 1]));
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = &u8> + '_ {
+        self.chunks.iter().flat_map(|e| e.iter())
+    }
+
+    /// `(position, self byte, other byte)` for every logical position where the snapshots differ.
+    ///
+    /// Only the common length is compared, chunks shared between the two are skipped without reading them.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// let before = bytes.snapshot();
+    /// bytes.set_bit(8, true);
+    /// let after = bytes.snapshot_from(&before);
+    ///
+    /// assert_eq!(before.diff(&after), [(2, 0x00, 0x01)]);
+    /// ```
+    pub fn diff(&self, other: &Snapshot) -> Vec<(usize, u8, u8)> {
+        let mut changes = Vec::new();
+
+        for (ix, (a, b)) in self.chunks.iter().zip(&other.chunks).enumerate() {
+            if Arc::ptr_eq(a, b) {
+                continue;
+            }
+
+            let base = ix * SNAPSHOT_CHUNK_LEN;
+            changes.extend(a.iter().zip(b.iter()).enumerate().filter(|(_, (x, y))| x != y).map(|(offset, (x, y))| (base + offset, *x, *y)));
+        }
+
+        changes
+    }
+}
+
+impl<'a> ByteString<'a> {
+
+    /// Copy the logical bytes into a new snapshot, sensitive if the buffer is.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0xAAu8, 0xBB];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// let saved = bytes.snapshot();
+    /// bytes.set_zero();
+    /// bytes.restore(&saved).unwrap();
+    /// assert_eq!(raw, [0xAA, 0xBB]);
+    /// ```
+    pub fn snapshot(&self) -> Snapshot {
+        let mut logical: Vec<u8> = self.iter().copied().collect();
+        let chunks = logical.chunks(SNAPSHOT_CHUNK_LEN).map(Arc::from).collect();
+
+        self.finish_snapshot(chunks, &mut logical)
+    }
+
+    /// Snapshot that reuses the storage of every chunk of `previous` whose contents are unchanged.
+    ///
+    /// Chunks are only shared between snapshots of the same sensitivity.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0u8; 8];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// let first = bytes.snapshot();
+    /// bytes.set_bytes_with_value(7);
+    /// let second = bytes.snapshot_from(&first);
+    ///
+    /// assert!(second.iter().all(|e| *e == 7));
+    /// assert_eq!(first.diff(&second).len(), 8);
+    /// ```
+    pub fn snapshot_from(&self, previous: &Snapshot) -> Snapshot {
+        let mut logical: Vec<u8> = self.iter().copied().collect();
+
+        let chunks = logical.chunks(SNAPSHOT_CHUNK_LEN).enumerate().map(|(ix, chunk)| match previous.chunks.get(ix) {
+            Some(old) if previous.sensitive == self.sensitive && **old == *chunk => Arc::clone(old),
+            _ => Arc::from(chunk)
+        }).collect();
+
+        self.finish_snapshot(chunks, &mut logical)
+    }
+
+    // Wrap the chunks, wiping the staging copy of a sensitive buffer
+    fn finish_snapshot(&self, chunks: Vec<Arc<[u8]>>, logical: &mut [u8]) -> Snapshot {
+        if self.sensitive {
+            util::wipe(logical);
+        }
+
+        Snapshot { chunks, len: logical.len(), sensitive: self.sensitive }
+    }
+
+    /// Overwrite the logical bytes with a snapshot of the same length, nothing changes on a mismatch.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut short = [1u8];
+    /// let saved = ByteString::new(&mut short).snapshot();
+    ///
+    /// let mut raw = [9u8, 9];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// assert!(bytes.restore(&saved).is_err());
+    /// assert_eq!(raw, [9, 9]);
+    /// ```
+    pub fn restore(&mut self, snapshot: &Snapshot) -> Result<(), LengthError> {
+        if snapshot.len != self.byte_len() {
+            return Err(LengthError { index: 0, expected: self.byte_len(), actual: snapshot.len });
+        }
+
+        for (dst, src) in self.iter_mut().zip(snapshot.iter()) {
+            *dst = *src;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::XorShift;
+
+    #[test]
+    fn test_snapshot_immutable_and_restore() {
+        let mut raw = vec![0u8; 1000];
+        XorShift::new(0x249).fill(&mut raw);
+        let original = raw.clone();
+
+        let mut bytes = ByteString::new(&mut raw);
+        bytes.interpret_reverse_endian();
+        let snap = bytes.snapshot();
+
+        bytes.set_bytes_with_value(0x55);
+        assert!(snap.iter().eq(original.iter().rev()));
+
+        bytes.restore(&snap).unwrap();
+        assert_eq!(raw, original);
+    }
+
+    #[test]
+    fn test_diff_reports_edits() {
+        let mut raw = vec![0u8; 700];
+        let mut bytes = ByteString::new(&mut raw);
+        let before = bytes.snapshot();
+
+        let edits = [(3usize, 0x11u8), (256, 0x22), (699, 0x33)];
+        for (ix, value) in edits {
+            *bytes.iter_range_mut(ix..ix + 1).next().unwrap() = value;
+        }
+        let after = bytes.snapshot_from(&before);
+
+        assert_eq!(before.diff(&after), edits.iter().map(|(ix, value)| (*ix, 0, *value)).collect::<Vec<_>>());
+        assert_eq!(after.diff(&after), []);
+    }
+
+    #[test]
+    fn test_unchanged_chunks_share_storage() {
+        let mut raw = vec![7u8; 3 * SNAPSHOT_CHUNK_LEN];
+        let mut bytes = ByteString::new(&mut raw);
+
+        let first = bytes.snapshot();
+        let second = bytes.snapshot_from(&first);
+        assert!(first.chunks.iter().all(|e| Arc::strong_count(e) == 2));
+
+        *bytes.iter_range_mut(SNAPSHOT_CHUNK_LEN..SNAPSHOT_CHUNK_LEN + 1).next().unwrap() = 0;
+        let third = bytes.snapshot_from(&second);
+
+        assert_eq!(Arc::strong_count(&first.chunks[0]), 3);
+        assert_eq!(Arc::strong_count(&first.chunks[1]), 2);
+        assert_eq!(Arc::strong_count(&third.chunks[1]), 1);
+        assert_eq!(Arc::strong_count(&first.chunks[2]), 3);
+    }
+
+    #[test]
+    fn test_restore_length_mismatch() {
+        let mut raw = [1u8, 2, 3];
+        let snap = ByteString::new(&mut raw).snapshot();
+
+        let mut other = [9u8; 2];
+        assert_eq!(ByteString::new(&mut other).restore(&snap), Err(LengthError { index: 0, expected: 2, actual: 3 }));
+        assert_eq!(other, [9, 9]);
+    }
+
+    #[test]
+    fn test_sensitive_snapshot_redacted() {
+        let mut raw = [0xDEu8, 0xAD, 0xBE, 0xEF];
+        let mut bytes = ByteString::new(&mut raw);
+        let plain = bytes.snapshot();
+        assert!(format!("{:?}", plain).contains("222"));
+
+        bytes.mark_sensitive();
+        let secret = bytes.snapshot();
+        assert_eq!(format!("{:?}", secret), "[REDACTED; 4 bytes]");
+        assert_eq!(format!("{:?}", secret.clone()), "[REDACTED; 4 bytes]");
+        assert_eq!(format!("{:?}", bytes.snapshot_from(&plain)), "[REDACTED; 4 bytes]");
+
+        // A sensitive snapshot never shares storage with a plain one that would not wipe it
+        let from_plain = bytes.snapshot_from(&plain);
+        assert!(!Arc::ptr_eq(&from_plain.chunks[0], &plain.chunks[0]));
+        let from_secret = bytes.snapshot_from(&secret);
+        assert!(Arc::ptr_eq(&from_secret.chunks[0], &secret.chunks[0]));
+    }
+}
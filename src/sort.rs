@@ -0,0 +1,229 @@
+//! Sorting and searching collections of buffers
+
+use std::cmp::Ordering;
+
+use crate::{
+    bytes::ByteString,
+    bytes_iter::BytesIter,
+    cmp::leading_zero_bytes,
+    typed::Endianness
+};
+
+/// Sort by numeric value, see [`ByteString::cmp_numeric`]. The sort is stable.
+///
+/// Equal length inputs that all use the forward interpretation take a radix sort path.
+///
+/// ```
+/// use rebite::{bytes::ByteString, sort::sort_byte_strings};
+///
+/// let (mut a, mut b, mut c) = ([0x00u8, 0x02], [0x01u8], [0x00u8, 0x00, 0x01]);
+/// let mut items = [ByteString::new(&mut a), ByteString::new(&mut b), ByteString::new(&mut c)];
+///
+/// // Leading zero bytes do not count, values compare as 2, 1 and 1
+/// sort_byte_strings(&mut items);
+/// assert_eq!(items.iter().map(|e| e.to_string()).collect::<Vec<_>>(), ["01", "000001", "0002"]);
+/// ```
+pub fn sort_byte_strings(items: &mut [ByteString]) {
+
+    let radix_eligible = items.first().is_some_and(|first| {
+        items.iter().all(|e| !e.interpret_reverse_endian && e.byte_len() == first.byte_len())
+    });
+
+    if radix_eligible {
+        let order = radix_order(&items.iter().map(|e| e.bytes()).collect::<Vec<_>>());
+        apply_order(items, &order);
+        return;
+    }
+
+    items.sort_by(|a, b| a.cmp_numeric(b));
+}
+
+/// Sort raw slices by numeric value, each read with the byte order `endian`. The sort is stable.
+///
+/// ```
+/// use rebite::{sort::sort_slices_numeric, typed::Endianness};
+///
+/// let (mut a, mut b) = ([0x00u8, 0x01], [0x01u8, 0x00]);
+/// let mut items: [&mut [u8]; 2] = [&mut a, &mut b];
+///
+/// // Little endian, so [0x00, 0x01] is 256 and [0x01, 0x00] is 1
+/// sort_slices_numeric(&mut items, Endianness::Little);
+/// assert_eq!(items[0], [0x01, 0x00]);
+/// ```
+pub fn sort_slices_numeric(items: &mut [&mut [u8]], endian: Endianness) {
+
+    let radix_eligible = endian == Endianness::Big
+        && items.first().is_some_and(|first| items.iter().all(|e| e.len() == first.len()));
+
+    if radix_eligible {
+        let order = radix_order(&items.iter().map(|e| &e[..]).collect::<Vec<_>>());
+        apply_order(items, &order);
+        return;
+    }
+
+    items.sort_by(|a, b| numeric_slice_cmp(a, b, endian));
+}
+
+/// Binary search `items`, sorted by numeric value, for `key`.
+///
+/// ```
+/// use rebite::{bytes::ByteString, sort::binary_search_numeric};
+///
+/// let (mut a, mut b, mut c) = ([1u8], [3u8], [5u8]);
+/// let items = [ByteString::new(&mut a), ByteString::new(&mut b), ByteString::new(&mut c)];
+///
+/// let (mut three, mut four) = ([0u8, 3], [4u8]);
+/// assert_eq!(binary_search_numeric(&items, &ByteString::new(&mut three)), Ok(1));
+/// assert_eq!(binary_search_numeric(&items, &ByteString::new(&mut four)), Err(2));
+/// ```
+pub fn binary_search_numeric(items: &[ByteString], key: &ByteString) -> Result<usize, usize> {
+    items.binary_search_by(|e| e.cmp_numeric(key))
+}
+
+fn numeric_slice_cmp(a: &[u8], b: &[u8], endian: Endianness) -> Ordering {
+    let (a, b) = (significant(a, endian), significant(b, endian));
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+// Logical bytes of a slice read in `endian` order, without leading zero bytes
+fn significant(bytes: &[u8], endian: Endianness) -> std::iter::Skip<BytesIter<'_>> {
+    let reversed = endian == Endianness::Little;
+    BytesIter::new(bytes, reversed).skip(leading_zero_bytes(BytesIter::new(bytes, reversed)))
+}
+
+// Stable LSD radix sort of equally long big-endian keys, returns the source index for each position
+fn radix_order(keys: &[&[u8]]) -> Vec<usize> {
+
+    let mut order: Vec<usize> = (0..keys.len()).collect();
+    let mut next = vec![0; keys.len()];
+    let width = keys.first().map_or(0, |e| e.len());
+
+    for position in (0..width).rev() {
+        let mut counts = [0usize; 257];
+        for ix in &order {
+            counts[keys[*ix][position] as usize + 1] += 1;
+        }
+        for digit in 0..256 {
+            counts[digit + 1] += counts[digit];
+        }
+
+        for ix in &order {
+            let slot = &mut counts[keys[*ix][position] as usize];
+            next[*slot] = *ix;
+            *slot += 1;
+        }
+
+        std::mem::swap(&mut order, &mut next);
+    }
+
+    order
+}
+
+// Permute in place so that position i receives the item previously at order[i]
+fn apply_order<T>(items: &mut [T], order: &[usize]) {
+
+    let mut placed = vec![false; items.len()];
+
+    for start in 0..items.len() {
+        let mut ix = start;
+        while !placed[ix] {
+            placed[ix] = true;
+            if order[ix] == start { break; }
+
+            items.swap(ix, order[ix]);
+            ix = order[ix];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::{view, XorShift};
+
+    fn value(logical: &[u8]) -> u128 {
+        logical.iter().fold(0, |acc, e| acc << 8 | *e as u128)
+    }
+
+    fn random_set(rng: &mut XorShift, equal_len: bool) -> Vec<(Vec<u8>, bool)> {
+        let len = 1 + rng.below(16);
+
+        (0..rng.below(60)).map(|_| {
+            let len = if equal_len { len } else { rng.below(17) };
+            let mut raw = vec![0u8; len];
+            rng.fill(&mut raw);
+
+            // Leading zero bytes and duplicates make the numeric order differ from memcmp
+            if len > 0 && rng.next_bool() { raw[0] = 0; }
+            if rng.below(8) == 0 { raw.iter_mut().for_each(|e| *e = 0); }
+
+            (raw, !equal_len && rng.next_bool())
+        }).collect()
+    }
+
+    #[test]
+    fn test_sort_byte_strings_against_reference() {
+        let mut rng = XorShift::new(0x228);
+
+        for round in 0..200 {
+            let mut set = random_set(&mut rng, round % 2 == 0);
+
+            let mut items: Vec<ByteString> = set.iter_mut().map(|(raw, reversed)| {
+                let bytes = view(raw, *reversed);
+                bytes
+            }).collect();
+
+            let mut expected: Vec<(u128, Vec<u8>)> = items.iter()
+                .map(|e| { let logical: Vec<u8> = e.iter().copied().collect(); (value(&logical), logical) })
+                .collect();
+            expected.sort_by_key(|e| e.0);
+
+            sort_byte_strings(&mut items);
+            let sorted: Vec<u128> = items.iter().map(|e| value(&e.iter().copied().collect::<Vec<_>>())).collect();
+            assert_eq!(sorted, expected.iter().map(|e| e.0).collect::<Vec<_>>());
+
+            for (ix, (key, _)) in expected.iter().enumerate() {
+                let mut probe = key.to_be_bytes();
+                let found = binary_search_numeric(&items, &ByteString::new(&mut probe)).unwrap();
+                assert_eq!(expected[found].0, expected[ix].0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_radix_path_is_stable() {
+        let mut a = [0u8, 1];
+        let mut b = [0u8, 0];
+        let mut c = [0u8, 1];
+
+        let (pa, pc) = (a.as_ptr(), c.as_ptr());
+        let mut items = [ByteString::new(&mut a), ByteString::new(&mut b), ByteString::new(&mut c)];
+        sort_byte_strings(&mut items);
+
+        assert_eq!(items[1].bytes().as_ptr(), pa);
+        assert_eq!(items[2].bytes().as_ptr(), pc);
+    }
+
+    #[test]
+    fn test_sort_slices_numeric() {
+        let mut rng = XorShift::new(0x2280);
+
+        for round in 0..200 {
+            let mut set = random_set(&mut rng, round % 2 == 0);
+            let endian = if rng.next_bool() { Endianness::Big } else { Endianness::Little };
+
+            let logical = |raw: &[u8]| -> Vec<u8> {
+                if endian == Endianness::Little { raw.iter().rev().copied().collect() } else { raw.to_vec() }
+            };
+
+            let mut expected: Vec<u128> = set.iter().map(|(raw, _)| value(&logical(raw))).collect();
+            expected.sort();
+
+            let mut items: Vec<&mut [u8]> = set.iter_mut().map(|(raw, _)| &mut raw[..]).collect();
+            sort_slices_numeric(&mut items, endian);
+
+            assert_eq!(items.iter().map(|e| value(&logical(e))).collect::<Vec<_>>(), expected);
+        }
+    }
+}
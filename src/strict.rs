@@ -0,0 +1,82 @@
+//! Length mismatch checks for the operators that pair bytes up by position
+
+#[cfg(all(feature = "debug-strict", debug_assertions))]
+use std::sync::Mutex;
+
+// Receives the left and right operand lengths
+#[cfg(all(feature = "debug-strict", debug_assertions))]
+type Handler = fn(usize, usize);
+
+#[cfg(all(feature = "debug-strict", debug_assertions))]
+static HANDLER: Mutex<Option<Handler>> = Mutex::new(None);
+
+/// Report operator length mismatches to `handler` instead of panicking.
+///
+/// The handler receives the byte lengths of the left and right operand and replaces any earlier
+/// one, the operator then goes on truncating to the shorter operand as in release builds. Only
+/// available in debug builds with the `debug-strict` feature.
+///
+/// ```
+/// use rebite::bytes::ByteString;
+///
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// static REPORTED: AtomicUsize = AtomicUsize::new(0);
+///
+/// let (mut a, mut b) = ([0xFFu8; 2], [0x0Fu8]);
+/// let mut left = ByteString::new(&mut a);
+/// let right = ByteString::new(&mut b);
+///
+/// rebite::set_mismatch_handler(|left, right| REPORTED.store(left * 10 + right, Ordering::Relaxed));
+/// left &= &right;
+/// rebite::clear_mismatch_handler();
+///
+/// // Pairs up from the most significant end, the extra byte is left alone
+/// assert_eq!(REPORTED.load(Ordering::Relaxed), 21);
+/// assert_eq!(a, [0x0F, 0xFF]);
+/// ```
+#[cfg(all(feature = "debug-strict", debug_assertions))]
+pub fn set_mismatch_handler(handler: fn(usize, usize)) {
+    *HANDLER.lock().unwrap_or_else(|e| e.into_inner()) = Some(handler);
+}
+
+/// Remove the handler of [`set_mismatch_handler`], mismatches panic again.
+///
+/// ```
+/// use rebite::bytes::ByteString;
+///
+/// let (mut a, mut b) = ([0u8; 2], [0u8]);
+/// let mut left = ByteString::new(&mut a);
+/// let right = ByteString::new(&mut b);
+///
+/// rebite::set_mismatch_handler(|_, _| ());
+/// rebite::clear_mismatch_handler();
+///
+/// let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| left |= &right));
+/// assert!(result.is_err());
+/// ```
+#[cfg(all(feature = "debug-strict", debug_assertions))]
+pub fn clear_mismatch_handler() {
+    *HANDLER.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+// Called by every operator that zips its operands, panics or reports on a mismatch
+#[cfg(all(feature = "debug-strict", debug_assertions))]
+#[track_caller]
+pub(crate) fn check_zip_lengths(lhs: usize, rhs: usize) {
+    if lhs == rhs {
+        return;
+    }
+
+    // Copied out so a panicking handler cannot poison the lock
+    let handler = *HANDLER.lock().unwrap_or_else(|e| e.into_inner());
+    match handler {
+        Some(handler) => handler(lhs, rhs),
+        None => panic!("operands of {} and {} bytes would be truncated", lhs, rhs),
+    }
+}
+
+// Compiled out unless both the feature and debug assertions are on
+#[cfg(not(all(feature = "debug-strict", debug_assertions)))]
+#[inline(always)]
+pub(crate) fn check_zip_lengths(_lhs: usize, _rhs: usize) {}
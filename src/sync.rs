@@ -0,0 +1,195 @@
+//! Locating a sync pattern at any bit offset and realigning the buffer to it
+
+use crate::{
+    bit_order::BitOrder,
+    bytes::ByteString,
+    error::SyncError
+};
+
+impl<'a> ByteString<'a> {
+
+    /// Find the first occurrence of the sync pattern at any bit offset and shift it to the start, returns the discarded bit count.
+    ///
+    /// The pattern is the low `sync_bits` bits of `sync_word`'s value, most significant first. The
+    /// buffer is searched as an [`BitOrder::Msb0`] stream, so offset 0 is the top bit of the first
+    /// logical byte and `<<=` moves later bits toward it. The shift happens in place and fills
+    /// the end with zeros.
+    ///
+    /// Only a complete occurrence counts, the buffer needs no data after it. A pattern cut off by
+    /// the end of the buffer is reported in [`SyncError::NotFound`] so the caller can carry those
+    /// bits over to the next capture. On error the buffer is left unchanged.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// // Sync 0xB5 three bits into the stream, followed by 0x3C
+    /// let mut raw = [0b1111_0110, 0b1010_0111, 0b1000_0000];
+    /// let mut sync = [0xB5u8];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.resync(&ByteString::new(&mut sync), 8), Ok(3));
+    /// assert_eq!(raw, [0xB5, 0x3C, 0x00]);
+    /// ```
+    pub fn resync(&mut self, sync_word: &ByteString, sync_bits: usize) -> Result<usize, SyncError> {
+        if sync_bits == 0 || sync_bits > sync_word.bit_len() {
+            return Err(SyncError::InvalidPattern);
+        }
+
+        let pattern: Vec<bool> = (0..sync_bits).rev().map(|offset| sync_word.bit(offset)).collect();
+        match self.find_stream_pattern(&pattern) {
+            Some(offset) => {
+                *self <<= offset;
+                Ok(offset)
+            },
+            None => Err(SyncError::NotFound { partial_at: self.trailing_partial(&pattern) }),
+        }
+    }
+
+    // First complete occurrence. A window over the last 64 stream bits screens candidates, longer
+    // patterns check their remaining bits only where the window matched.
+    fn find_stream_pattern(&self, pattern: &[bool]) -> Option<usize> {
+        let width = pattern.len().min(64);
+        let mask = u64::MAX >> (64 - width);
+        let head = pattern[..width].iter().fold(0u64, |acc, e| acc << 1 | *e as u64);
+
+        let mut window = 0u64;
+        for (ix, bit) in self.iter_bits(BitOrder::Msb0).enumerate() {
+            window = (window << 1 | bit as u64) & mask;
+            if ix + 1 < width || window != head {
+                continue;
+            }
+
+            let start = ix + 1 - width;
+            if start + pattern.len() > self.bit_len() {
+                return None;
+            }
+
+            if pattern[width..].iter().enumerate().all(|(k, e)| self.stream_bit(start + width + k, BitOrder::Msb0) == *e) {
+                return Some(start);
+            }
+        }
+
+        None
+    }
+
+    // Earliest offset whose bits up to the end equal the start of the pattern
+    fn trailing_partial(&self, pattern: &[bool]) -> Option<usize> {
+        let bit_len = self.bit_len();
+
+        ((bit_len + 1).saturating_sub(pattern.len())..bit_len)
+            .find(|&start| (start..bit_len).zip(pattern).all(|(position, e)| self.stream_bit(position, BitOrder::Msb0) == *e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::view_logical;
+
+    fn resync(raw: &[u8], reversed: bool, sync: &[u8], sync_bits: usize) -> (Result<usize, SyncError>, Vec<u8>) {
+        let mut raw = raw.to_vec();
+        let mut bytes = view_logical(&mut raw, reversed);
+
+        let mut sync = sync.to_vec();
+        let result = bytes.resync(&ByteString::new(&mut sync), sync_bits);
+        (result, bytes.iter().copied().collect())
+    }
+
+    #[test]
+    fn test_resync_byte_aligned_and_unaligned() {
+        for reversed in [false, true] {
+            // 0x1ACFFC1D, the CCSDS attached sync marker, two bytes in
+            let (result, bytes) = resync(&[0xFF, 0x00, 0x1A, 0xCF, 0xFC, 0x1D, 0x42], reversed, &[0x1A, 0xCF, 0xFC, 0x1D], 32);
+            assert_eq!(result, Ok(16));
+            assert_eq!(bytes, [0x1A, 0xCF, 0xFC, 0x1D, 0x42, 0x00, 0x00]);
+
+            // The same stream delayed by three bits
+            let (result, bytes) = resync(&[0x1F, 0xE0, 0x03, 0x59, 0xFF, 0x83, 0xA8, 0x40], reversed, &[0x1A, 0xCF, 0xFC, 0x1D], 32);
+            assert_eq!(result, Ok(19));
+            assert_eq!(bytes, [0x1A, 0xCF, 0xFC, 0x1D, 0x42, 0x00, 0x00, 0x00]);
+
+            // A 12 bit pattern taken from the low bits of a wider sync word, at offset 0
+            let (result, bytes) = resync(&[0xAB, 0xC0], reversed, &[0x0A, 0xBC], 12);
+            assert_eq!((result, bytes), (Ok(0), vec![0xAB, 0xC0]));
+        }
+    }
+
+    #[test]
+    fn test_resync_sync_word_interpretation() {
+        let mut raw = [0x00u8, 0x2D, 0x80];
+        let mut sync = [0x5Bu8, 0x00];
+        let mut sync_word = ByteString::new(&mut sync);
+        sync_word.interpret_reverse_endian();
+
+        // The pattern is the value 0x005B, whatever the sync word's storage order
+        assert_eq!(ByteString::new(&mut raw).resync(&sync_word, 8), Ok(9));
+        assert_eq!(raw, [0x5B, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_resync_absent_and_partial() {
+        for reversed in [false, true] {
+            let original = [0x12u8, 0x34, 0x56, 0x78];
+
+            let (result, bytes) = resync(&original, reversed, &[0xFF], 8);
+            assert_eq!((result, bytes), (Err(SyncError::NotFound { partial_at: None }), original.to_vec()));
+
+            // 0x8 starts 28 bits in, the rest of the 0x8F pattern is missing
+            let (result, bytes) = resync(&original, reversed, &[0x8F], 8);
+            assert_eq!((result, bytes), (Err(SyncError::NotFound { partial_at: Some(28) }), original.to_vec()));
+
+            assert_eq!(resync(&original, reversed, &[0xFF], 0).0, Err(SyncError::InvalidPattern));
+            assert_eq!(resync(&original, reversed, &[0xFF], 9).0, Err(SyncError::InvalidPattern));
+        }
+
+        assert_eq!(resync(&[], false, &[0xFF], 8).0, Err(SyncError::NotFound { partial_at: None }));
+    }
+
+    #[test]
+    fn test_resync_at_the_very_end() {
+        for reversed in [false, true] {
+            // Four bits follow the pattern and stay behind it
+            let (result, bytes) = resync(&[0x00, 0x00, 0x0B, 0x5F], reversed, &[0xB5], 8);
+            assert_eq!((result, bytes), (Ok(20), vec![0xB5, 0xF0, 0x00, 0x00]));
+
+            // The pattern ends exactly at the last bit, nothing follows it
+            let (result, bytes) = resync(&[0x00, 0x00, 0x00, 0xB5], reversed, &[0xB5], 8);
+            assert_eq!((result, bytes), (Ok(24), vec![0xB5, 0x00, 0x00, 0x00]));
+        }
+    }
+
+    #[test]
+    fn test_resync_long_pattern_against_bitwise_search() {
+        let mut rng = crate::test_util::XorShift::new(0x277);
+
+        for _ in 0..200 {
+            let len = 1 + rng.below(24);
+            let mut raw = vec![0u8; len];
+            rng.fill(&mut raw);
+
+            // Patterns of up to 96 bits copied from the buffer, half of them with one bit flipped
+            let sync_bits = 1 + rng.below(96.min(len * 8));
+            let start = rng.below(len * 8 - sync_bits + 1);
+            let flipped = if rng.next_bool() { Some(rng.below(sync_bits)) } else { None };
+            let mut sync = vec![0u8; sync_bits.div_ceil(8)];
+            {
+                let stream = ByteString::new(&mut raw);
+                let mut word = ByteString::new(&mut sync);
+                for k in 0..sync_bits {
+                    word.set_bit(sync_bits - 1 - k, stream.stream_bit(start + k, BitOrder::Msb0) ^ (flipped == Some(k)));
+                }
+            }
+
+            let stream: Vec<bool> = ByteString::new(&mut raw).iter_bits(BitOrder::Msb0).collect();
+            let pattern: Vec<bool> = {
+                let word = ByteString::new(&mut sync);
+                (0..sync_bits).rev().map(|e| word.bit(e)).collect()
+            };
+            let expected = stream.windows(sync_bits).position(|e| e == pattern);
+
+            let result = ByteString::new(&mut raw).resync(&ByteString::new(&mut sync), sync_bits);
+            assert_eq!(result.ok(), expected, "{:02x?} {:02x?} {}", raw, sync, sync_bits);
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! Lookup tables and word masks that can be built at compile time
+
+use crate::util;
+
+/// Table whose entry `i` is the byte `i` with its bit order reversed.
+///
+/// ```
+/// use rebite::table::build_bit_reverse_table;
+///
+/// const REVERSE: [u8; 256] = build_bit_reverse_table();
+/// assert_eq!(REVERSE[0x01], 0x80);
+/// assert_eq!(REVERSE[0xC0], 0x03);
+/// ```
+pub const fn build_bit_reverse_table() -> [u8; 256] {
+    let mut table = [0u8; 256];
+    let mut ix = 0;
+
+    while ix < 256 {
+        table[ix] = util::reverse_bit_endianness(ix as u8);
+        ix += 1;
+    }
+
+    table
+}
+
+/// `byte` repeated in each of the eight bytes of a word, for masks applied a word at a time
+///
+/// ```
+/// use rebite::table::splat;
+///
+/// const HIGH_BITS: u64 = splat(0x80);
+/// assert_eq!(HIGH_BITS, 0x8080_8080_8080_8080);
+/// ```
+pub const fn splat(byte: u8) -> u64 {
+    byte as u64 * 0x0101_0101_0101_0101
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const REVERSE: [u8; 256] = build_bit_reverse_table();
+    const ONES: u64 = splat(0xFF);
+    const _: [u8; 256] = build_bit_reverse_table();
+
+    #[test]
+    fn test_bit_reverse_table_in_const() {
+        assert_eq!((REVERSE[0x00], REVERSE[0x01], REVERSE[0x0F], REVERSE[0x9A], REVERSE[0xFF]), (0x00, 0x80, 0xF0, 0x59, 0xFF));
+        for byte in 0..=255u8 {
+            assert_eq!(REVERSE[REVERSE[byte as usize] as usize], byte);
+        }
+    }
+
+    #[test]
+    fn test_splat() {
+        assert_eq!(ONES, u64::MAX);
+        assert_eq!(splat(0x00), 0);
+        assert_eq!(splat(0x5A).to_be_bytes(), [0x5A; 8]);
+    }
+}
@@ -0,0 +1,52 @@
+use crate::bytes::ByteString;
+
+/// Small deterministic xorshift64 generator for randomized tests
+pub struct XorShift(u64);
+
+impl XorShift {
+
+    pub fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+
+    pub fn next_u8(&mut self) -> u8 {
+        (self.next_u64() >> 56) as u8
+    }
+
+    pub fn next_bool(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    /// Uniform-ish value in `0..bound`, `bound` must be nonzero
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    pub fn fill(&mut self, bytes: &mut [u8]) {
+        for byte in bytes {
+            *byte = self.next_u8();
+        }
+    }
+}
+
+/// ByteString over `raw`, with the reversed interpretation if `reversed`
+pub fn view(raw: &mut [u8], reversed: bool) -> ByteString<'_> {
+    let mut bytes = ByteString::new(raw);
+    if reversed { bytes.interpret_reverse_endian(); }
+    bytes
+}
+
+/// ByteString whose logical bytes are `raw` as given, stored physically reversed if `reversed`
+pub fn view_logical(raw: &mut [u8], reversed: bool) -> ByteString<'_> {
+    if reversed { raw.reverse(); }
+    view(raw, reversed)
+}
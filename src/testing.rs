@@ -0,0 +1,215 @@
+//! Deterministic fixtures for tests, enabled for other crates by the `testing` feature
+
+use std::iter;
+
+use crate::bytes::ByteString;
+
+/// Logical byte `i` becomes `start + i`, wrapping after 0xFF.
+///
+/// ```
+/// use rebite::{bytes::ByteString, testing::{fill_counter}};
+///
+/// let mut raw = [0u8; 3];
+/// fill_counter(&mut ByteString::new(&mut raw), 0xFE);
+/// assert_eq!(raw, [0xFE, 0xFF, 0x00]);
+/// ```
+pub fn fill_counter(bytes: &mut ByteString, start: u8) {
+    fill(bytes, counter(start));
+}
+
+/// Pseudo-random logical bytes from a 64 bit LCG, the same seed always gives the same bytes.
+///
+/// ```
+/// use rebite::{bytes::ByteString, testing::{fill_lcg}};
+///
+/// let (mut a, mut b) = ([0u8; 16], [0u8; 16]);
+/// fill_lcg(&mut ByteString::new(&mut a), 42);
+/// fill_lcg(&mut ByteString::new(&mut b), 42);
+/// assert_eq!(a, b);
+/// ```
+pub fn fill_lcg(bytes: &mut ByteString, seed: u64) {
+    let mut state = seed;
+
+    fill(bytes, iter::repeat_with(move || {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (state >> 56) as u8
+    }));
+}
+
+/// Repeat 4 byte records `label_hi label_lo offset_hi offset_lo` over the logical bytes.
+///
+/// The offset is the logical position of the record's first byte, truncated to 16 bits, so a hex
+/// dump of a misrouted copy shows both where it came from and from which position. A partial
+/// record at the end is cut off.
+///
+/// ```
+/// use rebite::{bytes::ByteString, testing::{labeled_pattern}};
+///
+/// let mut raw = [0u8; 10];
+/// labeled_pattern(&mut ByteString::new(&mut raw), 0xBEEF);
+/// assert_eq!(raw, [0xBE, 0xEF, 0x00, 0x00, 0xBE, 0xEF, 0x00, 0x04, 0xBE, 0xEF]);
+/// ```
+pub fn labeled_pattern(bytes: &mut ByteString, label: u16) {
+    fill(bytes, labeled(label));
+}
+
+/// Check the bytes written by [`fill_counter`], panics naming the first logical position that differs.
+///
+/// ```
+/// use rebite::{bytes::ByteString, testing::{assert_counter_pattern, fill_counter}};
+///
+/// let mut raw = [0u8; 4];
+/// let mut bytes = ByteString::new(&mut raw);
+///
+/// fill_counter(&mut bytes, 1);
+/// assert_counter_pattern(&bytes, 1);
+/// ```
+#[track_caller]
+pub fn assert_counter_pattern(bytes: &ByteString, start: u8) {
+    if let Some(ix) = find_counter_mismatch(bytes, start) {
+        mismatch(bytes, ix, counter(start));
+    }
+}
+
+/// First logical position that differs from the [`fill_counter`] pattern.
+///
+/// ```
+/// use rebite::{bytes::ByteString, testing::{find_counter_mismatch}};
+///
+/// let mut raw = [5u8, 6, 0, 8];
+/// let bytes = ByteString::new(&mut raw);
+///
+/// assert_eq!(find_counter_mismatch(&bytes, 5), Some(2));
+/// ```
+pub fn find_counter_mismatch(bytes: &ByteString, start: u8) -> Option<usize> {
+    bytes.iter().zip(counter(start)).position(|(found, expected)| *found != expected)
+}
+
+/// Check the bytes written by [`labeled_pattern`], panics naming the first logical position that differs.
+///
+/// ```
+/// use rebite::{bytes::ByteString, testing::{assert_labeled_pattern, labeled_pattern}};
+///
+/// let mut raw = [0u8; 8];
+/// let mut bytes = ByteString::new(&mut raw);
+///
+/// labeled_pattern(&mut bytes, 7);
+/// assert_labeled_pattern(&bytes, 7);
+/// ```
+#[track_caller]
+pub fn assert_labeled_pattern(bytes: &ByteString, label: u16) {
+    if let Some(ix) = find_labeled_mismatch(bytes, label) {
+        mismatch(bytes, ix, labeled(label));
+    }
+}
+
+/// First logical position that differs from the [`labeled_pattern`] pattern.
+///
+/// ```
+/// use rebite::{bytes::ByteString, testing::{find_labeled_mismatch, labeled_pattern}};
+///
+/// let mut raw = [0u8; 8];
+/// let mut bytes = ByteString::new(&mut raw);
+/// labeled_pattern(&mut bytes, 7);
+///
+/// // A copy that carries another label differs at its label byte
+/// assert_eq!(find_labeled_mismatch(&bytes, 0x0107), Some(0));
+/// assert_eq!(find_labeled_mismatch(&bytes, 7), None);
+/// ```
+pub fn find_labeled_mismatch(bytes: &ByteString, label: u16) -> Option<usize> {
+    bytes.iter().zip(labeled(label)).position(|(found, expected)| *found != expected)
+}
+
+fn counter(start: u8) -> impl Iterator<Item = u8> {
+    (0..=u8::MAX).cycle().map(move |e| e.wrapping_add(start))
+}
+
+fn labeled(label: u16) -> impl Iterator<Item = u8> {
+    (0usize..).step_by(4).flat_map(move |offset| {
+        let [label_hi, label_lo] = label.to_be_bytes();
+        let [offset_hi, offset_lo] = (offset as u16).to_be_bytes();
+        [label_hi, label_lo, offset_hi, offset_lo]
+    })
+}
+
+fn fill<I: Iterator<Item = u8>>(bytes: &mut ByteString, pattern: I) {
+    for (dst, value) in bytes.iter_mut().zip(pattern) {
+        *dst = value;
+    }
+}
+
+#[track_caller]
+fn mismatch<I: Iterator<Item = u8>>(bytes: &ByteString, ix: usize, mut pattern: I) -> ! {
+    let found = *bytes.iter().nth(ix).unwrap();
+    panic!("logical byte {} is {:#04x}, expected {:#04x}", ix, found, pattern.nth(ix).unwrap());
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_counter_wraps() {
+        let mut raw = [0u8; 260];
+        let mut bytes = ByteString::new(&mut raw);
+        fill_counter(&mut bytes, 0xFE);
+
+        assert_counter_pattern(&bytes, 0xFE);
+        assert_eq!(raw[..3], [0xFE, 0xFF, 0x00]);
+        assert_eq!(raw[259], 0x01);
+    }
+
+    #[test]
+    fn test_generators_follow_logical_order() {
+        let mut plain = [0u8; 9];
+        let mut reversed = [0u8; 9];
+
+        for generate in [|b: &mut ByteString| fill_counter(b, 7), |b: &mut ByteString| fill_lcg(b, 42), |b: &mut ByteString| labeled_pattern(b, 0xBEEF)] {
+            generate(&mut ByteString::new(&mut plain));
+
+            let mut bytes = ByteString::new(&mut reversed);
+            bytes.interpret_reverse_endian();
+            generate(&mut bytes);
+
+            reversed.reverse();
+            assert_eq!(plain, reversed);
+        }
+    }
+
+    #[test]
+    fn test_lcg_is_deterministic() {
+        let (mut a, mut b, mut c) = ([0u8; 32], [0u8; 32], [0u8; 32]);
+        fill_lcg(&mut ByteString::new(&mut a), 1);
+        fill_lcg(&mut ByteString::new(&mut b), 1);
+        fill_lcg(&mut ByteString::new(&mut c), 2);
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        // Not a degenerate sequence
+        assert!(a.iter().collect::<std::collections::HashSet<_>>().len() > 16);
+    }
+
+    #[test]
+    fn test_labeled_records() {
+        let mut raw = [0u8; 10];
+        let mut bytes = ByteString::new(&mut raw);
+        labeled_pattern(&mut bytes, 0xA1B2);
+
+        assert_labeled_pattern(&bytes, 0xA1B2);
+        assert_eq!(find_labeled_mismatch(&bytes, 0xA1B3), Some(1));
+        assert_eq!(raw, [0xA1, 0xB2, 0x00, 0x00, 0xA1, 0xB2, 0x00, 0x04, 0xA1, 0xB2]);
+    }
+
+    #[test]
+    fn test_mismatch_is_located() {
+        let mut raw = [0u8; 6];
+        let mut bytes = ByteString::new(&mut raw);
+        fill_counter(&mut bytes, 0);
+        *bytes.iter_mut().nth(4).unwrap() = 0xEE;
+
+        assert_eq!(find_counter_mismatch(&bytes, 0), Some(4));
+        let message = std::panic::catch_unwind(|| assert_counter_pattern(&bytes, 0)).unwrap_err();
+        assert_eq!(message.downcast_ref::<String>().unwrap(), "logical byte 4 is 0xee, expected 0x04");
+    }
+}
@@ -0,0 +1,640 @@
+//! Hex dump notations for parsing and display
+
+use std::fmt;
+
+use crate::{
+    bytes::ByteString,
+    error::{ParseError, ParseErrorKind}
+};
+
+/// Textual byte notations understood by [`ByteString::assign_from_text_auto`].
+///
+/// ```
+/// use rebite::{bytes::ByteString, text::TextFormat};
+///
+/// let mut raw = [0u8; 2];
+/// let mut bytes = ByteString::new(&mut raw);
+///
+/// assert_eq!(bytes.assign_from_text_auto("de ad"), Ok(TextFormat::Spaced));
+/// assert_eq!(bytes.assign_from_text_auto(r"\xde\xad"), Ok(TextFormat::Escaped));
+/// assert_eq!(raw, [0xDE, 0xAD]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextFormat {
+    /// `DE:AD:BE:EF`, two digits per byte separated by single colons
+    Colon,
+    /// `de ad be ef`, two digits per byte separated by runs of ASCII whitespace
+    Spaced,
+    /// `0xDE, 0xAD`, one or two digits per byte, commas with optional whitespace and an optional trailing comma
+    CArray,
+    /// `\xde\xad`, two digits per byte and no separators
+    Escaped,
+}
+
+/// Uppercase colon separated hex, see [`ByteString::display_colon_separated`].
+///
+/// ```
+/// use rebite::bytes::ByteString;
+///
+/// let mut raw = [0x0Au8, 0xBC];
+/// let mut bytes = ByteString::new(&mut raw);
+/// assert_eq!(format!("mac {}", bytes.display_colon_separated()), "mac 0A:BC");
+///
+/// bytes.mark_sensitive();
+/// assert_eq!(bytes.display_colon_separated().to_string(), "[REDACTED; 2 bytes]");
+/// ```
+pub struct ColonSeparated<'s, 'a> {
+    bytes: &'s ByteString<'a>,
+}
+
+impl<'s, 'a> fmt::Display for ColonSeparated<'s, 'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.bytes.is_sensitive() {
+            return fmt::Display::fmt(&self.bytes.redacted(), f);
+        }
+
+        for (ix, e) in self.bytes.iter().enumerate() {
+            if ix > 0 { f.write_str(":")?; }
+            write!(f, "{:02X}", e)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// C array elements, see [`ByteString::display_c_array`].
+///
+/// ```
+/// use rebite::bytes::ByteString;
+///
+/// let mut raw = [1u8, 2, 3, 4];
+/// let bytes = ByteString::new(&mut raw);
+///
+/// let text = bytes.display_c_array(2).to_string();
+/// assert_eq!(text, "0x01, 0x02,\n0x03, 0x04,");
+///
+/// // The output parses back
+/// let mut copy = [0u8; 4];
+/// ByteString::new(&mut copy).assign_from_c_array(&text).unwrap();
+/// assert_eq!(copy, [1, 2, 3, 4]);
+/// ```
+pub struct CArray<'s, 'a> {
+    bytes: &'s ByteString<'a>,
+    per_line: usize,
+    trailing_comma: bool,
+}
+
+impl<'s, 'a> CArray<'s, 'a> {
+
+    /// Whether the last element is followed by a comma, on by default.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0xDEu8, 0xAD];
+    /// let bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.display_c_array(0).trailing_comma(false).to_string(), "0xDE, 0xAD");
+    /// ```
+    pub fn trailing_comma(mut self, trailing_comma: bool) -> Self {
+        self.trailing_comma = trailing_comma;
+        self
+    }
+}
+
+impl<'s, 'a> fmt::Display for CArray<'s, 'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.bytes.is_sensitive() {
+            return fmt::Display::fmt(&self.bytes.redacted(), f);
+        }
+
+        let len = self.bytes.byte_len();
+        for (ix, e) in self.bytes.iter().enumerate() {
+            if ix > 0 {
+                let line_start = self.per_line > 0 && ix % self.per_line == 0;
+                f.write_str(if line_start { "\n" } else { " " })?;
+            }
+
+            write!(f, "0x{:02X}", e)?;
+            if ix + 1 < len || self.trailing_comma { f.write_str(",")?; }
+        }
+
+        Ok(())
+    }
+}
+
+/// Lowercase `\x` escaped bytes, see [`ByteString::display_escaped`].
+///
+/// ```
+/// use rebite::bytes::ByteString;
+///
+/// let mut raw = *b"hi";
+/// let bytes = ByteString::new(&mut raw);
+///
+/// assert_eq!(bytes.display_escaped().to_string(), r"\x68\x69");
+/// ```
+pub struct Escaped<'s, 'a> {
+    bytes: &'s ByteString<'a>,
+}
+
+impl<'s, 'a> fmt::Display for Escaped<'s, 'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.bytes.is_sensitive() {
+            return fmt::Display::fmt(&self.bytes.redacted(), f);
+        }
+
+        self.bytes.iter().try_for_each(|e| write!(f, "\\x{:02x}", e))
+    }
+}
+
+// Cursor over `s[pos..end]`, positions stay byte offsets into the whole input
+struct Cursor<'s> {
+    s: &'s str,
+    pos: usize,
+    end: usize,
+    out: Vec<u8>,
+    capacity: usize,
+}
+
+impl<'s> Cursor<'s> {
+
+    fn new(s: &'s str, start: usize, end: usize, capacity: usize) -> Self {
+        Self { s, pos: start, end, out: Vec::with_capacity(capacity), capacity }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        if self.pos < self.end { Some(self.s.as_bytes()[self.pos]) } else { None }
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos == self.end
+    }
+
+    fn expected(&self, what: &'static str) -> ParseError {
+        ParseError::new(self.pos, ParseErrorKind::Expected(what))
+    }
+
+    // Consume `token`, the comparison ignores ASCII case
+    fn expect(&mut self, token: &'static str, what: &'static str) -> Result<(), ParseError> {
+        let rest = &self.s.as_bytes()[self.pos..self.end];
+        if rest.len() < token.len() || !rest[..token.len()].eq_ignore_ascii_case(token.as_bytes()) {
+            return Err(self.expected(what));
+        }
+
+        self.pos += token.len();
+        Ok(())
+    }
+
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.pos;
+        while self.peek().is_some_and(|e| e.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+
+        self.pos != start
+    }
+
+    fn hex_digit(&mut self) -> Result<u8, ParseError> {
+        if self.at_end() {
+            return Err(self.expected("hex digit"));
+        }
+
+        let c = self.s[self.pos..].chars().next().unwrap();
+        let digit = c.to_digit(16).ok_or(ParseError::new(self.pos, ParseErrorKind::InvalidDigit(c)))?;
+
+        self.pos += 1;
+        Ok(digit as u8)
+    }
+
+    fn hex_pair(&mut self) -> Result<u8, ParseError> {
+        Ok(self.hex_digit()? << 4 | self.hex_digit()?)
+    }
+
+    // Record a parsed byte that started at `start`, a byte past the destination is an overflow there
+    fn push(&mut self, start: usize, byte: u8) -> Result<(), ParseError> {
+        if self.out.len() == self.capacity {
+            return Err(ParseError::new(start, ParseErrorKind::Overflow));
+        }
+
+        self.out.push(byte);
+        Ok(())
+    }
+
+    // Parsed bytes, which must fill the destination exactly
+    fn finish(self) -> Result<Vec<u8>, ParseError> {
+        if self.out.len() < self.capacity {
+            return Err(self.expected("another byte"));
+        }
+
+        Ok(self.out)
+    }
+
+    fn colon(mut self) -> Result<Vec<u8>, ParseError> {
+        while !self.at_end() {
+            let start = self.pos;
+            let byte = self.hex_pair()?;
+            self.push(start, byte)?;
+
+            if !self.at_end() {
+                self.expect(":", "':' or end of input")?;
+                if self.at_end() { return Err(self.expected("hex digit")); }
+            }
+        }
+
+        self.finish()
+    }
+
+    fn spaced(mut self) -> Result<Vec<u8>, ParseError> {
+        while !self.at_end() {
+            let start = self.pos;
+            let byte = self.hex_pair()?;
+            self.push(start, byte)?;
+
+            if !self.at_end() {
+                if !self.skip_whitespace() { return Err(self.expected("whitespace or end of input")); }
+                if self.at_end() { return Err(self.expected("hex digit")); }
+            }
+        }
+
+        self.finish()
+    }
+
+    fn c_array(mut self) -> Result<Vec<u8>, ParseError> {
+        while !self.at_end() {
+            let start = self.pos;
+            self.expect("0x", "'0x'")?;
+
+            let mut byte = self.hex_digit()?;
+            if self.peek().is_some_and(|e| e.is_ascii_hexdigit()) {
+                byte = byte << 4 | self.hex_digit()?;
+            }
+            self.push(start, byte)?;
+
+            self.skip_whitespace();
+            if self.at_end() { break; }
+            self.expect(",", "',' or end of input")?;
+            self.skip_whitespace();
+        }
+
+        self.finish()
+    }
+
+    fn escaped(mut self) -> Result<Vec<u8>, ParseError> {
+        while !self.at_end() {
+            let start = self.pos;
+            if self.peek() != Some(b'\\') { return Err(self.expected("'\\x'")); }
+            self.pos += 1;
+            if self.peek() != Some(b'x') { return Err(self.expected("'x'")); }
+            self.pos += 1;
+
+            let byte = self.hex_pair()?;
+            self.push(start, byte)?;
+        }
+
+        self.finish()
+    }
+}
+
+impl<'a> ByteString<'a> {
+
+    /// Format the logical bytes as `DE:AD:BE:EF`, readable by [`ByteString::assign_from_colon_hex`].
+    ///
+    /// Like the other notation formatters this writes straight to the formatter and shows only
+    /// the length of sensitive buffers.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0xDEu8, 0xAD, 0xBE, 0xEF];
+    /// let bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.display_colon_separated().to_string(), "DE:AD:BE:EF");
+    /// ```
+    pub fn display_colon_separated(&self) -> ColonSeparated<'_, 'a> {
+        ColonSeparated { bytes: self }
+    }
+
+    /// Format the logical bytes as C array elements `0xDE, 0xAD,` with `per_line` elements per line.
+    ///
+    /// A `per_line` of 0 keeps everything on one line. Lines end in a comma without trailing
+    /// whitespace, the comma after the last element is controlled by [`CArray::trailing_comma`].
+    /// Readable by [`ByteString::assign_from_c_array`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x01u8, 0x02, 0x03];
+    /// let bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.display_c_array(2).to_string(), "0x01, 0x02,\n0x03,");
+    /// ```
+    pub fn display_c_array(&self, per_line: usize) -> CArray<'_, 'a> {
+        CArray { bytes: self, per_line, trailing_comma: true }
+    }
+
+    /// Format the logical bytes as `\xde\xad`, readable by [`ByteString::assign_from_escaped`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0xDEu8, 0xAD];
+    /// let bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.display_escaped().to_string(), r"\xde\xad");
+    /// ```
+    pub fn display_escaped(&self) -> Escaped<'_, 'a> {
+        Escaped { bytes: self }
+    }
+
+    /// Detect the notation of `s`, parse it into the logical bytes and report the notation found.
+    ///
+    /// Surrounding whitespace is ignored. The first match in this order wins: a leading `\` is
+    /// [`TextFormat::Escaped`], a leading `0x` or `0X` is [`TextFormat::CArray`], any `:` makes it
+    /// [`TextFormat::Colon`], anything else is [`TextFormat::Spaced`]. A lone byte such as `de`
+    /// therefore reads as spaced. Error positions are byte offsets into `s`.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, text::TextFormat};
+    ///
+    /// let mut raw = [0u8; 2];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.assign_from_text_auto(" de:ad "), Ok(TextFormat::Colon));
+    /// assert_eq!(bytes.assign_from_text_auto("0x1, 0x2"), Ok(TextFormat::CArray));
+    /// assert_eq!(raw, [0x01, 0x02]);
+    /// ```
+    pub fn assign_from_text_auto(&mut self, s: &str) -> Result<TextFormat, ParseError> {
+
+        let start = s.len() - s.trim_start().len();
+        let body = s.trim();
+        if body.is_empty() {
+            return Err(ParseError::new(0, ParseErrorKind::Empty));
+        }
+
+        let format = if body.starts_with('\\') { TextFormat::Escaped }
+        else if body.starts_with("0x") || body.starts_with("0X") { TextFormat::CArray }
+        else if body.contains(':') { TextFormat::Colon }
+        else { TextFormat::Spaced };
+
+        self.assign_parsed(Cursor::new(s, start, start + body.len(), self.byte_len()), format)?;
+        Ok(format)
+    }
+
+    /// Parse colon separated hex such as `DE:AD:BE:EF`, see [`TextFormat::Colon`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0u8; 2];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.assign_from_colon_hex("de:AD").unwrap();
+    /// assert_eq!(bytes.bytes(), [0xDE, 0xAD]);
+    ///
+    /// // Too few bytes for the buffer
+    /// assert!(bytes.assign_from_colon_hex("de").is_err());
+    /// ```
+    pub fn assign_from_colon_hex(&mut self, s: &str) -> Result<(), ParseError> {
+        self.assign_parsed(Cursor::new(s, 0, s.len(), self.byte_len()), TextFormat::Colon)
+    }
+
+    /// Parse whitespace separated hex such as `de ad be ef`, see [`TextFormat::Spaced`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0u8; 3];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.assign_from_spaced_hex("de  ad\tbe").unwrap();
+    /// assert_eq!(raw, [0xDE, 0xAD, 0xBE]);
+    /// ```
+    pub fn assign_from_spaced_hex(&mut self, s: &str) -> Result<(), ParseError> {
+        self.assign_parsed(Cursor::new(s, 0, s.len(), self.byte_len()), TextFormat::Spaced)
+    }
+
+    /// Parse C array elements such as `0xDE, 0xAD,`, see [`TextFormat::CArray`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0u8; 2];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.assign_from_c_array("0xDE,\n0x5,").unwrap();
+    /// assert_eq!(raw, [0xDE, 0x05]);
+    /// ```
+    pub fn assign_from_c_array(&mut self, s: &str) -> Result<(), ParseError> {
+        self.assign_parsed(Cursor::new(s, 0, s.len(), self.byte_len()), TextFormat::CArray)
+    }
+
+    /// Parse escaped bytes such as `\xde\xad`, see [`TextFormat::Escaped`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0u8; 2];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.assign_from_escaped(r"\xde\xad").unwrap();
+    /// assert_eq!(raw, [0xDE, 0xAD]);
+    /// ```
+    pub fn assign_from_escaped(&mut self, s: &str) -> Result<(), ParseError> {
+        self.assign_parsed(Cursor::new(s, 0, s.len(), self.byte_len()), TextFormat::Escaped)
+    }
+
+    // The input has to name every logical byte, the buffer is only written once all of it parsed
+    fn assign_parsed(&mut self, cursor: Cursor, format: TextFormat) -> Result<(), ParseError> {
+
+        let parsed = match format {
+            TextFormat::Colon => cursor.colon(),
+            TextFormat::Spaced => cursor.spaced(),
+            TextFormat::CArray => cursor.c_array(),
+            TextFormat::Escaped => cursor.escaped(),
+        }?;
+
+        for (dst, value) in self.iter_mut().zip(parsed) {
+            *dst = value;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::{view, XorShift};
+
+    const FORMATS: [TextFormat; 4] = [TextFormat::Colon, TextFormat::Spaced, TextFormat::CArray, TextFormat::Escaped];
+
+    // Writer for each notation, spaced hex has no formatter of its own
+    fn render(bytes: &ByteString, format: TextFormat) -> String {
+        match format {
+            TextFormat::Colon => bytes.display_colon_separated().to_string(),
+            TextFormat::Spaced => bytes.iter().map(|e| format!("{:02x}", e)).collect::<Vec<_>>().join(" "),
+            TextFormat::CArray => bytes.display_c_array(bytes.byte_len() % 4).to_string(),
+            TextFormat::Escaped => bytes.display_escaped().to_string(),
+        }
+    }
+
+    fn parse(bytes: &mut ByteString, s: &str, format: TextFormat) -> Result<(), ParseError> {
+        match format {
+            TextFormat::Colon => bytes.assign_from_colon_hex(s),
+            TextFormat::Spaced => bytes.assign_from_spaced_hex(s),
+            TextFormat::CArray => bytes.assign_from_c_array(s),
+            TextFormat::Escaped => bytes.assign_from_escaped(s),
+        }
+    }
+
+    fn expected(position: usize, what: &'static str) -> Result<(), ParseError> {
+        Err(ParseError::new(position, ParseErrorKind::Expected(what)))
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut rng = XorShift::new(0x251);
+
+        for _ in 0..500 {
+            let len = 1 + rng.below(12);
+            let mut raw = vec![0u8; len];
+            rng.fill(&mut raw);
+            let bytes = view(&mut raw, rng.next_bool());
+
+            for format in FORMATS {
+                let text = render(&bytes, format);
+
+                let mut strict = vec![0u8; len];
+                let mut parsed = ByteString::new(&mut strict);
+                parse(&mut parsed, &text, format).unwrap();
+                assert!(parsed.iter().eq(bytes.iter()));
+
+                let mut auto = vec![0u8; len];
+                let mut parsed = ByteString::new(&mut auto);
+                parsed.interpret_reverse_endian();
+                let detected = parsed.assign_from_text_auto(&format!(" {}\n", text)).unwrap();
+                assert!(parsed.iter().eq(bytes.iter()));
+
+                // A single byte has no separator to tell colon and spaced apart
+                let expected = if len == 1 && format == TextFormat::Colon { TextFormat::Spaced } else { format };
+                assert_eq!(detected, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_detection_precedence() {
+        let mut raw = [0u8; 2];
+        let mut bytes = ByteString::new(&mut raw);
+
+        assert_eq!(bytes.assign_from_text_auto("\\xde\\xad"), Ok(TextFormat::Escaped));
+        assert_eq!(bytes.assign_from_text_auto("0XDE,0xad,"), Ok(TextFormat::CArray));
+        assert_eq!(bytes.assign_from_text_auto("de:ad"), Ok(TextFormat::Colon));
+        assert_eq!(bytes.assign_from_text_auto("de\tad"), Ok(TextFormat::Spaced));
+        assert_eq!(raw, [0xDE, 0xAD]);
+
+        // The leading token decides, a later ':' does not turn a C array into colon notation
+        let mut bytes = ByteString::new(&mut raw);
+        assert_eq!(bytes.assign_from_text_auto("0x01:02"), expected(4, "',' or end of input").map(|_| TextFormat::CArray));
+        assert_eq!(bytes.assign_from_text_auto(" \n"), Err(ParseError::new(0, ParseErrorKind::Empty)));
+    }
+
+    #[test]
+    fn test_variants_within_a_format() {
+        let mut raw = [0u8; 3];
+        let mut bytes = ByteString::new(&mut raw);
+
+        bytes.assign_from_c_array("0x1,0xA,  0xff ,").unwrap();
+        assert_eq!(raw, [0x01, 0x0A, 0xFF]);
+
+        let mut bytes = ByteString::new(&mut raw);
+        bytes.assign_from_spaced_hex("01\n02  \t03").unwrap();
+        assert_eq!(raw, [0x01, 0x02, 0x03]);
+
+        let mut empty = [];
+        for format in FORMATS {
+            assert_eq!(parse(&mut ByteString::new(&mut empty), "", format), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_malformed_positions() {
+        let mut raw = [0x55u8; 2];
+        let mut bytes = ByteString::new(&mut raw);
+
+        let invalid = |position, c| Err(ParseError::new(position, ParseErrorKind::InvalidDigit(c)));
+        let overflow = |position| Err(ParseError::new(position, ParseErrorKind::Overflow));
+
+        assert_eq!(bytes.assign_from_colon_hex("DE:A"), expected(4, "hex digit"));
+        assert_eq!(bytes.assign_from_colon_hex("DE::AD"), invalid(3, ':'));
+        assert_eq!(bytes.assign_from_colon_hex("DE:AD:"), expected(6, "hex digit"));
+        assert_eq!(bytes.assign_from_colon_hex("DE-AD"), expected(2, "':' or end of input"));
+        assert_eq!(bytes.assign_from_colon_hex("DE:AD:BE"), overflow(6));
+        assert_eq!(bytes.assign_from_colon_hex("DE"), expected(2, "another byte"));
+        assert_eq!(bytes.assign_from_colon_hex("DE:é0"), invalid(3, 'é'));
+
+        assert_eq!(bytes.assign_from_spaced_hex("dead"), expected(2, "whitespace or end of input"));
+        assert_eq!(bytes.assign_from_spaced_hex("de ad "), expected(6, "hex digit"));
+        assert_eq!(bytes.assign_from_spaced_hex(" de ad"), invalid(0, ' '));
+
+        assert_eq!(bytes.assign_from_c_array("0xde, xad"), expected(6, "'0x'"));
+        assert_eq!(bytes.assign_from_c_array("0xde 0xad"), expected(5, "',' or end of input"));
+        assert_eq!(bytes.assign_from_c_array("0xde,0x"), expected(7, "hex digit"));
+        assert_eq!(bytes.assign_from_c_array("0xde,0xadb"), expected(9, "',' or end of input"));
+        assert_eq!(bytes.assign_from_c_array("0x1, 0x2, 0x3"), overflow(10));
+
+        assert_eq!(bytes.assign_from_escaped("\\xde\\Xad"), expected(5, "'x'"));
+        assert_eq!(bytes.assign_from_escaped("\\xde xad"), expected(4, "'\\x'"));
+        assert_eq!(bytes.assign_from_escaped("\\xdg"), invalid(3, 'g'));
+
+        // Positions of the auto parser count from the start of the untrimmed input
+        assert_eq!(bytes.assign_from_text_auto("  de:zz").map(|_| ()), invalid(5, 'z'));
+
+        // Nothing was written by any failed parse
+        assert_eq!(raw, [0x55, 0x55]);
+    }
+
+    #[test]
+    fn test_formatters_golden() {
+        let mut raw = [0xDEu8, 0xAD, 0xBE, 0xEF, 0x01];
+        let mut bytes = ByteString::new(&mut raw);
+
+        assert_eq!(bytes.display_colon_separated().to_string(), "DE:AD:BE:EF:01");
+        assert_eq!(bytes.display_escaped().to_string(), "\\xde\\xad\\xbe\\xef\\x01");
+        assert_eq!(bytes.display_c_array(0).to_string(), "0xDE, 0xAD, 0xBE, 0xEF, 0x01,");
+        assert_eq!(bytes.display_c_array(0).trailing_comma(false).to_string(), "0xDE, 0xAD, 0xBE, 0xEF, 0x01");
+
+        bytes.interpret_reverse_endian();
+        assert_eq!(bytes.display_colon_separated().to_string(), "01:EF:BE:AD:DE");
+
+        bytes.mark_sensitive();
+        assert_eq!(bytes.display_colon_separated().to_string(), "[REDACTED; 5 bytes]");
+        assert_eq!(bytes.display_c_array(2).to_string(), "[REDACTED; 5 bytes]");
+        assert_eq!(bytes.display_escaped().to_string(), "[REDACTED; 5 bytes]");
+    }
+
+    #[test]
+    fn test_c_array_line_wrapping() {
+        let mut raw = [1u8, 2, 3, 4, 5, 6];
+        let bytes = ByteString::new(&mut raw);
+
+        assert_eq!(bytes.display_c_array(2).to_string(), "0x01, 0x02,\n0x03, 0x04,\n0x05, 0x06,");
+        assert_eq!(bytes.display_c_array(2).trailing_comma(false).to_string(), "0x01, 0x02,\n0x03, 0x04,\n0x05, 0x06");
+        assert_eq!(bytes.display_c_array(4).to_string(), "0x01, 0x02, 0x03, 0x04,\n0x05, 0x06,");
+        assert_eq!(bytes.display_c_array(1).trailing_comma(false).to_string(), "0x01,\n0x02,\n0x03,\n0x04,\n0x05,\n0x06");
+        assert_eq!(bytes.display_c_array(6).to_string(), "0x01, 0x02, 0x03, 0x04, 0x05, 0x06,");
+        assert_eq!(bytes.display_c_array(7).to_string(), bytes.display_c_array(0).to_string());
+    }
+
+    #[test]
+    fn test_formatters_empty() {
+        let mut raw = [];
+        let bytes = ByteString::new(&mut raw);
+
+        assert_eq!(bytes.display_colon_separated().to_string(), "");
+        assert_eq!(bytes.display_c_array(4).to_string(), "");
+        assert_eq!(bytes.display_c_array(4).trailing_comma(false).to_string(), "");
+        assert_eq!(bytes.display_escaped().to_string(), "");
+    }
+}
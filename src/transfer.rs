@@ -0,0 +1,132 @@
+//! Moving a value between buffers and erasing the source
+
+use crate::{
+    bytes::ByteString,
+    error::LengthError,
+    util
+};
+
+impl<'a> ByteString<'a> {
+
+    /// Move the logical value of self into `out` and zero self.
+    ///
+    /// The copy completes before self is cleared, on a length mismatch neither buffer is touched.
+    /// Self is erased with volatile writes if either buffer is marked sensitive, with `forbid-unsafe`
+    /// on a best-effort basis only.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut key = [0x12u8, 0x34];
+    /// let mut out = [0u8; 2];
+    ///
+    /// ByteString::new(&mut key).take_into(&mut ByteString::new(&mut out)).unwrap();
+    /// assert_eq!((key, out), ([0x00, 0x00], [0x12, 0x34]));
+    ///
+    /// // A length mismatch leaves both alone
+    /// let mut short = [0u8];
+    /// assert!(ByteString::new(&mut out).take_into(&mut ByteString::new(&mut short)).is_err());
+    /// assert_eq!(out, [0x12, 0x34]);
+    /// ```
+    pub fn take_into(&mut self, out: &mut ByteString) -> Result<(), LengthError> {
+
+        if self.byte_len() != out.byte_len() {
+            return Err(LengthError { index: 0, expected: self.byte_len(), actual: out.byte_len() });
+        }
+
+        for (dst, src) in out.iter_mut().zip(self.iter()) {
+            *dst = *src;
+        }
+
+        self.erase(out.sensitive);
+        Ok(())
+    }
+
+    /// Move the logical value of `src` into self and zero `src`, the mirror of [`ByteString::take_into`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0u8; 2];
+    /// let mut src = [0xABu8, 0xCD];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    ///
+    /// // The value moves, so it lands physically reversed
+    /// bytes.replace_from(&mut ByteString::new(&mut src)).unwrap();
+    /// assert_eq!((raw, src), ([0xCD, 0xAB], [0x00, 0x00]));
+    /// ```
+    pub fn replace_from(&mut self, src: &mut ByteString) -> Result<(), LengthError> {
+        src.take_into(self)
+    }
+
+    // Zero the buffer, through util::wipe if it holds secrets
+    fn erase(&mut self, sensitive: bool) {
+
+        if !(sensitive || self.sensitive) {
+            self.set_zero();
+            return;
+        }
+
+        util::wipe(self.bytes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::{view, XorShift};
+
+    #[test]
+    fn test_take_and_replace() {
+        let mut rng = XorShift::new(0x239);
+
+        for _ in 0..200 {
+            let len = rng.below(20);
+            let mut src_raw = vec![0u8; len];
+            rng.fill(&mut src_raw);
+
+            let mut src = view(&mut src_raw, rng.next_bool());
+            if rng.next_bool() { src.mark_sensitive(); }
+            let logical: Vec<u8> = src.iter().copied().collect();
+
+            let mut dst_raw = vec![0xEEu8; len];
+            let mut dst = view(&mut dst_raw, rng.next_bool());
+
+            if rng.next_bool() { src.take_into(&mut dst).unwrap(); } else { dst.replace_from(&mut src).unwrap(); }
+
+            assert!(dst.iter().eq(logical.iter()));
+            assert!(src.is_zero());
+        }
+    }
+
+    #[test]
+    fn test_labeled_source_arrives_in_order() {
+        use crate::testing::{labeled_pattern, assert_labeled_pattern};
+
+        let mut src_raw = [0u8; 11];
+        let mut src = ByteString::new(&mut src_raw);
+        src.interpret_reverse_endian();
+        labeled_pattern(&mut src, 0x0239);
+
+        let mut dst_raw = [0u8; 11];
+        let mut dst = ByteString::new(&mut dst_raw);
+        src.take_into(&mut dst).unwrap();
+
+        assert_labeled_pattern(&dst, 0x0239);
+        assert!(src.is_zero());
+    }
+
+    #[test]
+    fn test_length_mismatch_changes_nothing() {
+        let mut src_raw = [1u8, 2, 3];
+        let mut dst_raw = [9u8; 2];
+        let mut src = ByteString::new(&mut src_raw);
+        let mut dst = ByteString::new(&mut dst_raw);
+
+        assert_eq!(src.take_into(&mut dst), Err(LengthError { index: 0, expected: 3, actual: 2 }));
+        assert_eq!(dst.replace_from(&mut src), Err(LengthError { index: 0, expected: 3, actual: 2 }));
+        assert_eq!((src_raw, dst_raw), ([1, 2, 3], [9, 9]));
+    }
+}
@@ -0,0 +1,122 @@
+//! Sub-views with padding stripped from either end
+
+use crate::bytes::ByteString;
+
+impl<'a> ByteString<'a> {
+
+    /// View without the leading logical bytes that satisfy `pred`, such as 0x00 or 0xFF padding.
+    ///
+    /// The view keeps the interpretation and sensitivity flags, an all matching buffer gives an empty view.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x00u8, 0x00, 0x12, 0x00];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.trim_start_matches(|e| e == 0).bytes(), [0x12, 0x00]);
+    /// assert_eq!(bytes.trim_start_matches(|_| true).byte_len(), 0);
+    /// ```
+    pub fn trim_start_matches(&mut self, pred: impl Fn(u8) -> bool) -> ByteString<'_> {
+        let start = self.iter().take_while(|b| pred(**b)).count();
+        self.sub_view(start..self.byte_len())
+    }
+
+    /// View without the trailing logical bytes that satisfy `pred`, see [`ByteString::trim_start_matches`].
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x12u8, 0xFF, 0xFF];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.trim_end_matches(|e| e == 0xFF).bytes(), [0x12]);
+    /// ```
+    pub fn trim_end_matches(&mut self, pred: impl Fn(u8) -> bool) -> ByteString<'_> {
+        let end = self.byte_len() - self.iter().rev().take_while(|b| pred(**b)).count();
+        self.sub_view(0..end)
+    }
+
+    /// View without the leading and trailing logical bytes that satisfy `pred`.
+    ///
+    /// ```
+    /// use rebite::bytes::ByteString;
+    ///
+    /// let mut raw = [0x00u8, 0x12, 0x34, 0x00];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// let mut trimmed = bytes.trim_matches(|e| e == 0);
+    /// trimmed.set_zero();
+    /// assert_eq!(raw, [0x00; 4]);
+    /// ```
+    pub fn trim_matches(&mut self, pred: impl Fn(u8) -> bool) -> ByteString<'_> {
+        let start = self.iter().take_while(|b| pred(**b)).count();
+        // An all matching buffer is consumed from the start alone
+        let end = self.byte_len() - self.iter().skip(start).rev().take_while(|b| pred(**b)).count();
+        self.sub_view(start..end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_zero_and_ff_padding() {
+        let mut raw = [0x00u8, 0x00, 0x12, 0x00, 0x34, 0x00];
+        let mut bytes = ByteString::new(&mut raw);
+        assert!(bytes.trim_matches(|b| b == 0x00).iter().eq([0x12, 0x00, 0x34].iter()));
+        assert!(bytes.trim_start_matches(|b| b == 0x00).iter().eq([0x12, 0x00, 0x34, 0x00].iter()));
+        assert!(bytes.trim_end_matches(|b| b == 0x00).iter().eq([0x00, 0x00, 0x12, 0x00, 0x34].iter()));
+
+        // Erased flash is 0xFF
+        let mut raw = [0xFFu8, 0xA5, 0xFF, 0xFF];
+        let mut bytes = ByteString::new(&mut raw);
+        let mut view = bytes.trim_matches(|b| b == 0xFF);
+        assert_eq!(view.byte_len(), 1);
+        view.set_zero();
+        assert_eq!(raw, [0xFF, 0x00, 0xFF, 0xFF]);
+    }
+
+    #[test]
+    fn test_one_sided_padding() {
+        let mut raw = *b"  card";
+        let mut bytes = ByteString::new(&mut raw);
+        assert_eq!(bytes.trim_end_matches(|b| b == b' ').byte_len(), 6);
+        assert_eq!(bytes.trim_matches(|b| b == b' ').bytes(), b"card");
+
+        let mut raw = *b"card  ";
+        let mut bytes = ByteString::new(&mut raw);
+        assert_eq!(bytes.trim_start_matches(|b| b == b' ').byte_len(), 6);
+        assert_eq!(bytes.trim_matches(|b| b == b' ').bytes(), b"card");
+    }
+
+    #[test]
+    fn test_all_matching_is_empty() {
+        let mut raw = [0xFFu8; 4];
+        let mut bytes = ByteString::new(&mut raw);
+
+        assert_eq!(bytes.trim_start_matches(|b| b == 0xFF).byte_len(), 0);
+        assert_eq!(bytes.trim_end_matches(|b| b == 0xFF).byte_len(), 0);
+        assert_eq!(bytes.trim_matches(|b| b == 0xFF).byte_len(), 0);
+
+        let mut empty: [u8; 0] = [];
+        assert_eq!(ByteString::new(&mut empty).trim_matches(|_| true).byte_len(), 0);
+    }
+
+    #[test]
+    fn test_reversed_start_is_the_physical_end() {
+        let mut raw = [0x12u8, 0x34, 0x00, 0x00];
+        let mut bytes = ByteString::new(&mut raw);
+        bytes.interpret_reverse_endian();
+        bytes.mark_sensitive();
+
+        let mut view = bytes.trim_start_matches(|b| b == 0x00);
+        assert!(view.interpret_reverse_endian && view.is_sensitive());
+        assert_eq!(view.to_u64(), Ok(0x3412));
+
+        view.assign_from_u64(0xBEEF).unwrap();
+        assert_eq!(raw, [0xEF, 0xBE, 0x00, 0x00]);
+    }
+}
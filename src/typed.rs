@@ -0,0 +1,360 @@
+//! Typed integer and float access at byte offsets
+
+use crate::{
+    bytes::ByteString,
+    error::{Error, Overflow}
+};
+
+/// Byte order of a typed value relative to the logical byte order of the buffer.
+///
+/// ```
+/// use rebite::{bytes::ByteString, typed::Endianness};
+///
+/// let mut raw = [0x12u8, 0x34];
+/// let mut bytes = ByteString::new(&mut raw);
+///
+/// assert_eq!(bytes.read_u16_at(0, Endianness::Big), Ok(0x1234));
+/// assert_eq!(bytes.read_u16_at(0, Endianness::Little), Ok(0x3412));
+///
+/// // Both are relative to the logical order
+/// bytes.interpret_reverse_endian();
+/// assert_eq!(bytes.read_u16_at(0, Endianness::Big), Ok(0x3412));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endianness {
+    /// Most significant byte at the lowest logical offset
+    Big,
+    /// Least significant byte at the lowest logical offset
+    Little,
+}
+
+impl<'a> ByteString<'a> {
+
+    // Copy N logical bytes starting at `offset`, in logical order
+    fn read_array_at<const N: usize>(&self, offset: usize) -> Result<[u8; N], Error> {
+        let end = offset.checked_add(N).ok_or(Error::OutOfBounds)?;
+
+        let mut out = [0u8; N];
+        for (dst, src) in out.iter_mut().zip(self.try_iter_range(offset..end)?) {
+            *dst = *src;
+        }
+
+        Ok(out)
+    }
+
+    // Store N bytes in logical order starting at logical `offset`
+    fn write_array_at<const N: usize>(&mut self, offset: usize, value: [u8; N]) -> Result<(), Error> {
+        let end = offset.checked_add(N).ok_or(Error::OutOfBounds)?;
+
+        for (src, dst) in value.into_iter().zip(self.try_iter_range_mut(offset..end)?) {
+            *dst = src;
+        }
+
+        Ok(())
+    }
+
+    /// u16 from the two logical bytes at `offset`.
+    ///
+    /// Offsets and the byte order of the value are both relative to the logical order.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error, typed::Endianness};
+    ///
+    /// let mut raw = [0x12u8, 0x34, 0x56];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    ///
+    /// assert_eq!(bytes.read_u16_at(0, Endianness::Big), Ok(0x5634));
+    /// assert_eq!(bytes.read_u16_at(0, Endianness::Little), Ok(0x3456));
+    /// assert_eq!(bytes.read_u16_at(2, Endianness::Big), Err(Error::OutOfBounds));
+    /// ```
+    pub fn read_u16_at(&self, offset: usize, endian: Endianness) -> Result<u16, Error> {
+        let raw = self.read_array_at(offset)?;
+        Ok(match endian { Endianness::Big => u16::from_be_bytes(raw), Endianness::Little => u16::from_le_bytes(raw) })
+    }
+
+    /// u32 from the four logical bytes at `offset`, see [`ByteString::read_u16_at`].
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, typed::Endianness};
+    ///
+    /// let mut raw = [0x00u8, 0x12, 0x34, 0x56, 0x78];
+    /// let bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.read_u32_at(1, Endianness::Big), Ok(0x12345678));
+    /// assert_eq!(bytes.read_u32_at(1, Endianness::Little), Ok(0x78563412));
+    /// assert!(bytes.read_u32_at(2, Endianness::Big).is_err());
+    /// ```
+    pub fn read_u32_at(&self, offset: usize, endian: Endianness) -> Result<u32, Error> {
+        let raw = self.read_array_at(offset)?;
+        Ok(match endian { Endianness::Big => u32::from_be_bytes(raw), Endianness::Little => u32::from_le_bytes(raw) })
+    }
+
+    /// u64 from the eight logical bytes at `offset`, see [`ByteString::read_u16_at`].
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, typed::Endianness};
+    ///
+    /// let mut raw = [0x01u8, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+    /// let bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.read_u64_at(0, Endianness::Big), Ok(0x0102030405060708));
+    /// assert_eq!(bytes.read_u64_at(0, Endianness::Little), Ok(0x0807060504030201));
+    /// ```
+    pub fn read_u64_at(&self, offset: usize, endian: Endianness) -> Result<u64, Error> {
+        let raw = self.read_array_at(offset)?;
+        Ok(match endian { Endianness::Big => u64::from_be_bytes(raw), Endianness::Little => u64::from_le_bytes(raw) })
+    }
+
+    /// Store `value` into the two logical bytes at `offset`, nothing is written if they do not fit.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, typed::Endianness};
+    ///
+    /// let mut raw = [0u8; 3];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    ///
+    /// bytes.write_u16_at(1, 0xABCD, Endianness::Big).unwrap();
+    /// assert!(bytes.write_u16_at(2, 0xFFFF, Endianness::Big).is_err());
+    /// assert_eq!(raw, [0xCD, 0xAB, 0x00]);
+    /// ```
+    pub fn write_u16_at(&mut self, offset: usize, value: u16, endian: Endianness) -> Result<(), Error> {
+        self.write_array_at(offset, match endian { Endianness::Big => value.to_be_bytes(), Endianness::Little => value.to_le_bytes() })
+    }
+
+    /// Store `value` into the four logical bytes at `offset`, see [`ByteString::write_u16_at`].
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, typed::Endianness};
+    ///
+    /// let mut raw = [0u8; 5];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.write_u32_at(1, 0x12345678, Endianness::Little).unwrap();
+    /// assert_eq!(raw, [0x00, 0x78, 0x56, 0x34, 0x12]);
+    /// ```
+    pub fn write_u32_at(&mut self, offset: usize, value: u32, endian: Endianness) -> Result<(), Error> {
+        self.write_array_at(offset, match endian { Endianness::Big => value.to_be_bytes(), Endianness::Little => value.to_le_bytes() })
+    }
+
+    /// Store `value` into the eight logical bytes at `offset`, see [`ByteString::write_u16_at`].
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, typed::Endianness};
+    ///
+    /// let mut raw = [0u8; 8];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.write_u64_at(0, 1, Endianness::Big).unwrap();
+    /// assert_eq!(raw, [0, 0, 0, 0, 0, 0, 0, 1]);
+    ///
+    /// // Nothing is written when the value does not fit
+    /// assert!(ByteString::new(&mut raw).write_u64_at(1, u64::MAX, Endianness::Big).is_err());
+    /// assert_eq!(raw, [0, 0, 0, 0, 0, 0, 0, 1]);
+    /// ```
+    pub fn write_u64_at(&mut self, offset: usize, value: u64, endian: Endianness) -> Result<(), Error> {
+        self.write_array_at(offset, match endian { Endianness::Big => value.to_be_bytes(), Endianness::Little => value.to_le_bytes() })
+    }
+
+    /// The whole value as a u64, fails if it has set bits above the low 64
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Overflow};
+    ///
+    /// // Leading zero bytes beyond the low 64 bits are fine
+    /// let mut raw = [0x00u8, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02];
+    /// assert_eq!(ByteString::new(&mut raw).to_u64(), Ok(0x0100_0000_0000_0002));
+    ///
+    /// raw[0] = 0x01;
+    /// assert_eq!(ByteString::new(&mut raw).to_u64(), Err(Overflow));
+    /// ```
+    pub fn to_u64(&self) -> Result<u64, Overflow> {
+        let len = self.byte_len();
+        if self.iter().take(len.saturating_sub(8)).any(|e| *e != 0) {
+            return Err(Overflow);
+        }
+
+        Ok(self.iter().skip(len.saturating_sub(8)).fold(0, |acc, e| acc << 8 | *e as u64))
+    }
+
+    /// Replace the whole value with `value`, failing without modification if it does not fit
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Overflow};
+    ///
+    /// let mut raw = [0xEEu8; 2];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.assign_from_u64(0x1_0000), Err(Overflow));
+    /// assert_eq!(bytes.assign_from_u64(0x0102), Ok(()));
+    /// assert_eq!(raw, [0x01, 0x02]);
+    /// ```
+    pub fn assign_from_u64(&mut self, value: u64) -> Result<(), Overflow> {
+        let len = self.byte_len();
+        if len < 8 && value >> (len * 8) != 0 {
+            return Err(Overflow);
+        }
+
+        for (ix, byte) in self.iter_mut().rev().enumerate() {
+            *byte = if ix < 8 { (value >> (ix * 8)) as u8 } else { 0 };
+        }
+
+        Ok(())
+    }
+
+    /// IEEE-754 single at logical byte `offset`, NaN payloads are preserved bit for bit.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, typed::Endianness};
+    ///
+    /// let mut raw = [0x3Fu8, 0x80, 0x00, 0x00];
+    /// let bytes = ByteString::new(&mut raw);
+    ///
+    /// assert_eq!(bytes.read_f32_at(0, Endianness::Big), Ok(1.0));
+    /// ```
+    pub fn read_f32_at(&self, offset: usize, endian: Endianness) -> Result<f32, Error> {
+        self.read_u32_at(offset, endian).map(f32::from_bits)
+    }
+
+    /// IEEE-754 double at logical byte `offset`, NaN payloads are preserved bit for bit.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, typed::Endianness};
+    ///
+    /// let mut raw = [0x7Fu8, 0xF0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01];
+    /// let bytes = ByteString::new(&mut raw);
+    ///
+    /// // A signalling NaN comes back with its payload intact
+    /// let value = bytes.read_f64_at(0, Endianness::Big).unwrap();
+    /// assert_eq!(value.to_bits(), 0x7FF0_0000_0000_0001);
+    /// ```
+    pub fn read_f64_at(&self, offset: usize, endian: Endianness) -> Result<f64, Error> {
+        self.read_u64_at(offset, endian).map(f64::from_bits)
+    }
+
+    /// Store the bits of `value` at logical byte `offset`.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, typed::Endianness};
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.write_f32_at(0, -2.0, Endianness::Little).unwrap();
+    /// assert_eq!(raw, [0x00, 0x00, 0x00, 0xC0]);
+    /// ```
+    pub fn write_f32_at(&mut self, offset: usize, value: f32, endian: Endianness) -> Result<(), Error> {
+        self.write_u32_at(offset, value.to_bits(), endian)
+    }
+
+    /// Store the bits of `value` at logical byte `offset`.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, typed::Endianness};
+    ///
+    /// let mut raw = [0u8; 8];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.write_f64_at(0, 1.0, Endianness::Big).unwrap();
+    /// assert_eq!(raw, [0x3F, 0xF0, 0, 0, 0, 0, 0, 0]);
+    /// ```
+    pub fn write_f64_at(&mut self, offset: usize, value: f64, endian: Endianness) -> Result<(), Error> {
+        self.write_u64_at(offset, value.to_bits(), endian)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::view;
+
+    const F32_BITS: [u32; 8] = [
+        0x3F80_0000,  // 1.0
+        0xC2F6_E979,  // -123.456
+        0x0000_0001,  // smallest subnormal
+        0x807F_FFFF,  // largest negative subnormal
+        0x7F80_0000,  // inf
+        0xFF80_0000,  // -inf
+        0x7FC0_1234,  // quiet NaN with payload
+        0xFF80_0001,  // signalling NaN
+    ];
+
+    const F64_BITS: [u64; 6] = [
+        0x3FF0_0000_0000_0000,
+        0x0000_0000_0000_0001,
+        0x7FF0_0000_0000_0000,
+        0xFFF0_0000_0000_0000,
+        0x7FF8_0000_DEAD_BEEF,
+        0xFFF0_0000_0000_0001,
+    ];
+
+    #[test]
+    fn test_float_round_trip_bit_exact() {
+        for reversed in [false, true] {
+            for endian in [Endianness::Big, Endianness::Little] {
+                let mut raw = [0u8; 13];
+                let mut bytes = view(&mut raw, reversed);
+
+                for bits in F32_BITS {
+                    bytes.write_f32_at(3, f32::from_bits(bits), endian).unwrap();
+                    assert_eq!(bytes.read_f32_at(3, endian).unwrap().to_bits(), bits);
+                }
+
+                for bits in F64_BITS {
+                    bytes.write_f64_at(5, f64::from_bits(bits), endian).unwrap();
+                    assert_eq!(bytes.read_f64_at(5, endian).unwrap().to_bits(), bits);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_float_layout() {
+        let mut raw = [0u8; 6];
+        let mut bytes = ByteString::new(&mut raw);
+        bytes.interpret_reverse_endian();
+
+        // Logical bytes 1..5 are physical bytes 1..5 read backwards
+        bytes.write_f32_at(1, 1.0, Endianness::Big).unwrap();
+        assert_eq!(raw, [0x00, 0x00, 0x00, 0x80, 0x3F, 0x00]);
+
+        let mut raw = [0x00u8, 0x00, 0x80, 0x3F];
+        let bytes = ByteString::new(&mut raw);
+        assert_eq!(bytes.read_f32_at(0, Endianness::Little), Ok(1.0));
+        assert_eq!(bytes.read_u16_at(2, Endianness::Big), Ok(0x803F));
+    }
+
+    #[test]
+    fn test_u64_conversions() {
+        let mut raw = [0x00u8, 0x00, 0x12, 0x34];
+        let mut bytes = ByteString::new(&mut raw);
+        bytes.interpret_reverse_endian();
+
+        assert_eq!(bytes.to_u64(), Ok(0x3412_0000));
+        assert_eq!(bytes.assign_from_u64(0x1_0000_0000), Err(Overflow));
+        bytes.assign_from_u64(0xABCD).unwrap();
+        assert_eq!(raw, [0xCD, 0xAB, 0x00, 0x00]);
+
+        let mut raw = [0x01u8, 0, 0, 0, 0, 0, 0, 0, 0];
+        let mut bytes = ByteString::new(&mut raw);
+        assert_eq!(bytes.to_u64(), Err(Overflow));
+
+        bytes.assign_from_u64(u64::MAX).unwrap();
+        assert_eq!(bytes.to_u64(), Ok(u64::MAX));
+        assert_eq!(raw[0], 0);
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let mut raw = [0u8; 8];
+        let mut bytes = ByteString::new(&mut raw);
+
+        assert_eq!(bytes.read_f32_at(5, Endianness::Big), Err(Error::OutOfBounds));
+        assert_eq!(bytes.read_f64_at(1, Endianness::Big), Err(Error::OutOfBounds));
+        assert_eq!(bytes.read_f32_at(usize::MAX, Endianness::Big), Err(Error::OutOfBounds));
+        assert_eq!(bytes.write_f64_at(1, 0.0, Endianness::Little), Err(Error::OutOfBounds));
+        assert!(bytes.read_f64_at(0, Endianness::Little).is_ok());
+    }
+}
@@ -1,26 +1,90 @@
 
+//! Helpers shared across modules.
+//!
+//! Pure helpers are `const fn` so [`crate::table`] can build on them, including `swap_bytes` and
+//! `set_bytes` which take `&mut` and need Rust 1.83. The crate's minimum supported version is the
+//! `rust-version` of its manifest.
+
+use std::sync::atomic::{compiler_fence, Ordering};
+
+#[cfg(not(feature = "forbid-unsafe"))]
+use std::ptr;
+
+#[cfg(feature = "forbid-unsafe")]
+use std::hint;
+
+/// Bit order of every byte reversed, looked up rather than computed per byte
+pub const BIT_REVERSE: [u8; 256] = crate::table::build_bit_reverse_table();
+
 /// From [Bit Twiddling Hacks](https://graphics.stanford.edu/~seander/bithacks.html#ReverseByteWith64BitsDiv)
-pub fn reverse_bit_endianness(byte: u8) -> u8 {
+pub const fn reverse_bit_endianness(byte: u8) -> u8 {
     let mut as_u64 = byte as u64;
 
-    as_u64 = (as_u64 * 0x0202020202u64 & 0x010884422010u64) % 1023;
+    as_u64 = ((as_u64 * 0x0202020202u64) & 0x010884422010u64) % 1023;
 
     as_u64 as u8
 }
 
 // From [Bit Twiddling Hacks](https://graphics.stanford.edu/~seander/bithacks.html#SwappingValuesXOR)
-pub fn swap_bytes(a: &mut u8, b: &mut u8) {
+#[allow(clippy::manual_swap)]
+pub const fn swap_bytes(a: &mut u8, b: &mut u8) {
     *a ^= *b;
     *b ^= *a;
     *a ^= *b;
 }
 
-pub fn set_bytes(bytes: &mut [u8], value: u8) {
-    for byte in bytes {
-        *byte = value;
+pub const fn set_bytes(bytes: &mut [u8], value: u8) {
+    let mut ix = 0;
+    while ix < bytes.len() {
+        bytes[ix] = value;
+        ix += 1;
     }
 }
 
+/// Zero `bytes` holding secrets, through volatile writes the optimizer cannot elide.
+/// With `forbid-unsafe` this is best-effort, see below.
+pub fn wipe(bytes: &mut [u8]) {
+    #[cfg(not(feature = "forbid-unsafe"))]
+    for byte in bytes.iter_mut() {
+        /*
+            This is ok, the pointer comes from a live exclusive reference
+            to an initialized byte
+        */
+        unsafe { ptr::write_volatile(byte, 0) };
+    }
+
+    // Without volatile writes, handing the zeroed bytes to black_box is only a hint against
+    // eliding the stores, best-effort like the rest of black_box, not a guarantee
+    #[cfg(feature = "forbid-unsafe")]
+    {
+        bytes.fill(0);
+        hint::black_box(&*bytes);
+    }
+
+    compiler_fence(Ordering::SeqCst);
+}
+
+/// Product in GF(2^8) reduced by `x^8 + poly`, Russian peasant multiplication
+pub const fn gf256_mul(mut a: u8, mut b: u8, poly: u8) -> u8 {
+    let mut product = 0;
+
+    while b != 0 {
+        if b & 1 == 1 {
+            product ^= a;
+        }
+
+        // a *= x, folding the x^8 term back in through the polynomial
+        let carry = a & 0x80 != 0;
+        a <<= 1;
+        if carry {
+            a ^= poly;
+        }
+        b >>= 1;
+    }
+
+    product
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -44,6 +108,14 @@ mod tests {
         assert_eq!(reverse_bit_endianness(byte), expected);
     }
 
+    #[test]
+    fn test_bit_reverse_table_matches_computation() {
+        for byte in 0..=255u8 {
+            assert_eq!(BIT_REVERSE[byte as usize], reverse_bit_endianness(byte));
+            assert_eq!(BIT_REVERSE[byte as usize], byte.reverse_bits());
+        }
+    }
+
     #[test]
     fn test_swap_bytes() {
 
@@ -56,4 +128,34 @@ mod tests {
         assert_eq!(b, 1);
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn test_mutating_helpers_in_const() {
+        const FILLED: [u8; 3] = {
+            let mut bytes = [0u8; 3];
+            set_bytes(&mut bytes, 0xAB);
+            bytes[0] = 0x01;
+            let (a, b) = bytes.split_at_mut(1);
+            swap_bytes(&mut a[0], &mut b[1]);
+            bytes
+        };
+
+        assert_eq!(FILLED, [0xAB, 0xAB, 0x01]);
+    }
+
+    #[test]
+    fn test_gf256_mul_aes() {
+        // FIPS-197 section 4.2
+        assert_eq!(gf256_mul(0x57, 0x83, 0x1B), 0xC1);
+        assert_eq!(gf256_mul(0x57, 0x13, 0x1B), 0xFE);
+        assert_eq!(gf256_mul(0x53, 0xCA, 0x1B), 0x01);
+
+        assert_eq!(gf256_mul(0x80, 0x02, 0x1B), 0x1B);
+        for a in 0..=255 {
+            assert_eq!(gf256_mul(a, 0x00, 0x1B), 0x00);
+            assert_eq!(gf256_mul(0x00, a, 0x1B), 0x00);
+            assert_eq!(gf256_mul(a, 0x01, 0x1B), a);
+            assert_eq!(gf256_mul(a, 0x03, 0x1B), gf256_mul(0x03, a, 0x1B));
+        }
+    }
+
+}
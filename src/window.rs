@@ -0,0 +1,115 @@
+//! Transformations between disjoint windows of one buffer
+
+use std::ops::Range;
+
+use crate::{
+    bytes::ByteString,
+    error::Error
+};
+
+impl<'a> ByteString<'a> {
+
+    /// Run `f` with the logical window `src` as input and the disjoint window `dst` as output.
+    ///
+    /// The windows must have equal length and may not overlap, an overlap is reported with its
+    /// logical extent. The closure receives physical slices, so with the flag set both windows are
+    /// reversed alike and position `i` of the input still pairs with position `i` of the output.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error};
+    ///
+    /// let mut raw = [1u8, 2, 3, 0, 0, 0];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// bytes.transform_within(0..3, 3..6, |src, dst| {
+    ///     for (d, s) in dst.iter_mut().zip(src) {
+    ///         *d = s * 10;
+    ///     }
+    /// }).unwrap();
+    /// assert_eq!(bytes.bytes(), [1, 2, 3, 10, 20, 30]);
+    ///
+    /// assert_eq!(bytes.transform_within(0..3, 2..5, |_, _| ()), Err(Error::Overlap { start: 2, end: 3 }));
+    /// ```
+    pub fn transform_within<F>(&mut self, src: Range<usize>, dst: Range<usize>, f: F) -> Result<(), Error>
+    where F: Fn(&[u8], &mut [u8]) {
+
+        let src_physical = self.physical_range(src.clone())?;
+        let dst_physical = self.physical_range(dst.clone())?;
+
+        if src.len() != dst.len() {
+            return Err(Error::LengthMismatch);
+        }
+
+        let start = src.start.max(dst.start);
+        let end = src.end.min(dst.end);
+        if start < end {
+            return Err(Error::Overlap { start, end });
+        }
+
+        // Split at the boundary between the two physical windows
+        if src_physical.start < dst_physical.start {
+            let (low, high) = self.bytes.split_at_mut(dst_physical.start);
+            f(&low[src_physical], &mut high[..dst_physical.len()]);
+        }
+        else {
+            let (low, high) = self.bytes.split_at_mut(src_physical.start);
+            f(&high[..src_physical.len()], &mut low[dst_physical]);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn copy_invert(src: &[u8], dst: &mut [u8]) {
+        for (d, s) in dst.iter_mut().zip(src) {
+            *d = !*s;
+        }
+    }
+
+    #[test]
+    fn test_adjacent_windows() {
+        let mut raw = [0x01u8, 0x02, 0x03, 0x00, 0x00, 0x00];
+        let mut bytes = ByteString::new(&mut raw);
+
+        bytes.transform_within(0..3, 3..6, copy_invert).unwrap();
+        assert_eq!(raw, [0x01, 0x02, 0x03, 0xFE, 0xFD, 0xFC]);
+
+        let mut bytes = ByteString::new(&mut raw);
+        bytes.transform_within(3..6, 0..3, |src, dst| dst.copy_from_slice(src)).unwrap();
+        assert_eq!(raw, [0xFE, 0xFD, 0xFC, 0xFE, 0xFD, 0xFC]);
+    }
+
+    #[test]
+    fn test_reversed_mapping() {
+        let mut raw = [0xA0u8, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5, 0xA6];
+        let mut bytes = ByteString::new(&mut raw);
+        bytes.interpret_reverse_endian();
+
+        // Logical 0..2 is physical 5..7, logical 4..6 is physical 1..3
+        bytes.transform_within(0..2, 4..6, copy_invert).unwrap();
+        assert!(bytes.iter_range(4..6).eq([!0xA6u8, !0xA5].iter()));
+        assert_eq!(raw, [0xA0, !0xA5, !0xA6, 0xA3, 0xA4, 0xA5, 0xA6]);
+    }
+
+    #[test]
+    fn test_rejections() {
+        let mut raw = [0u8; 8];
+        let mut bytes = ByteString::new(&mut raw);
+
+        assert_eq!(bytes.transform_within(0..4, 2..6, copy_invert), Err(Error::Overlap { start: 2, end: 4 }));
+        assert_eq!(bytes.transform_within(5..7, 1..6, copy_invert), Err(Error::LengthMismatch));
+        assert_eq!(bytes.transform_within(6..9, 0..3, copy_invert), Err(Error::OutOfBounds));
+
+        bytes.interpret_reverse_endian();
+        assert_eq!(bytes.transform_within(3..5, 4..6, copy_invert), Err(Error::Overlap { start: 4, end: 5 }));
+        assert_eq!(raw, [0; 8]);
+
+        // Empty windows never overlap
+        assert_eq!(ByteString::new(&mut raw).transform_within(2..2, 2..2, copy_invert), Ok(()));
+    }
+}
@@ -0,0 +1,130 @@
+//! Every word at a time path against its byte by byte reference.
+//!
+//! Buffers start at each offset 0..8 from an 8 byte boundary, with every length 0..40 and both
+//! interpretations, so each path sees every split into head, words and tail. A new path built on
+//! [`ByteString::words_and_tail`] is registered in [`PATHS`] with a reference over the logical
+//! bytes.
+
+use crate::{
+    bytes::ByteString,
+    test_util::{view, XorShift}
+};
+
+const ALIGNMENTS: usize = 8;
+const MAX_LEN: usize = 40;
+
+// Optimized path and its scalar reference over the logical bytes
+type Path = (&'static str, fn(&ByteString) -> usize, fn(&[u8]) -> usize);
+
+const PATHS: &[Path] = &[
+    ("is_zero", |bytes| bytes.is_zero() as usize, |logical| logical.iter().all(|e| *e == 0) as usize),
+    ("count_ones", |bytes| bytes.count_ones(), |logical| logical.iter().map(|e| e.count_ones() as usize).sum()),
+    ("leading_zero_len", |bytes| bytes.leading_zero_len(), |logical| logical.iter().take_while(|e| **e == 0).count()),
+    ("trailing_zero_len", |bytes| bytes.trailing_zero_len(), |logical| logical.iter().rev().take_while(|e| **e == 0).count()),
+];
+
+// Fill patterns, zero heavy ones reach the word branches of the zero scans
+fn fill(rng: &mut XorShift, logical: &mut [u8], pattern: usize) {
+    logical.fill(0);
+    match pattern {
+        0 => rng.fill(logical),
+        1 => (),
+        2 if !logical.is_empty() => {
+            let ix = rng.below(logical.len());
+            logical[ix] = 1 << rng.below(8);
+        },
+        _ => for byte in logical.iter_mut() {
+            if rng.below(8) == 0 { *byte = rng.next_u8(); }
+        },
+    }
+}
+
+// Run `f` on every alignment, length and interpretation with each fill pattern
+fn for_each_layout<F: FnMut(&ByteString, &[u8], &str)>(seed: u64, mut f: F) {
+    let mut rng = XorShift::new(seed);
+    let mut storage = [0u8; MAX_LEN + 2 * ALIGNMENTS];
+    let base = storage.as_ptr().align_offset(ALIGNMENTS);
+
+    for align in 0..ALIGNMENTS {
+        for len in 0..MAX_LEN {
+            for reversed in [false, true] {
+                for pattern in 0..4 {
+                    let mut logical = vec![0u8; len];
+                    fill(&mut rng, &mut logical, pattern);
+
+                    let raw = &mut storage[base + align..base + align + len];
+                    raw.copy_from_slice(&logical);
+                    if reversed { raw.reverse(); }
+
+                    let bytes = view(raw, reversed);
+
+                    let case = format!("align={} len={} reversed={} pattern={}", align, len, reversed, pattern);
+                    f(&bytes, &logical, &case);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn split_covers_logical_bytes_in_order() {
+    for_each_layout(0x267, |bytes, logical, case| {
+        let split = bytes.words_and_tail();
+        assert!(split.head.iter().len() < 8 && split.tail.iter().len() < 8, "{}", case);
+
+        let words: Vec<u8> = split.words.clone().flat_map(|e| e.to_be_bytes()).collect();
+        let joined: Vec<u8> = split.head.iter().chain(words.iter().copied()).chain(split.tail.iter()).collect();
+        assert_eq!(joined, logical, "{}", case);
+
+        // Back to front walks the same bytes
+        let backwards: Vec<u8> = split.tail.iter().rev()
+            .chain(split.words.rev().flat_map(|e| e.to_le_bytes()))
+            .chain(split.head.iter().rev())
+            .collect();
+        assert!(backwards.iter().eq(logical.iter().rev()), "{}", case);
+
+        // The words sit on physical 8 byte boundaries
+        if !words.is_empty() {
+            let start = if bytes.interpret_reverse_endian { split.tail.physical().len() } else { split.head.physical().len() };
+            assert_eq!(bytes.bytes()[start..].as_ptr().align_offset(8), 0, "{}", case);
+        }
+    });
+}
+
+#[test]
+fn paths_match_scalar_references() {
+    for_each_layout(0x268, |bytes, logical, case| {
+        for (name, path, reference) in PATHS {
+            assert_eq!(path(bytes), reference(logical), "{} {}", name, case);
+        }
+    });
+}
+
+// The shifts write in place, so they run on copies against the byte at a time shifts
+#[test]
+fn shift_words_match_byte_paths() {
+    let mut rng = XorShift::new(0x278);
+
+    for _ in 0..400 {
+        let len = if rng.below(8) == 0 { 4096 } else { 1 + rng.below(MAX_LEN) };
+        let n = match rng.below(3) {
+            0 => 8 * rng.below(len),
+            1 => rng.below(24.min(8 * len)),
+            _ => rng.below(8 * len),
+        };
+
+        let mut logical = vec![0u8; len];
+        let pattern = rng.below(4);
+        fill(&mut rng, &mut logical, pattern);
+
+        let (mut fast, mut slow) = (logical.clone(), logical.clone());
+        ByteString::new(&mut fast).shl_words(n);
+        ByteString::new(&mut slow).shl_bits(n);
+        assert_eq!(fast, slow, "shl len={} n={}", len, n);
+
+        let (mut fast, mut slow) = (logical.clone(), logical);
+        ByteString::new(&mut fast).shr_words(n);
+        ByteString::new(&mut slow).shr_bits(n);
+        assert_eq!(fast, slow, "shr len={} n={}", len, n);
+    }
+}
@@ -0,0 +1,204 @@
+//! Word at a time access with the unaligned head and tail handled once.
+//!
+//! A fast path splits the buffer with [`ByteString::words_and_tail`] into a logical head, aligned
+//! u64 words and a logical tail. Each part already walks in logical order, so a path only has to
+//! get its word arithmetic right. Every path is checked against a byte by byte reference in
+//! `word_paths`, a new one must be registered there.
+//!
+//! The shifts are the exception: they only run without the reversed interpretation, where the
+//! value is big-endian over the physical bytes, and load their words unaligned from any offset.
+
+use std::slice::ChunksExact;
+
+use crate::bytes::ByteString;
+
+const WORD: usize = 8;
+
+/// Physical run of bytes read in logical order, backwards under a reversed interpretation
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Run<'s> {
+    bytes: &'s [u8],
+    reversed: bool,
+}
+
+impl<'s> Run<'s> {
+
+    /// The bytes in physical order, for order independent paths
+    pub(crate) fn physical(&self) -> &'s [u8] {
+        self.bytes
+    }
+
+    /// The bytes in logical order
+    pub(crate) fn iter(&self) -> impl DoubleEndedIterator<Item = u8> + ExactSizeIterator + 's {
+        let (bytes, reversed) = (self.bytes, self.reversed);
+        (0..bytes.len()).map(move |ix| bytes[if reversed { bytes.len() - 1 - ix } else { ix }])
+    }
+}
+
+/// Aligned words in logical order, each the big-endian value of its eight logical bytes
+#[derive(Debug, Clone)]
+pub(crate) struct Words<'s> {
+    chunks: ChunksExact<'s, u8>,
+    reversed: bool,
+}
+
+impl<'s> Words<'s> {
+    // A reversed chunk holds its logical bytes backwards, reading it little-endian undoes that
+    fn decode(&self, chunk: &[u8]) -> u64 {
+        let chunk: [u8; WORD] = chunk.try_into().unwrap();
+        if self.reversed { u64::from_le_bytes(chunk) } else { u64::from_be_bytes(chunk) }
+    }
+}
+
+impl<'s> Iterator for Words<'s> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let chunk = if self.reversed { self.chunks.next_back() } else { self.chunks.next() };
+        chunk.map(|e| self.decode(e))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+impl<'s> DoubleEndedIterator for Words<'s> {
+    fn next_back(&mut self) -> Option<u64> {
+        let chunk = if self.reversed { self.chunks.next() } else { self.chunks.next_back() };
+        chunk.map(|e| self.decode(e))
+    }
+}
+
+impl<'s> ExactSizeIterator for Words<'s> {}
+
+/// A buffer split for word at a time processing, see [`ByteString::words_and_tail`]
+#[derive(Debug, Clone)]
+pub(crate) struct WordSplit<'s> {
+    /// Logical bytes before the first word, fewer than eight
+    pub(crate) head: Run<'s>,
+    /// Words in logical order
+    pub(crate) words: Words<'s>,
+    /// Logical bytes after the last word
+    pub(crate) tail: Run<'s>,
+}
+
+impl<'a> ByteString<'a> {
+
+    /// Split into logical head bytes, 8 byte aligned words and logical tail bytes.
+    ///
+    /// Alignment is a physical property, so with the flag set the physical suffix becomes the
+    /// logical head and the words are visited from the physical end.
+    pub(crate) fn words_and_tail(&self) -> WordSplit<'_> {
+        let len = self.bytes.len();
+        let skip = self.bytes.as_ptr().align_offset(WORD).min(len);
+        let (prefix, rest) = self.bytes.split_at(skip);
+        let (middle, suffix) = rest.split_at((len - skip) / WORD * WORD);
+
+        let reversed = self.interpret_reverse_endian;
+        let (head, tail) = if reversed { (suffix, prefix) } else { (prefix, suffix) };
+
+        WordSplit {
+            head: Run { bytes: head, reversed },
+            words: Words { chunks: middle.chunks_exact(WORD), reversed },
+            tail: Run { bytes: tail, reversed },
+        }
+    }
+
+    /// Number of leading logical zero bytes, the zero bytes at the top of the value
+    pub(crate) fn leading_zero_len(&self) -> usize {
+        let split = self.words_and_tail();
+        let mut count = 0;
+
+        for byte in split.head.iter() {
+            if byte != 0 { return count; }
+            count += 1;
+        }
+        for word in split.words {
+            if word != 0 { return count + word.leading_zeros() as usize / 8; }
+            count += WORD;
+        }
+
+        count + split.tail.iter().take_while(|e| *e == 0).count()
+    }
+
+    /// Number of trailing logical zero bytes, the zero bytes at the bottom of the value
+    pub(crate) fn trailing_zero_len(&self) -> usize {
+        let split = self.words_and_tail();
+        let mut count = 0;
+
+        for byte in split.tail.iter().rev() {
+            if byte != 0 { return count; }
+            count += 1;
+        }
+        for word in split.words.rev() {
+            if word != 0 { return count + word.trailing_zeros() as usize / 8; }
+            count += WORD;
+        }
+
+        count + split.head.iter().rev().take_while(|e| *e == 0).count()
+    }
+
+    /// Left shift by `n` bits below the bit length, moving eight bytes per step
+    pub(crate) fn shl_words(&mut self, n: usize) {
+        debug_assert!(!self.interpret_reverse_endian && n < self.bit_len());
+
+        let bytes = &mut *self.bytes;
+        let (shift, bits) = (n / 8, n % 8);
+        let len = bytes.len();
+
+        // Whole bytes are a rotation of the zeroed top bytes to the end
+        if bits == 0 {
+            bytes[..shift].fill(0);
+            bytes.rotate_left(shift);
+            return;
+        }
+
+        // Sources lie at or after their destination, walking forward reads each before it is overwritten
+        let mut ix = 0;
+        while ix + shift + WORD < len {
+            let src = ix + shift;
+            let word = u64::from_be_bytes(bytes[src..src + WORD].try_into().unwrap());
+            let value = word << bits | (bytes[src + WORD] >> (8 - bits)) as u64;
+            bytes[ix..ix + WORD].copy_from_slice(&value.to_be_bytes());
+            ix += WORD;
+        }
+
+        for ix in ix..len {
+            let high = bytes.get(ix + shift).copied().unwrap_or(0);
+            let low = bytes.get(ix + shift + 1).copied().unwrap_or(0);
+            bytes[ix] = high << bits | low >> (8 - bits);
+        }
+    }
+
+    /// Right shift by `n` bits below the bit length, moving eight bytes per step
+    pub(crate) fn shr_words(&mut self, n: usize) {
+        debug_assert!(!self.interpret_reverse_endian && n < self.bit_len());
+
+        let bytes = &mut *self.bytes;
+        let (shift, bits) = (n / 8, n % 8);
+        let len = bytes.len();
+
+        if bits == 0 {
+            bytes.copy_within(..len - shift, shift);
+            bytes[..shift].fill(0);
+            return;
+        }
+
+        // Sources lie at or before their destination, so words are filled from the end
+        let mut end = len;
+        while end > WORD + shift {
+            let src = end - WORD - shift;
+            let word = u64::from_be_bytes(bytes[src..src + WORD].try_into().unwrap());
+            let value = word >> bits | (bytes[src - 1] as u64) << (64 - bits);
+            bytes[end - WORD..end].copy_from_slice(&value.to_be_bytes());
+            end -= WORD;
+        }
+
+        for ix in (0..end).rev() {
+            let high = if ix >= shift { bytes[ix - shift] } else { 0 };
+            let low = if ix > shift { bytes[ix - shift - 1] } else { 0 };
+            bytes[ix] = high >> bits | low << (8 - bits);
+        }
+    }
+}
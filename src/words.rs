@@ -0,0 +1,287 @@
+//! ByteString views over slices of u64 words
+
+use std::ops::{Deref, DerefMut};
+
+#[cfg(not(feature = "forbid-unsafe"))]
+use std::slice;
+
+use crate::{
+    bytes::ByteString,
+    error::Error,
+    typed::Endianness
+};
+
+/// ByteString over a slice of `u64` bitmask words, see [`ByteString::with_words_mut`].
+///
+/// Once the closure returns, or unwinds, the words are back in native order, so direct word access
+/// before and after sees the same bits.
+///
+/// ```
+/// use rebite::{bytes::ByteString, typed::Endianness};
+///
+/// let mut words = [0u64, 0];
+/// ByteString::with_words_mut(&mut words, Endianness::Big, |bytes| {
+///     bytes.set_bit(0, true);
+///     bytes.set_bit(64, true);
+/// });
+///
+/// // With the view gone the words read natively again
+/// assert_eq!(words, [1, 1]);
+/// ```
+pub struct WordBytes<'a> {
+    bytes: ByteString<'a>,
+    endian: Endianness
+}
+
+impl<'a> ByteString<'a> {
+
+    /// Run `f` on bitmask words viewed as one ByteString value, returns its result.
+    ///
+    /// With [`Endianness::Little`] word 0 holds the least significant bits, bit `i` of word `j`
+    /// is value bit `64 * j + i`. With [`Endianness::Big`] word 0 holds the most significant bits,
+    /// bit `i` of word `j` is value bit `64 * (n - 1 - j) + i`. Use [`WordBytes::word_for_bit`] for the inverse.
+    ///
+    /// The view only lives inside `f`. It reborrows the words in place, stored in `endian` order
+    /// while `f` runs and put back in native order after it from the word slice itself, whatever
+    /// `f` does to the view and also when it unwinds. With `forbid-unsafe` the view is a copy
+    /// instead, written back when `f` returns, so the writes of an `f` that unwinds are lost.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, typed::Endianness};
+    ///
+    /// let mut words = [0u64; 2];
+    /// let bit_len = ByteString::with_words_mut(&mut words, Endianness::Little, |bytes| {
+    ///     bytes.set_bit(65, true);
+    ///     bytes.bit_len()
+    /// });
+    ///
+    /// assert_eq!((words, bit_len), ([0, 2], 128));
+    /// ```
+    pub fn with_words_mut<R>(words: &mut [u64], endian: Endianness, f: impl FnOnce(&mut WordBytes) -> R) -> R {
+        #[cfg(not(feature = "forbid-unsafe"))]
+        {
+            let restore = NativeOrder::swap(words, endian);
+
+            /*
+                This is ok, u8 has no alignment requirement and every bit pattern is a valid u8,
+                the byte slice covers exactly the memory of the words and reborrows them until
+                the view is dropped, before `restore` touches the words again
+            */
+            let raw = unsafe { slice::from_raw_parts_mut(restore.words.as_mut_ptr() as *mut u8, restore.words.len() * 8) };
+
+            view_words(raw, endian, f)
+        }
+
+        // Without reborrowing the words, the same bytes are copied out and back
+        #[cfg(feature = "forbid-unsafe")]
+        {
+            let mut raw: Vec<u8> = words.iter().flat_map(|word| match endian {
+                Endianness::Little => word.to_le_bytes(),
+                Endianness::Big => word.to_be_bytes()
+            }).collect();
+
+            let result = view_words(&mut raw, endian, f);
+
+            for (word, chunk) in words.iter_mut().zip(raw.chunks_exact(8)) {
+                let chunk = chunk.try_into().unwrap();
+                *word = match endian {
+                    Endianness::Little => u64::from_le_bytes(chunk),
+                    Endianness::Big => u64::from_be_bytes(chunk)
+                };
+            }
+
+            result
+        }
+    }
+}
+
+// Run `f` on the word bytes stored in `endian` order
+fn view_words<R>(raw: &mut [u8], endian: Endianness, f: impl FnOnce(&mut WordBytes) -> R) -> R {
+    let mut bytes = ByteString::new(raw);
+    if endian == Endianness::Little {
+        bytes.interpret_reverse_endian();
+    }
+
+    f(&mut WordBytes { bytes, endian })
+}
+
+impl<'a> WordBytes<'a> {
+
+    /// Word index and bit within that word holding value bit `index`, panics if out of range.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, typed::Endianness};
+    ///
+    /// let mut words = [0u64; 2];
+    /// ByteString::with_words_mut(&mut words, Endianness::Big, |bytes| {
+    ///     // Word 0 holds the most significant bits
+    ///     assert_eq!(bytes.word_for_bit(65), (0, 1));
+    ///     assert_eq!(bytes.word_for_bit(3), (1, 3));
+    /// });
+    /// ```
+    #[track_caller]
+    pub fn word_for_bit(&self, index: usize) -> (usize, u32) {
+        self.try_word_for_bit(index).expect("bit index out of range")
+    }
+
+    /// Non-panicking form of [`WordBytes::word_for_bit`].
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error, typed::Endianness};
+    ///
+    /// let mut words = [0u64; 1];
+    /// ByteString::with_words_mut(&mut words, Endianness::Little, |bytes| {
+    ///     assert_eq!(bytes.try_word_for_bit(63), Ok((0, 63)));
+    ///     assert_eq!(bytes.try_word_for_bit(64), Err(Error::OutOfBounds));
+    /// });
+    /// ```
+    pub fn try_word_for_bit(&self, index: usize) -> Result<(usize, u32), Error> {
+        if index >= self.bytes.bit_len() {
+            return Err(Error::OutOfBounds);
+        }
+
+        let words = self.bytes.byte_len() / 8;
+        let word = index / 64;
+
+        Ok(match self.endian {
+            Endianness::Little => (word, (index % 64) as u32),
+            Endianness::Big => (words - 1 - word, (index % 64) as u32)
+        })
+    }
+}
+
+// Words stored in `endian` order for the lifetime of a view, flipped back to native order on drop
+#[cfg(not(feature = "forbid-unsafe"))]
+struct NativeOrder<'w> {
+    words: &'w mut [u64],
+    endian: Endianness
+}
+
+#[cfg(not(feature = "forbid-unsafe"))]
+impl<'w> NativeOrder<'w> {
+    fn swap(words: &'w mut [u64], endian: Endianness) -> Self {
+        let mut this = NativeOrder { words, endian };
+        this.flip();
+        this
+    }
+
+    // Flip each word between native order and `endian` order, a no-op when they agree
+    fn flip(&mut self) {
+        let native = if cfg!(target_endian = "little") { Endianness::Little } else { Endianness::Big };
+
+        if native != self.endian {
+            self.words.iter_mut().for_each(|word| *word = word.swap_bytes());
+        }
+    }
+}
+
+#[cfg(not(feature = "forbid-unsafe"))]
+impl<'w> Drop for NativeOrder<'w> {
+    fn drop(&mut self) {
+        self.flip();
+    }
+}
+
+impl<'a> Deref for WordBytes<'a> {
+    type Target = ByteString<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.bytes
+    }
+}
+
+impl<'a> DerefMut for WordBytes<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::XorShift;
+
+    #[test]
+    fn test_bits_agree_with_words() {
+        let mut rng = XorShift::new(0x234);
+
+        for endian in [Endianness::Little, Endianness::Big] {
+            for _ in 0..50 {
+                let count = 1 + rng.below(4);
+                let mut words: Vec<u64> = (0..count).map(|_| rng.next_u64()).collect();
+                let original = words.clone();
+
+                ByteString::with_words_mut(&mut words, endian, |view| {
+                    for index in 0..view.bit_len() {
+                        let (word, bit) = view.word_for_bit(index);
+                        assert_eq!(view.bit(index), original[word] >> bit & 1 == 1);
+                    }
+                });
+
+                assert_eq!(words, original);
+            }
+        }
+    }
+
+    #[test]
+    fn test_set_bit_visible_in_words() {
+        for endian in [Endianness::Little, Endianness::Big] {
+            let mut words = [0u64; 3];
+            let positions = [0, 7, 8, 63, 64, 100, 127, 128, 191];
+
+            let expected = ByteString::with_words_mut(&mut words, endian, |view| {
+                for position in positions {
+                    view.set_bit(position, true);
+                }
+                positions.map(|e| view.word_for_bit(e))
+            });
+
+            let mut direct = [0u64; 3];
+            for (word, bit) in expected {
+                direct[word] |= 1 << bit;
+            }
+            assert_eq!(words, direct);
+        }
+    }
+
+    #[test]
+    fn test_word_for_bit_mapping() {
+        let mut words = [0u64; 2];
+
+        ByteString::with_words_mut(&mut words, Endianness::Little, |view| {
+            assert_eq!(view.word_for_bit(0), (0, 0));
+            assert_eq!(view.word_for_bit(70), (1, 6));
+        });
+
+        ByteString::with_words_mut(&mut words, Endianness::Big, |view| {
+            assert_eq!(view.word_for_bit(0), (1, 0));
+            assert_eq!(view.word_for_bit(127), (0, 63));
+        });
+    }
+
+    #[test]
+    fn test_native_order_restored_whatever_the_closure_does() {
+        for endian in [Endianness::Little, Endianness::Big] {
+            let mut words = [0x0102_0304_0506_0708u64, 0x1112_1314_1516_1718];
+
+            // Swapping an unrelated view in leaves nothing for the restore to miss
+            ByteString::with_words_mut(&mut words, endian, |view| {
+                view.set_bit(0, true);
+                let mut other = ByteString::new(Box::leak(Box::new([0u8; 16])));
+                std::mem::swap(&mut **view, &mut other);
+            });
+            assert_eq!(words.iter().map(|e| e & 1).sum::<u64>(), 1);
+            assert_eq!(words.map(|e| e >> 8), [0x0001_0203_0405_0607, 0x0011_1213_1415_1617]);
+
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                ByteString::with_words_mut(&mut words, endian, |view| {
+                    view.set_bit(1, true);
+                    panic!("inside the view");
+                })
+            }));
+            assert!(result.is_err());
+            assert_eq!(words.map(|e| e >> 8), [0x0001_0203_0405_0607, 0x0011_1213_1415_1617]);
+        }
+    }
+}
@@ -0,0 +1,733 @@
+//! Cursors writing sequentially into a ByteString
+
+use crate::{
+    bit_order::BitOrder,
+    bytes::ByteString,
+    error::{Error, OutOfSpace, WriteError, WriteErrorKind},
+    typed::Endianness
+};
+
+/// Sequential write cursor over the logical bytes of a ByteString.
+///
+/// ```
+/// use rebite::{bytes::ByteString, writer::ByteStringWriter};
+///
+/// let mut raw = [0u8; 4];
+/// let mut bytes = ByteString::new(&mut raw);
+/// let mut writer = ByteStringWriter::new(&mut bytes);
+///
+/// writer.write_u8(0x01).unwrap();
+/// writer.write_slice(&[0x02, 0x03]).unwrap();
+/// assert_eq!(writer.remaining(), 1);
+/// assert_eq!(raw, [0x01, 0x02, 0x03, 0x00]);
+/// ```
+#[derive(Debug)]
+pub struct ByteStringWriter<'w, 'a> {
+    bytes: &'w mut ByteString<'a>,
+    position: usize,
+}
+
+impl<'w, 'a> ByteStringWriter<'w, 'a> {
+
+    /// Cursor at logical offset 0.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, writer::ByteStringWriter};
+    ///
+    /// let mut raw = [0u8; 2];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// bytes.interpret_reverse_endian();
+    ///
+    /// // The first logical byte is the physical last one
+    /// ByteStringWriter::new(&mut bytes).write_u8(0xAA).unwrap();
+    /// assert_eq!(raw, [0x00, 0xAA]);
+    /// ```
+    pub fn new(bytes: &'w mut ByteString<'a>) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    /// Logical offset of the next byte to be written.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, writer::ByteStringWriter};
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// let mut writer = ByteStringWriter::new(&mut bytes);
+    ///
+    /// writer.write_slice(&[1, 2, 3]).unwrap();
+    /// assert_eq!(writer.position(), 3);
+    /// ```
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Bytes left before the end of the buffer.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::OutOfSpace, writer::ByteStringWriter};
+    ///
+    /// let mut raw = [0u8; 3];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// let mut writer = ByteStringWriter::new(&mut bytes);
+    ///
+    /// writer.write_slice(&[0x01, 0x02]).unwrap();
+    /// assert_eq!(writer.remaining(), 1);
+    ///
+    /// // A write that does not fit is rejected whole
+    /// assert_eq!(writer.write_slice(&[0x03, 0x04]), Err(OutOfSpace { needed: 2, remaining: 1 }));
+    /// assert_eq!(writer.position(), 2);
+    /// ```
+    pub fn remaining(&self) -> usize {
+        self.bytes.byte_len() - self.position
+    }
+
+    /// Bits left before the end of the buffer.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, writer::ByteStringWriter};
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// let mut writer = ByteStringWriter::new(&mut bytes);
+    ///
+    /// writer.write_u8(0).unwrap();
+    /// assert_eq!(writer.remaining_bits(), 24);
+    /// ```
+    pub fn remaining_bits(&self) -> usize {
+        self.remaining() * 8
+    }
+
+    /// Check that `needed` more bytes fit without writing anything.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::OutOfSpace, writer::ByteStringWriter};
+    ///
+    /// let mut raw = [0u8; 2];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// let writer = ByteStringWriter::new(&mut bytes);
+    ///
+    /// assert!(writer.ensure(2).is_ok());
+    /// assert_eq!(writer.ensure(3), Err(OutOfSpace { needed: 3, remaining: 2 }));
+    /// ```
+    pub fn ensure(&self, needed: usize) -> Result<(), OutOfSpace> {
+        let remaining = self.remaining();
+        if needed > remaining {
+            return Err(OutOfSpace { needed, remaining });
+        }
+
+        Ok(())
+    }
+
+    /// Write a single byte.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, writer::ByteStringWriter};
+    ///
+    /// let mut raw = [0u8; 1];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// let mut writer = ByteStringWriter::new(&mut bytes);
+    ///
+    /// assert!(writer.write_u8(7).is_ok());
+    /// assert!(writer.write_u8(8).is_err());
+    /// assert_eq!(raw, [7]);
+    /// ```
+    pub fn write_u8(&mut self, value: u8) -> Result<(), OutOfSpace> {
+        self.write_slice(&[value])
+    }
+
+    /// Write `src` in order, nothing is written if it does not fit.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, writer::ByteStringWriter};
+    ///
+    /// let mut raw = [0u8; 3];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// let mut writer = ByteStringWriter::new(&mut bytes);
+    ///
+    /// writer.write_slice(&[1, 2]).unwrap();
+    /// assert!(writer.write_slice(&[3, 4]).is_err());
+    /// assert_eq!(raw, [1, 2, 0]);
+    /// ```
+    pub fn write_slice(&mut self, src: &[u8]) -> Result<(), OutOfSpace> {
+        self.ensure(src.len())?;
+
+        let end = self.position + src.len();
+        for (dst, value) in self.bytes.iter_range_mut(self.position..end).zip(src) {
+            *dst = *value;
+        }
+
+        self.position = end;
+        Ok(())
+    }
+
+    /// Write the logical bytes of `src`, nothing is written if they do not fit.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, writer::ByteStringWriter};
+    ///
+    /// let mut src = [0x01u8, 0x02];
+    /// let mut src = ByteString::new(&mut src);
+    /// src.interpret_reverse_endian();
+    ///
+    /// let mut raw = [0u8; 2];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// ByteStringWriter::new(&mut bytes).write_bytes(&src).unwrap();
+    /// assert_eq!(raw, [0x02, 0x01]);
+    /// ```
+    pub fn write_bytes(&mut self, src: &ByteString) -> Result<(), OutOfSpace> {
+        self.ensure(src.byte_len())?;
+
+        let end = self.position + src.byte_len();
+        for (dst, value) in self.bytes.iter_range_mut(self.position..end).zip(src.iter()) {
+            *dst = *value;
+        }
+
+        self.position = end;
+        Ok(())
+    }
+
+    /// Run a composite write, rolling back position and bytes if `f` fails.
+    ///
+    /// The unwritten tail is snapshotted first, so on error the buffer is exactly as before the call.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, error::Error, writer::ByteStringWriter};
+    ///
+    /// let mut raw = [0u8; 3];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// let mut writer = ByteStringWriter::new(&mut bytes);
+    ///
+    /// let result = writer.write_all_or_nothing(|w| {
+    ///     w.write_slice(&[1, 2]).map_err(|_| Error::OutputTooSmall)?;
+    ///     w.write_slice(&[3, 4]).map_err(|_| Error::OutputTooSmall)
+    /// });
+    ///
+    /// assert_eq!(result, Err(Error::OutputTooSmall));
+    /// assert_eq!(writer.position(), 0);
+    /// assert_eq!(raw, [0, 0, 0]);
+    /// ```
+    pub fn write_all_or_nothing<F>(&mut self, f: F) -> Result<(), Error>
+    where F: FnOnce(&mut Self) -> Result<(), Error> {
+
+        let start = self.position;
+        let snapshot: Vec<u8> = self.bytes.iter_range(start..self.bytes.byte_len()).copied().collect();
+
+        let result = f(self);
+
+        if result.is_err() {
+            for (dst, value) in self.bytes.iter_range_mut(start..start + snapshot.len()).zip(snapshot) {
+                *dst = value;
+            }
+            self.position = start;
+        }
+
+        result
+    }
+}
+
+/// Sequential writer of typed fields and bit fields, in logical order.
+///
+/// Bits are appended most significant first, as protocol diagrams draw them. A byte-level append
+/// after bits first pads the partial byte with zero bits, [`FieldWriter::pad_to_byte`] does the
+/// same explicitly. An append that does not fit fails with a [`WriteError`] and leaves buffer and
+/// cursor as they were. [`FieldWriter::bit_order`] switches to formats packing bit
+/// fields from the low bit of each byte.
+///
+/// ```
+/// use rebite::{bytes::ByteString, typed::Endianness, writer::FieldWriter};
+///
+/// let mut raw = [0u8; 4];
+/// let mut bytes = ByteString::new(&mut raw);
+/// let mut w = FieldWriter::new(&mut bytes);
+///
+/// w.put_bits(0x4, 4).unwrap();
+/// w.put_bits(0x5, 4).unwrap();
+/// w.put_u8(0x00).unwrap();
+/// w.put_u16(0x0054, Endianness::Big).unwrap();
+///
+/// assert_eq!(w.finish(), 4);
+/// assert_eq!(raw, [0x45, 0x00, 0x00, 0x54]);
+/// ```
+#[derive(Debug)]
+pub struct FieldWriter<'w, 'a> {
+    bytes: &'w mut ByteString<'a>,
+    bit_position: usize,
+    bit_order: BitOrder,
+}
+
+impl<'w, 'a> FieldWriter<'w, 'a> {
+
+    /// Cursor at logical bit 0, the most significant bit of the first logical byte.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, writer::FieldWriter};
+    ///
+    /// let mut raw = [0u8; 2];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// FieldWriter::new(&mut bytes).put_bits(1, 1).unwrap();
+    /// assert_eq!(raw, [0x80, 0x00]);
+    /// ```
+    pub fn new(bytes: &'w mut ByteString<'a>) -> Self {
+        Self { bytes, bit_position: 0, bit_order: BitOrder::Msb0 }
+    }
+
+    /// Append the following bit fields in `order`, until the next call.
+    ///
+    /// With [`BitOrder::Lsb0`] the bits of a byte are filled from its least significant one up and
+    /// each field is written least significant bit first, the layout [`FieldReader::bit_order`]
+    /// reads back. Byte-level appends are not affected. Change the order on a byte boundary, the
+    /// orders fill a partial byte from opposite ends.
+    ///
+    /// [`FieldReader::bit_order`]: crate::reader::FieldReader::bit_order
+    ///
+    /// ```
+    /// use rebite::{bit_order::BitOrder, bytes::ByteString, writer::FieldWriter};
+    ///
+    /// let mut raw = [0u8; 2];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// let mut w = FieldWriter::new(&mut bytes);
+    ///
+    /// // A DEFLATE block header, BFINAL = 1 and BTYPE = 2, then a byte
+    /// w.bit_order(BitOrder::Lsb0);
+    /// w.put_bits(1, 1).unwrap();
+    /// w.put_bits(2, 2).unwrap();
+    /// w.put_u8(0xAB).unwrap();
+    /// assert_eq!(raw, [0b0000_0101, 0xAB]);
+    /// ```
+    pub fn bit_order(&mut self, order: BitOrder) -> &mut Self {
+        self.bit_order = order;
+        self
+    }
+
+    /// Bits appended or skipped so far.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, writer::FieldWriter};
+    ///
+    /// let mut raw = [0u8; 2];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// let mut w = FieldWriter::new(&mut bytes);
+    ///
+    /// w.put_bits(0, 3).unwrap();
+    /// assert_eq!(w.bit_position(), 3);
+    ///
+    /// // A byte-level append first pads to the byte boundary
+    /// w.put_u8(0).unwrap();
+    /// assert_eq!(w.bit_position(), 16);
+    /// ```
+    pub fn bit_position(&self) -> usize {
+        self.bit_position
+    }
+
+    /// Logical offset of the next byte-level append, past any partially written byte.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, writer::FieldWriter};
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// let mut w = FieldWriter::new(&mut bytes);
+    ///
+    /// assert_eq!(w.position(), 0);
+    /// w.put_bits(1, 1).unwrap();
+    /// assert_eq!(w.position(), 1);
+    /// ```
+    pub fn position(&self) -> usize {
+        self.bit_position.div_ceil(8)
+    }
+
+    /// Whole bytes left for byte-level appends.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, writer::FieldWriter};
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// let mut w = FieldWriter::new(&mut bytes);
+    ///
+    /// w.skip(1).unwrap();
+    /// w.put_bits(0, 2).unwrap();
+    /// assert_eq!(w.remaining(), 2);
+    /// ```
+    pub fn remaining(&self) -> usize {
+        self.bytes.byte_len().saturating_sub(self.position())
+    }
+
+    /// Append a byte.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, writer::FieldWriter};
+    ///
+    /// let mut raw = [0u8; 2];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// let mut w = FieldWriter::new(&mut bytes);
+    ///
+    /// w.put_bits(0x1, 1).unwrap();
+    /// w.put_u8(0xAB).unwrap();
+    /// assert!(w.put_u8(0xCD).is_err());
+    /// assert_eq!(raw, [0x80, 0xAB]);
+    /// ```
+    pub fn put_u8(&mut self, value: u8) -> Result<(), WriteError> {
+        self.put_bytes(&[value])
+    }
+
+    /// Append a u16 in the given byte order.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, typed::Endianness, writer::FieldWriter};
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// let mut w = FieldWriter::new(&mut bytes);
+    ///
+    /// w.put_u16(0x1234, Endianness::Big).unwrap();
+    /// w.put_u16(0x1234, Endianness::Little).unwrap();
+    /// assert_eq!(raw, [0x12, 0x34, 0x34, 0x12]);
+    /// ```
+    pub fn put_u16(&mut self, value: u16, endian: Endianness) -> Result<(), WriteError> {
+        self.put_bytes(&match endian { Endianness::Big => value.to_be_bytes(), Endianness::Little => value.to_le_bytes() })
+    }
+
+    /// Append a u32 in the given byte order.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, typed::Endianness, writer::FieldWriter};
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// FieldWriter::new(&mut bytes).put_u32(0x0A0B0C0D, Endianness::Little).unwrap();
+    /// assert_eq!(raw, [0x0D, 0x0C, 0x0B, 0x0A]);
+    /// ```
+    pub fn put_u32(&mut self, value: u32, endian: Endianness) -> Result<(), WriteError> {
+        self.put_bytes(&match endian { Endianness::Big => value.to_be_bytes(), Endianness::Little => value.to_le_bytes() })
+    }
+
+    /// Append a u64 in the given byte order.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, typed::Endianness, writer::FieldWriter};
+    ///
+    /// let mut raw = [0u8; 8];
+    /// let mut bytes = ByteString::new(&mut raw);
+    ///
+    /// FieldWriter::new(&mut bytes).put_u64(1, Endianness::Big).unwrap();
+    /// assert_eq!(raw, [0, 0, 0, 0, 0, 0, 0, 1]);
+    /// ```
+    pub fn put_u64(&mut self, value: u64, endian: Endianness) -> Result<(), WriteError> {
+        self.put_bytes(&match endian { Endianness::Big => value.to_be_bytes(), Endianness::Little => value.to_le_bytes() })
+    }
+
+    /// Append `src` in order.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, writer::FieldWriter};
+    ///
+    /// let mut raw = [0u8; 3];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// let mut w = FieldWriter::new(&mut bytes);
+    ///
+    /// w.put_bytes(&[1, 2]).unwrap();
+    /// assert!(w.put_bytes(&[3, 4]).is_err());
+    /// assert_eq!(raw, [1, 2, 0]);
+    /// ```
+    pub fn put_bytes(&mut self, src: &[u8]) -> Result<(), WriteError> {
+        let start = self.reserve(src.len())?;
+        for (dst, value) in self.bytes.iter_range_mut(start..start + src.len()).zip(src) {
+            *dst = *value;
+        }
+
+        Ok(())
+    }
+
+    /// Append the low `width` bits of `value`, most significant first unless the bit order is [`BitOrder::Lsb0`].
+    ///
+    /// Fails with [`WriteErrorKind::TooWide`] if `width` exceeds 64.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, writer::FieldWriter};
+    ///
+    /// let mut raw = [0u8; 2];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// let mut w = FieldWriter::new(&mut bytes);
+    ///
+    /// w.put_bits(0b101, 3).unwrap();
+    /// w.put_bits(0x1FFF, 13).unwrap();
+    /// assert!(w.put_bits(1, 1).is_err());
+    /// assert_eq!(raw, [0xBF, 0xFF]);
+    /// ```
+    pub fn put_bits(&mut self, value: u64, width: usize) -> Result<(), WriteError> {
+        if width > 64 {
+            return Err(WriteError { position: self.bit_position, kind: WriteErrorKind::TooWide(width) });
+        }
+
+        let remaining = self.bytes.bit_len() - self.bit_position;
+        if width > remaining {
+            return Err(WriteError { position: self.bit_position, kind: WriteErrorKind::Full { needed: width, remaining } });
+        }
+
+        match self.bit_order {
+            BitOrder::Msb0 => (0..width).rev().for_each(|shift| self.put_bit(value >> shift & 1 == 1)),
+            BitOrder::Lsb0 => (0..width).for_each(|shift| self.put_bit(value >> shift & 1 == 1)),
+        }
+
+        Ok(())
+    }
+
+    /// Skip `n` bytes, leaving them as they are.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, writer::FieldWriter};
+    ///
+    /// let mut raw = [0xEEu8; 3];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// let mut w = FieldWriter::new(&mut bytes);
+    ///
+    /// w.skip(2).unwrap();
+    /// w.put_u8(0).unwrap();
+    /// assert_eq!(raw, [0xEE, 0xEE, 0x00]);
+    /// ```
+    pub fn skip(&mut self, n: usize) -> Result<(), WriteError> {
+        self.reserve(n).map(|_| ())
+    }
+
+    /// Fill the rest of a partial byte with zero bits, a no-op on a byte boundary.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, writer::FieldWriter};
+    ///
+    /// let mut raw = [0xFFu8; 2];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// let mut w = FieldWriter::new(&mut bytes);
+    ///
+    /// w.put_bits(0b11, 2).unwrap();
+    /// w.pad_to_byte();
+    /// assert_eq!(w.bit_position(), 8);
+    /// assert_eq!(raw, [0xC0, 0xFF]);
+    /// ```
+    pub fn pad_to_byte(&mut self) {
+        while !self.bit_position.is_multiple_of(8) {
+            self.put_bit(false);
+        }
+    }
+
+    /// Pad a partial last byte and return the number of bytes written, including skipped ones.
+    ///
+    /// ```
+    /// use rebite::{bytes::ByteString, writer::FieldWriter};
+    ///
+    /// let mut raw = [0u8; 4];
+    /// let mut bytes = ByteString::new(&mut raw);
+    /// let mut w = FieldWriter::new(&mut bytes);
+    ///
+    /// w.skip(1).unwrap();
+    /// w.put_bits(1, 1).unwrap();
+    /// assert_eq!(w.finish(), 2);
+    /// ```
+    pub fn finish(mut self) -> usize {
+        self.pad_to_byte();
+        self.bit_position / 8
+    }
+
+    // Offset of the next whole byte, the cursor stays put if `len` bytes do not fit after it
+    fn reserve(&mut self, len: usize) -> Result<usize, WriteError> {
+        let start = self.position();
+        match start.checked_add(len) {
+            Some(end) if end <= self.bytes.byte_len() => {
+                self.pad_to_byte();
+                self.bit_position = end * 8;
+                Ok(start)
+            },
+            _ => {
+                let remaining = self.bytes.bit_len().saturating_sub(start * 8);
+                Err(WriteError { position: start * 8, kind: WriteErrorKind::Full { needed: len.saturating_mul(8), remaining } })
+            },
+        }
+    }
+
+    fn put_bit(&mut self, value: bool) {
+        self.bytes.set_stream_bit(self.bit_position, self.bit_order, value);
+        self.bit_position += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_util::view;
+
+    fn frame(w: &mut ByteStringWriter, payload: &[u8]) -> Result<(), Error> {
+        w.write_u8(0x7E)?;
+        w.write_u8(payload.len() as u8)?;
+        w.write_slice(payload)?;
+        w.write_u8(0x7E)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_ensure_and_remaining() {
+        let mut raw = [0u8; 4];
+        let mut bytes = ByteString::new(&mut raw);
+        let mut w = ByteStringWriter::new(&mut bytes);
+
+        assert_eq!((w.remaining(), w.remaining_bits()), (4, 32));
+        w.write_slice(&[1, 2, 3]).unwrap();
+
+        assert_eq!(w.ensure(1), Ok(()));
+        assert_eq!(w.ensure(2), Err(OutOfSpace { needed: 2, remaining: 1 }));
+        assert_eq!(w.write_slice(&[4, 5]), Err(OutOfSpace { needed: 2, remaining: 1 }));
+        assert_eq!(w.position(), 3);
+        assert_eq!(raw, [1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn test_torn_frame_rolls_back() {
+        for reversed in [false, true] {
+            let mut raw = [0xEEu8; 8];
+            let mut bytes = view(&mut raw, reversed);
+
+            let mut w = ByteStringWriter::new(&mut bytes);
+            w.write_u8(0xAA).unwrap();
+
+            // The header is written before the payload runs out of space
+            let result = w.write_all_or_nothing(|w| frame(w, &[1, 2, 3, 4, 5, 6]));
+            assert_eq!(result, Err(Error::OutputTooSmall));
+            assert_eq!(w.position(), 1);
+
+            let mut expected = [0xAAu8, 0xEE, 0xEE, 0xEE, 0xEE, 0xEE, 0xEE, 0xEE];
+            if reversed { expected.reverse(); }
+            assert_eq!(raw, expected);
+        }
+    }
+
+    #[test]
+    fn test_success_matches_plain_writes() {
+        let mut plain = [0u8; 10];
+        let mut bytes = ByteString::new(&mut plain);
+        let mut w = ByteStringWriter::new(&mut bytes);
+        frame(&mut w, &[9, 8, 7]).unwrap();
+        let plain_position = w.position();
+
+        let mut snapshotted = [0u8; 10];
+        let mut bytes = ByteString::new(&mut snapshotted);
+        let mut w = ByteStringWriter::new(&mut bytes);
+        w.write_all_or_nothing(|w| frame(w, &[9, 8, 7])).unwrap();
+
+        assert_eq!(w.position(), plain_position);
+        assert_eq!(plain, snapshotted);
+    }
+
+    #[test]
+    fn test_field_writer_ipv4_header() {
+        // RFC 791 header of a 84 byte ICMP packet from 192.168.0.1 to 192.168.0.199
+        let expected = [
+            0x45, 0x00, 0x00, 0x54, 0x1C, 0x46, 0x40, 0x00, 0x40, 0x01, 0x00, 0x00,
+            0xC0, 0xA8, 0x00, 0x01, 0xC0, 0xA8, 0x00, 0xC7,
+        ];
+
+        for reversed in [false, true] {
+            let mut raw = [0xEEu8; 20];
+            let mut bytes = view(&mut raw, reversed);
+            let mut w = FieldWriter::new(&mut bytes);
+
+            w.put_bits(4, 4).unwrap();
+            w.put_bits(5, 4).unwrap();
+            w.put_bits(0, 6).unwrap();
+            w.put_bits(0, 2).unwrap();
+            w.put_u16(84, Endianness::Big).unwrap();
+            w.put_u16(0x1C46, Endianness::Big).unwrap();
+            w.put_bits(0b010, 3).unwrap();
+            w.put_bits(0, 13).unwrap();
+            w.put_u8(64).unwrap();
+            w.put_u8(1).unwrap();
+            w.put_u16(0, Endianness::Little).unwrap();
+            w.put_u32(0xC0A8_0001, Endianness::Big).unwrap();
+            w.put_bytes(&[192, 168, 0, 199]).unwrap();
+
+            assert_eq!(w.finish(), 20);
+            assert!(bytes.iter().eq(expected.iter()));
+        }
+    }
+
+    #[test]
+    fn test_field_writer_exhaustion_keeps_prior_fields() {
+        let mut raw = [0xEEu8; 5];
+        let mut bytes = ByteString::new(&mut raw);
+        let mut w = FieldWriter::new(&mut bytes);
+
+        w.put_u8(0x01).unwrap();
+        w.put_bits(0b1, 1).unwrap();
+        assert_eq!((w.position(), w.remaining()), (2, 3));
+
+        // Byte-level appends would start on the next byte boundary, bit fields right at the cursor
+        let full = |position, needed, remaining| Err(WriteError { position, kind: WriteErrorKind::Full { needed, remaining } });
+        assert_eq!(w.put_u32(0xFFFF_FFFF, Endianness::Big), full(16, 32, 24));
+        assert_eq!(w.put_u64(0, Endianness::Little), full(16, 64, 24));
+        assert_eq!(w.skip(4), full(16, 32, 24));
+        assert_eq!(w.skip(usize::MAX), full(16, usize::MAX, 24));
+        assert_eq!(w.put_bits(0, 32), full(9, 32, 31));
+        assert_eq!(w.put_bits(0, 65), Err(WriteError { position: 9, kind: WriteErrorKind::TooWide(65) }));
+        assert_eq!(w.bit_position(), 9);
+
+        // Exactly filling the buffer still works
+        w.put_bits(0, 7).unwrap();
+        w.skip(1).unwrap();
+        w.put_u16(0xABCD, Endianness::Little).unwrap();
+        assert_eq!((w.position(), w.remaining()), (5, 0));
+        assert_eq!(w.put_u8(0), full(40, 8, 0));
+        assert_eq!(Error::from(w.put_u8(0).unwrap_err()), Error::OutputTooSmall);
+        assert_eq!(w.finish(), 5);
+        assert_eq!(raw, [0x01, 0x80, 0xEE, 0xCD, 0xAB]);
+    }
+
+    #[test]
+    fn test_field_writer_mixed_bits_and_bytes() {
+        for reversed in [false, true] {
+            let mut raw = [0xEEu8; 5];
+            let mut bytes = view(&mut raw, reversed);
+            let mut w = FieldWriter::new(&mut bytes);
+
+            // A byte append pads the partial byte with zeros
+            w.put_bits(0b101, 3).unwrap();
+            w.put_u8(0xFF).unwrap();
+            assert_eq!(w.bit_position(), 16);
+
+            // Bits spanning a byte boundary, then an explicit pad
+            w.put_bits(0b1_1111_1111, 9).unwrap();
+            w.pad_to_byte();
+            w.pad_to_byte();
+            assert_eq!(w.bit_position(), 32);
+
+            // finish pads a trailing partial byte
+            w.put_bits(0b11, 2).unwrap();
+            assert_eq!(w.finish(), 5);
+            assert!(bytes.iter().eq([0xA0, 0xFF, 0xFF, 0x80, 0xC0].iter()));
+        }
+    }
+
+    #[test]
+    fn test_field_writer_lsb_first() {
+        for reversed in [false, true] {
+            let mut raw = [0xEEu8; 4];
+            let mut bytes = view(&mut raw, reversed);
+            let mut w = FieldWriter::new(&mut bytes);
+            w.bit_order(BitOrder::Lsb0);
+
+            // Fields fill each byte from the low bit up, low bits first
+            w.put_bits(0b110, 3).unwrap();
+            w.put_bits(0b11_10000, 7).unwrap();
+            w.put_bits(0b010100, 6).unwrap();
+
+            // Padding zeros go above the written bits, bytes are unaffected
+            w.put_bits(0b1, 1).unwrap();
+            w.put_u8(0x80).unwrap();
+            assert_eq!(w.put_bits(0, 1).unwrap_err().kind, WriteErrorKind::Full { needed: 1, remaining: 0 });
+
+            assert_eq!(w.finish(), 4);
+            assert!(bytes.iter().eq([0b1000_0110, 0b0101_0011, 0b0000_0001, 0x80].iter()));
+        }
+    }
+}
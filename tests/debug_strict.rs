@@ -0,0 +1,104 @@
+//! The `debug-strict` length checks of the bytewise operators.
+//!
+//! The mismatch handler is process wide, so everything that installs or relies on it stays in
+//! one test.
+
+#![cfg(feature = "debug-strict")]
+
+use rebite::bytes::ByteString;
+
+// Apply `&=`, `|=` and `^=` in turn, returning the logical bytes after each
+fn apply_all(lhs: &[u8], rhs: &[u8], reversed: (bool, bool)) -> [Vec<u8>; 3] {
+    let mut rhs = rhs.to_vec();
+    let mut rhs_bytes = ByteString::new(&mut rhs);
+    if reversed.1 { rhs_bytes.interpret_reverse_endian(); }
+    let rhs_bytes = rhs_bytes;
+
+    [0, 1, 2].map(|op| {
+        let mut raw = lhs.to_vec();
+        let mut bytes = ByteString::new(&mut raw);
+        if reversed.0 { bytes.interpret_reverse_endian(); }
+
+        match op {
+            0 => bytes &= &rhs_bytes,
+            1 => bytes |= &rhs_bytes,
+            _ => bytes ^= &rhs_bytes,
+        }
+        bytes.iter().copied().collect()
+    })
+}
+
+#[test]
+fn test_matching_lengths_unchanged() {
+    for reversed in [(false, false), (false, true), (true, false), (true, true)] {
+        let [and, or, xor] = apply_all(&[0xF0, 0x0F, 0xAA], &[0xFF, 0x00, 0x55], reversed);
+
+        // Logical bytes pair up by position
+        let rhs = if reversed.1 { [0x55, 0x00, 0xFF] } else { [0xFF, 0x00, 0x55] };
+        let lhs = if reversed.0 { [0xAA, 0x0F, 0xF0] } else { [0xF0, 0x0F, 0xAA] };
+        let expected = |f: fn(u8, u8) -> u8| lhs.iter().zip(rhs).map(|(a, b)| f(*a, b)).collect::<Vec<u8>>();
+
+        assert_eq!(and, expected(|a, b| a & b));
+        assert_eq!(or, expected(|a, b| a | b));
+        assert_eq!(xor, expected(|a, b| a ^ b));
+    }
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn test_mismatch_handler() {
+    use std::{panic, sync::Mutex};
+
+    static SEEN: Mutex<Vec<(usize, usize)>> = Mutex::new(Vec::new());
+
+    fn record(lhs: usize, rhs: usize) {
+        SEEN.lock().unwrap().push((lhs, rhs));
+    }
+
+    rebite::set_mismatch_handler(record);
+
+    // Recorded with the left and right lengths, the operators still truncate afterwards
+    let [and, or, xor] = apply_all(&[0xFF, 0xFF, 0xFF], &[0x0F], (false, false));
+    assert_eq!((and, or, xor), (vec![0x0F, 0xFF, 0xFF], vec![0xFF, 0xFF, 0xFF], vec![0xF0, 0xFF, 0xFF]));
+    apply_all(&[0x00], &[0x01, 0x02], (true, false));
+    apply_all(&[], &[0x01], (false, true));
+    apply_all(&[0x01, 0x02], &[0x03, 0x04], (true, true));
+
+    let seen = std::mem::take(&mut *SEEN.lock().unwrap());
+    assert_eq!(seen, [(3, 1), (3, 1), (3, 1), (1, 2), (1, 2), (1, 2), (0, 1), (0, 1), (0, 1)]);
+
+    // Without a handler a mismatch panics, reporting both lengths
+    rebite::clear_mismatch_handler();
+    let err = panic::catch_unwind(|| apply_all(&[0x00, 0x00], &[0x00], (false, false))).unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.contains("2 and 1 bytes"), "{}", message);
+
+    // The nand, nor and xnor methods panic as well, reported at their caller
+    static LOCATION: Mutex<Option<String>> = Mutex::new(None);
+    panic::set_hook(Box::new(|info| *LOCATION.lock().unwrap() = info.location().map(|e| e.file().to_string())));
+
+    for op in 0..3 {
+        let (mut a, mut b) = ([0u8; 2], [0u8]);
+        let mut bytes = ByteString::new(&mut a);
+        let rhs = ByteString::new(&mut b);
+
+        *LOCATION.lock().unwrap() = None;
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| match op {
+            0 => bytes.nand_assign(&rhs),
+            1 => bytes.nor_assign(&rhs),
+            _ => bytes.xnor_assign(&rhs),
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(LOCATION.lock().unwrap().as_deref(), Some(file!()));
+    }
+
+    let _ = panic::take_hook();
+}
+
+#[cfg(not(debug_assertions))]
+#[test]
+fn test_release_truncates_silently() {
+    let [and, or, xor] = apply_all(&[0xFF, 0xFF, 0xFF], &[0x0F], (false, false));
+    assert_eq!((and, or, xor), (vec![0x0F, 0xFF, 0xFF], vec![0xFF, 0xFF, 0xFF], vec![0xF0, 0xFF, 0xFF]));
+}
@@ -0,0 +1,231 @@
+//! Property-based differential tests against primitive integers.
+//!
+//! Every operation is described once as a closure over the primitive matching the buffer width,
+//! picked by length and zero-extended (3 bytes run on u32, 9 on u128, ...), then masked back to the
+//! buffer width. Operands cover lengths 1..=16 with each operand's interpretation flag drawn
+//! independently.
+
+use std::cmp::Ordering;
+
+use proptest::prelude::*;
+
+use rebite::{
+    bytes::ByteString,
+    error::Overflow
+};
+
+const CASES: u32 = 1000;
+
+trait Primitive: Copy {
+    fn from_u128(value: u128) -> Self;
+    fn to_u128(self) -> u128;
+}
+
+macro_rules! primitive {
+    ($($ty:ty),*) => {$(
+        impl Primitive for $ty {
+            fn from_u128(value: u128) -> Self { value as $ty }
+            fn to_u128(self) -> u128 { self as u128 }
+        }
+    )*};
+}
+
+primitive!(u8, u16, u32, u64, u128);
+
+/// An oracle operation, applied to zero-extended operands on the primitive `P`
+type Oracle<P> = fn(P, P, u32) -> P;
+
+// Holder letting one description of an operation be instantiated for every primitive
+struct OracleSet {
+    u8: Oracle<u8>,
+    u16: Oracle<u16>,
+    u32: Oracle<u32>,
+    u64: Oracle<u64>,
+    u128: Oracle<u128>,
+}
+
+macro_rules! oracle_set {
+    (|$a:ident, $b:ident, $n:ident| $body:expr) => {
+        OracleSet {
+            u8: |$a, $b, $n| $body,
+            u16: |$a, $b, $n| $body,
+            u32: |$a, $b, $n| $body,
+            u64: |$a, $b, $n| $body,
+            u128: |$a, $b, $n| $body,
+        }
+    };
+}
+
+fn eval<P: Primitive>(f: Oracle<P>, a: u128, b: u128, n: u32) -> u128 {
+    f(P::from_u128(a), P::from_u128(b), n).to_u128()
+}
+
+fn mask(len: usize) -> u128 {
+    if len >= 16 { u128::MAX } else { (1 << (len * 8)) - 1 }
+}
+
+// Pick the primitive by byte length, the result is reduced to the buffer width
+fn run(set: &OracleSet, len: usize, a: u128, b: u128, n: u32) -> u128 {
+    let result = match len {
+        1 => eval(set.u8, a, b, n),
+        2 => eval(set.u16, a, b, n),
+        3..=4 => eval(set.u32, a, b, n),
+        5..=8 => eval(set.u64, a, b, n),
+        _ => eval(set.u128, a, b, n),
+    };
+
+    result & mask(len)
+}
+
+#[derive(Debug, Clone)]
+struct Operand {
+    len: usize,
+    value: u128,
+    reversed: bool,
+}
+
+impl Operand {
+
+    fn physical(&self) -> Vec<u8> {
+        let mut raw = self.value.to_be_bytes()[16 - self.len..].to_vec();
+        if self.reversed { raw.reverse(); }
+        raw
+    }
+}
+
+fn operand(lengths: std::ops::RangeInclusive<usize>) -> impl Strategy<Value = Operand> {
+    (lengths, any::<u128>(), any::<bool>()).prop_map(|(len, value, reversed)| Operand { len, value: value & mask(len), reversed })
+}
+
+// Output width with two operands no longer than it
+fn binary() -> impl Strategy<Value = (usize, Operand, Operand, bool)> {
+    (1usize..=16).prop_flat_map(|out_len| (Just(out_len), operand(1..=out_len), operand(1..=out_len), any::<bool>()))
+}
+
+fn view<'b>(raw: &'b mut [u8], reversed: bool) -> ByteString<'b> {
+    let mut bytes = ByteString::new(raw);
+    if reversed { bytes.interpret_reverse_endian(); }
+    bytes
+}
+
+fn value_of(bytes: &ByteString) -> u128 {
+    bytes.iter().fold(0, |acc, e| acc << 8 | *e as u128)
+}
+
+// Apply `f` to a view of `a` and return the resulting value
+fn mutate<F: FnOnce(&mut ByteString)>(a: &Operand, f: F) -> u128 {
+    let mut raw = a.physical();
+    let mut bytes = view(&mut raw, a.reversed);
+    f(&mut bytes);
+    value_of(&bytes)
+}
+
+// A compound assignment operator, its right operand borrowed for as long as the target
+type AssignOp = for<'x> fn(&mut ByteString<'x>, &'x ByteString<'x>);
+
+// Apply `a op= b` in place and return the resulting value of `a`
+fn assign(a: &Operand, b: &Operand, op: AssignOp) -> u128 {
+    let (mut a_raw, mut b_raw) = (a.physical(), b.physical());
+    let rhs = view(&mut b_raw, b.reversed);
+
+    let mut bytes = view(&mut a_raw, a.reversed);
+    op(&mut bytes, &rhs);
+    value_of(&bytes)
+}
+
+// Write `a op b` into a fresh output of `out_len` bytes with its own flag
+fn combine<F>(a: &Operand, b: &Operand, out_len: usize, out_reversed: bool, f: F) -> (u128, bool)
+where F: FnOnce(&ByteString, &ByteString, &mut ByteString) -> bool {
+
+    let (mut a_raw, mut b_raw) = (a.physical(), b.physical());
+    let mut out_raw = vec![0xEE; out_len];
+
+    let mut out = view(&mut out_raw, out_reversed);
+    let flag = f(&view(&mut a_raw, a.reversed), &view(&mut b_raw, b.reversed), &mut out);
+    (value_of(&out), flag)
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(CASES))]
+
+    #[test]
+    fn test_shl(a in operand(1..=16), n in 0u32..140) {
+        let set = oracle_set!(|a, _b, n| a.checked_shl(n).unwrap_or(0));
+        prop_assert_eq!(mutate(&a, |bytes| *bytes <<= n as usize), run(&set, a.len, a.value, 0, n));
+    }
+
+    #[test]
+    fn test_shr(a in operand(1..=16), n in 0u32..140) {
+        let set = oracle_set!(|a, _b, n| a.checked_shr(n).unwrap_or(0));
+        prop_assert_eq!(mutate(&a, |bytes| { bytes.ops().shr_bits(n as usize); }), run(&set, a.len, a.value, 0, n));
+    }
+
+    #[test]
+    fn test_add((out_len, a, b, out_reversed) in binary()) {
+        let set = oracle_set!(|a, b, _n| a.wrapping_add(b));
+        let (sum, carry) = combine(&a, &b, out_len, out_reversed, |a, b, out| ByteString::add_into(a, b, out).unwrap());
+
+        prop_assert_eq!(sum, run(&set, out_len, a.value, b.value, 0));
+        prop_assert_eq!(carry, a.value.checked_add(b.value).is_none_or(|sum| sum > mask(out_len)));
+    }
+
+    #[test]
+    fn test_sub((out_len, a, b, out_reversed) in binary()) {
+        let set = oracle_set!(|a, b, _n| a.wrapping_sub(b));
+        let (difference, borrow) = combine(&a, &b, out_len, out_reversed, |a, b, out| ByteString::sub_into(a, b, out).unwrap());
+
+        prop_assert_eq!(difference, run(&set, out_len, a.value, b.value, 0));
+        prop_assert_eq!(borrow, a.value < b.value);
+    }
+
+    #[test]
+    fn test_add_assign(a in operand(1..=16), b in operand(1..=16)) {
+        let set = oracle_set!(|a, b, _n| a.wrapping_add(b));
+        prop_assert_eq!(assign(&a, &b, |a, b| *a += b), run(&set, a.len, a.value, b.value, 0));
+    }
+
+    #[test]
+    fn test_sub_assign(a in operand(1..=16), b in operand(1..=16)) {
+        let set = oracle_set!(|a, b, _n| a.wrapping_sub(b));
+        prop_assert_eq!(assign(&a, &b, |a, b| *a -= b), run(&set, a.len, a.value, b.value, 0));
+    }
+
+    #[test]
+    fn test_mul_assign(a in operand(1..=16), b in operand(1..=16)) {
+        let set = oracle_set!(|a, b, _n| a.wrapping_mul(b));
+        prop_assert_eq!(assign(&a, &b, |a, b| *a *= b), run(&set, a.len, a.value, b.value, 0));
+    }
+
+    #[test]
+    fn test_cmp(a in operand(1..=16), b in operand(1..=16)) {
+        let (mut a_raw, mut b_raw) = (a.physical(), b.physical());
+        let ordering = view(&mut a_raw, a.reversed).cmp_numeric(&view(&mut b_raw, b.reversed));
+
+        prop_assert_eq!(ordering, a.value.cmp(&b.value));
+        prop_assert_eq!(ordering == Ordering::Equal, a.value == b.value);
+    }
+
+    #[test]
+    fn test_to_u64(a in operand(1..=16)) {
+        let mut raw = a.physical();
+        let expected = u64::try_from(a.value).map_err(|_| Overflow);
+
+        prop_assert_eq!(view(&mut raw, a.reversed).to_u64(), expected);
+    }
+
+    #[test]
+    fn test_assign_from_u64(len in 1usize..=16, reversed in any::<bool>(), value in any::<u64>()) {
+        let target = Operand { len, value: 0xA5A5_A5A5_A5A5_A5A5_A5A5_A5A5_A5A5_A5A5 & mask(len), reversed };
+        let mut result = Ok(());
+        let after = mutate(&target, |bytes| result = bytes.assign_from_u64(value));
+
+        if (value as u128) <= mask(len) {
+            prop_assert_eq!(result, Ok(()));
+            prop_assert_eq!(after, value as u128);
+        }
+        else {
+            prop_assert_eq!(result, Err(Overflow));
+            prop_assert_eq!(after, target.value);
+        }
+    }
+}
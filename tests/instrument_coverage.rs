@@ -0,0 +1,106 @@
+//! Keeps the `delegate!` list of `src/instrument.rs` in step with the inherent ByteString API:
+//! every public method is either counted by CountingByteString or listed here as uncounted.
+
+use std::{
+    fs,
+    path::Path
+};
+
+// Methods CountingByteString deliberately does not forward, each with the reason
+const UNCOUNTED: &[&str] = &[
+    // Associated functions without a receiver, nothing to count on
+    "add_into",
+    "sub_into",
+    "parse_cbor_bytes",
+    "import",
+    "with_words_mut",
+    // Generic over a closure or trait, reached through `inner`
+    "checksum",
+    "reduce_words_u64",
+    "assign_xor_of",
+    "transform_within",
+    // Views and conversions that hand out the buffer itself
+    "ops",
+    "into_raw_parts",
+    // Debug assertion hook, not an operation
+    "debug_validate",
+];
+
+fn src_dir() -> std::path::PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("src")
+}
+
+// Source up to the unit tests, which define helpers of their own
+fn without_tests(source: &str) -> &str {
+    source.split("\n#[cfg(test)]").next().unwrap()
+}
+
+fn fn_name(rest: &str) -> String {
+    rest.chars().take_while(|c| c.is_alphanumeric() || *c == '_').collect()
+}
+
+// Public fn names declared inside the inherent `impl ByteString` blocks of a file
+fn inherent_methods(source: &str) -> Vec<String> {
+    let mut found = Vec::new();
+    let mut inside = false;
+
+    for line in without_tests(source).lines() {
+        if line.starts_with("impl") {
+            let header = line.trim_start_matches("impl").trim_start_matches(|c| c != ' ');
+            inside = header.trim_start().starts_with("ByteString<") && !line.contains(" for ");
+        }
+        else if line.starts_with('}') {
+            inside = false;
+        }
+        else if let Some(rest) = line.trim().strip_prefix("pub fn ").filter(|_| inside) {
+            found.push(fn_name(rest));
+        }
+    }
+
+    found
+}
+
+// Methods of CountingByteString, from `delegate!` entries and hand written forwarders
+fn counted_methods(source: &str) -> Vec<String> {
+    let mut found = Vec::new();
+
+    for line in without_tests(source).lines().map(str::trim) {
+        let rest = ["Read fn ", "Write fn ", "Compare fn ", "Shift fn ", "pub fn "].iter().find_map(|e| line.strip_prefix(e));
+        if let Some(rest) = rest {
+            found.push(fn_name(rest));
+        }
+    }
+
+    found
+}
+
+#[test]
+fn test_every_method_is_counted_or_exempt() {
+    let counted = counted_methods(&fs::read_to_string(src_dir().join("instrument.rs")).unwrap());
+    let mut missing = Vec::new();
+
+    for entry in fs::read_dir(src_dir()).unwrap() {
+        let path = entry.unwrap().path();
+        let source = fs::read_to_string(&path).unwrap();
+
+        for name in inherent_methods(&source) {
+            if !counted.contains(&name) && !UNCOUNTED.contains(&name.as_str()) {
+                missing.push(format!("{}: {}", path.display(), name));
+            }
+        }
+    }
+
+    assert!(missing.is_empty(), "add these to `delegate!` or to UNCOUNTED:\n{}", missing.join("\n"));
+}
+
+#[test]
+fn test_exemptions_are_current() {
+    let mut methods = Vec::new();
+    for entry in fs::read_dir(src_dir()).unwrap() {
+        methods.extend(inherent_methods(&fs::read_to_string(entry.unwrap().path()).unwrap()));
+    }
+
+    for name in UNCOUNTED {
+        assert!(methods.iter().any(|e| e == name), "{} is exempt but no longer a ByteString method", name);
+    }
+}
@@ -0,0 +1,271 @@
+//! Executable specification of the interpretation flag.
+//!
+//! Every order-sensitive operation is derived from one definition in [`Model`] and checked
+//! against the real implementation with the flag both cleared and set.
+//!
+//! A byte string has a *logical* byte sequence `L`. With the flag cleared `L` is the physical
+//! slice, with it set `L` is the physical slice reversed. Everything else follows from `L`:
+//!
+//! - iteration, ranges and offsets walk `L` front to back, index 0 is `L[0]`
+//! - the numeric value is `L` read as an unsigned big-endian integer
+//! - bit `k` is bit `k % 8` of `L[len - 1 - k / 8]`, bit 0 being the least significant bit of the value
+//! - shifts, arithmetic and radix output operate on that value
+//! - formatting prints `L` as hex, front to back
+
+use std::cmp::Ordering;
+
+use rebite::{
+    bytes::ByteString,
+    typed::Endianness
+};
+
+/// The single definition every expectation below is derived from
+#[derive(Debug, Clone, PartialEq)]
+struct Model {
+    logical: Vec<u8>,
+}
+
+impl Model {
+
+    fn value(&self) -> u128 {
+        self.logical.iter().fold(0, |acc, e| acc << 8 | *e as u128)
+    }
+
+    fn from_value(value: u128, len: usize) -> Self {
+        Self { logical: value.to_be_bytes()[16 - len..].to_vec() }
+    }
+
+    fn mask(&self) -> u128 {
+        if self.logical.len() == 16 { u128::MAX } else { (1 << (self.logical.len() * 8)) - 1 }
+    }
+
+    fn bit(&self, k: usize) -> bool {
+        self.logical[self.logical.len() - 1 - k / 8] >> (k % 8) & 1 == 1
+    }
+
+    fn bit_len(&self) -> usize {
+        self.logical.len() * 8
+    }
+
+    // The physical layout that realizes this model under `reversed`
+    fn physical(&self, reversed: bool) -> Vec<u8> {
+        let mut raw = self.logical.clone();
+        if reversed { raw.reverse(); }
+        raw
+    }
+
+    fn shl(&self, n: usize) -> Self {
+        let value = if n >= 128 { 0 } else { self.value() << n };
+        Self::from_value(value & self.mask(), self.logical.len())
+    }
+
+    fn shr(&self, n: usize) -> Self {
+        let value = if n >= 128 { 0 } else { self.value() >> n };
+        Self::from_value(value, self.logical.len())
+    }
+
+    fn hex(&self) -> String {
+        self.logical.iter().map(|e| format!("{:02x}", e)).collect()
+    }
+}
+
+const LENGTHS: [usize; 6] = [1, 2, 3, 5, 8, 16];
+
+// Canonical values plus a few pseudo random ones per length
+fn models() -> Vec<Model> {
+    let mut state = 0x237u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state as u8
+    };
+
+    let mut models = Vec::new();
+    for len in LENGTHS {
+        models.push(Model { logical: (1..=len as u8).collect() });
+        models.push(Model { logical: vec![0; len] });
+        models.push(Model { logical: vec![0xFF; len] });
+
+        for _ in 0..4 {
+            models.push(Model { logical: (0..len).map(|_| next()).collect() });
+        }
+    }
+
+    models
+}
+
+// Run `f` on a byte string realizing `model` under `reversed`, returns the model it now realizes
+fn apply<F: FnOnce(&mut ByteString)>(model: &Model, reversed: bool, f: F) -> Model {
+    let mut raw = model.physical(reversed);
+    let mut bytes = ByteString::new(&mut raw);
+    if reversed { bytes.interpret_reverse_endian(); }
+
+    f(&mut bytes);
+
+    let logical: Vec<u8> = bytes.iter().copied().collect();
+    assert_eq!(Model { logical: logical.clone() }.physical(reversed), raw, "iteration disagrees with the physical layout");
+    Model { logical }
+}
+
+fn for_each_case<F: FnMut(&Model, bool)>(mut f: F) {
+    for model in models() {
+        for reversed in [false, true] {
+            f(&model, reversed);
+        }
+    }
+}
+
+#[test]
+fn test_iteration() {
+    for_each_case(|model, reversed| {
+        apply(model, reversed, |bytes| {
+            assert!(bytes.iter().eq(model.logical.iter()));
+            assert!(bytes.iter().rev().eq(model.logical.iter().rev()));
+            assert_eq!(bytes.iter().len(), model.logical.len());
+            assert_eq!(bytes.byte_len(), model.logical.len());
+            assert_eq!(bytes.bit_len(), model.bit_len());
+        });
+    });
+}
+
+#[test]
+fn test_slicing_offsets() {
+    for_each_case(|model, reversed| {
+        let len = model.logical.len();
+
+        for start in 0..=len {
+            for end in start..=len {
+                apply(model, reversed, |bytes| {
+                    assert!(bytes.iter_range(start..end).eq(model.logical[start..end].iter()), "{}..{} reversed={}", start, end, reversed);
+                });
+
+                // Writing through a range touches exactly the logical bytes of that range
+                let mut expected = model.clone();
+                expected.logical[start..end].iter_mut().for_each(|e| *e = !*e);
+                let written = apply(model, reversed, |bytes| bytes.iter_range_mut(start..end).for_each(|e| *e = !*e));
+                assert_eq!(written, expected);
+            }
+        }
+
+        apply(model, reversed, |bytes| assert!(bytes.try_iter_range(0..len + 1).is_err()));
+    });
+}
+
+#[test]
+fn test_typed_indexing() {
+    for_each_case(|model, reversed| {
+        for offset in 0..model.logical.len().saturating_sub(1) {
+            let pair = [model.logical[offset], model.logical[offset + 1]];
+
+            apply(model, reversed, |bytes| {
+                assert_eq!(bytes.read_u16_at(offset, Endianness::Big), Ok(u16::from_be_bytes(pair)));
+                assert_eq!(bytes.read_u16_at(offset, Endianness::Little), Ok(u16::from_le_bytes(pair)));
+            });
+        }
+    });
+}
+
+#[test]
+fn test_bit_numbering() {
+    for_each_case(|model, reversed| {
+        for k in 0..model.bit_len() {
+            let mut expected = model.clone();
+            expected.logical[model.logical.len() - 1 - k / 8] ^= 1 << (k % 8);
+
+            let flipped = apply(model, reversed, |bytes| { bytes.ops().set_bit(k, !model.bit(k)); });
+            assert_eq!(flipped, expected, "bit {} reversed={}", k, reversed);
+            assert_eq!(flipped.value(), model.value() ^ 1 << k);
+        }
+    });
+}
+
+#[test]
+fn test_shifts() {
+    for_each_case(|model, reversed| {
+        for n in [0, 1, 3, 7, 8, 9, 15, 16, 17, 31, model.bit_len() - 1, model.bit_len(), model.bit_len() + 5] {
+            assert_eq!(apply(model, reversed, |bytes| *bytes <<= n), model.shl(n), "<< {} reversed={}", n, reversed);
+            assert_eq!(apply(model, reversed, |bytes| { bytes.ops().shr_bits(n); }), model.shr(n), ">> {} reversed={}", n, reversed);
+        }
+    });
+}
+
+#[test]
+fn test_byte_rotation() {
+    for_each_case(|model, reversed| {
+        for count in 0..2 * model.logical.len() + 1 {
+            let mut expected = model.clone();
+            expected.logical.rotate_left(count % model.logical.len().max(1));
+
+            assert_eq!(apply(model, reversed, |bytes| bytes.rotl_bytes(count)), expected);
+        }
+
+        for count in 0..2 * model.logical.len() + 1 {
+            let mut expected = model.clone();
+            expected.logical.rotate_right(count % model.logical.len().max(1));
+
+            assert_eq!(apply(model, reversed, |bytes| bytes.rotr_bytes(count)), expected);
+        }
+    });
+}
+
+#[test]
+fn test_arithmetic_significance() {
+    for_each_case(|model, reversed| {
+        let one = Model::from_value(1, 1);
+
+        for one_reversed in [false, true] {
+            let mut one_raw = one.physical(one_reversed);
+            let mut one_bytes = ByteString::new(&mut one_raw);
+            if one_reversed { one_bytes.interpret_reverse_endian(); }
+
+            let mut carry = false;
+            let sum = apply(&Model { logical: vec![0; model.logical.len()] }, reversed, |out| {
+                let mut raw = model.physical(reversed);
+                let mut a = ByteString::new(&mut raw);
+                if reversed { a.interpret_reverse_endian(); }
+
+                carry = ByteString::add_into(&a, &one_bytes, out).unwrap();
+            });
+
+            let expected = model.value().wrapping_add(1) & model.mask();
+            assert_eq!(sum, Model::from_value(expected, model.logical.len()));
+            assert_eq!(carry, model.value() == model.mask());
+        }
+    });
+}
+
+#[test]
+fn test_formatting() {
+    for_each_case(|model, reversed| {
+        apply(model, reversed, |bytes| {
+            assert_eq!(bytes.to_string(), model.hex());
+
+            let mut digits = [0u8; 40];
+            let n = bytes.write_radix(&mut digits, 10, false).unwrap();
+            assert_eq!(std::str::from_utf8(&digits[..n]).unwrap(), model.value().to_string());
+        });
+    });
+}
+
+#[test]
+fn test_numeric_ordering() {
+    let models = models();
+
+    for a in &models {
+        for b in &models {
+            for (a_reversed, b_reversed) in [(false, false), (false, true), (true, false), (true, true)] {
+                let mut a_raw = a.physical(a_reversed);
+                let mut b_raw = b.physical(b_reversed);
+
+                let mut a_bytes = ByteString::new(&mut a_raw);
+                if a_reversed { a_bytes.interpret_reverse_endian(); }
+                let mut b_bytes = ByteString::new(&mut b_raw);
+                if b_reversed { b_bytes.interpret_reverse_endian(); }
+
+                let expected: Ordering = a.value().cmp(&b.value());
+                assert_eq!(a_bytes.cmp_numeric(&b_bytes), expected, "{:?} {:?}", a, b);
+            }
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! Enforces the panic policy: every public method that can panic is `#[track_caller]`
+//! and has a non-panicking `try_` twin.
+
+use std::{
+    fs,
+    ops::{DivAssign, RemAssign},
+    panic,
+    path::Path,
+    sync::atomic::{AtomicU32, Ordering}
+};
+
+use rebite::{
+    bytes::ByteString,
+    checksum::RollingHash,
+    matrix::ByteMatrix,
+    ops::ByteStringOps,
+    testing
+};
+
+#[cfg(not(feature = "forbid-unsafe"))]
+use rebite::words::WordBytes;
+
+// Registry of panicking methods and their twins, naming them makes both fail to compile if removed
+macro_rules! panic_policy {
+    ($($(#[$attr:meta])* $ty:ident :: $fast:ident => $twin:ident),* $(,)?) => {
+        const REGISTRY: &[(&str, &str)] = &[$((stringify!($fast), stringify!($twin))),*];
+
+        #[test]
+        fn test_twins_exist() {
+            $(
+                $(#[$attr])*
+                {
+                    let _ = $ty::$fast;
+                    let _ = $ty::$twin;
+                }
+            )*
+        }
+    };
+}
+
+panic_policy! {
+    ByteString::iter_range => try_iter_range,
+    ByteString::iter_range_mut => try_iter_range_mut,
+    ByteString::bit => try_bit,
+    ByteString::set_bit => try_set_bit,
+    ByteString::rolling_hashes => try_rolling_hashes,
+    ByteString::ct_eq_padded => try_ct_eq_padded,
+    ByteString::div_assign => try_div_assign,
+    ByteString::rem_assign => try_rem_assign,
+    ByteString::merge_bits => try_merge_bits,
+    ByteString::nand_assign => try_nand_assign,
+    ByteString::nor_assign => try_nor_assign,
+    ByteString::xnor_assign => try_xnor_assign,
+    ByteString::shl_carry => try_shl_carry,
+    ByteString::shr_carry => try_shr_carry,
+    ByteString::shl_assign_with_carry_in => try_shl_assign_with_carry_in,
+    ByteString::shr_assign_with_carry_in => try_shr_assign_with_carry_in,
+    RollingHash::new => try_new,
+    ByteStringOps::set_bit => try_set_bit,
+    ByteMatrix::row_mut => try_row_mut,
+    ByteMatrix::get => try_get,
+    ByteMatrix::set => try_set,
+    ByteMatrix::iter_column => try_iter_column,
+    #[cfg(not(feature = "forbid-unsafe"))]
+    WordBytes::word_for_bit => try_word_for_bit,
+    testing::assert_counter_pattern => find_counter_mismatch,
+    testing::assert_labeled_pattern => find_labeled_mismatch,
+}
+
+// Public fn names whose attribute and doc block mark them as panicking
+fn scan(source: &str) -> Vec<(String, bool, bool)> {
+    let mut found = Vec::new();
+    let mut track_caller = false;
+    let mut documents_panic = false;
+    let mut in_tests = false;
+
+    for line in source.lines().map(str::trim) {
+        if line.starts_with("mod tests") { in_tests = true; }
+        if in_tests { continue; }
+
+        if line.starts_with("///") {
+            documents_panic |= line.to_lowercase().contains("panics");
+        }
+        else if line == "#[track_caller]" {
+            track_caller = true;
+        }
+        else if line.starts_with("pub fn $") {
+            // Macro generated forwarders inherit the contract of the method they wrap
+            track_caller = false;
+            documents_panic = false;
+        }
+        else if let Some(rest) = line.strip_prefix("pub fn ") {
+            let name: String = rest.chars().take_while(|c| c.is_alphanumeric() || *c == '_').collect();
+            found.push((name, track_caller, documents_panic));
+            track_caller = false;
+            documents_panic = false;
+        }
+        else if !line.starts_with("#[") {
+            track_caller = false;
+            documents_panic = false;
+        }
+    }
+
+    found
+}
+
+#[test]
+fn test_panicking_methods_are_registered() {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+
+    for entry in fs::read_dir(src).unwrap() {
+        let path = entry.unwrap().path();
+        let source = fs::read_to_string(&path).unwrap();
+
+        for (name, track_caller, documents_panic) in scan(&source) {
+            let location = format!("{}: {}", path.display(), name);
+
+            assert_eq!(track_caller, documents_panic, "{} must both document its panic and be #[track_caller]", location);
+            if track_caller {
+                assert!(REGISTRY.iter().any(|(fast, _)| *fast == name), "{} has no registered try_ twin", location);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_twins_report_instead_of_panicking() {
+    let mut raw = [1u8, 2, 3];
+    let mut bytes = ByteString::new(&mut raw);
+
+    assert!(bytes.try_iter_range(0..4).is_err());
+    assert!(bytes.try_bit(24).is_err());
+    assert!(bytes.try_set_bit(24, true).is_err());
+    assert!(bytes.try_rolling_hashes(0).is_err());
+    assert!(bytes.try_ct_eq_padded(&ByteString::new(&mut [0u8; 2]), 2).is_err());
+    assert!(bytes.try_div_assign(&ByteString::new(&mut [0u8])).is_err());
+    assert!(bytes.try_rem_assign(&ByteString::new(&mut [0u8])).is_err());
+    assert!(bytes.try_merge_bits(&ByteString::new(&mut [0u8; 3]), &ByteString::new(&mut [0u8; 2])).is_err());
+    assert!(bytes.try_nand_assign(&ByteString::new(&mut [0u8; 2])).is_err());
+    assert!(bytes.try_nor_assign(&ByteString::new(&mut [0u8; 4])).is_err());
+    assert!(bytes.try_xnor_assign(&ByteString::new(&mut [0u8])).is_err());
+    assert!(bytes.ops().try_set_bit(24, true).is_err());
+    assert!(RollingHash::try_new(0, 1, 1).is_err());
+
+    let mut matrix = bytes.as_matrix_mut(3).unwrap();
+    assert!(matrix.try_row_mut(1).is_err());
+    assert!(matrix.try_set(3, 0, 0).is_err());
+    assert!(matrix.try_iter_column(3).is_err());
+    assert_eq!(raw, [1, 2, 3]);
+}
+
+#[test]
+fn test_panic_location_is_the_caller() {
+    static PANIC_LINE: AtomicU32 = AtomicU32::new(0);
+
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|info| {
+        if let Some(location) = info.location().filter(|e| e.file().ends_with("panic_policy.rs")) {
+            PANIC_LINE.store(location.line(), Ordering::SeqCst);
+        }
+    }));
+
+    let expected = line!() + 3;
+    let result = panic::catch_unwind(|| {
+        let mut raw = [0u8; 2];
+        ByteString::new(&mut raw).set_bit(16, true);
+    });
+    panic::set_hook(default_hook);
+
+    assert!(result.is_err());
+    assert_eq!(PANIC_LINE.load(Ordering::SeqCst), expected);
+}
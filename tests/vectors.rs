@@ -0,0 +1,165 @@
+//! Data driven test vectors from `tests/vectors/*.toml`, regenerated by `examples/regen_vectors.rs`.
+//!
+//! Each file is a list of `[[vector]]` tables with the string keys `op`, `param`, `input` and
+//! `expected`. Only that subset of TOML is read, so the harness needs no parser dependency. The
+//! `op` names an entry of [`OPS`] which maps the other three strings to an output compared with
+//! `expected`. Byte strings are lowercase hex in logical order, and every vector runs once with the
+//! plain and once with the reversed interpretation.
+
+use std::{
+    fs,
+    path::Path
+};
+
+use rebite::{
+    bytes::ByteString,
+    checksum::Crc32
+};
+
+/// An operation under test, `(param, input, reversed) -> output`
+type Op = fn(&str, &str, bool) -> String;
+
+/// Registered operations keyed by the `op` of a vector
+const OPS: &[(&str, Op)] = &[
+    ("crc32", |_, input, reversed| with_bytes(input, reversed, |b| format!("{:08x}", b.checksum(Crc32::default())))),
+    ("hex_display", |_, input, reversed| with_bytes(input, reversed, |b| b.to_string())),
+    ("hex_radix", hex_radix),
+    ("hex_parse", hex_parse),
+    ("shl", |param, input, reversed| shifted(input, reversed, |b| *b <<= count(param))),
+    ("shr", |param, input, reversed| shifted(input, reversed, |b| *b >>= count(param))),
+    ("sar", |param, input, reversed| shifted(input, reversed, |b| b.sar_assign(count(param)))),
+    ("rotl_bits", |param, input, reversed| shifted(input, reversed, |b| b.rotl_bits(count(param)))),
+    ("rotr_bits", |param, input, reversed| shifted(input, reversed, |b| b.rotr_bits(count(param)))),
+];
+
+#[derive(Debug, Default)]
+struct Vector {
+    file: String,
+    line: usize,
+    op: String,
+    param: String,
+    input: String,
+    expected: String,
+}
+
+fn unhex(s: &str) -> Vec<u8> {
+    assert!(s.len().is_multiple_of(2), "odd hex length in {:?}", s);
+    (0..s.len()).step_by(2).map(|ix| u8::from_str_radix(&s[ix..ix + 2], 16).unwrap()).collect()
+}
+
+fn count(param: &str) -> usize {
+    param.parse().unwrap()
+}
+
+// Store the logical bytes of `input` physically reversed when `reversed` is set
+fn with_bytes<T>(input: &str, reversed: bool, f: impl FnOnce(&mut ByteString) -> T) -> T {
+    let mut raw = unhex(input);
+    if reversed { raw.reverse(); }
+    let mut bytes = ByteString::new(&mut raw);
+    if reversed { bytes.interpret_reverse_endian(); }
+
+    f(&mut bytes)
+}
+
+fn shifted(input: &str, reversed: bool, f: impl FnOnce(&mut ByteString)) -> String {
+    with_bytes(input, reversed, |b| {
+        f(b);
+        b.to_string()
+    })
+}
+
+fn hex_radix(param: &str, input: &str, reversed: bool) -> String {
+    with_bytes(input, reversed, |b| {
+        let mut out = vec![0u8; 2 * b.byte_len() + 1];
+        let written = b.write_radix(&mut out, 16, param == "upper").unwrap();
+        String::from_utf8(out[..written].to_vec()).unwrap()
+    })
+}
+
+// The param is the buffer width in bytes, a rejected input gives "error"
+fn hex_parse(param: &str, input: &str, reversed: bool) -> String {
+    let mut raw = vec![0u8; count(param)];
+    let mut bytes = ByteString::new(&mut raw);
+    if reversed { bytes.interpret_reverse_endian(); }
+
+    match bytes.assign_from_radix_str(input, 16) {
+        Ok(()) => bytes.to_string(),
+        Err(_) => "error".to_string(),
+    }
+}
+
+// The `[[vector]]` subset of TOML written by the generator: headers, string keys, comments
+fn load(path: &Path) -> Vec<Vector> {
+    let file = path.file_name().unwrap().to_string_lossy().into_owned();
+    let text = fs::read_to_string(path).unwrap();
+    let mut vectors: Vec<Vector> = Vec::new();
+
+    for (ix, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line == "[[vector]]" {
+            vectors.push(Vector { file: file.clone(), line: ix + 1, ..Vector::default() });
+            continue;
+        }
+
+        let (key, value) = line.split_once('=').unwrap_or_else(|| panic!("{}:{}: expected `key = \"value\"`", file, ix + 1));
+        let value = value.trim().strip_prefix('"').and_then(|e| e.strip_suffix('"'))
+            .unwrap_or_else(|| panic!("{}:{}: expected a string value", file, ix + 1))
+            .to_string();
+
+        let vector = vectors.last_mut().unwrap_or_else(|| panic!("{}:{}: key outside of [[vector]]", file, ix + 1));
+        match key.trim() {
+            "op" => vector.op = value,
+            "param" => vector.param = value,
+            "input" => vector.input = value,
+            "expected" => vector.expected = value,
+            other => panic!("{}:{}: unknown key {:?}", file, ix + 1, other),
+        }
+    }
+
+    vectors
+}
+
+fn load_all() -> Vec<Vector> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/vectors");
+    let mut paths: Vec<_> = fs::read_dir(dir).unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|e| e.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+
+    paths.iter().flat_map(|e| load(e)).collect()
+}
+
+#[test]
+fn test_vectors() {
+    let vectors = load_all();
+    let mut failures = Vec::new();
+
+    for vector in &vectors {
+        let op = OPS.iter().find(|(name, _)| *name == vector.op)
+            .unwrap_or_else(|| panic!("{}:{}: no operation registered as {:?}", vector.file, vector.line, vector.op)).1;
+
+        for reversed in [false, true] {
+            let actual = op(&vector.param, &vector.input, reversed);
+            if actual != vector.expected {
+                failures.push(format!("{}:{}: {} param={:?} input={:?} reversed={}: expected {:?}, got {:?}",
+                    vector.file, vector.line, vector.op, vector.param, vector.input, reversed, vector.expected, actual));
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{} of {} vectors failed:\n{}", failures.len(), vectors.len(), failures.join("\n"));
+}
+
+#[test]
+fn test_every_operation_has_vectors() {
+    let vectors = load_all();
+
+    for (name, _) in OPS {
+        assert!(vectors.iter().any(|e| e.op == *name), "no vectors for {:?}", name);
+    }
+}